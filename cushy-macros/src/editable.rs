@@ -0,0 +1,340 @@
+use manyhow::{bail, ensure};
+use proc_macro2::Literal;
+use quote::ToTokens;
+use syn::{Attribute, Data, DeriveInput, Expr, Field, LitStr, Path, Variant};
+
+use crate::*;
+
+/// The options parsed from `#[editable(...)]` on a field or variant.
+#[derive(Default)]
+struct Options {
+    skip: bool,
+    label: Option<LitStr>,
+    slider: bool,
+    text: bool,
+    min: Option<Expr>,
+    max: Option<Expr>,
+    step: Option<Expr>,
+    with: Option<Path>,
+    validate: Option<Path>,
+}
+
+impl Options {
+    fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("editable")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    options.skip = true;
+                } else if meta.path.is_ident("slider") {
+                    options.slider = true;
+                } else if meta.path.is_ident("text") {
+                    options.text = true;
+                } else if meta.path.is_ident("label") {
+                    options.label = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("min") {
+                    options.min = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("max") {
+                    options.max = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("step") {
+                    options.step = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("with") {
+                    options.with = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("validate") {
+                    options.validate = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("unknown editable option"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+
+    fn editor(&self, span: &impl ToTokens) -> Result<Option<TokenStream>> {
+        let choices =
+            usize::from(self.slider) + usize::from(self.text) + usize::from(self.with.is_some());
+        ensure!(
+            choices <= 1,
+            span,
+            "only one of `slider`, `text`, and `with` can be specified"
+        );
+
+        let editor = if self.slider {
+            let (Some(min), Some(max)) = (&self.min, &self.max) else {
+                bail!(span, "`slider` requires `min` and `max`");
+            };
+            ensure!(
+                self.step.is_none(),
+                span,
+                "`step` is not supported with `slider`"
+            );
+            quote!(::cushy::widgets::slider::Slidable::slider_between(__field.clone(), #min, #max))
+        } else if self.text {
+            quote!(::cushy::widgets::input::InputValue::into_input(
+                __field.linked_string()
+            ))
+        } else if let Some(with) = &self.with {
+            quote!(#with(__field.clone()))
+        } else if self.min.is_some() || self.max.is_some() || self.step.is_some() {
+            let min = self.min.iter();
+            let max = self.max.iter();
+            let step = self.step.iter();
+            quote!(::cushy::widgets::NumberInput::new(__field.clone())
+                #(.minimum(#min))*
+                #(.maximum(#max))*
+                #(.step_by(#step))*)
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(editor))
+    }
+}
+
+/// Converts an identifier such as `window_title` or `WindowTitle` into a label
+/// such as `Window title`.
+fn humanize(ident: &str) -> String {
+    let mut label = String::with_capacity(ident.len());
+    let mut previous_lowercase = false;
+    for ch in ident.trim_start_matches("r#").chars() {
+        if ch == '_' {
+            if !label.is_empty() && !label.ends_with(' ') {
+                label.push(' ');
+            }
+            previous_lowercase = false;
+            continue;
+        }
+        if ch.is_uppercase() && previous_lowercase {
+            label.push(' ');
+        }
+        previous_lowercase = ch.is_lowercase() || ch.is_ascii_digit();
+
+        if label.is_empty() {
+            label.extend(ch.to_uppercase());
+        } else if label.ends_with(' ') {
+            label.extend(ch.to_lowercase());
+        } else {
+            label.push(ch);
+        }
+    }
+    label
+}
+
+pub fn editable(
+    DeriveInput {
+        ident: item_ident,
+        generics,
+        data,
+        ..
+    }: DeriveInput,
+) -> Result<TokenStream> {
+    // `Editable` requires `'static` types, and editing a generic field
+    // requires knowing how to edit its type.
+    if let Some(generic) = generics.params.first() {
+        bail!(generic, "generics not supported");
+    }
+
+    let (section, body) = match data {
+        Data::Struct(data) => {
+            let fields = data
+                .fields
+                .into_iter()
+                .enumerate()
+                .map(
+                    |(
+                        idx,
+                        Field {
+                            attrs, ident, ty, ..
+                        },
+                    )| {
+                        let options = Options::parse(&attrs)?;
+                        if options.skip {
+                            return Ok(None);
+                        }
+                        let label = match (&options.label, &ident) {
+                            (Some(label), _) => label.value(),
+                            (None, Some(ident)) => humanize(&ident.to_string()),
+                            (None, None) => idx.to_string(),
+                        };
+                        let member = ident
+                            .map(ToTokens::into_token_stream)
+                            .unwrap_or_else(|| Literal::usize_unsuffixed(idx).into_token_stream());
+
+                        let editor = options.editor(&ty)?;
+                        let call = match (editor, &options.validate) {
+                            (None, None) => quote!(edit(#label, __field)),
+                            (editor, validate) => {
+                                let mut editor = editor.unwrap_or_else(|| {
+                                    quote!(<#ty as ::cushy::widgets::inspector::Editable>::editor(__field.clone()))
+                                });
+                                if let Some(validate) = validate {
+                                    editor = quote!(::cushy::widgets::Validated::new(
+                                        __field.validate_with(#validate),
+                                        #editor,
+                                    ));
+                                }
+                                quote!(field(#label, #editor))
+                            }
+                        };
+
+                        Ok(Some(quote! {
+                            let __field = ::cushy::widgets::inspector::linked_field(
+                                &__value,
+                                |__value: &Self| &__value.#member,
+                                |__value: &mut Self| &mut __value.#member,
+                            );
+                            let __form = __form.#call;
+                        }))
+                    },
+                )
+                .filter_map(Result::transpose)
+                .collect::<Result<Vec<_>>>()?;
+
+            (
+                true,
+                quote! {
+                    let __form = ::cushy::widgets::inspector::Form::new();
+                    #(#fields)*
+                    ::cushy::widget::MakeWidget::make_widget(__form)
+                },
+            )
+        }
+        Data::Enum(data) => {
+            let variants = data
+                .variants
+                .into_iter()
+                .map(
+                    |Variant {
+                         attrs,
+                         ident,
+                         fields,
+                         ..
+                     }| {
+                        ensure!(fields.is_empty(), fields, "enum fields are not supported");
+                        let options = Options::parse(&attrs)?;
+                        if options.skip {
+                            return Ok(None);
+                        }
+                        let label = options
+                            .label
+                            .map_or_else(|| humanize(&ident.to_string()), |label| label.value());
+                        Ok(Some(
+                            quote!(.and(__value.new_select(#item_ident::#ident, #label))),
+                        ))
+                    },
+                )
+                .filter_map(Result::transpose)
+                .collect::<Result<Vec<_>>>()?;
+
+            (
+                false,
+                quote! {
+                    ::cushy::widget::MakeWidget::make_widget(
+                        ::cushy::widget::WidgetList::new()
+                            #(#variants)*
+                            .into_rows()
+                    )
+                },
+            )
+        }
+        Data::Union(union) => bail!((union.union_token, union.fields), "unions not supported"),
+    };
+
+    Ok(quote! {
+        impl ::cushy::widgets::inspector::Editable for #item_ident {
+            const SECTION: bool = #section;
+
+            fn editor(__value: ::cushy::value::Dynamic<Self>) -> ::cushy::widget::WidgetInstance {
+                #body
+            }
+        }
+    })
+}
+
+#[test]
+fn humanized_labels() {
+    assert_eq!(humanize("window_title"), "Window title");
+    assert_eq!(humanize("WindowTitle"), "Window title");
+    assert_eq!(humanize("max_fps_2"), "Max fps 2");
+    assert_eq!(humanize("r#type"), "Type");
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    expansion_snapshot! {struct_
+        #[derive(editable)]
+        struct Settings {
+            name: String,
+            #[editable(label = "Master volume", slider, min = 0, max = 100)]
+            volume: u8,
+            #[editable(min = 1, max = 10, step = 2)]
+            count: u32,
+            #[editable(text)]
+            port: u16,
+            #[editable(with = custom_editor)]
+            custom: Custom,
+            #[editable(validate = check_name)]
+            validated: String,
+            #[editable(skip)]
+            skipped: bool,
+        }
+    }
+    expansion_snapshot! {tuple_struct
+        #[derive(editable)]
+        struct Pair(String, #[editable(label = "Enabled")] bool);
+    }
+    expansion_snapshot! {enum_
+        #[derive(editable)]
+        enum Mode {
+            Fast,
+            #[editable(label = "Slow and steady")]
+            SlowAndSteady,
+            #[editable(skip)]
+            Hidden,
+        }
+    }
+
+    #[test]
+    fn generics_are_rejected() {
+        use syn::parse_quote;
+
+        assert!(editable(parse_quote!(
+            struct Typed<T>(T);
+        ))
+        .is_err());
+        assert!(editable(parse_quote!(
+            struct Borrowed<'a>(&'a str);
+        ))
+        .is_err());
+        assert!(editable(parse_quote!(
+            struct Sized<const N: usize>([u8; N]);
+        ))
+        .is_err());
+        assert!(editable(parse_quote!(
+            enum Mode<T> {
+                A,
+                B(T),
+            }
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn invalid_options_are_rejected() {
+        use syn::parse_quote;
+
+        assert!(editable(parse_quote!(
+            struct Slider(#[editable(slider)] u8);
+        ))
+        .is_err());
+        assert!(editable(parse_quote!(
+            struct Both(#[editable(slider, text, min = 0, max = 1)] u8);
+        ))
+        .is_err());
+        assert!(editable(parse_quote!(
+            struct Unknown(#[editable(unknown)] u8);
+        ))
+        .is_err());
+    }
+}
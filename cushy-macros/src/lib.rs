@@ -24,6 +24,10 @@ macro_rules! expansion_snapshot {
 }
 
 mod animation;
+mod editable;
 
 #[manyhow(proc_macro_derive(LinearInterpolate))]
 pub use animation::linear_interpolate;
+
+#[manyhow(proc_macro_derive(Editable, attributes(editable)))]
+pub use editable::editable;
@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use cushy::value::{Dynamic, Source};
+use cushy::widget::MakeWidget;
+use cushy::widgets::animated_image::{AnimationFrame, LoopCount};
+use cushy::widgets::checkbox::Checkable;
+use cushy::widgets::image::ImageScaling;
+use cushy::widgets::AnimatedImage;
+use cushy::Run;
+use image::{DynamicImage, Rgba, RgbaImage};
+use kludgine::wgpu::FilterMode;
+use kludgine::LazyTexture;
+
+const FRAMES: u32 = 12;
+const SIZE: u32 = 64;
+
+fn main() -> cushy::Result {
+    // AnimatedImage::from_path and AnimatedImage::from_bytes decode animated
+    // PNGs and GIFs. This example generates its frames instead.
+    let frames = (0..FRAMES)
+        .map(|frame| {
+            let filled = (frame + 1) * SIZE / FRAMES;
+            let image = RgbaImage::from_fn(SIZE, SIZE, |x, _| {
+                if x < filled {
+                    Rgba([255, 128, 0, 255])
+                } else {
+                    Rgba([64, 64, 64, 255])
+                }
+            });
+            AnimationFrame::new(
+                LazyTexture::from_image(DynamicImage::ImageRgba8(image), FilterMode::Nearest),
+                Duration::from_millis(80),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let playing = Dynamic::new(true);
+    let loop_forever = Dynamic::new(true);
+    let loops = loop_forever.map_each(|forever| {
+        if *forever {
+            LoopCount::Forever
+        } else {
+            LoopCount::Times(3)
+        }
+    });
+
+    AnimatedImage::new(frames)
+        .playing(playing.clone())
+        .loops(loops)
+        .scaling(ImageScaling::Scale(4.))
+        .and(playing.into_checkbox("Playing"))
+        .and(loop_forever.into_checkbox("Loop forever"))
+        .into_rows()
+        .centered()
+        .run()
+}
@@ -0,0 +1,32 @@
+use cushy::widget::{MakeWidget, WidgetList};
+use cushy::widgets::image::ImageScaling;
+use cushy::widgets::Image;
+use cushy::Run;
+
+fn main() -> cushy::Result {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/examples/assets/ferris-happy.png"
+    );
+
+    // Each thumbnail loads the same path, so they share a single texture from
+    // the shared texture cache.
+    let thumbnails = (0..4)
+        .map(|_| Image::from_path(path).scaling(ImageScaling::Scale(0.25)))
+        .collect::<WidgetList>()
+        .into_columns();
+
+    "Loaded from a path"
+        .and(thumbnails)
+        .and("Decoded from bytes")
+        .and(
+            Image::from_bytes(include_bytes!("assets/ferris-happy.png"))
+                .scaling(ImageScaling::Scale(0.5)),
+        )
+        .and("Failed to load")
+        .and(Image::from_path("missing.png").placeholder("Loading..."))
+        .into_rows()
+        .vertical_scroll()
+        .expand()
+        .run()
+}
@@ -0,0 +1,41 @@
+use cushy::value::{Dynamic, Source};
+use cushy::widget::MakeWidget;
+use cushy::widgets::calendar::{Date, DateRange, Time, Weekday};
+use cushy::widgets::layers::OverlayLayer;
+use cushy::widgets::{Calendar, DatePicker, TimePicker};
+use cushy::Run;
+
+fn main() -> cushy::Result {
+    let overlay = OverlayLayer::default();
+    let today = Date::today();
+    let date = Dynamic::new(today);
+    let time = Dynamic::new(Time::now());
+    let trip = Dynamic::new(DateRange::new(today, today.add_days(6)));
+    let trip_length = trip.map_each(|trip| format!("{} days", trip.day_count()));
+
+    let pickers = "Weekday appointment"
+        .and(
+            DatePicker::new(date, &overlay)
+                .minimum(today)
+                .disabled_when(|date| {
+                    matches!(date.weekday(), Weekday::Saturday | Weekday::Sunday)
+                }),
+        )
+        .and("Time")
+        .and(TimePicker::new(time, &overlay))
+        .into_rows();
+
+    let range = "Trip"
+        .and(Calendar::range(trip))
+        .and(trip_length)
+        .into_rows();
+
+    pickers
+        .and(range)
+        .into_columns()
+        .centered()
+        .expand()
+        .and(overlay)
+        .into_layers()
+        .run()
+}
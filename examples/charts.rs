@@ -0,0 +1,59 @@
+use cushy::value::{Destination, Dynamic};
+use cushy::widget::MakeWidget;
+use cushy::widgets::{Chart, Series};
+use cushy::Run;
+use rand::{thread_rng, Rng};
+
+fn random_values(count: usize) -> Vec<f64> {
+    let mut rng = thread_rng();
+    (0..count).map(|_| rng.gen_range(0.0..100.0)).collect()
+}
+
+fn main() -> cushy::Result {
+    let first = Dynamic::new(random_values(12));
+    let second = Dynamic::new(random_values(12));
+    let points = Dynamic::new(
+        random_values(40)
+            .into_iter()
+            .zip(random_values(40))
+            .collect::<Vec<_>>(),
+    );
+
+    let randomize = "Randomize".into_button().on_click({
+        let first = first.clone();
+        let second = second.clone();
+        let points = points.clone();
+        move |()| {
+            first.set(random_values(12));
+            second.set(random_values(12));
+            points.set(
+                random_values(40)
+                    .into_iter()
+                    .zip(random_values(40))
+                    .collect(),
+            );
+        }
+    });
+
+    let sparkline = "Sparkline"
+        .and(Chart::sparkline(first.clone()))
+        .into_columns();
+
+    let line = Chart::line([
+        Series::values(first.clone()),
+        Series::values(second.clone()),
+    ])
+    .expand();
+    let area = Chart::area([Series::values(second.clone())]).expand();
+    let bar = Chart::bar([Series::values(first), Series::values(second)]).expand();
+    let scatter = Chart::scatter([Series::new(points)]).expand();
+
+    randomize
+        .and(sparkline)
+        .and(line.and(area).into_columns().expand())
+        .and(bar.and(scatter).into_columns().expand())
+        .into_rows()
+        .pad()
+        .expand()
+        .run()
+}
@@ -0,0 +1,54 @@
+use cushy::value::Dynamic;
+use cushy::widget::MakeWidget;
+use cushy::widgets::code_view::Language;
+use cushy::widgets::CodeView;
+use cushy::Run;
+
+const RUST: &str = r#"use std::collections::HashMap;
+
+/// Counts how many times each word appears in `text`.
+fn count_words(text: &str) -> HashMap<&str, usize> {
+    let mut counts = HashMap::new();
+    for word in text.split_whitespace() {
+        *counts.entry(word).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn main() {
+    let counts = count_words("the quick brown fox jumps over the lazy dog");
+    println!("{counts:?}");
+}
+"#;
+
+const TOML: &str = r#"[package]
+name = "example"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+cushy = { version = "0.2.0", features = ["markdown"] }
+"#;
+
+fn main() -> cushy::Result {
+    let source = Dynamic::new(String::from(RUST));
+
+    "Editable Rust"
+        .and(
+            CodeView::new(source)
+                .highlighter(Language::Rust)
+                .editable()
+                .vertical_scroll()
+                .expand(),
+        )
+        .and("Read-only TOML")
+        .and(
+            CodeView::new(Dynamic::new(String::from(TOML)))
+                .highlighter(Language::Toml)
+                .line_numbers(false),
+        )
+        .into_rows()
+        .pad()
+        .expand()
+        .run()
+}
@@ -0,0 +1,26 @@
+use cushy::value::{Dynamic, Source};
+use cushy::widget::MakeWidget;
+use cushy::widgets::{Disclose, DiscloseGroup};
+use cushy::Run;
+
+fn main() -> cushy::Result {
+    let expanded = Dynamic::new(Some(0));
+    let summary = expanded.map_each(|expanded| match expanded {
+        Some(index) => format!("Section {} is expanded", index + 1),
+        None => String::from("All sections are collapsed"),
+    });
+
+    summary
+        .and(
+            DiscloseGroup::new([
+                Disclose::new("Display settings".contain()).labelled_by("Display"),
+                Disclose::new("Sound settings".contain()).labelled_by("Sound"),
+                Disclose::new("Network settings".contain()).labelled_by("Network"),
+            ])
+            .expanded(expanded),
+        )
+        .into_rows()
+        .pad()
+        .expand_horizontally()
+        .run()
+}
@@ -0,0 +1,46 @@
+use cushy::value::{Destination, Dynamic};
+use cushy::widget::MakeWidget;
+use cushy::widgets::file_browser::{FileDialog, FileFilter};
+use cushy::widgets::Modal;
+use cushy::Run;
+
+fn main() -> cushy::Result {
+    let modal = Modal::new();
+    let chosen = Dynamic::new(String::from("Nothing chosen yet"));
+
+    "Open..."
+        .into_button()
+        .on_click({
+            let modal = modal.clone();
+            let chosen = chosen.clone();
+            move |()| {
+                let chosen = chosen.clone();
+                FileDialog::open()
+                    .multiple()
+                    .filter(FileFilter::extensions("Rust source", ["rs"]))
+                    .filter(FileFilter::all())
+                    .on_confirm(move |paths| chosen.set(format!("Opened {paths:?}")))
+                    .show_in(&modal);
+            }
+        })
+        .and("Save As...".into_button().on_click({
+            let modal = modal.clone();
+            let chosen = chosen.clone();
+            move |()| {
+                let confirmed = chosen.clone();
+                let cancelled = chosen.clone();
+                FileDialog::save()
+                    .file_name("untitled.txt")
+                    .on_confirm(move |paths| confirmed.set(format!("Saving to {paths:?}")))
+                    .on_cancel(move || cancelled.set(String::from("Save cancelled")))
+                    .show_in(&modal);
+            }
+        }))
+        .into_columns()
+        .and(chosen)
+        .into_rows()
+        .centered()
+        .and(modal)
+        .into_layers()
+        .run()
+}
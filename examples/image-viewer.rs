@@ -0,0 +1,54 @@
+use cushy::figures::Point;
+use cushy::value::{Destination, Dynamic};
+use cushy::widget::MakeWidget;
+use cushy::widgets::image_viewer::ImageViewer;
+use cushy::Run;
+use kludgine::include_texture;
+
+fn main() -> cushy::Result {
+    let texture = include_texture!("assets/ferris-happy.png").expect("valid image");
+
+    // Both viewers share the same zoom, offset, and fit state, which keeps
+    // them synchronized as either one is zoomed or panned.
+    let zoom = Dynamic::new(1.);
+    let offset = Dynamic::new(Point::default());
+    let fit = Dynamic::new(true);
+
+    let viewer = |texture| {
+        ImageViewer::new(texture)
+            .zoom(zoom.clone())
+            .offset(offset.clone())
+            .fit(fit.clone())
+            .minimap()
+            .expand()
+    };
+
+    let controls = "Fit"
+        .into_button()
+        .on_click({
+            let fit = fit.clone();
+            move |()| fit.set(true)
+        })
+        .and("Actual Size".into_button().on_click({
+            let fit = fit.clone();
+            let zoom = zoom.clone();
+            let offset = offset.clone();
+            move |()| {
+                fit.set(false);
+                zoom.set(1.);
+                offset.set(Point::default());
+            }
+        }))
+        .into_columns();
+
+    controls
+        .and(
+            viewer(texture.clone())
+                .and(viewer(texture))
+                .into_columns()
+                .expand(),
+        )
+        .into_rows()
+        .expand()
+        .run()
+}
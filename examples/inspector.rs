@@ -0,0 +1,62 @@
+use cushy::value::{Dynamic, Source};
+use cushy::widget::MakeWidget;
+use cushy::widgets::inspector::{Editable, Inspector};
+use cushy::Run;
+
+#[derive(Editable, Clone, PartialEq, Debug)]
+enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+    #[editable(label = "Nightmare!")]
+    Nightmare,
+}
+
+#[derive(Editable, Clone, PartialEq, Debug)]
+struct Audio {
+    muted: bool,
+    #[editable(slider, min = 0., max = 1.)]
+    music_volume: f32,
+    #[editable(slider, min = 0., max = 1.)]
+    effects_volume: f32,
+}
+
+#[derive(Editable, Clone, PartialEq, Debug)]
+struct Settings {
+    #[editable(label = "Player name", validate = valid_name)]
+    name: String,
+    #[editable(min = 1, max = 99)]
+    level: u8,
+    difficulty: Difficulty,
+    audio: Audio,
+}
+
+fn valid_name(name: &String) -> Result<(), &'static str> {
+    if name.trim().is_empty() {
+        Err("A name is required")
+    } else {
+        Ok(())
+    }
+}
+
+fn main() -> cushy::Result {
+    let settings = Dynamic::new(Settings {
+        name: String::from("Ferris"),
+        level: 1,
+        difficulty: Difficulty::Normal,
+        audio: Audio {
+            muted: false,
+            music_volume: 0.8,
+            effects_volume: 1.0,
+        },
+    });
+    let summary = settings.map_each(|settings| format!("{settings:#?}"));
+
+    Inspector::new(settings)
+        .expand()
+        .and(summary.expand())
+        .into_columns()
+        .pad()
+        .expand()
+        .run()
+}
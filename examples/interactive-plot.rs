@@ -0,0 +1,37 @@
+use cushy::value::{Dynamic, Source};
+use cushy::widget::MakeWidget;
+use cushy::widgets::InteractivePlot;
+use cushy::Run;
+use plotters::prelude::*;
+
+fn main() -> cushy::Result<()> {
+    let samples = Dynamic::new(
+        (0..=200)
+            .map(|i| {
+                let x = f64::from(i) / 10.;
+                (x, x.sin() * (x / 5.).cos())
+            })
+            .collect::<Vec<_>>(),
+    );
+    let x = Dynamic::new(0.0..20.0);
+    let y = Dynamic::new(-1.5..1.5);
+
+    let x_label = x.map_each(|x| format!("x: {:.2} to {:.2}", x.start, x.end));
+    let y_label = y.map_each(|y| format!("y: {:.2} to {:.2}", y.start, y.end));
+
+    x_label
+        .and(y_label)
+        .into_columns()
+        .and(
+            InteractivePlot::new(x, y, move |chart, plot| {
+                let samples = samples.get_tracking_redraw(plot.widget);
+                chart.configure_mesh().draw()?;
+                chart.draw_series(LineSeries::new(samples.iter().copied(), &BLUE))?;
+                plot.hoverable("signal", samples);
+                Ok(())
+            })
+            .expand(),
+        )
+        .into_rows()
+        .run()
+}
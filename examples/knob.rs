@@ -0,0 +1,33 @@
+use cushy::value::{Dynamic, Source};
+use cushy::widget::MakeWidget;
+use cushy::widgets::knob::Turnable;
+use cushy::Run;
+
+fn main() -> cushy::Result {
+    let gain = Dynamic::new(0.5_f32);
+    let gain_label = gain.map_each(|gain| format!("Gain: {:.0}%", gain * 100.));
+    let pan = Dynamic::new(0_i8);
+    let pan_label = pan.map_each(|pan| format!("Pan: {pan}"));
+    let octave = Dynamic::new(4_u8);
+    let octave_label = octave.map_each(|octave| format!("Octave: {octave}"));
+
+    gain.knob_between(0., 1.)
+        .and(gain_label)
+        .into_rows()
+        .and(
+            pan.knob_between(-50, 50)
+                .bipolar(0)
+                .and(pan_label)
+                .into_rows(),
+        )
+        .and(
+            octave
+                .knob_between(0, 8)
+                .detents(9)
+                .and(octave_label)
+                .into_rows(),
+        )
+        .into_columns()
+        .centered()
+        .run()
+}
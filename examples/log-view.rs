@@ -0,0 +1,68 @@
+//! Displays this application's own tracing output in a `LogView`.
+use std::time::Duration;
+
+use cushy::value::Dynamic;
+use cushy::widget::{MakeWidget, WidgetList};
+use cushy::widgets::checkbox::Checkable;
+use cushy::widgets::input::InputValue;
+use cushy::widgets::log_view::LogBuffer;
+use cushy::widgets::LogView;
+use cushy::Run;
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+fn main() -> cushy::Result {
+    let logs = LogBuffer::new();
+    tracing_subscriber::registry()
+        .with(LevelFilter::DEBUG)
+        .with(logs.layer())
+        .init();
+
+    // Simulate a long-running task that periodically logs its status.
+    std::thread::spawn(|| {
+        for step in 0_u64.. {
+            match step % 10 {
+                0 => tracing::warn!(step, "falling behind"),
+                7 => tracing::error!(step, "request failed"),
+                3 | 5 => tracing::debug!(step, "cache hit"),
+                _ => tracing::info!(step, "processed batch"),
+            }
+            std::thread::sleep(Duration::from_millis(250));
+        }
+    });
+
+    let filter = Dynamic::<String>::default();
+    let level = Dynamic::new(LevelFilter::TRACE);
+    let following = Dynamic::new(true);
+
+    let levels = [
+        ("All", LevelFilter::TRACE),
+        ("Info", LevelFilter::INFO),
+        ("Warnings", LevelFilter::WARN),
+        ("Errors", LevelFilter::ERROR),
+    ]
+    .into_iter()
+    .map(|(label, value)| level.new_select(value, label))
+    .collect::<WidgetList>()
+    .into_columns();
+
+    filter
+        .clone()
+        .into_input()
+        .placeholder("Filter")
+        .expand_horizontally()
+        .and(levels)
+        .and(following.clone().into_checkbox("Follow"))
+        .into_columns()
+        .and(
+            LogView::new(&logs)
+                .filter(filter)
+                .level(level)
+                .following(following)
+                .expand(),
+        )
+        .into_rows()
+        .expand()
+        .run()
+}
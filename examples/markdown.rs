@@ -0,0 +1,48 @@
+use cushy::value::Dynamic;
+use cushy::widget::MakeWidget;
+use cushy::widgets::input::InputValue;
+use cushy::widgets::Markdown;
+use cushy::Run;
+
+const INITIAL_MARKDOWN: &str = r#"# Markdown
+
+This is a **live** preview of the _Markdown_ entered on the left. Inline
+`code` and [links](https://github.com/khonsulabs/cushy) are supported.
+
+## Lists
+
+1. Ordered lists
+2. Unordered lists
+   - Including nested lists
+
+> Block quotes are shown with an accent.
+
+```rust
+fn main() {
+    println!("Fenced code blocks are contained.");
+}
+```
+
+| Widget   | Purpose           |
+|----------|-------------------|
+| Markdown | Renders CommonMark |
+| RichText | Styled spans       |
+"#;
+
+fn main() -> cushy::Result {
+    let source = Dynamic::new(String::from(INITIAL_MARKDOWN));
+
+    source
+        .to_input()
+        .expand()
+        .and(
+            Markdown::new(source)
+                .on_link(|url| println!("Clicked {url}"))
+                .vertical_scroll()
+                .expand(),
+        )
+        .into_columns()
+        .expand()
+        .pad()
+        .run()
+}
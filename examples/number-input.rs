@@ -0,0 +1,31 @@
+use cushy::value::{Dynamic, MapEach};
+use cushy::widget::MakeWidget;
+use cushy::widgets::number_input::NumberFormat;
+use cushy::widgets::NumberInput;
+use cushy::Run;
+use figures::units::Lp;
+
+fn main() -> cushy::Result {
+    let quantity = Dynamic::new(1_u32);
+    let price = Dynamic::new(9.99_f64);
+    let total = (&quantity, &price)
+        .map_each_cloned(|(quantity, price)| format!("Total: {:.2}", f64::from(quantity) * price));
+
+    NumberInput::new(quantity)
+        .minimum(1)
+        .maximum(1_000)
+        .label("Quantity")
+        .and(
+            NumberInput::new(price)
+                .minimum(0.)
+                .step_by(0.25)
+                .format(NumberFormat::PLAIN)
+                .label("Price"),
+        )
+        .and(total)
+        .into_rows()
+        .width(Lp::points(300))
+        .centered()
+        .expand()
+        .run()
+}
@@ -0,0 +1,36 @@
+use cushy::value::{Destination, Dynamic, Source};
+use cushy::widget::MakeWidget;
+use cushy::widgets::rich_text::Span;
+use cushy::widgets::RichText;
+use cushy::Run;
+use kludgine::Color;
+
+fn main() -> cushy::Result {
+    let clicks = Dynamic::new(0_usize);
+    let label = clicks.map_each(|clicks| format!("The link has been clicked {clicks} times."));
+
+    RichText::new(vec![
+        Span::new("Rich text can mix "),
+        Span::new("bold").bold(),
+        Span::new(", "),
+        Span::new("italic").italic(),
+        Span::new(", and "),
+        Span::new("monospace").monospace(),
+        Span::new(" text. Spans can be "),
+        Span::new("colored").colored(Color::RED),
+        Span::new(", "),
+        Span::new("underlined").underlined(),
+        Span::new(", or "),
+        Span::new("highlighted")
+            .background_color(Color::YELLOW)
+            .colored(Color::BLACK),
+        Span::new(". Spans can also be "),
+        Span::new("links").on_click(move |()| clicks.map_mut(|mut clicks| *clicks += 1)),
+        Span::new(" that can be clicked or activated using the keyboard."),
+    ])
+    .and(label)
+    .into_rows()
+    .centered()
+    .pad()
+    .run()
+}
@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use cushy::animation::ZeroToOne;
+use cushy::value::{Destination, Dynamic, Source};
+use cushy::widget::MakeWidget;
+use cushy::widgets::progress::{Progress, ProgressTracker, SegmentedProgress};
+use cushy::widgets::slider::Slidable;
+use cushy::widgets::ProgressBar;
+use cushy::Run;
+use figures::units::Lp;
+use figures::Size;
+
+fn main() -> cushy::Result {
+    let verified = Dynamic::new(ZeroToOne::new(0.3));
+    let downloaded = Dynamic::new(ZeroToOne::new(0.2));
+    let failed = Dynamic::new(ZeroToOne::new(0.05));
+
+    let import = Dynamic::new(Progress::Percent(ZeroToOne::ZERO));
+    let estimate = ProgressTracker::new().track(import.clone());
+    let status = estimate.map_each(|estimate| match estimate.remaining {
+        Some(remaining) => format!("{}s remaining", remaining.as_secs()),
+        None => String::from("Estimating..."),
+    });
+    std::thread::spawn({
+        let import = import.clone();
+        move || loop {
+            for step in 0..=100_u8 {
+                import.set(Progress::Percent(ZeroToOne::new(f32::from(step) / 100.)));
+                std::thread::sleep(Duration::from_millis(100));
+            }
+        }
+    });
+
+    "Verified"
+        .and(verified.clone().slider())
+        .and("Downloaded")
+        .and(downloaded.clone().slider())
+        .and("Failed")
+        .and(failed.clone().slider())
+        .and(
+            SegmentedProgress::new()
+                .segment(verified.clone())
+                .segment(downloaded.clone())
+                .segment(failed.clone()),
+        )
+        .and(
+            SegmentedProgress::new()
+                .segment(verified)
+                .segment(downloaded)
+                .segment(failed)
+                .circular()
+                .size(Size::squared(Lp::inches(1))),
+        )
+        .and("Import")
+        .and(ProgressBar::new(import.clone()).percent_text())
+        .and(
+            ProgressBar::new(import)
+                .spinner()
+                .percent_text()
+                .size(Size::squared(Lp::inches(1))),
+        )
+        .and(status)
+        .into_rows()
+        .pad()
+        .width(Lp::inches(4))
+        .centered()
+        .run()
+}
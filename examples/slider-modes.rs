@@ -0,0 +1,71 @@
+use cushy::animation::ZeroToOne;
+use cushy::value::{Dynamic, Source};
+use cushy::widget::MakeWidget;
+use cushy::widgets::layers::OverlayLayer;
+use cushy::widgets::slider::{Slidable, SliderMapping};
+use cushy::Run;
+use figures::units::Lp;
+
+fn main() -> cushy::Result {
+    let overlay = OverlayLayer::default();
+
+    let frequency = Dynamic::new(440_f32);
+    let frequency_label = frequency.map_each(|hz| format!("{hz:.0} Hz"));
+    let frequency = "Frequency"
+        .and(
+            frequency
+                .slider_between(20., 20_000.)
+                .logarithmic()
+                .ticks([20., 100., 1_000., 10_000., 20_000.])
+                .tick_labels()
+                .format_values(|hz| format!("{hz:.0}"))
+                .value_tooltip(&overlay),
+        )
+        .and(frequency_label)
+        .into_rows();
+
+    let zoom = Dynamic::new(1_f32);
+    let zoom = "Zoom"
+        .and(
+            zoom.slider_between(0.25, 4.)
+                .mapping(SliderMapping::new(
+                    |percent| ZeroToOne::new(percent.sqrt()),
+                    |position| ZeroToOne::new(*position * *position),
+                ))
+                .evenly_spaced_ticks(5)
+                .tick_labels()
+                .format_values(|zoom| format!("{zoom:.2}x"))
+                .value_tooltip(&overlay),
+        )
+        .into_rows();
+
+    let volume = Dynamic::new(50_u8);
+    let band = Dynamic::new(20_u8..=80);
+    let vertical = volume
+        .slider_between(0, 100)
+        .vertical()
+        .evenly_spaced_ticks(11)
+        .tick_labels()
+        .value_tooltip(&overlay)
+        .and(
+            band.slider_between(0, 100)
+                .vertical()
+                .evenly_spaced_ticks(5)
+                .page_step_by(ZeroToOne::new(0.1)),
+        )
+        .into_columns()
+        .height(Lp::inches(3));
+
+    frequency
+        .and(zoom)
+        .into_rows()
+        .width(Lp::inches(5))
+        .and(vertical)
+        .into_columns()
+        .pad()
+        .centered()
+        .expand()
+        .and(overlay)
+        .into_layers()
+        .run()
+}
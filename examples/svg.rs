@@ -0,0 +1,32 @@
+use cushy::styles::components::TextColor;
+use cushy::widget::MakeWidget;
+use cushy::widgets::Svg;
+use cushy::Run;
+use figures::units::Lp;
+use figures::Size;
+use kludgine::Color;
+
+// `currentColor` is replaced with the current text color when rasterizing.
+const ICON: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="24" height="24" viewBox="0 0 24 24">
+    <circle cx="12" cy="12" r="10" fill="none" stroke="currentColor" stroke-width="2"/>
+    <path d="M8 12l3 3 5-6" fill="none" stroke="currentColor" stroke-width="2"/>
+</svg>"#;
+
+fn icon() -> Svg {
+    Svg::from_bytes(ICON.as_bytes()).expect("valid svg")
+}
+
+fn main() -> cushy::Result {
+    // Each icon is rasterized at the exact size it is displayed at.
+    icon()
+        .and(icon().size(Size::squared(Lp::points(48))))
+        .and(
+            icon()
+                .size(Size::squared(Lp::points(96)))
+                .with(&TextColor, Color::RED),
+        )
+        .and(icon().color(Color::GREEN).expand())
+        .into_columns()
+        .expand()
+        .run()
+}
@@ -0,0 +1,25 @@
+use cushy::figures::units::Px;
+use cushy::figures::Point;
+use cushy::kludgine::tilemap::TileMapFocus;
+use cushy::tiled::TiledMap;
+use cushy::widgets::TileMap;
+use cushy::Run;
+
+fn main() -> cushy::Result {
+    let map = TiledMap::load(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/assets/tiled/test.tmx"
+    ))
+    .expect("valid map");
+
+    // Tiled pixels are world pixels, so the center of the map is half of its
+    // size in pixels.
+    let center = Point::new(
+        Px::new((map.size.width * map.tile_size.width) as i32 / 2),
+        Px::new((map.size.height * map.tile_size.height) as i32 / 2),
+    );
+
+    TileMap::new(map.into_layers().expect("tileset images load"))
+        .focus_on(TileMapFocus::Point(center))
+        .run()
+}
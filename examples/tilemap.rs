@@ -12,24 +12,29 @@ use cushy::kludgine::tilemap::{
 };
 use cushy::kludgine::Color;
 use cushy::value::{Destination, Dynamic};
+use cushy::widgets::sprite::{SpriteAnimation, SpriteSheet};
+use cushy::widgets::tilemap::{Camera, CameraFollow, ScreenShake};
 use cushy::widgets::TileMap;
 use cushy::{Run, Tick};
 use figures::FloatConversion;
 use kludgine::app::winit::keyboard::NamedKey;
-use kludgine::sprite::{Sprite, SpriteSource};
-use kludgine::{include_aseprite_sprite, DrawableExt};
+use kludgine::{include_aseprite_sprite, include_texture, DrawableExt};
 
 const PLAYER_SIZE: Px = Px::new(16);
 
 fn main() -> cushy::Result {
     let mut characters = ObjectLayer::new();
 
-    let mut sprite = include_aseprite_sprite!("assets/stickguy").unwrap();
-    sprite.set_current_tag(Some("Idle")).unwrap();
+    let sheet = SpriteSheet::from_aseprite_json(
+        include_texture!("assets/stickguy.png").expect("valid image"),
+        include_str!("assets/stickguy.json"),
+    )
+    .expect("valid sprite sheet");
 
     let myself = characters.push(Player {
-        sprite,
-        current_frame: None,
+        animation: SpriteAnimation::new(sheet)
+            .with_tag("Idle")
+            .expect("valid tag"),
         hovered: false,
         position: Point::new(TILE_SIZE.into_float(), TILE_SIZE.into_float()),
     });
@@ -45,11 +50,19 @@ fn main() -> cushy::Result {
         DebugGrid,
     ));
 
+    let shake = ScreenShake::new();
+
     let tilemap = TileMap::dynamic(layers.clone())
         .focus_on(TileMapFocus::Object {
             layer: 1,
             id: myself,
         })
+        .camera(
+            Camera::new()
+                .follow(CameraFollow::critically_damped_spring(40.))
+                .dead_zone(Size::squared(PLAYER_SIZE * 2)),
+        )
+        .screen_shake(shake.clone())
         .tick(Tick::times_per_second(60, move |elapsed, input| {
             // get mouse cursor position and subsequently get the object under the cursor
 
@@ -67,6 +80,10 @@ fn main() -> cushy::Result {
                 direction.x -= 1.0;
             }
 
+            if input.keys.contains(&Key::Named(NamedKey::Space)) {
+                shake.impulse(Px::new(6), Duration::from_millis(300));
+            }
+
             let one_second_movement = direction * TILE_SIZE.into_float();
 
             let cursor_pos = input.mouse.as_ref().map(|mouse| mouse.position);
@@ -80,12 +97,10 @@ fn main() -> cushy::Result {
                     Ordering::Greater => "WalkRight",
                 };
                 player
-                    .sprite
-                    .set_current_tag(Some(animation_tag))
+                    .animation
+                    .set_tag(Some(animation_tag))
                     .expect("valid tag");
-
-                player.current_frame =
-                    Some(player.sprite.get_frame(Some(elapsed)).expect("valid tag"));
+                player.animation.advance(elapsed);
 
                 player.position += one_second_movement * elapsed.as_secs_f32();
 
@@ -100,8 +115,7 @@ fn main() -> cushy::Result {
 
 #[derive(Debug)]
 struct Player {
-    sprite: Sprite,
-    current_frame: Option<SpriteSource>,
+    animation: SpriteAnimation,
     hovered: bool,
     position: Point<f32>,
 }
@@ -128,14 +142,12 @@ impl Object for Player {
             );
         }
 
-        if let Some(frame) = &self.current_frame {
-            context.draw_texture(
-                frame,
-                Rect::new(center - zoomed_size / 2, Size::squared(zoomed_size)),
-                1.,
-            );
-        }
+        self.animation.draw(
+            Rect::new(center - zoomed_size / 2, Size::squared(zoomed_size)),
+            Color::WHITE,
+            context,
+        );
 
-        self.sprite.remaining_frame_duration().ok().flatten()
+        self.animation.remaining_frame_duration()
     }
 }
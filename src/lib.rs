@@ -17,6 +17,8 @@ pub mod styles;
 mod app;
 pub mod debug;
 mod tick;
+#[cfg(feature = "tiled")]
+pub mod tiled;
 mod tree;
 pub mod value;
 pub mod widget;
@@ -33,7 +35,9 @@ pub use utils::{Lazy, WithClone};
 pub use {figures, kludgine};
 
 pub use self::graphics::Graphics;
-pub use self::tick::{InputState, Tick};
+pub use self::tick::{InputState, Mouse, Tick};
+#[cfg(feature = "recording")]
+pub use self::tick::{RecordedTick, RecordingError, TickRecording};
 
 /// A limit used when measuring a widget.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -9,12 +9,19 @@ use intentional::Assert;
 use kludgine::app::winit::event::{ElementState, MouseButton};
 use kludgine::app::winit::keyboard::Key;
 
+use crate::animation::ZeroToOne;
 use crate::context::WidgetContext;
 use crate::utils::IgnorePoison;
 use crate::value::{Destination, Dynamic};
 use crate::widget::{EventHandling, HANDLED, IGNORED};
 use crate::window::KeyEvent;
 
+#[cfg(feature = "recording")]
+mod recording;
+
+#[cfg(feature = "recording")]
+pub use self::recording::{RecordedTick, RecordingError, TickRecording};
+
 /// A fixed-rate callback that provides access to tracked input on its
 /// associated widget.
 #[derive(Clone, Debug)]
@@ -26,6 +33,10 @@ pub struct Tick {
 
 impl Tick {
     /// Signals that this widget has been redrawn.
+    ///
+    /// Widgets driven by a [fixed update](Self::fixed_update) tick are redrawn
+    /// continuously so that they can [interpolate](Self::interpolation)
+    /// between updates.
     pub fn rendered(&self, context: &WidgetContext<'_>) {
         context.redraw_when_changed(&self.data.tick_number);
 
@@ -40,9 +51,12 @@ impl Tick {
     pub fn key_input(&self, input: &KeyEvent) -> EventHandling {
         let mut state = self.data.state();
         if input.state.is_pressed() {
-            state.input.keys.insert(input.logical_key.clone());
-        } else {
-            state.input.keys.remove(&input.logical_key);
+            // Repeated key presses are not new presses.
+            if state.input.keys.insert(input.logical_key.clone()) {
+                state.input.pressed_keys.insert(input.logical_key.clone());
+            }
+        } else if state.input.keys.remove(&input.logical_key) {
+            state.input.released_keys.insert(input.logical_key.clone());
         }
         drop(state);
 
@@ -59,15 +73,15 @@ impl Tick {
         match pos {
             Some(pos) => {
                 if state.input.mouse.is_none() {
-                    state.input.mouse = Some(Mouse::default());
+                    state.input.mouse = Some(Mouse {
+                        position: pos,
+                        ..Mouse::default()
+                    });
                 }
 
-                state
-                    .input
-                    .mouse
-                    .as_mut()
-                    .assert("always initialized")
-                    .position = pos;
+                let mouse = state.input.mouse.as_mut().assert("always initialized");
+                mouse.delta += pos - mouse.position;
+                mouse.position = pos;
             }
             None => {
                 state.input.mouse = None;
@@ -80,9 +94,11 @@ impl Tick {
         let mut state = self.data.state();
         if let Some(mouse) = &mut state.input.mouse {
             if button_state.is_pressed() {
-                mouse.buttons.insert(button);
-            } else {
-                mouse.buttons.remove(&button);
+                if mouse.buttons.insert(button) {
+                    mouse.pressed_buttons.insert(button);
+                }
+            } else if mouse.buttons.remove(&button) {
+                mouse.released_buttons.insert(button);
             }
         }
     }
@@ -90,6 +106,113 @@ impl Tick {
     /// Returns a new tick that invokes `tick`, aiming to repeat at the given
     /// duration.
     pub fn new<F>(tick_every: Duration, tick: F) -> Self
+    where
+        F: FnMut(Duration, &InputState) + Send + 'static,
+    {
+        Self::spawn(tick_every, TickMode::Variable, tick)
+    }
+
+    /// Returns a new tick that invokes `update` once for every `step` of time
+    /// that elapses, always passing `step` as the elapsed time.
+    ///
+    /// Using a fixed timestep keeps game logic and physics deterministic
+    /// regardless of how quickly frames are rendered. If the updates fall
+    /// behind, at most [`DEFAULT_MAX_CATCH_UP`](Self::DEFAULT_MAX_CATCH_UP)
+    /// updates are performed before the remaining time is discarded. To use a
+    /// different limit, use [`Tick::fixed_update_with_max_catch_up`].
+    ///
+    /// Widgets driven by this tick are redrawn continuously. While redrawing,
+    /// [`Tick::interpolation`] returns how far the current frame is between
+    /// the last update and the next, which can be used to smoothly draw
+    /// objects between their previous and current positions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    pub fn fixed_update<F>(step: Duration, update: F) -> Self
+    where
+        F: FnMut(Duration, &InputState) + Send + 'static,
+    {
+        Self::fixed_update_with_max_catch_up(step, Self::DEFAULT_MAX_CATCH_UP, update)
+    }
+
+    /// Returns a new [fixed update](Self::fixed_update) tick that performs at
+    /// most `max_catch_up` updates at once to catch up after falling behind.
+    ///
+    /// When the limit is reached, the remaining time is discarded, which
+    /// causes the game to slow down rather than spending all of its time
+    /// updating. At least one update is always performed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    pub fn fixed_update_with_max_catch_up<F>(step: Duration, max_catch_up: u32, update: F) -> Self
+    where
+        F: FnMut(Duration, &InputState) + Send + 'static,
+    {
+        assert!(!step.is_zero(), "fixed update step must be non-zero");
+        Self::spawn(
+            step,
+            TickMode::Fixed {
+                max_catch_up: max_catch_up.max(1),
+            },
+            update,
+        )
+    }
+
+    /// Returns a new tick that replays `recording` by invoking `tick` with
+    /// each recorded elapsed duration and input state.
+    ///
+    /// Instead of measuring time with the system clock, a virtual clock is
+    /// advanced by the recorded durations, which reproduces the recorded
+    /// session exactly. The ticks are still paced at the rate they were
+    /// recorded so that the replay can be watched. Live input is ignored while
+    /// replaying. Once all ticks have been replayed, `tick` is no longer
+    /// invoked.
+    ///
+    /// To replay a recording without a widget, such as in a test, use
+    /// [`TickRecording::replay`].
+    ///
+    /// This function is only available when the `recording` feature is
+    /// enabled.
+    #[cfg(feature = "recording")]
+    pub fn replay<F>(recording: TickRecording, tick: F) -> Self
+    where
+        F: FnMut(Duration, &InputState) + Send + 'static,
+    {
+        let period = recording.period;
+        Self::spawn(period, TickMode::Replay(recording), tick)
+    }
+
+    /// Begins recording the elapsed duration and input state of every tick.
+    ///
+    /// If a recording is already in progress, it is discarded. The recording
+    /// is returned from [`Tick::stop_recording`].
+    ///
+    /// This function is only available when the `recording` feature is
+    /// enabled.
+    #[cfg(feature = "recording")]
+    pub fn start_recording(&self) {
+        let mut state = self.data.state();
+        state.recording = Some(TickRecording::new(self.data.period, self.data.fixed));
+    }
+
+    /// Stops recording and returns the recorded ticks, if
+    /// [recording was started](Self::start_recording).
+    #[cfg(feature = "recording")]
+    #[must_use]
+    pub fn stop_recording(&self) -> Option<TickRecording> {
+        self.data.state().recording.take()
+    }
+
+    /// Returns true if this tick is currently being recorded.
+    #[cfg(feature = "recording")]
+    #[must_use]
+    pub fn is_recording(&self) -> bool {
+        self.data.state().recording.is_some()
+    }
+
+    fn spawn<F>(period: Duration, mode: TickMode, tick: F) -> Self
     where
         F: FnMut(Duration, &InputState) + Send + 'static,
     {
@@ -98,11 +221,20 @@ impl Tick {
             state: Mutex::new(TickState {
                 last_time: now,
                 next_target: now,
+                simulated_until: now,
+                #[cfg(feature = "recording")]
+                recording: None,
                 keep_running: true,
                 frame: 0,
                 input: InputState::default(),
             }),
-            period: tick_every,
+            period,
+            #[cfg(feature = "recording")]
+            fixed: match &mode {
+                TickMode::Variable => false,
+                TickMode::Fixed { .. } => true,
+                TickMode::Replay(recording) => recording.fixed,
+            },
             sync: Condvar::new(),
             rendered_frame: AtomicUsize::new(0),
             tick_number: Dynamic::default(),
@@ -110,7 +242,12 @@ impl Tick {
 
         std::thread::spawn({
             let data = data.clone();
-            move || tick_loop(&data, tick)
+            move || match mode {
+                TickMode::Variable => tick_loop(&data, tick),
+                TickMode::Fixed { max_catch_up } => fixed_update_loop(&data, max_catch_up, tick),
+                #[cfg(feature = "recording")]
+                TickMode::Replay(recording) => replay_loop(&data, recording, tick),
+            }
         });
 
         Self {
@@ -119,6 +256,29 @@ impl Tick {
         }
     }
 
+    /// The default maximum number of updates a
+    /// [fixed update](Self::fixed_update) tick performs to catch up.
+    pub const DEFAULT_MAX_CATCH_UP: u32 = 5;
+
+    /// Returns how far between the most recent update and the next update the
+    /// current time is.
+    ///
+    /// This is primarily useful with [fixed update](Self::fixed_update) ticks:
+    /// drawing objects at their previous position plus the interpolation
+    /// factor multiplied by the distance to their current position hides the
+    /// difference between the update rate and the redraw rate.
+    #[must_use]
+    pub fn interpolation(&self) -> ZeroToOne {
+        let state = self.data.state();
+        let since_update = Instant::now().saturating_duration_since(state.simulated_until);
+        drop(state);
+        if self.data.period.is_zero() {
+            ZeroToOne::ONE
+        } else {
+            ZeroToOne::new(since_update.as_secs_f32() / self.data.period.as_secs_f32())
+        }
+    }
+
     /// Returns a new tick that invokes `tick` at a target number of times per
     /// second.
     pub fn times_per_second<F>(times_per_second: u32, tick: F) -> Self
@@ -145,24 +305,68 @@ impl Tick {
 }
 
 /// The current state of input during the execution of a [`Tick`].
-#[derive(Default, Debug)]
+///
+/// When the `recording` feature is enabled, input states can be serialized
+/// using `serde`.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "recording",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        from = "recording::SerializedInput",
+        into = "recording::SerializedInput"
+    )
+)]
 pub struct InputState {
     /// A collection of all keys currently pressed.
     pub keys: AHashSet<Key>,
+    /// The keys that were pressed since the previous tick.
+    pub pressed_keys: AHashSet<Key>,
+    /// The keys that were released since the previous tick.
+    ///
+    /// A key that was pressed and released between ticks is contained in both
+    /// [`pressed_keys`](Self::pressed_keys) and this collection, but not in
+    /// [`keys`](Self::keys).
+    pub released_keys: AHashSet<Key>,
     /// The state of the mouse cursor and any buttons pressed.
     pub mouse: Option<Mouse>,
 }
 
-#[derive(Debug, Default)]
+impl InputState {
+    /// Clears the changes that have been reported to a tick.
+    fn end_tick(&mut self) {
+        self.pressed_keys.clear();
+        self.released_keys.clear();
+        if let Some(mouse) = &mut self.mouse {
+            mouse.delta = Point::default();
+            mouse.pressed_buttons.clear();
+            mouse.released_buttons.clear();
+        }
+    }
+}
+
+/// The state of the mouse cursor while it is over a [`Tick`]'s widget.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct Mouse {
+    /// The location of the cursor.
     pub position: Point<Px>,
+    /// The distance the cursor moved since the previous tick.
+    pub delta: Point<Px>,
+    /// The buttons currently pressed.
     pub buttons: AHashSet<MouseButton>,
+    /// The buttons pressed since the previous tick.
+    pub pressed_buttons: AHashSet<MouseButton>,
+    /// The buttons released since the previous tick.
+    pub released_buttons: AHashSet<MouseButton>,
 }
 
 #[derive(Debug)]
 struct TickData {
     state: Mutex<TickState>,
     period: Duration,
+    /// Whether the ticks are fixed updates, which is stored in recordings.
+    #[cfg(feature = "recording")]
+    fixed: bool,
     sync: Condvar,
     rendered_frame: AtomicUsize,
     tick_number: Dynamic<u64>,
@@ -178,11 +382,41 @@ impl TickData {
 struct TickState {
     last_time: Instant,
     next_target: Instant,
+    /// The moment the most recent update represents.
+    simulated_until: Instant,
+    #[cfg(feature = "recording")]
+    recording: Option<TickRecording>,
     keep_running: bool,
     frame: usize,
     input: InputState,
 }
 
+impl TickState {
+    /// Records the tick that was just invoked, if recording, and clears the
+    /// input changes that were reported to it.
+    #[cfg_attr(not(feature = "recording"), allow(unused_variables))]
+    fn end_tick(&mut self, elapsed: Duration) {
+        #[cfg(feature = "recording")]
+        if let Some(recording) = &mut self.recording {
+            recording.ticks.push(RecordedTick {
+                elapsed,
+                input: self.input.clone(),
+            });
+        }
+        self.input.end_tick();
+    }
+}
+
+#[derive(Debug)]
+enum TickMode {
+    Variable,
+    Fixed {
+        max_catch_up: u32,
+    },
+    #[cfg(feature = "recording")]
+    Replay(TickRecording),
+}
+
 fn tick_loop<F>(data: &TickData, mut tick: F)
 where
     F: FnMut(Duration, &InputState),
@@ -207,8 +441,10 @@ where
         state.frame += 1;
 
         tick(elapsed, &state.input);
+        state.end_tick(elapsed);
         state.next_target = (state.next_target + data.period).max(now);
         state.last_time = now;
+        state.simulated_until = now;
 
         // Signal that we have a new frame, which will cause the widget to
         // redraw.
@@ -225,3 +461,108 @@ where
         }
     }
 }
+
+fn fixed_update_loop<F>(data: &TickData, max_catch_up: u32, mut update: F)
+where
+    F: FnMut(Duration, &InputState),
+{
+    let mut state = data.state();
+    while state.keep_running {
+        let now = Instant::now();
+        let (updates, discard) = pending_updates(
+            now.saturating_duration_since(state.simulated_until),
+            data.period,
+            max_catch_up,
+        );
+        for _ in 0..updates {
+            state.frame += 1;
+            let input = state.input.clone();
+            state.end_tick(data.period);
+
+            // Release the lock while updating so that input can continue to
+            // be processed during a burst of catch-up updates.
+            drop(state);
+            update(data.period, &input);
+            state = data.state();
+
+            state.simulated_until += data.period;
+        }
+        if discard {
+            // Discard the time we could not catch up on.
+            state.simulated_until = now;
+        }
+        state.last_time = now;
+
+        // Signal that we have a new frame, which will cause the widget to
+        // redraw.
+        data.tick_number.map_mut(|mut tick| *tick += 1);
+
+        // Wait for a frame to be rendered or for the next update to be due.
+        // Each rendered frame signals another change above, which keeps the
+        // widget redrawing so that it can interpolate between updates.
+        let remaining =
+            (state.simulated_until + data.period).saturating_duration_since(Instant::now());
+        if !remaining.is_zero() {
+            state = data.sync.wait_timeout(state, remaining).ignore_poison().0;
+        }
+    }
+}
+
+#[cfg(feature = "recording")]
+fn replay_loop<F>(data: &TickData, recording: TickRecording, mut tick: F)
+where
+    F: FnMut(Duration, &InputState),
+{
+    let mut state = data.state();
+    for recorded in recording.ticks {
+        // Wait until the virtual clock catches up to the recorded tick.
+        let target = state.simulated_until + recorded.elapsed;
+        if let Some(remaining) = target.checked_duration_since(Instant::now()) {
+            drop(state);
+            std::thread::sleep(remaining);
+            state = data.state();
+        }
+        if !state.keep_running {
+            break;
+        }
+
+        state.frame += 1;
+        tick(recorded.elapsed, &recorded.input);
+        state.last_time = Instant::now();
+        state.simulated_until = target;
+
+        data.tick_number.map_mut(|mut tick| *tick += 1);
+    }
+}
+
+/// Returns the number of `step`-sized updates needed to catch up after falling
+/// `behind`, limited to `max_catch_up`, and whether the remaining time should
+/// be discarded.
+fn pending_updates(behind: Duration, step: Duration, max_catch_up: u32) -> (u32, bool) {
+    if step.is_zero() {
+        return (1, true);
+    }
+    let needed = behind.as_nanos() / step.as_nanos();
+    match u32::try_from(needed) {
+        Ok(needed) if needed <= max_catch_up => (needed, false),
+        _ => (max_catch_up, true),
+    }
+}
+
+#[test]
+fn fixed_update_catch_up() {
+    let step = Duration::from_millis(10);
+    assert_eq!(
+        pending_updates(Duration::from_millis(5), step, 5),
+        (0, false)
+    );
+    assert_eq!(
+        pending_updates(Duration::from_millis(25), step, 5),
+        (2, false)
+    );
+    assert_eq!(
+        pending_updates(Duration::from_millis(50), step, 5),
+        (5, false)
+    );
+    assert_eq!(pending_updates(Duration::from_secs(1), step, 5), (5, true));
+}
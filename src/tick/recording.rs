@@ -0,0 +1,237 @@
+use std::fmt::{self, Display};
+use std::path::Path;
+use std::time::Duration;
+use std::{fs, io};
+
+use figures::units::Px;
+use figures::Point;
+use kludgine::app::winit::event::MouseButton;
+use kludgine::app::winit::keyboard::Key;
+use serde::{Deserialize, Serialize};
+
+use super::{InputState, Mouse};
+
+/// A recording of the elapsed durations and input states passed to a
+/// [`Tick`](super::Tick).
+///
+/// Recordings are started with
+/// [`Tick::start_recording`](super::Tick::start_recording) and can be saved
+/// to a file to be replayed later, either within a widget using
+/// [`Tick::replay`](super::Tick::replay) or directly using
+/// [`TickRecording::replay`]. Because each tick's elapsed time is recorded
+/// rather than measured, replaying a recording reproduces the original session
+/// exactly.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TickRecording {
+    /// The period of the recorded tick.
+    pub period: Duration,
+    /// True if the recorded tick used a
+    /// [fixed update](super::Tick::fixed_update).
+    pub fixed: bool,
+    /// The recorded ticks, in the order they were invoked.
+    pub ticks: Vec<RecordedTick>,
+}
+
+impl TickRecording {
+    /// Returns an empty recording for a tick with the given `period`.
+    #[must_use]
+    pub const fn new(period: Duration, fixed: bool) -> Self {
+        Self {
+            period,
+            fixed,
+            ticks: Vec::new(),
+        }
+    }
+
+    /// Returns the total amount of time this recording spans.
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        self.ticks.iter().map(|tick| tick.elapsed).sum()
+    }
+
+    /// Invokes `tick` with each recorded elapsed duration and input state, as
+    /// quickly as possible.
+    ///
+    /// This function is useful for reproducing a session in a regression test
+    /// without opening a window.
+    pub fn replay<F>(&self, mut tick: F)
+    where
+        F: FnMut(Duration, &InputState),
+    {
+        for recorded in &self.ticks {
+            tick(recorded.elapsed, &recorded.input);
+        }
+    }
+
+    /// Parses a recording from JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `json` is not a valid recording.
+    pub fn from_json(json: &str) -> Result<Self, RecordingError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Returns this recording encoded as JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("recordings are always serializable")
+    }
+
+    /// Reads a recording from the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` cannot be read or is not a valid recording.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RecordingError> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    /// Writes this recording to the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), RecordingError> {
+        fs::write(path, self.to_json())?;
+        Ok(())
+    }
+}
+
+/// A single invocation of a recorded [`Tick`](super::Tick).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedTick {
+    /// The elapsed duration passed to the tick.
+    pub elapsed: Duration,
+    /// The input state passed to the tick.
+    pub input: InputState,
+}
+
+/// An error reading or writing a [`TickRecording`].
+#[derive(Debug)]
+pub enum RecordingError {
+    /// An error occurred reading or writing the recording.
+    Io(io::Error),
+    /// The recording could not be parsed.
+    Json(serde_json::Error),
+}
+
+impl From<io::Error> for RecordingError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<serde_json::Error> for RecordingError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+impl Display for RecordingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordingError::Io(err) => Display::fmt(err, f),
+            RecordingError::Json(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+impl std::error::Error for RecordingError {}
+
+/// The serialized representation of an [`InputState`].
+#[derive(Serialize, Deserialize)]
+pub(super) struct SerializedInput {
+    keys: Vec<Key>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pressed_keys: Vec<Key>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    released_keys: Vec<Key>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mouse: Option<SerializedMouse>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedMouse {
+    position: (i32, i32),
+    #[serde(default)]
+    delta: (i32, i32),
+    buttons: Vec<MouseButton>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pressed_buttons: Vec<MouseButton>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    released_buttons: Vec<MouseButton>,
+}
+
+impl From<InputState> for SerializedInput {
+    fn from(input: InputState) -> Self {
+        Self {
+            keys: input.keys.into_iter().collect(),
+            pressed_keys: input.pressed_keys.into_iter().collect(),
+            released_keys: input.released_keys.into_iter().collect(),
+            mouse: input.mouse.map(|mouse| SerializedMouse {
+                position: (mouse.position.x.get(), mouse.position.y.get()),
+                delta: (mouse.delta.x.get(), mouse.delta.y.get()),
+                buttons: mouse.buttons.into_iter().collect(),
+                pressed_buttons: mouse.pressed_buttons.into_iter().collect(),
+                released_buttons: mouse.released_buttons.into_iter().collect(),
+            }),
+        }
+    }
+}
+
+impl From<SerializedInput> for InputState {
+    fn from(input: SerializedInput) -> Self {
+        Self {
+            keys: input.keys.into_iter().collect(),
+            pressed_keys: input.pressed_keys.into_iter().collect(),
+            released_keys: input.released_keys.into_iter().collect(),
+            mouse: input.mouse.map(|mouse| Mouse {
+                position: Point::new(Px::new(mouse.position.0), Px::new(mouse.position.1)),
+                delta: Point::new(Px::new(mouse.delta.0), Px::new(mouse.delta.1)),
+                buttons: mouse.buttons.into_iter().collect(),
+                pressed_buttons: mouse.pressed_buttons.into_iter().collect(),
+                released_buttons: mouse.released_buttons.into_iter().collect(),
+            }),
+        }
+    }
+}
+
+#[test]
+fn recordings_round_trip_and_replay() {
+    use kludgine::app::winit::keyboard::NamedKey;
+
+    let mut recording = TickRecording::new(Duration::from_millis(16), false);
+    let mut input = InputState::default();
+    input.keys.insert(Key::Named(NamedKey::Space));
+    input.pressed_keys.insert(Key::Named(NamedKey::Space));
+    recording.ticks.push(RecordedTick {
+        elapsed: Duration::from_millis(16),
+        input: input.clone(),
+    });
+    input.pressed_keys.clear();
+    input.mouse = Some(Mouse {
+        position: Point::new(Px::new(10), Px::new(20)),
+        delta: Point::new(Px::new(2), Px::new(-3)),
+        buttons: [MouseButton::Left].into_iter().collect(),
+        pressed_buttons: [MouseButton::Left].into_iter().collect(),
+        released_buttons: Default::default(),
+    });
+    recording.ticks.push(RecordedTick {
+        elapsed: Duration::from_millis(17),
+        input,
+    });
+
+    let parsed = TickRecording::from_json(&recording.to_json()).unwrap();
+    assert_eq!(parsed, recording);
+    assert_eq!(parsed.duration(), Duration::from_millis(33));
+
+    let mut replayed = Vec::new();
+    parsed.replay(|elapsed, input| replayed.push((elapsed, input.clone())));
+    let expected = recording
+        .ticks
+        .into_iter()
+        .map(|tick| (tick.elapsed, tick.input))
+        .collect::<Vec<_>>();
+    assert_eq!(replayed, expected);
+}
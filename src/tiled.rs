@@ -0,0 +1,1557 @@
+//! Loading maps created with the [Tiled](https://www.mapeditor.org/) map
+//! editor.
+//!
+//! This module is only available when the `tiled` feature is enabled.
+//!
+//! [`TiledMap::load`] parses a map saved in either the XML (`.tmx`) or JSON
+//! (`.tmj`) format, including any external tilesets it references. The parsed
+//! map can be inspected directly, which is useful for spawning game objects
+//! from the map's object layers, or converted into layers that can be
+//! displayed by a [`TileMap`](crate::widgets::TileMap) using
+//! [`TiledMap::into_layers`].
+//!
+//! Tiled measures maps in pixels of its own. One Tiled pixel is one [`Px`] of
+//! world space in the loaded layers, which means the positions of objects in
+//! the map can be used as world coordinates without any conversion.
+//!
+//! Only orthogonal, finite maps using tilesets based on a single image are
+//! supported.
+
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use base64::Engine;
+use figures::units::{Px, UPx};
+use figures::{FloatConversion, Point, Rect, Size};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use image::ImageError;
+use intentional::Cast;
+use kludgine::tilemap::{self, LayerContext, ObjectId};
+use kludgine::wgpu::FilterMode;
+use kludgine::{AnyTexture, Color, DrawableSource, LazyTexture, ShapeSource, Vertex};
+use roxmltree::Node;
+use serde::Deserialize;
+
+/// A map loaded from Tiled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TiledMap {
+    /// The number of tiles in each row and column of the map.
+    pub size: Size<u32>,
+    /// The size of each cell of the map, in pixels.
+    pub tile_size: Size<u32>,
+    /// The tilesets used by the map.
+    pub tilesets: Vec<Tileset>,
+    /// The map's layers, ordered from bottom to top.
+    ///
+    /// Group layers are flattened: each layer inside of a group is included
+    /// with the group's offset, opacity, and visibility applied.
+    pub layers: Vec<TiledLayer>,
+    /// The map's custom properties.
+    pub properties: Properties,
+}
+
+impl TiledMap {
+    /// Loads the map at `path`.
+    ///
+    /// Files ending in `.tmx` are parsed as XML, and all other files are
+    /// parsed as JSON. External tilesets and images are resolved relative to
+    /// the map's location.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the map or any of its tilesets cannot be read or
+    /// parsed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, TiledError> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path)?;
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        if is_xml(path) {
+            Self::from_tmx(&source, base)
+        } else {
+            Self::from_tmj(&source, base)
+        }
+    }
+
+    /// Parses a map in Tiled's XML format.
+    ///
+    /// External tilesets and images are resolved relative to `base`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `source` or any of its tilesets cannot be read or
+    /// parsed.
+    pub fn from_tmx(source: &str, base: impl AsRef<Path>) -> Result<Self, TiledError> {
+        let base = base.as_ref();
+        let document = roxmltree::Document::parse(source)?;
+        let map = document.root_element();
+        if !map.has_tag_name("map") {
+            return Err(TiledError::Invalid(String::from("expected a map")));
+        }
+        check_map_support(
+            map.attribute("orientation").unwrap_or("orthogonal"),
+            map.attribute("infinite") == Some("1"),
+        )?;
+
+        let mut tilesets = Vec::new();
+        let mut layers = Vec::new();
+        let mut properties = Properties::new();
+        for child in map.children().filter(Node::is_element) {
+            match child.tag_name().name() {
+                "tileset" => tilesets.push(xml_tileset_reference(child, base)?),
+                "layer" | "objectgroup" | "group" => {
+                    xml_layers(child, Inherited::default(), base, &mut layers)?;
+                }
+                "properties" => properties = xml_properties(child, base)?,
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            size: Size::new(attribute(map, "width")?, attribute(map, "height")?),
+            tile_size: Size::new(attribute(map, "tilewidth")?, attribute(map, "tileheight")?),
+            tilesets,
+            layers,
+            properties,
+        })
+    }
+
+    /// Parses a map in Tiled's JSON format.
+    ///
+    /// External tilesets and images are resolved relative to `base`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `source` or any of its tilesets cannot be read or
+    /// parsed.
+    pub fn from_tmj(source: &str, base: impl AsRef<Path>) -> Result<Self, TiledError> {
+        let base = base.as_ref();
+        let map: JsonMap = serde_json::from_str(source)?;
+        check_map_support(&map.orientation, map.infinite)?;
+
+        let tilesets = map
+            .tilesets
+            .into_iter()
+            .map(|tileset| json_tileset_reference(tileset, base))
+            .collect::<Result<_, _>>()?;
+        let mut layers = Vec::new();
+        for layer in map.layers {
+            json_layers(layer, Inherited::default(), base, &mut layers)?;
+        }
+
+        Ok(Self {
+            size: Size::new(map.width, map.height),
+            tile_size: Size::new(map.tilewidth, map.tileheight),
+            tilesets,
+            layers,
+            properties: json_properties(map.properties, base)?,
+        })
+    }
+
+    /// Returns the tileset containing `gid` and the tile's index within it.
+    #[must_use]
+    pub fn tileset_for(&self, gid: u32) -> Option<(&Tileset, u32)> {
+        find_tileset(&self.tilesets, gid, |tileset| tileset)
+    }
+
+    /// Returns the first layer named `name`.
+    #[must_use]
+    pub fn layer(&self, name: &str) -> Option<&TiledLayer> {
+        self.layers.iter().find(|layer| layer.name() == name)
+    }
+
+    /// Loads the images of this map's tilesets and returns layers that can be
+    /// displayed by a [`TileMap`](crate::widgets::TileMap).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a tileset's image cannot be loaded.
+    pub fn into_layers(self) -> Result<TiledLayers, TiledError> {
+        let tilesets = TilesetTextures(Arc::new(
+            self.tilesets
+                .into_iter()
+                .map(|tileset| {
+                    let image = image::open(&tileset.image)?;
+                    Ok((
+                        AnyTexture::from(LazyTexture::from_image(image, FilterMode::Nearest)),
+                        tileset,
+                    ))
+                })
+                .collect::<Result<_, TiledError>>()?,
+        ));
+
+        let layers = self
+            .layers
+            .into_iter()
+            .map(|layer| match layer {
+                TiledLayer::Tiles(layer) => LoadedLayer::Tiles(TiledTileLayer {
+                    layer,
+                    tile_size: self.tile_size,
+                    tilesets: tilesets.clone(),
+                }),
+                TiledLayer::Objects(group) => LoadedLayer::Objects(TiledObjectLayer {
+                    group,
+                    tilesets: tilesets.clone(),
+                }),
+            })
+            .collect();
+
+        Ok(TiledLayers {
+            layers,
+            properties: self.properties,
+        })
+    }
+}
+
+/// A tileset used by a [`TiledMap`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tileset {
+    /// The global id of the first tile in this tileset.
+    pub first_gid: u32,
+    /// The name of this tileset.
+    pub name: String,
+    /// The size of each tile, in pixels.
+    pub tile_size: Size<u32>,
+    /// The number of pixels between each tile in the image.
+    pub spacing: u32,
+    /// The number of pixels around the tiles in the image.
+    pub margin: u32,
+    /// The number of tiles in this tileset.
+    pub tile_count: u32,
+    /// The number of tiles in each row of the image.
+    pub columns: u32,
+    /// The path to this tileset's image.
+    pub image: PathBuf,
+    /// The custom properties of individual tiles, keyed by the tile's index
+    /// within this tileset.
+    pub tiles: HashMap<u32, Properties>,
+    /// The tileset's custom properties.
+    pub properties: Properties,
+}
+
+impl Tileset {
+    /// Returns the region of this tileset's image that contains the tile at
+    /// `index`.
+    #[must_use]
+    pub fn region(&self, index: u32) -> Rect<UPx> {
+        let columns = self.columns.max(1);
+        let column = index % columns;
+        let row = index / columns;
+        Rect::new(
+            Point::new(
+                UPx::new(self.margin + column * (self.tile_size.width + self.spacing)),
+                UPx::new(self.margin + row * (self.tile_size.height + self.spacing)),
+            ),
+            Size::new(
+                UPx::new(self.tile_size.width),
+                UPx::new(self.tile_size.height),
+            ),
+        )
+    }
+}
+
+/// A layer of a [`TiledMap`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum TiledLayer {
+    /// A grid of tiles.
+    Tiles(TileLayer),
+    /// A collection of objects.
+    Objects(ObjectGroup),
+}
+
+impl TiledLayer {
+    /// Returns the name of this layer.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            TiledLayer::Tiles(layer) => &layer.name,
+            TiledLayer::Objects(group) => &group.name,
+        }
+    }
+}
+
+/// A grid of tiles in a [`TiledMap`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileLayer {
+    /// The name of this layer.
+    pub name: String,
+    /// The number of tiles in each row and column of this layer.
+    pub size: Size<u32>,
+    /// The tiles of this layer, ordered left-to-right, top-to-bottom. Empty
+    /// cells are `None`.
+    pub tiles: Vec<Option<Tile>>,
+    /// The offset this layer is drawn at, in pixels.
+    pub offset: Point<f32>,
+    /// The opacity this layer is drawn with.
+    pub opacity: f32,
+    /// Whether this layer is drawn.
+    pub visible: bool,
+    /// The layer's custom properties.
+    pub properties: Properties,
+}
+
+impl TileLayer {
+    /// Returns the tile at `x` and `y`, if present.
+    #[must_use]
+    pub fn tile(&self, x: u32, y: u32) -> Option<Tile> {
+        if x < self.size.width && y < self.size.height {
+            self.tiles
+                .get(usize::try_from(y * self.size.width + x).ok()?)
+                .copied()
+                .flatten()
+        } else {
+            None
+        }
+    }
+}
+
+/// A tile placed in a [`TiledMap`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Tile {
+    /// The global id of the tile.
+    pub gid: u32,
+    /// Whether the tile is flipped horizontally.
+    pub flip_horizontal: bool,
+    /// Whether the tile is flipped vertically.
+    pub flip_vertical: bool,
+    /// Whether the tile is flipped across its top-left to bottom-right
+    /// diagonal.
+    pub flip_diagonal: bool,
+}
+
+impl Tile {
+    const FLIP_HORIZONTAL: u32 = 0x8000_0000;
+    const FLIP_VERTICAL: u32 = 0x4000_0000;
+    const FLIP_DIAGONAL: u32 = 0x2000_0000;
+    const ROTATED_HEXAGONAL: u32 = 0x1000_0000;
+
+    /// Decodes a tile id as stored by Tiled, returning `None` for empty
+    /// cells.
+    #[must_use]
+    pub const fn from_raw(raw: u32) -> Option<Self> {
+        let gid = raw
+            & !(Self::FLIP_HORIZONTAL
+                | Self::FLIP_VERTICAL
+                | Self::FLIP_DIAGONAL
+                | Self::ROTATED_HEXAGONAL);
+        if gid == 0 {
+            None
+        } else {
+            Some(Self {
+                gid,
+                flip_horizontal: raw & Self::FLIP_HORIZONTAL != 0,
+                flip_vertical: raw & Self::FLIP_VERTICAL != 0,
+                flip_diagonal: raw & Self::FLIP_DIAGONAL != 0,
+            })
+        }
+    }
+}
+
+/// A collection of objects in a [`TiledMap`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectGroup {
+    /// The name of this layer.
+    pub name: String,
+    /// The objects in this layer.
+    pub objects: Vec<TiledObject>,
+    /// The offset this layer is drawn at, in pixels.
+    pub offset: Point<f32>,
+    /// The opacity this layer is drawn with.
+    pub opacity: f32,
+    /// Whether this layer is drawn.
+    pub visible: bool,
+    /// The layer's custom properties.
+    pub properties: Properties,
+}
+
+impl ObjectGroup {
+    /// Returns the first object named `name`.
+    #[must_use]
+    pub fn object(&self, name: &str) -> Option<&TiledObject> {
+        self.objects.iter().find(|object| object.name == name)
+    }
+}
+
+/// An object in a [`TiledMap`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TiledObject {
+    /// The unique id of this object.
+    pub id: u32,
+    /// The name of this object.
+    pub name: String,
+    /// The class of this object.
+    pub class: String,
+    /// The location of this object, in pixels.
+    ///
+    /// For tile objects, this is the bottom-left corner of the tile. For all
+    /// other objects, this is the top-left corner.
+    pub position: Point<f32>,
+    /// The size of this object, in pixels.
+    pub size: Size<f32>,
+    /// The clockwise rotation of this object, in degrees.
+    pub rotation: f32,
+    /// The tile this object displays, if it is a tile object.
+    pub tile: Option<Tile>,
+    /// Whether this object is drawn.
+    pub visible: bool,
+    /// The object's custom properties.
+    pub properties: Properties,
+}
+
+/// Custom properties of an element of a [`TiledMap`].
+pub type Properties = HashMap<String, PropertyValue>;
+
+/// The value of a custom property.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyValue {
+    /// A string.
+    String(String),
+    /// An integer.
+    Int(i64),
+    /// A floating point number.
+    Float(f64),
+    /// A boolean.
+    Bool(bool),
+    /// A color.
+    Color(Color),
+    /// A path, resolved relative to the file the property was loaded from.
+    File(PathBuf),
+    /// The id of an object in the map, or 0 if no object is referenced.
+    Object(u32),
+}
+
+/// The layers of a [`TiledMap`], ready to be displayed by a
+/// [`TileMap`](crate::widgets::TileMap).
+///
+/// The individual layers implement [`tilemap::Layer`], which allows them to be
+/// combined with other layers, such as an
+/// [`ObjectLayer`](kludgine::tilemap::ObjectLayer) containing the game's
+/// characters:
+///
+/// ```rust,ignore
+/// let mut map = TiledMap::load("level.tmx")?.into_layers()?;
+/// let ground = map.layers.remove(0);
+/// let layers = (ground, characters);
+/// ```
+#[derive(Debug)]
+pub struct TiledLayers {
+    /// The layers, ordered from bottom to top.
+    pub layers: Vec<LoadedLayer>,
+    /// The map's custom properties.
+    pub properties: Properties,
+}
+
+impl TiledLayers {
+    /// Returns the index of the first layer named `name`.
+    #[must_use]
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.layers.iter().position(|layer| layer.name() == name)
+    }
+}
+
+impl tilemap::Layers for TiledLayers {
+    fn layer(&self, index: usize) -> Option<&dyn tilemap::Layer> {
+        self.layers
+            .get(index)
+            .map(|layer| layer as &dyn tilemap::Layer)
+    }
+
+    fn layer_mut(&mut self, index: usize) -> Option<&mut dyn tilemap::Layer> {
+        self.layers
+            .get_mut(index)
+            .map(|layer| layer as &mut dyn tilemap::Layer)
+    }
+}
+
+/// A layer of [`TiledLayers`].
+#[derive(Debug)]
+pub enum LoadedLayer {
+    /// A grid of tiles.
+    Tiles(TiledTileLayer),
+    /// A collection of objects.
+    Objects(TiledObjectLayer),
+}
+
+impl LoadedLayer {
+    /// Returns the name of this layer.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            LoadedLayer::Tiles(layer) => &layer.layer.name,
+            LoadedLayer::Objects(layer) => &layer.group.name,
+        }
+    }
+}
+
+impl tilemap::Layer for LoadedLayer {
+    fn render(&mut self, context: &mut LayerContext<'_, '_, '_, '_>) -> Option<Duration> {
+        match self {
+            LoadedLayer::Tiles(layer) => layer.render(context),
+            LoadedLayer::Objects(layer) => layer.render(context),
+        }
+    }
+
+    fn find_object(&self, _object: ObjectId) -> Option<Point<Px>> {
+        None
+    }
+}
+
+/// A [`TileLayer`] whose tileset images have been loaded.
+#[derive(Debug)]
+pub struct TiledTileLayer {
+    /// The tiles of this layer.
+    pub layer: TileLayer,
+    tile_size: Size<u32>,
+    tilesets: TilesetTextures,
+}
+
+impl tilemap::Layer for TiledTileLayer {
+    fn render(&mut self, context: &mut LayerContext<'_, '_, '_, '_>) -> Option<Duration> {
+        if !self.layer.visible {
+            return None;
+        }
+
+        let zoom = context.zoom();
+        let top_left = context.top_left();
+        let visible = context.visible_rect();
+        let color = Color::WHITE.with_alpha_f32(self.layer.opacity);
+        let cell = self.tile_size.map(|length| length.cast::<f32>());
+
+        let (start, end) = visible.extents();
+        let first_column = visible_cell(start.x.into_float() - self.layer.offset.x, cell.width);
+        let first_row = visible_cell(start.y.into_float() - self.layer.offset.y, cell.height);
+        let last_column = visible_cell(end.x.into_float() - self.layer.offset.x, cell.width)
+            .saturating_add(1)
+            .min(self.layer.size.width);
+        let last_row = visible_cell(end.y.into_float() - self.layer.offset.y, cell.height)
+            // Tiles taller than a cell extend above their cell.
+            .saturating_add(2)
+            .min(self.layer.size.height);
+
+        for y in first_row..last_row {
+            for x in first_column..last_column {
+                let Some(tile) = self.layer.tile(x, y) else {
+                    continue;
+                };
+                let Some((texture, tileset, index)) = self.tilesets.find(tile.gid) else {
+                    continue;
+                };
+                // Tiles are aligned to the bottom-left corner of their cell.
+                let bottom_left = Point::new(
+                    x.cast::<f32>() * cell.width + self.layer.offset.x,
+                    (y + 1).cast::<f32>() * cell.height + self.layer.offset.y,
+                );
+                let mut size = tileset.tile_size.map(|length| length.cast::<f32>());
+                if tile.flip_diagonal {
+                    size = Size::new(size.height, size.width);
+                }
+                let quad = TileQuad::new(
+                    tileset.region(index),
+                    tile,
+                    Rect::new(Point::new(bottom_left.x, bottom_left.y - size.height), size),
+                    0.,
+                    color,
+                    top_left,
+                    zoom,
+                );
+                context.draw_textured_shape(&quad, texture);
+            }
+        }
+
+        None
+    }
+
+    fn find_object(&self, _object: ObjectId) -> Option<Point<Px>> {
+        None
+    }
+}
+
+/// An [`ObjectGroup`] whose tileset images have been loaded.
+///
+/// Tile objects are drawn using their tile. All other objects are not drawn.
+#[derive(Debug)]
+pub struct TiledObjectLayer {
+    /// The objects of this layer.
+    pub group: ObjectGroup,
+    tilesets: TilesetTextures,
+}
+
+impl tilemap::Layer for TiledObjectLayer {
+    fn render(&mut self, context: &mut LayerContext<'_, '_, '_, '_>) -> Option<Duration> {
+        if !self.group.visible {
+            return None;
+        }
+
+        let zoom = context.zoom();
+        let top_left = context.top_left();
+        let color = Color::WHITE.with_alpha_f32(self.group.opacity);
+        for object in self.group.objects.iter().filter(|object| object.visible) {
+            let Some(tile) = object.tile else {
+                continue;
+            };
+            let Some((texture, tileset, index)) = self.tilesets.find(tile.gid) else {
+                continue;
+            };
+            let position = object.position + self.group.offset;
+            let quad = TileQuad::new(
+                tileset.region(index),
+                tile,
+                Rect::new(
+                    Point::new(position.x, position.y - object.size.height),
+                    object.size,
+                ),
+                object.rotation,
+                color,
+                top_left,
+                zoom,
+            );
+            context.draw_textured_shape(&quad, texture);
+        }
+
+        None
+    }
+
+    fn find_object(&self, _object: ObjectId) -> Option<Point<Px>> {
+        None
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TilesetTextures(Arc<Vec<(AnyTexture, Tileset)>>);
+
+impl TilesetTextures {
+    fn find(&self, gid: u32) -> Option<(&AnyTexture, &Tileset, u32)> {
+        let ((texture, tileset), index) =
+            find_tileset(&self.0, gid, |(_, tileset): &(AnyTexture, Tileset)| tileset)?;
+        Some((texture, tileset, index))
+    }
+}
+
+/// Returns the entry in `tilesets` that contains `gid`, along with the index
+/// of `gid` within the tileset.
+fn find_tileset<T>(
+    tilesets: &[T],
+    gid: u32,
+    tileset: impl Fn(&T) -> &Tileset,
+) -> Option<(&T, u32)> {
+    // Tilesets are stored in ascending order of their first gid.
+    let entry = tilesets
+        .iter()
+        .rev()
+        .find(|entry| tileset(entry).first_gid <= gid)?;
+    let index = gid - tileset(entry).first_gid;
+    (index < tileset(entry).tile_count).then_some((entry, index))
+}
+
+/// Returns the index of the cell containing `world`.
+fn visible_cell(world: f32, cell: f32) -> u32 {
+    if world <= 0. || cell <= 0. {
+        0
+    } else {
+        (world / cell).cast::<u32>()
+    }
+}
+
+/// A tile drawn into a possibly rotated quad.
+///
+/// Unlike a `TextureBlit`, the texture coordinates of each corner are chosen
+/// individually, which allows drawing flipped tiles.
+#[derive(Debug, Clone, Copy)]
+struct TileQuad {
+    vertices: [Vertex<Px>; 4],
+}
+
+impl TileQuad {
+    /// Returns a quad drawing `region` of a tileset into `world`, applying
+    /// `tile`'s flips and rotating it clockwise by `rotation` degrees around
+    /// its bottom-left corner.
+    fn new(
+        region: Rect<UPx>,
+        tile: Tile,
+        world: Rect<f32>,
+        rotation: f32,
+        color: Color,
+        top_left: Point<Px>,
+        zoom: f32,
+    ) -> Self {
+        let (source_start, source_end) = region.extents();
+        let (world_start, world_end) = world.extents();
+        let pivot = Point::new(world_start.x, world_end.y);
+        let (sin, cos) = rotation.to_radians().sin_cos();
+        let vertex = |right: bool, bottom: bool| {
+            let world = Point::new(
+                if right { world_end.x } else { world_start.x },
+                if bottom { world_end.y } else { world_start.y },
+            ) - pivot;
+            let world = Point::new(
+                pivot.x + world.x * cos - world.y * sin,
+                pivot.y + world.x * sin + world.y * cos,
+            );
+
+            // Tiled applies the diagonal flip before the horizontal and
+            // vertical flips.
+            let (right, bottom) = (right != tile.flip_horizontal, bottom != tile.flip_vertical);
+            let (right, bottom) = if tile.flip_diagonal {
+                (bottom, right)
+            } else {
+                (right, bottom)
+            };
+
+            Vertex {
+                location: Point::new(
+                    ((world.x - top_left.x.into_float()) * zoom).round(),
+                    ((world.y - top_left.y.into_float()) * zoom).round(),
+                )
+                .cast(),
+                texture: Point::new(
+                    if right { source_end.x } else { source_start.x },
+                    if bottom { source_end.y } else { source_start.y },
+                ),
+                color,
+            }
+        };
+        Self {
+            vertices: [
+                vertex(false, false),
+                vertex(true, false),
+                vertex(false, true),
+                vertex(true, true),
+            ],
+        }
+    }
+}
+
+impl DrawableSource for TileQuad {}
+
+impl ShapeSource<Px, true> for TileQuad {
+    fn vertices(&self) -> &[Vertex<Px>] {
+        &self.vertices
+    }
+
+    fn indices(&self) -> &[u32] {
+        &[1, 0, 2, 1, 2, 3]
+    }
+}
+
+/// An error loading a [`TiledMap`].
+#[derive(Debug)]
+pub enum TiledError {
+    /// An error occurred reading a file.
+    Io(io::Error),
+    /// An XML document could not be parsed.
+    Xml(roxmltree::Error),
+    /// A JSON document could not be parsed.
+    Json(serde_json::Error),
+    /// A tileset's image could not be loaded.
+    Image(ImageError),
+    /// The map is not valid.
+    Invalid(String),
+    /// The map uses a feature that is not supported.
+    Unsupported(&'static str),
+}
+
+impl From<io::Error> for TiledError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<roxmltree::Error> for TiledError {
+    fn from(value: roxmltree::Error) -> Self {
+        Self::Xml(value)
+    }
+}
+
+impl From<serde_json::Error> for TiledError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+impl From<ImageError> for TiledError {
+    fn from(value: ImageError) -> Self {
+        Self::Image(value)
+    }
+}
+
+impl Display for TiledError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TiledError::Io(err) => Display::fmt(err, f),
+            TiledError::Xml(err) => Display::fmt(err, f),
+            TiledError::Json(err) => Display::fmt(err, f),
+            TiledError::Image(err) => Display::fmt(err, f),
+            TiledError::Invalid(reason) => write!(f, "invalid map: {reason}"),
+            TiledError::Unsupported(feature) => write!(f, "unsupported: {feature}"),
+        }
+    }
+}
+
+impl std::error::Error for TiledError {}
+
+fn is_xml(path: &Path) -> bool {
+    path.extension().map_or(false, |extension| {
+        extension.eq_ignore_ascii_case("tmx") || extension.eq_ignore_ascii_case("tsx")
+    })
+}
+
+fn check_map_support(orientation: &str, infinite: bool) -> Result<(), TiledError> {
+    if orientation != "orthogonal" {
+        Err(TiledError::Unsupported("non-orthogonal maps"))
+    } else if infinite {
+        Err(TiledError::Unsupported("infinite maps"))
+    } else {
+        Ok(())
+    }
+}
+
+/// The properties a layer inherits from the groups containing it.
+#[derive(Clone, Copy)]
+struct Inherited {
+    offset: Point<f32>,
+    opacity: f32,
+    visible: bool,
+}
+
+impl Default for Inherited {
+    fn default() -> Self {
+        Self {
+            offset: Point::default(),
+            opacity: 1.,
+            visible: true,
+        }
+    }
+}
+
+impl Inherited {
+    fn apply(self, offset: Point<f32>, opacity: f32, visible: bool) -> Self {
+        Self {
+            offset: self.offset + offset,
+            opacity: self.opacity * opacity,
+            visible: self.visible && visible,
+        }
+    }
+}
+
+/// Decodes the tile data of a layer.
+fn decode_tiles(
+    data: &str,
+    encoding: Option<&str>,
+    compression: Option<&str>,
+) -> Result<Vec<Option<Tile>>, TiledError> {
+    match encoding {
+        Some("csv") => data
+            .split(',')
+            .map(|gid| {
+                gid.trim()
+                    .parse()
+                    .map(Tile::from_raw)
+                    .map_err(|_| TiledError::Invalid(format!("invalid tile: {gid}")))
+            })
+            .collect(),
+        Some("base64") => {
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(data.trim())
+                .map_err(|err| TiledError::Invalid(err.to_string()))?;
+            let bytes = match compression {
+                None | Some("") => bytes,
+                Some("zlib") => decompress(ZlibDecoder::new(&bytes[..]))?,
+                Some("gzip") => decompress(GzDecoder::new(&bytes[..]))?,
+                Some(_) => return Err(TiledError::Unsupported("tile data compression")),
+            };
+            Ok(bytes
+                .chunks_exact(4)
+                .map(|gid| Tile::from_raw(u32::from_le_bytes([gid[0], gid[1], gid[2], gid[3]])))
+                .collect())
+        }
+        _ => Err(TiledError::Unsupported("tile data encoding")),
+    }
+}
+
+fn decompress(mut decoder: impl Read) -> Result<Vec<u8>, TiledError> {
+    let mut bytes = Vec::new();
+    decoder.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn check_tile_count(name: &str, size: Size<u32>, tiles: &[Option<Tile>]) -> Result<(), TiledError> {
+    if size
+        .width
+        .checked_mul(size.height)
+        .and_then(|count| usize::try_from(count).ok())
+        == Some(tiles.len())
+    {
+        Ok(())
+    } else {
+        Err(TiledError::Invalid(format!(
+            "layer {name} does not contain {}x{} tiles",
+            size.width, size.height
+        )))
+    }
+}
+
+fn parse_color(color: &str) -> Result<Color, TiledError> {
+    let invalid = || TiledError::Invalid(format!("invalid color: {color}"));
+    let hex = color.strip_prefix('#').unwrap_or(color);
+    let channel = |index: usize| {
+        hex.get(index..index + 2)
+            .and_then(|channel| u8::from_str_radix(channel, 16).ok())
+            .ok_or_else(invalid)
+    };
+    match hex.len() {
+        // #RRGGBB
+        6 => Ok(Color::new(channel(0)?, channel(2)?, channel(4)?, 255)),
+        // #AARRGGBB
+        8 => Ok(Color::new(
+            channel(2)?,
+            channel(4)?,
+            channel(6)?,
+            channel(0)?,
+        )),
+        // Colors can be unset.
+        0 => Ok(Color::CLEAR_BLACK),
+        _ => Err(invalid()),
+    }
+}
+
+fn attribute<T>(node: Node<'_, '_>, name: &str) -> Result<T, TiledError>
+where
+    T: FromStr,
+{
+    let value = node.attribute(name).ok_or_else(|| {
+        TiledError::Invalid(format!(
+            "{} is missing the {name} attribute",
+            node.tag_name().name()
+        ))
+    })?;
+    value.parse().map_err(|_| {
+        TiledError::Invalid(format!(
+            "{} has an invalid {name} attribute: {value}",
+            node.tag_name().name()
+        ))
+    })
+}
+
+fn attribute_or<T>(node: Node<'_, '_>, name: &str, default: T) -> Result<T, TiledError>
+where
+    T: FromStr,
+{
+    if node.has_attribute(name) {
+        attribute(node, name)
+    } else {
+        Ok(default)
+    }
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|child| child.has_tag_name(name))
+}
+
+fn xml_properties_of(node: Node<'_, '_>, base: &Path) -> Result<Properties, TiledError> {
+    child(node, "properties").map_or_else(
+        || Ok(Properties::new()),
+        |properties| xml_properties(properties, base),
+    )
+}
+
+fn xml_properties(node: Node<'_, '_>, base: &Path) -> Result<Properties, TiledError> {
+    node.children()
+        .filter(|child| child.has_tag_name("property"))
+        .map(|property| {
+            let name: String = attribute(property, "name")?;
+            // Multi-line strings are stored as the element's text.
+            let value = property
+                .attribute("value")
+                .or_else(|| property.text())
+                .unwrap_or_default();
+            let value = match property.attribute("type").unwrap_or("string") {
+                "string" => PropertyValue::String(value.to_string()),
+                "int" => PropertyValue::Int(attribute(property, "value")?),
+                "float" => PropertyValue::Float(attribute(property, "value")?),
+                "bool" => PropertyValue::Bool(attribute(property, "value")?),
+                "color" => PropertyValue::Color(parse_color(value)?),
+                "file" => PropertyValue::File(base.join(value)),
+                "object" => PropertyValue::Object(attribute(property, "value")?),
+                _ => return Err(TiledError::Unsupported("class properties")),
+            };
+            Ok((name, value))
+        })
+        .collect()
+}
+
+fn xml_tileset_reference(node: Node<'_, '_>, base: &Path) -> Result<Tileset, TiledError> {
+    let first_gid = attribute(node, "firstgid")?;
+    let mut tileset = if let Some(source) = node.attribute("source") {
+        load_tileset(&base.join(source))?
+    } else {
+        xml_tileset(node, base)?
+    };
+    tileset.first_gid = first_gid;
+    Ok(tileset)
+}
+
+fn load_tileset(path: &Path) -> Result<Tileset, TiledError> {
+    let source = fs::read_to_string(path)?;
+    let base = path.parent().unwrap_or_else(|| Path::new(""));
+    if is_xml(path) {
+        let document = roxmltree::Document::parse(&source)?;
+        xml_tileset(document.root_element(), base)
+    } else {
+        json_tileset(serde_json::from_str(&source)?, base)
+    }
+}
+
+fn xml_tileset(node: Node<'_, '_>, base: &Path) -> Result<Tileset, TiledError> {
+    let image = child(node, "image").ok_or(TiledError::Unsupported("image collection tilesets"))?;
+    let mut tiles = HashMap::new();
+    for tile in node.children().filter(|child| child.has_tag_name("tile")) {
+        let properties = xml_properties_of(tile, base)?;
+        if !properties.is_empty() {
+            tiles.insert(attribute(tile, "id")?, properties);
+        }
+    }
+
+    Ok(Tileset {
+        first_gid: attribute_or(node, "firstgid", 1)?,
+        name: attribute_or(node, "name", String::new())?,
+        tile_size: Size::new(
+            attribute(node, "tilewidth")?,
+            attribute(node, "tileheight")?,
+        ),
+        spacing: attribute_or(node, "spacing", 0)?,
+        margin: attribute_or(node, "margin", 0)?,
+        tile_count: attribute(node, "tilecount")?,
+        columns: attribute(node, "columns")?,
+        image: base.join(attribute::<String>(image, "source")?),
+        tiles,
+        properties: xml_properties_of(node, base)?,
+    })
+}
+
+fn xml_layers(
+    node: Node<'_, '_>,
+    inherited: Inherited,
+    base: &Path,
+    layers: &mut Vec<TiledLayer>,
+) -> Result<(), TiledError> {
+    let name = attribute_or(node, "name", String::new())?;
+    let inherited = inherited.apply(
+        Point::new(
+            attribute_or(node, "offsetx", 0.)?,
+            attribute_or(node, "offsety", 0.)?,
+        ),
+        attribute_or(node, "opacity", 1.)?,
+        attribute_or(node, "visible", 1_u8)? != 0,
+    );
+    let properties = xml_properties_of(node, base)?;
+
+    match node.tag_name().name() {
+        "layer" => {
+            let size = Size::new(attribute(node, "width")?, attribute(node, "height")?);
+            let data = child(node, "data")
+                .ok_or_else(|| TiledError::Invalid(format!("layer {name} has no data")))?;
+            let tiles = match data.attribute("encoding") {
+                None => data
+                    .children()
+                    .filter(|child| child.has_tag_name("tile"))
+                    .map(|tile| attribute_or(tile, "gid", 0).map(Tile::from_raw))
+                    .collect::<Result<_, _>>()?,
+                encoding => decode_tiles(
+                    data.text().unwrap_or_default(),
+                    encoding,
+                    data.attribute("compression"),
+                )?,
+            };
+            check_tile_count(&name, size, &tiles)?;
+            layers.push(TiledLayer::Tiles(TileLayer {
+                name,
+                size,
+                tiles,
+                offset: inherited.offset,
+                opacity: inherited.opacity,
+                visible: inherited.visible,
+                properties,
+            }));
+        }
+        "objectgroup" => {
+            let objects = node
+                .children()
+                .filter(|child| child.has_tag_name("object"))
+                .map(|object| xml_object(object, base))
+                .collect::<Result<_, _>>()?;
+            layers.push(TiledLayer::Objects(ObjectGroup {
+                name,
+                objects,
+                offset: inherited.offset,
+                opacity: inherited.opacity,
+                visible: inherited.visible,
+                properties,
+            }));
+        }
+        "group" => {
+            for child in node.children().filter(|child| {
+                child.has_tag_name("layer")
+                    || child.has_tag_name("objectgroup")
+                    || child.has_tag_name("group")
+            }) {
+                xml_layers(child, inherited, base, layers)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn xml_object(node: Node<'_, '_>, base: &Path) -> Result<TiledObject, TiledError> {
+    let class = match node.attribute("class").or_else(|| node.attribute("type")) {
+        Some(class) => class.to_string(),
+        None => String::new(),
+    };
+    Ok(TiledObject {
+        id: attribute(node, "id")?,
+        name: attribute_or(node, "name", String::new())?,
+        class,
+        position: Point::new(attribute_or(node, "x", 0.)?, attribute_or(node, "y", 0.)?),
+        size: Size::new(
+            attribute_or(node, "width", 0.)?,
+            attribute_or(node, "height", 0.)?,
+        ),
+        rotation: attribute_or(node, "rotation", 0.)?,
+        tile: Tile::from_raw(attribute_or(node, "gid", 0)?),
+        visible: attribute_or(node, "visible", 1_u8)? != 0,
+        properties: xml_properties_of(node, base)?,
+    })
+}
+
+#[derive(Deserialize)]
+struct JsonMap {
+    width: u32,
+    height: u32,
+    tilewidth: u32,
+    tileheight: u32,
+    #[serde(default)]
+    infinite: bool,
+    #[serde(default = "orthogonal")]
+    orientation: String,
+    #[serde(default)]
+    tilesets: Vec<JsonTileset>,
+    #[serde(default)]
+    layers: Vec<JsonLayer>,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+fn orthogonal() -> String {
+    String::from("orthogonal")
+}
+
+#[derive(Deserialize)]
+struct JsonTileset {
+    firstgid: Option<u32>,
+    source: Option<String>,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    tilewidth: u32,
+    #[serde(default)]
+    tileheight: u32,
+    #[serde(default)]
+    spacing: u32,
+    #[serde(default)]
+    margin: u32,
+    #[serde(default)]
+    tilecount: u32,
+    #[serde(default)]
+    columns: u32,
+    image: Option<String>,
+    #[serde(default)]
+    tiles: Vec<JsonTile>,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+#[derive(Deserialize)]
+struct JsonTile {
+    id: u32,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+#[derive(Deserialize)]
+struct JsonLayer {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    width: u32,
+    #[serde(default)]
+    height: u32,
+    #[serde(default)]
+    offsetx: f32,
+    #[serde(default)]
+    offsety: f32,
+    #[serde(default = "one")]
+    opacity: f32,
+    #[serde(default = "yes")]
+    visible: bool,
+    data: Option<JsonTileData>,
+    encoding: Option<String>,
+    compression: Option<String>,
+    #[serde(default)]
+    objects: Vec<JsonObject>,
+    #[serde(default)]
+    layers: Vec<JsonLayer>,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+const fn one() -> f32 {
+    1.
+}
+
+const fn yes() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonTileData {
+    Array(Vec<u32>),
+    Encoded(String),
+}
+
+#[derive(Deserialize)]
+struct JsonObject {
+    id: u32,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    class: Option<String>,
+    #[serde(default, rename = "type")]
+    kind: Option<String>,
+    #[serde(default)]
+    x: f32,
+    #[serde(default)]
+    y: f32,
+    #[serde(default)]
+    width: f32,
+    #[serde(default)]
+    height: f32,
+    #[serde(default)]
+    rotation: f32,
+    gid: Option<u32>,
+    #[serde(default = "yes")]
+    visible: bool,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+#[derive(Deserialize)]
+struct JsonProperty {
+    name: String,
+    #[serde(default = "string", rename = "type")]
+    kind: String,
+    value: serde_json::Value,
+}
+
+fn string() -> String {
+    String::from("string")
+}
+
+fn json_properties(properties: Vec<JsonProperty>, base: &Path) -> Result<Properties, TiledError> {
+    properties
+        .into_iter()
+        .map(|property| {
+            let invalid = || TiledError::Invalid(format!("invalid property: {}", property.name));
+            let value = match property.kind.as_str() {
+                "string" => {
+                    PropertyValue::String(property.value.as_str().ok_or_else(invalid)?.to_string())
+                }
+                "int" => PropertyValue::Int(property.value.as_i64().ok_or_else(invalid)?),
+                "float" => PropertyValue::Float(property.value.as_f64().ok_or_else(invalid)?),
+                "bool" => PropertyValue::Bool(property.value.as_bool().ok_or_else(invalid)?),
+                "color" => {
+                    PropertyValue::Color(parse_color(property.value.as_str().ok_or_else(invalid)?)?)
+                }
+                "file" => {
+                    PropertyValue::File(base.join(property.value.as_str().ok_or_else(invalid)?))
+                }
+                "object" => PropertyValue::Object(
+                    property
+                        .value
+                        .as_u64()
+                        .and_then(|id| u32::try_from(id).ok())
+                        .ok_or_else(invalid)?,
+                ),
+                _ => return Err(TiledError::Unsupported("class properties")),
+            };
+            Ok((property.name, value))
+        })
+        .collect()
+}
+
+fn json_tileset_reference(tileset: JsonTileset, base: &Path) -> Result<Tileset, TiledError> {
+    let first_gid = tileset.firstgid.unwrap_or(1);
+    let mut tileset = if let Some(source) = &tileset.source {
+        load_tileset(&base.join(source))?
+    } else {
+        json_tileset(tileset, base)?
+    };
+    tileset.first_gid = first_gid;
+    Ok(tileset)
+}
+
+fn json_tileset(tileset: JsonTileset, base: &Path) -> Result<Tileset, TiledError> {
+    let image = tileset
+        .image
+        .ok_or(TiledError::Unsupported("image collection tilesets"))?;
+    let mut tiles = HashMap::new();
+    for tile in tileset.tiles {
+        let properties = json_properties(tile.properties, base)?;
+        if !properties.is_empty() {
+            tiles.insert(tile.id, properties);
+        }
+    }
+
+    Ok(Tileset {
+        first_gid: tileset.firstgid.unwrap_or(1),
+        name: tileset.name,
+        tile_size: Size::new(tileset.tilewidth, tileset.tileheight),
+        spacing: tileset.spacing,
+        margin: tileset.margin,
+        tile_count: tileset.tilecount,
+        columns: tileset.columns,
+        image: base.join(image),
+        tiles,
+        properties: json_properties(tileset.properties, base)?,
+    })
+}
+
+fn json_layers(
+    layer: JsonLayer,
+    inherited: Inherited,
+    base: &Path,
+    layers: &mut Vec<TiledLayer>,
+) -> Result<(), TiledError> {
+    let inherited = inherited.apply(
+        Point::new(layer.offsetx, layer.offsety),
+        layer.opacity,
+        layer.visible,
+    );
+
+    match layer.kind.as_str() {
+        "tilelayer" => {
+            let size = Size::new(layer.width, layer.height);
+            let tiles = match &layer.data {
+                Some(JsonTileData::Array(gids)) => {
+                    gids.iter().copied().map(Tile::from_raw).collect()
+                }
+                Some(JsonTileData::Encoded(data)) => decode_tiles(
+                    data,
+                    layer.encoding.as_deref(),
+                    layer.compression.as_deref(),
+                )?,
+                None => {
+                    return Err(TiledError::Invalid(format!(
+                        "layer {} has no data",
+                        layer.name
+                    )))
+                }
+            };
+            check_tile_count(&layer.name, size, &tiles)?;
+            layers.push(TiledLayer::Tiles(TileLayer {
+                name: layer.name,
+                size,
+                tiles,
+                offset: inherited.offset,
+                opacity: inherited.opacity,
+                visible: inherited.visible,
+                properties: json_properties(layer.properties, base)?,
+            }));
+        }
+        "objectgroup" => {
+            let objects = layer
+                .objects
+                .into_iter()
+                .map(|object| {
+                    Ok(TiledObject {
+                        id: object.id,
+                        name: object.name,
+                        class: object.class.or(object.kind).unwrap_or_default(),
+                        position: Point::new(object.x, object.y),
+                        size: Size::new(object.width, object.height),
+                        rotation: object.rotation,
+                        tile: object.gid.and_then(Tile::from_raw),
+                        visible: object.visible,
+                        properties: json_properties(object.properties, base)?,
+                    })
+                })
+                .collect::<Result<_, TiledError>>()?;
+            layers.push(TiledLayer::Objects(ObjectGroup {
+                name: layer.name,
+                objects,
+                offset: inherited.offset,
+                opacity: inherited.opacity,
+                visible: inherited.visible,
+                properties: json_properties(layer.properties, base)?,
+            }));
+        }
+        "group" => {
+            for child in layer.layers {
+                json_layers(child, inherited, base, layers)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[test]
+fn tmx_and_tmj_maps_match() {
+    let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/tiled");
+    let tmx = TiledMap::load(assets.join("test.tmx")).unwrap();
+    let tmj = TiledMap::load(assets.join("test.tmj")).unwrap();
+    assert_eq!(tmx, tmj);
+
+    assert_eq!(tmx.size, Size::new(6, 4));
+    assert_eq!(tmx.tilesets[0].image, assets.join("tiles.png"));
+    assert_eq!(
+        tmx.tilesets[0].tiles[&2]["solid"],
+        PropertyValue::Bool(true)
+    );
+    assert_eq!(tmx.properties["difficulty"], PropertyValue::Int(2));
+
+    let Some(TiledLayer::Tiles(detail)) = tmx.layer("Detail") else {
+        unreachable!("detail is a tile layer")
+    };
+    assert_eq!(detail.offset, Point::new(8., 8.));
+    assert!((detail.opacity - 0.5).abs() < f32::EPSILON);
+    assert_eq!(detail.tile(0, 0), None);
+    assert_eq!(
+        detail.tile(1, 1),
+        Some(Tile {
+            gid: 4,
+            flip_horizontal: true,
+            flip_vertical: false,
+            flip_diagonal: false,
+        })
+    );
+
+    let Some(TiledLayer::Objects(entities)) = tmx.layer("Entities") else {
+        unreachable!("entities is an object layer")
+    };
+    let spawn = entities.object("Spawn").unwrap();
+    assert_eq!(spawn.class, "spawn");
+    assert_eq!(
+        spawn.properties["tint"],
+        PropertyValue::Color(Color::new(0x33, 0x66, 0x99, 0xff))
+    );
+    assert_eq!(
+        spawn.properties["script"],
+        PropertyValue::File(assets.join("spawn.ron"))
+    );
+    let (tileset, index) = tmx
+        .tileset_for(entities.object("Pool").unwrap().tile.unwrap().gid)
+        .unwrap();
+    assert_eq!(tileset.name, "terrain");
+    assert_eq!(
+        tileset.region(index),
+        Rect::new(
+            Point::new(UPx::new(0), UPx::new(16)),
+            Size::squared(UPx::new(16))
+        )
+    );
+}
+
+#[test]
+fn tile_quads_apply_flips_and_rotation() {
+    let region = Rect::new(
+        Point::new(UPx::new(16), UPx::new(0)),
+        Size::squared(UPx::new(16)),
+    );
+    let tile = |flip_horizontal, flip_vertical, flip_diagonal| Tile {
+        gid: 1,
+        flip_horizontal,
+        flip_vertical,
+        flip_diagonal,
+    };
+    let world = Rect::new(Point::new(8., 8.), Size::squared(16.));
+    let corners = |quad: TileQuad| {
+        quad.vertices
+            .map(|vertex| (vertex.location, vertex.texture))
+    };
+    let px = |x, y| Point::new(Px::new(x), Px::new(y));
+    let upx = |x, y| Point::new(UPx::new(x), UPx::new(y));
+
+    // An unflipped tile is translated and scaled into the destination.
+    let quad = TileQuad::new(
+        region,
+        tile(false, false, false),
+        world,
+        0.,
+        Color::WHITE,
+        px(4, 4),
+        2.,
+    );
+    assert_eq!(
+        corners(quad),
+        [
+            (px(8, 8), upx(16, 0)),
+            (px(40, 8), upx(32, 0)),
+            (px(8, 40), upx(16, 16)),
+            (px(40, 40), upx(32, 16)),
+        ]
+    );
+
+    let texture = |flip_horizontal, flip_vertical, flip_diagonal| {
+        TileQuad::new(
+            region,
+            tile(flip_horizontal, flip_vertical, flip_diagonal),
+            world,
+            0.,
+            Color::WHITE,
+            Point::default(),
+            1.,
+        )
+        .vertices
+        .map(|vertex| vertex.texture)
+    };
+    assert_eq!(
+        texture(true, false, false),
+        [upx(32, 0), upx(16, 0), upx(32, 16), upx(16, 16)]
+    );
+    assert_eq!(
+        texture(false, true, false),
+        [upx(16, 16), upx(32, 16), upx(16, 0), upx(32, 0)]
+    );
+    assert_eq!(
+        texture(false, false, true),
+        [upx(16, 0), upx(16, 16), upx(32, 0), upx(32, 16)]
+    );
+    // Tiled encodes a clockwise rotation as a diagonal and horizontal flip.
+    assert_eq!(
+        texture(true, false, true),
+        [upx(16, 16), upx(16, 0), upx(32, 16), upx(32, 0)]
+    );
+
+    // Objects rotate clockwise around their bottom-left corner.
+    let quad = TileQuad::new(
+        region,
+        tile(false, false, false),
+        world,
+        90.,
+        Color::WHITE,
+        Point::default(),
+        1.,
+    );
+    assert_eq!(
+        quad.vertices.map(|vertex| vertex.location),
+        [px(24, 24), px(24, 40), px(8, 24), px(8, 40)]
+    );
+}
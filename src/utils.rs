@@ -2,8 +2,8 @@ use std::ops::Deref;
 use std::sync::mpsc::{self, SyncSender};
 use std::sync::{OnceLock, PoisonError};
 
-use intentional::Assert;
-use kludgine::app::winit::event::Modifiers;
+use intentional::{Assert, Cast};
+use kludgine::app::winit::event::{Modifiers, MouseScrollDelta};
 use kludgine::app::winit::keyboard::ModifiersState;
 
 /// Invokes the provided macro with a pattern that can be matched using this
@@ -184,3 +184,32 @@ where
 }
 
 impl<T> BgFunction for T where T: FnOnce() + Send + 'static {}
+
+/// Splits a locale identifier such as `en-US` or `de_DE.UTF-8` into its
+/// lowercase language and uppercase region. The region is empty if the
+/// identifier does not contain one.
+pub(crate) fn locale_language_and_region(locale: &str) -> (String, String) {
+    let identifier = locale.split(['.', '@']).next().unwrap_or_default();
+    let mut parts = identifier.split(['-', '_']);
+    let language = parts.next().unwrap_or_default().to_ascii_lowercase();
+    let region = parts
+        .find(|part| {
+            part.len() == 2 || (part.len() == 3 && part.bytes().all(|b| b.is_ascii_digit()))
+        })
+        .unwrap_or_default()
+        .to_ascii_uppercase();
+    (language, region)
+}
+
+/// Returns the factor to multiply a zoom level by in response to a mouse
+/// wheel event. Each line scrolled changes the zoom by 10%.
+///
+/// The returned factor is always positive, regardless of how far the wheel was
+/// scrolled.
+pub(crate) fn wheel_zoom_factor(delta: MouseScrollDelta) -> f32 {
+    let lines = match delta {
+        MouseScrollDelta::LineDelta(_, lines) => lines,
+        MouseScrollDelta::PixelDelta(px) => px.y.cast::<f32>() / 16.0,
+    };
+    1.1_f32.powf(lines)
+}
@@ -1,9 +1,13 @@
 //! Built-in [`Widget`](crate::widget::Widget) implementations.
 
 mod align;
+pub mod animated_image;
 pub mod button;
+pub mod calendar;
 mod canvas;
+pub mod chart;
 pub mod checkbox;
+pub mod code_view;
 mod collapse;
 pub mod color;
 pub mod container;
@@ -12,54 +16,87 @@ mod data;
 pub mod delimiter;
 pub mod disclose;
 mod expand;
+pub mod file_browser;
 pub mod grid;
 pub mod image;
+pub mod image_viewer;
 pub mod input;
+pub mod inspector;
+pub mod knob;
 pub mod label;
 pub mod layers;
 pub mod list;
+pub mod log_view;
+#[cfg(feature = "markdown")]
+pub mod markdown;
 mod mode_switch;
+pub mod number_input;
+#[cfg(feature = "plotters")]
+pub mod plot;
 pub mod progress;
 pub mod radio;
 mod resize;
+pub mod rich_text;
 pub mod scroll;
 pub mod select;
+mod selection;
 pub mod slider;
 mod space;
+pub mod sprite;
 pub mod stack;
 mod style;
+#[cfg(feature = "svg")]
+pub mod svg;
 mod switcher;
 mod themed;
-mod tilemap;
+pub mod tilemap;
 pub mod validated;
 pub mod wrap;
 
 pub use self::align::Align;
+pub use self::animated_image::AnimatedImage;
 pub use self::button::Button;
+pub use self::calendar::{Calendar, DatePicker, TimePicker};
 pub use self::canvas::Canvas;
+pub use self::chart::{Chart, Series};
 pub use self::checkbox::Checkbox;
+pub use self::code_view::CodeView;
 pub use self::collapse::Collapse;
 pub use self::container::Container;
 pub use self::custom::Custom;
 pub use self::data::Data;
 pub use self::delimiter::Delimiter;
-pub use self::disclose::Disclose;
+pub use self::disclose::{Disclose, DiscloseGroup};
 pub use self::expand::Expand;
+pub use self::file_browser::FileBrowser;
 pub use self::grid::Grid;
 pub use self::image::Image;
+pub use self::image_viewer::ImageViewer;
 pub use self::input::Input;
+pub use self::inspector::Inspector;
+pub use self::knob::Knob;
 pub use self::label::Label;
-pub use self::layers::Layers;
+pub use self::layers::{Layers, Modal};
+pub use self::log_view::LogView;
+#[cfg(feature = "markdown")]
+pub use self::markdown::Markdown;
 pub use self::mode_switch::ThemedMode;
+pub use self::number_input::NumberInput;
+#[cfg(feature = "plotters")]
+pub use self::plot::InteractivePlot;
 pub use self::progress::ProgressBar;
 pub use self::radio::Radio;
 pub use self::resize::Resize;
+pub use self::rich_text::RichText;
 pub use self::scroll::Scroll;
 pub use self::select::Select;
 pub use self::slider::Slider;
 pub use self::space::Space;
+pub use self::sprite::AnimatedSprite;
 pub use self::stack::Stack;
 pub use self::style::Style;
+#[cfg(feature = "svg")]
+pub use self::svg::Svg;
 pub use self::switcher::Switcher;
 pub use self::themed::Themed;
 pub use self::tilemap::TileMap;
@@ -0,0 +1,301 @@
+//! A widget that plays animated images.
+
+use std::io::Cursor;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use figures::units::UPx;
+use figures::Size;
+use image::codecs::gif::GifDecoder;
+use image::codecs::png::PngDecoder;
+use image::{AnimationDecoder, DynamicImage, Frames, ImageError, ImageFormat};
+use kludgine::wgpu::FilterMode;
+use kludgine::{AnyTexture, Color, LazyTexture};
+
+use crate::animation::ZeroToOne;
+use crate::context::{GraphicsContext, LayoutContext};
+use crate::value::{Destination, Dynamic, IntoDynamic, IntoValue, Source, Value};
+use crate::widget::Widget;
+use crate::widgets::image::ImageScaling;
+use crate::widgets::Image;
+use crate::ConstraintLimit;
+
+/// A widget that plays an animated PNG or GIF.
+///
+/// Playback is controlled through [`Dynamic`]s: [`playing`](Self::playing)
+/// pauses and resumes the animation, and [`loops`](Self::loops) controls how
+/// many times the animation is played. The animation only advances while it is
+/// being drawn, so hidden images and occluded windows do not cause redraws.
+#[derive(Debug)]
+#[must_use]
+pub struct AnimatedImage {
+    frames: Arc<[AnimationFrame]>,
+    image: Image,
+    playing: Dynamic<bool>,
+    loops: Dynamic<LoopCount>,
+    playback: Playback,
+    last_redraw: Option<Instant>,
+}
+
+impl AnimatedImage {
+    /// Returns a widget that plays `frames`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frames` is empty.
+    pub fn new(frames: impl Into<Arc<[AnimationFrame]>>) -> Self {
+        let frames = frames.into();
+        assert!(!frames.is_empty(), "at least one frame is required");
+        Self {
+            image: Image::new(frames[0].texture.clone()),
+            frames,
+            playing: Dynamic::new(true),
+            loops: Dynamic::new(LoopCount::Forever),
+            playback: Playback::default(),
+            last_redraw: None,
+        }
+    }
+
+    /// Decodes the animated PNG or GIF contained in `bytes`.
+    ///
+    /// Images that are not animated are decoded as a single frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` cannot be decoded.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ImageError> {
+        decode_frames(bytes).map(Self::new)
+    }
+
+    /// Reads and decodes the animated PNG or GIF at `path`.
+    ///
+    /// Images that are not animated are decoded as a single frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` cannot be read or decoded.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, ImageError> {
+        let bytes = std::fs::read(path).map_err(ImageError::IoError)?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Uses `playing` to control whether the animation is playing, and
+    /// returns self.
+    ///
+    /// When the animation finishes playing its [loops](Self::loops), `playing`
+    /// is set to false. Setting it to true again restarts the animation.
+    pub fn playing(mut self, playing: impl IntoDynamic<bool>) -> Self {
+        self.playing = playing.into_dynamic();
+        self
+    }
+
+    /// Uses `loops` to control how many times the animation is played, and
+    /// returns self.
+    ///
+    /// By default, the animation loops forever.
+    pub fn loops(mut self, loops: impl IntoDynamic<LoopCount>) -> Self {
+        self.loops = loops.into_dynamic();
+        self
+    }
+
+    /// Applies the `scaling` strategy and returns self.
+    pub fn scaling(mut self, scaling: impl IntoValue<ImageScaling>) -> Self {
+        self.image.scaling = scaling.into_value();
+        self
+    }
+
+    /// Multiplies the image's colors by `tint` and returns self.
+    ///
+    /// See [`Image::tint`] for more information.
+    pub fn tint(mut self, tint: impl IntoValue<Color>) -> Self {
+        self.image.tint = tint.into_value();
+        self
+    }
+
+    /// Draws the image with `opacity` and returns self.
+    pub fn opacity(mut self, opacity: impl IntoValue<ZeroToOne>) -> Self {
+        self.image.opacity = opacity.into_value();
+        self
+    }
+
+    fn update_frame(&mut self) {
+        self.image.contents = Value::Constant(self.frames[self.playback.frame].texture.clone());
+    }
+}
+
+impl Widget for AnimatedImage {
+    fn redraw(&mut self, context: &mut GraphicsContext<'_, '_, '_, '_>) {
+        let playing = self.playing.get_tracking_redraw(context);
+        let occluded = context.occluded().clone();
+        let occluded = occluded.get_tracking_redraw(context);
+        let loops = self.loops.get_tracking_redraw(context);
+        let now = Instant::now();
+
+        if playing && self.playback.finished {
+            self.playback = Playback::default();
+        }
+
+        if playing && !occluded && self.frames.len() > 1 {
+            let elapsed = self
+                .last_redraw
+                .map_or(Duration::ZERO, |last| now.saturating_duration_since(last));
+            let frames = &self.frames;
+            self.playback
+                .advance(frames.len(), |frame| frames[frame].delay, elapsed, loops);
+            if self.playback.finished {
+                self.playing.set(false);
+            } else {
+                let delay = self.frames[self.playback.frame].delay;
+                context.redraw_in(delay.saturating_sub(self.playback.into_frame));
+            }
+            self.last_redraw = Some(now);
+        } else {
+            self.last_redraw = None;
+        }
+
+        self.update_frame();
+        self.image.redraw(context);
+    }
+
+    fn layout(
+        &mut self,
+        available_space: Size<ConstraintLimit>,
+        context: &mut LayoutContext<'_, '_, '_, '_>,
+    ) -> Size<UPx> {
+        self.image.layout(available_space, context)
+    }
+}
+
+/// A single frame of an [`AnimatedImage`].
+#[derive(Debug, Clone)]
+pub struct AnimationFrame {
+    /// The texture to display.
+    pub texture: AnyTexture,
+    /// The amount of time to display this frame.
+    pub delay: Duration,
+}
+
+impl AnimationFrame {
+    /// The delay used for frames that do not specify a delay.
+    ///
+    /// Many encoders write a delay of zero, and browsers display these frames
+    /// for 100 milliseconds.
+    pub const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+
+    /// Returns a frame that displays `texture` for `delay`.
+    ///
+    /// If `delay` is zero, [`Self::DEFAULT_DELAY`] is used.
+    #[must_use]
+    pub fn new(texture: impl Into<AnyTexture>, delay: Duration) -> Self {
+        Self {
+            texture: texture.into(),
+            delay: if delay.is_zero() {
+                Self::DEFAULT_DELAY
+            } else {
+                delay
+            },
+        }
+    }
+}
+
+/// The number of times an [`AnimatedImage`] plays its animation.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum LoopCount {
+    /// The animation repeats until paused.
+    #[default]
+    Forever,
+    /// The animation is played this many times and then stops on its last
+    /// frame.
+    Times(u32),
+}
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+struct Playback {
+    frame: usize,
+    into_frame: Duration,
+    loops_completed: u32,
+    finished: bool,
+}
+
+impl Playback {
+    /// Advances the animation by `elapsed`, where `delay` returns the delay of
+    /// each of the `frame_count` frames.
+    fn advance(
+        &mut self,
+        frame_count: usize,
+        delay: impl Fn(usize) -> Duration,
+        elapsed: Duration,
+        loops: LoopCount,
+    ) {
+        self.into_frame += elapsed;
+        while !self.finished && self.into_frame >= delay(self.frame) {
+            if self.frame + 1 < frame_count {
+                self.into_frame -= delay(self.frame);
+                self.frame += 1;
+            } else {
+                self.loops_completed = self.loops_completed.saturating_add(1);
+                if matches!(loops, LoopCount::Times(times) if self.loops_completed >= times) {
+                    self.finished = true;
+                    self.into_frame = Duration::ZERO;
+                } else {
+                    self.into_frame -= delay(self.frame);
+                    self.frame = 0;
+                }
+            }
+        }
+    }
+}
+
+fn decode_frames(bytes: &[u8]) -> Result<Vec<AnimationFrame>, ImageError> {
+    match image::guess_format(bytes)? {
+        ImageFormat::Png => {
+            let decoder = PngDecoder::new(Cursor::new(bytes))?;
+            if decoder.is_apng() {
+                collect_frames(decoder.apng().into_frames())
+            } else {
+                single_frame(DynamicImage::from_decoder(decoder)?)
+            }
+        }
+        ImageFormat::Gif => collect_frames(GifDecoder::new(Cursor::new(bytes))?.into_frames()),
+        _ => single_frame(image::load_from_memory(bytes)?),
+    }
+}
+
+fn collect_frames(frames: Frames<'_>) -> Result<Vec<AnimationFrame>, ImageError> {
+    frames
+        .map(|frame| {
+            let frame = frame?;
+            let delay = Duration::from(frame.delay());
+            let image = DynamicImage::ImageRgba8(frame.into_buffer());
+            Ok(AnimationFrame::new(
+                LazyTexture::from_image(image, FilterMode::Linear),
+                delay,
+            ))
+        })
+        .collect()
+}
+
+fn single_frame(image: DynamicImage) -> Result<Vec<AnimationFrame>, ImageError> {
+    Ok(vec![AnimationFrame::new(
+        LazyTexture::from_image(image, FilterMode::Linear),
+        Duration::ZERO,
+    )])
+}
+
+#[test]
+fn playback_advances_and_loops() {
+    let delay = |_| Duration::from_millis(100);
+    let mut playback = Playback::default();
+    playback.advance(3, delay, Duration::from_millis(150), LoopCount::Forever);
+    assert_eq!(playback.frame, 1);
+    assert_eq!(playback.into_frame, Duration::from_millis(50));
+    playback.advance(3, delay, Duration::from_millis(200), LoopCount::Forever);
+    assert_eq!(playback.frame, 0);
+    assert_eq!(playback.loops_completed, 1);
+
+    let mut playback = Playback::default();
+    playback.advance(3, delay, Duration::from_secs(1), LoopCount::Times(2));
+    assert!(playback.finished);
+    assert_eq!(playback.frame, 2);
+}
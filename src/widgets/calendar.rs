@@ -0,0 +1,1593 @@
+//! Widgets for choosing dates and times.
+//!
+//! [`Calendar`] displays a month at a time, allowing a single [`Date`] or a
+//! [`DateRange`] to be selected. [`DatePicker`] and [`TimePicker`] are text
+//! inputs that show a calendar or a set of spinners in an
+//! [`OverlayLayer`](crate::widgets::layers::OverlayLayer) when their button is
+//! clicked.
+//!
+//! [`Date`] and [`Time`] are intentionally plain. When the `time` or `chrono`
+//! features are enabled, they can be converted to and from the equivalent
+//! types from those crates. These features are also required to determine the
+//! local time zone. Without either feature, local dates and times use UTC.
+
+use std::array;
+use std::error::Error;
+use std::fmt::{self, Debug, Display, Write};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use figures::units::{Lp, Px, UPx};
+use figures::{IntoUnsigned, Point, Rect, Round, ScreenScale, Size};
+use kludgine::app::winit::event::MouseButton;
+use kludgine::app::winit::keyboard::{Key, NamedKey};
+use kludgine::app::winit::window::CursorIcon;
+use kludgine::shapes::{Shape, StrokeOptions};
+use kludgine::text::{Text, TextOrigin};
+use kludgine::{Color, DrawableExt};
+
+use super::layers::{OverlayHandle, OverlayLayer};
+use super::number_input::NumberFormat;
+use super::{Button, Grid, Input, NumberInput};
+use crate::context::{EventContext, GraphicsContext, LayoutContext, WidgetContext};
+use crate::styles::components::{
+    AutoFocusableControls, DisabledOutlineColor, HighlightColor, IntrinsicPadding, PrimaryColor,
+    TextColor, TextColorVariant,
+};
+use crate::utils::locale_language_and_region;
+use crate::value::{
+    CallbackDisconnected, Destination, Dynamic, IntoDynamic, IntoValue, Source, Validation, Value,
+};
+use crate::widget::{
+    EventHandling, MakeWidget, MakeWidgetWithTag, Widget, WidgetId, WidgetInstance, WidgetRef,
+    WidgetTag, WrapperWidget, HANDLED, IGNORED,
+};
+use crate::widgets::container::ContainerShadow;
+use crate::widgets::grid::{GridSection, GridWidgets};
+use crate::window::{DeviceId, KeyEvent};
+use crate::ConstraintLimit;
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// A calendar date in the proleptic Gregorian calendar.
+///
+/// Dates between the years -9999 and 9999 can be represented.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// The maximum date that can be represented: December 31, 9999.
+    pub const MAX: Self = Self {
+        year: 9999,
+        month: 12,
+        day: 31,
+    };
+    /// The minimum date that can be represented: January 1, -9999.
+    pub const MIN: Self = Self {
+        year: -9999,
+        month: 1,
+        day: 1,
+    };
+
+    /// Returns the date for `day` of `month` in `year`, or `None` if the date
+    /// does not exist or is outside of the supported range.
+    ///
+    /// `month` and `day` both start at 1.
+    #[must_use]
+    pub const fn new(year: i32, month: u8, day: u8) -> Option<Self> {
+        if year < Self::MIN.year
+            || year > Self::MAX.year
+            || month < 1
+            || month > 12
+            || day < 1
+            || day > Self::days_in_month(year, month)
+        {
+            None
+        } else {
+            Some(Self { year, month, day })
+        }
+    }
+
+    /// Returns the current date in the local time zone.
+    #[must_use]
+    pub fn today() -> Self {
+        Self::local(SystemTime::now())
+    }
+
+    /// Returns the date of `time` in the local time zone.
+    ///
+    /// The local time zone can only be determined when the `time` or `chrono`
+    /// feature is enabled. Otherwise, this function returns the date in UTC.
+    #[must_use]
+    pub fn local(time: SystemTime) -> Self {
+        Self::from(local_time(time))
+    }
+
+    /// Returns the number of days in `month` of `year`.
+    #[must_use]
+    pub const fn days_in_month(year: i32, month: u8) -> u8 {
+        match month {
+            2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    /// Returns the year of this date.
+    #[must_use]
+    pub const fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Returns the month of this date, starting at 1 for January.
+    #[must_use]
+    pub const fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Returns the day of the month of this date, starting at 1.
+    #[must_use]
+    pub const fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Returns the English name of this date's month.
+    #[must_use]
+    pub const fn month_name(&self) -> &'static str {
+        MONTH_NAMES[self.month as usize - 1]
+    }
+
+    /// Returns the day of the week this date falls on.
+    #[must_use]
+    pub fn weekday(&self) -> Weekday {
+        // January 1, 1970 was a Thursday.
+        Weekday::Thursday.offset(self.days_since_epoch())
+    }
+
+    /// Returns the first day of this date's month.
+    #[must_use]
+    pub const fn first_of_month(&self) -> Self {
+        Self {
+            year: self.year,
+            month: self.month,
+            day: 1,
+        }
+    }
+
+    /// Returns this date moved by `days`, clamped to [`Date::MIN`] and
+    /// [`Date::MAX`].
+    #[must_use]
+    pub fn add_days(&self, days: i64) -> Self {
+        Self::from_days_since_epoch(self.days_since_epoch().saturating_add(days))
+    }
+
+    /// Returns this date moved by `months`, clamped to [`Date::MIN`] and
+    /// [`Date::MAX`].
+    ///
+    /// If the day does not exist in the resulting month, the last day of that
+    /// month is returned.
+    #[must_use]
+    pub fn add_months(&self, months: i32) -> Self {
+        let month_index = i64::from(self.year) * 12 + i64::from(self.month) - 1 + i64::from(months);
+        let year = month_index.div_euclid(12);
+        let Ok(year) = i32::try_from(year) else {
+            return if months < 0 { Self::MIN } else { Self::MAX };
+        };
+        if year < Self::MIN.year {
+            return Self::MIN;
+        } else if year > Self::MAX.year {
+            return Self::MAX;
+        }
+        let month = u8::try_from(month_index.rem_euclid(12) + 1).expect("always 1..=12");
+        Self {
+            year,
+            month,
+            day: self.day.min(Self::days_in_month(year, month)),
+        }
+    }
+
+    /// Returns the number of days from `self` until `other`. If `other` is
+    /// before `self`, the result is negative.
+    #[must_use]
+    pub fn days_until(&self, other: Date) -> i64 {
+        other.days_since_epoch() - self.days_since_epoch()
+    }
+
+    #[allow(clippy::cast_lossless)] // `From` cannot be used in const fns.
+    const fn days_since_epoch(&self) -> i64 {
+        // Adapted from Howard Hinnant's `days_from_civil` algorithm.
+        let month = self.month as i64;
+        let year = self.year as i64 - (month <= 2) as i64;
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    fn from_days_since_epoch(days: i64) -> Self {
+        const MIN_DAYS: i64 = Date::MIN.days_since_epoch();
+        const MAX_DAYS: i64 = Date::MAX.days_since_epoch();
+        let days = days.clamp(MIN_DAYS, MAX_DAYS) + 719_468;
+        // Adapted from Howard Hinnant's `civil_from_days` algorithm.
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Self {
+            year: i32::try_from(year).expect("clamped to supported range"),
+            month: u8::try_from(month).expect("always 1..=12"),
+            day: u8::try_from(day).expect("always 1..=31"),
+        }
+    }
+}
+
+impl Display for Date {
+    /// Formats this date using the ISO 8601 format: `YYYY-MM-DD`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.year < 0 {
+            f.write_char('-')?;
+        }
+        write!(
+            f,
+            "{:04}-{:02}-{:02}",
+            self.year.unsigned_abs(),
+            self.month,
+            self.day
+        )
+    }
+}
+
+impl FromStr for Date {
+    type Err = InvalidDate;
+
+    /// Parses a date in the ISO 8601 format: `YYYY-MM-DD`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (negative, s) = s
+            .strip_prefix('-')
+            .map_or((false, s), |unsigned| (true, unsigned));
+        let mut parts = s.split('-');
+        let (Some(year), Some(month), Some(day), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(InvalidDate);
+        };
+        let year = year.parse::<i32>().map_err(|_| InvalidDate)?;
+        let year = if negative { -year } else { year };
+        let month = month.parse().map_err(|_| InvalidDate)?;
+        let day = day.parse().map_err(|_| InvalidDate)?;
+        Self::new(year, month, day).ok_or(InvalidDate)
+    }
+}
+
+/// An inclusive range of [`Date`]s.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct DateRange {
+    /// The first date in the range.
+    pub start: Date,
+    /// The last date in the range.
+    pub end: Date,
+}
+
+impl DateRange {
+    /// Returns a range containing all dates between `a` and `b`, regardless of
+    /// which date is first.
+    #[must_use]
+    pub fn new(a: Date, b: Date) -> Self {
+        Self {
+            start: a.min(b),
+            end: a.max(b),
+        }
+    }
+
+    /// Returns true if `date` is within this range.
+    #[must_use]
+    pub fn contains(&self, date: Date) -> bool {
+        self.start <= date && date <= self.end
+    }
+
+    /// Returns the number of days in this range.
+    #[must_use]
+    pub fn day_count(&self) -> u64 {
+        self.start.days_until(self.end).unsigned_abs() + 1
+    }
+}
+
+/// Returns `time` shifted by the local time zone's offset from UTC, such that
+/// converting the result as a UTC time produces the local date and time.
+fn local_time(time: SystemTime) -> SystemTime {
+    let offset = local_offset_seconds(time);
+    let shift = Duration::from_secs(u64::from(offset.unsigned_abs()));
+    if offset >= 0 {
+        time.checked_add(shift)
+    } else {
+        time.checked_sub(shift)
+    }
+    .unwrap_or(time)
+}
+
+/// Returns the local time zone's offset from UTC at `time`, in seconds.
+#[cfg(feature = "chrono")]
+fn local_offset_seconds(time: SystemTime) -> i32 {
+    chrono::DateTime::<chrono::Local>::from(time)
+        .offset()
+        .local_minus_utc()
+}
+
+/// Returns the local time zone's offset from UTC at `time`, in seconds.
+///
+/// `time` is unable to determine the offset on some platforms while multiple
+/// threads are running, in which case UTC is used.
+#[cfg(all(feature = "time", not(feature = "chrono")))]
+fn local_offset_seconds(time: SystemTime) -> i32 {
+    time::UtcOffset::local_offset_at(time::OffsetDateTime::from(time))
+        .map_or(0, time::UtcOffset::whole_seconds)
+}
+
+/// Without the `time` or `chrono` features, the local time zone is unknown and
+/// UTC is used.
+#[cfg(not(any(feature = "time", feature = "chrono")))]
+fn local_offset_seconds(_time: SystemTime) -> i32 {
+    0
+}
+
+impl From<SystemTime> for Date {
+    /// Returns the date of `time` in UTC.
+    ///
+    /// Times before the Unix epoch are treated as the epoch.
+    fn from(time: SystemTime) -> Self {
+        let days = time
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() / 86_400);
+        Self::from_days_since_epoch(i64::try_from(days).unwrap_or(i64::MAX))
+    }
+}
+
+impl From<Date> for DateRange {
+    fn from(date: Date) -> Self {
+        Self::new(date, date)
+    }
+}
+
+/// A time of day, with a precision of one second.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub struct Time {
+    hour: u8,
+    minute: u8,
+    second: u8,
+}
+
+impl Time {
+    /// Midnight: 00:00:00.
+    pub const MIDNIGHT: Self = Self {
+        hour: 0,
+        minute: 0,
+        second: 0,
+    };
+
+    /// Returns a new time, or `None` if any component is out of range.
+    #[must_use]
+    pub const fn new(hour: u8, minute: u8, second: u8) -> Option<Self> {
+        if hour < 24 && minute < 60 && second < 60 {
+            Some(Self {
+                hour,
+                minute,
+                second,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the current time in the local time zone.
+    #[must_use]
+    pub fn now() -> Self {
+        Self::local(SystemTime::now())
+    }
+
+    /// Returns the time of day of `time` in the local time zone.
+    ///
+    /// The local time zone can only be determined when the `time` or `chrono`
+    /// feature is enabled. Otherwise, this function returns the time in UTC.
+    #[must_use]
+    pub fn local(time: SystemTime) -> Self {
+        Self::from(local_time(time))
+    }
+
+    /// Returns the hour, from 0 to 23.
+    #[must_use]
+    pub const fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Returns the minute, from 0 to 59.
+    #[must_use]
+    pub const fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// Returns the second, from 0 to 59.
+    #[must_use]
+    pub const fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// Returns this time with its hour replaced, or `None` if `hour` is out of
+    /// range.
+    #[must_use]
+    pub const fn with_hour(self, hour: u8) -> Option<Self> {
+        Self::new(hour, self.minute, self.second)
+    }
+
+    /// Returns this time with its minute replaced, or `None` if `minute` is
+    /// out of range.
+    #[must_use]
+    pub const fn with_minute(self, minute: u8) -> Option<Self> {
+        Self::new(self.hour, minute, self.second)
+    }
+
+    /// Returns this time with its second replaced, or `None` if `second` is
+    /// out of range.
+    #[must_use]
+    pub const fn with_second(self, second: u8) -> Option<Self> {
+        Self::new(self.hour, self.minute, second)
+    }
+
+    fn format(&self, seconds: bool) -> String {
+        if seconds {
+            self.to_string()
+        } else {
+            format!("{:02}:{:02}", self.hour, self.minute)
+        }
+    }
+}
+
+impl From<SystemTime> for Time {
+    /// Returns the time of day of `time` in UTC.
+    ///
+    /// Times before the Unix epoch are treated as the epoch.
+    fn from(time: SystemTime) -> Self {
+        let seconds = time
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() % 86_400);
+        let seconds = u32::try_from(seconds).expect("less than a day");
+        Self {
+            hour: u8::try_from(seconds / 3600).expect("less than 24"),
+            minute: u8::try_from(seconds / 60 % 60).expect("less than 60"),
+            second: u8::try_from(seconds % 60).expect("less than 60"),
+        }
+    }
+}
+
+impl Display for Time {
+    /// Formats this time as `HH:MM:SS`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)
+    }
+}
+
+impl FromStr for Time {
+    type Err = InvalidTime;
+
+    /// Parses a 24-hour time formatted as `HH:MM` or `HH:MM:SS`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split(':');
+        let (Some(hour), Some(minute), second, None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(InvalidTime);
+        };
+        let hour = hour.parse().map_err(|_| InvalidTime)?;
+        let minute = minute.parse().map_err(|_| InvalidTime)?;
+        let second = second.map_or(Ok(0), str::parse).map_err(|_| InvalidTime)?;
+        Self::new(hour, minute, second).ok_or(InvalidTime)
+    }
+}
+
+/// An error parsing a [`Date`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct InvalidDate;
+
+impl Display for InvalidDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("expected a date formatted as YYYY-MM-DD")
+    }
+}
+
+impl Error for InvalidDate {}
+
+/// An error parsing a [`Time`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct InvalidTime;
+
+impl Display for InvalidTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("expected a time formatted as HH:MM or HH:MM:SS")
+    }
+}
+
+impl Error for InvalidTime {}
+
+/// A day of the week.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Weekday {
+    /// Monday
+    Monday,
+    /// Tuesday
+    Tuesday,
+    /// Wednesday
+    Wednesday,
+    /// Thursday
+    Thursday,
+    /// Friday
+    Friday,
+    /// Saturday
+    Saturday,
+    /// Sunday
+    Sunday,
+}
+
+impl Weekday {
+    const ALL: [Self; 7] = [
+        Self::Monday,
+        Self::Tuesday,
+        Self::Wednesday,
+        Self::Thursday,
+        Self::Friday,
+        Self::Saturday,
+        Self::Sunday,
+    ];
+
+    /// Returns the first day of the week for the operating system's current
+    /// locale, or [`Weekday::Monday`] if the locale cannot be determined.
+    #[must_use]
+    pub fn system() -> Self {
+        sys_locale::get_locale().map_or(Self::Monday, |locale| Self::first_for_locale(&locale))
+    }
+
+    /// Returns the first day of the week typically used by `locale`, which is
+    /// a [BCP 47](https://www.rfc-editor.org/info/bcp47) language tag such as
+    /// `en-US` or `de-DE`.
+    ///
+    /// This is an approximation of the week data in the Unicode CLDR.
+    #[must_use]
+    pub fn first_for_locale(locale: &str) -> Self {
+        let (language, region) = locale_language_and_region(locale);
+        match (language.as_str(), region.as_str()) {
+            (
+                _,
+                "AG" | "AS" | "BD" | "BR" | "BS" | "BT" | "BW" | "BZ" | "CA" | "CO" | "DM" | "DO"
+                | "ET" | "GT" | "GU" | "HK" | "HN" | "ID" | "IL" | "IN" | "JM" | "JP" | "KE" | "KH"
+                | "KR" | "LA" | "MH" | "MM" | "MO" | "MT" | "MX" | "MZ" | "NI" | "NP" | "PA" | "PE"
+                | "PH" | "PK" | "PR" | "PT" | "PY" | "SA" | "SG" | "SV" | "TH" | "TT" | "TW" | "UM"
+                | "US" | "VE" | "VI" | "WS" | "YE" | "ZA" | "ZW",
+            )
+            | ("en" | "he" | "ja" | "ko", "") => Self::Sunday,
+            (
+                _,
+                "AE" | "AF" | "BH" | "DJ" | "DZ" | "EG" | "IQ" | "IR" | "JO" | "KW" | "LY" | "OM"
+                | "QA" | "SD" | "SY",
+            )
+            | ("ar" | "fa", "") => Self::Saturday,
+            (_, "MV") => Self::Friday,
+            _ => Self::Monday,
+        }
+    }
+
+    /// Returns the weekday `days` after this weekday.
+    #[must_use]
+    pub fn offset(self, days: i64) -> Self {
+        let index = usize::try_from((self.index() + days).rem_euclid(7)).expect("always 0..7");
+        Self::ALL[index]
+    }
+
+    /// Returns the number of days from `start` until this weekday, from 0 to
+    /// 6.
+    #[must_use]
+    pub fn days_since(self, start: Weekday) -> i64 {
+        (self.index() - start.index()).rem_euclid(7)
+    }
+
+    /// Returns a two-letter English abbreviation of this weekday.
+    #[must_use]
+    pub const fn abbreviation(self) -> &'static str {
+        match self {
+            Weekday::Monday => "Mo",
+            Weekday::Tuesday => "Tu",
+            Weekday::Wednesday => "We",
+            Weekday::Thursday => "Th",
+            Weekday::Friday => "Fr",
+            Weekday::Saturday => "Sa",
+            Weekday::Sunday => "Su",
+        }
+    }
+
+    fn index(self) -> i64 {
+        match self {
+            Weekday::Monday => 0,
+            Weekday::Tuesday => 1,
+            Weekday::Wednesday => 2,
+            Weekday::Thursday => 3,
+            Weekday::Friday => 4,
+            Weekday::Saturday => 5,
+            Weekday::Sunday => 6,
+        }
+    }
+}
+
+impl Display for Weekday {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+
+/// A month calendar for selecting a [`Date`] or a [`DateRange`].
+///
+/// Dates can be selected by clicking them. When focused, the arrow keys move
+/// between days, Page Up and Page Down move between months (or years when
+/// Shift is held), Home and End move to the start and end of the week, and
+/// Enter or Space select the highlighted date.
+///
+/// When selecting a range, the first selection starts a new range and the
+/// second completes it. Holding Shift while clicking or using the arrow keys
+/// extends the current range instead.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct Calendar {
+    selection: CalendarSelection,
+    minimum: Value<Date>,
+    maximum: Value<Date>,
+    disabled: Option<DisabledDates>,
+    on_select: Option<OnSelect>,
+    first_day_of_week: Weekday,
+}
+
+impl Calendar {
+    /// Returns a calendar that selects a single date, storing it in `date`.
+    pub fn new(date: impl IntoDynamic<Date>) -> Self {
+        Self::with_selection(CalendarSelection::Single(date.into_dynamic()))
+    }
+
+    /// Returns a calendar that selects a range of dates, storing it in
+    /// `range`.
+    pub fn range(range: impl IntoDynamic<DateRange>) -> Self {
+        Self::with_selection(CalendarSelection::Range(range.into_dynamic()))
+    }
+
+    fn with_selection(selection: CalendarSelection) -> Self {
+        Self {
+            selection,
+            minimum: Value::Constant(Date::MIN),
+            maximum: Value::Constant(Date::MAX),
+            disabled: None,
+            on_select: None,
+            first_day_of_week: Weekday::system(),
+        }
+    }
+
+    /// Sets the earliest date that can be selected and returns self.
+    pub fn minimum(mut self, minimum: impl IntoValue<Date>) -> Self {
+        self.minimum = minimum.into_value();
+        self
+    }
+
+    /// Sets the latest date that can be selected and returns self.
+    pub fn maximum(mut self, maximum: impl IntoValue<Date>) -> Self {
+        self.maximum = maximum.into_value();
+        self
+    }
+
+    /// Prevents dates from being selected when `disabled` returns true, and
+    /// returns self.
+    pub fn disabled_when<F>(mut self, disabled: F) -> Self
+    where
+        F: Fn(Date) -> bool + Send + Sync + 'static,
+    {
+        self.disabled = Some(DisabledDates(Arc::new(disabled)));
+        self
+    }
+
+    /// Invokes `on_select` each time a date is chosen by clicking it or by
+    /// pressing Enter or Space, and returns self.
+    ///
+    /// Unlike observing the selected date, this is not invoked when the
+    /// selection is changed elsewhere or extended with the arrow keys.
+    pub fn on_select<F>(mut self, on_select: F) -> Self
+    where
+        F: Fn(Date) + Send + Sync + 'static,
+    {
+        self.on_select = Some(OnSelect(Arc::new(on_select)));
+        self
+    }
+
+    /// Sets the day displayed in the first column of the calendar and returns
+    /// self.
+    ///
+    /// By default, the first day of the week for the system's locale is used.
+    pub fn first_day_of_week(mut self, weekday: Weekday) -> Self {
+        self.first_day_of_week = weekday;
+        self
+    }
+
+    /// Returns true if `date` can be selected in this calendar.
+    #[must_use]
+    pub fn allows(&self, date: Date) -> bool {
+        let (minimum, maximum) = self.bounds();
+        date >= minimum
+            && date <= maximum
+            && !self
+                .disabled
+                .as_ref()
+                .map_or(false, |disabled| (disabled.0)(date))
+    }
+
+    /// Returns the minimum and maximum dates, ordered such that the minimum
+    /// is never after the maximum.
+    fn bounds(&self) -> (Date, Date) {
+        let minimum = self.minimum.get();
+        let maximum = self.maximum.get();
+        (minimum.min(maximum), minimum.max(maximum))
+    }
+
+    /// Returns `date` limited to the minimum and maximum dates.
+    fn clamp(&self, date: Date) -> Date {
+        let (minimum, maximum) = self.bounds();
+        date.clamp(minimum, maximum)
+    }
+}
+
+impl MakeWidgetWithTag for Calendar {
+    fn make_with_tag(self, id: WidgetTag) -> WidgetInstance {
+        let calendar_id = id.id();
+        let initial = self.selection.cursor_date();
+        let cursor = Dynamic::new(self.clamp(initial));
+
+        // Keep the displayed month in sync with the selection when it is
+        // changed elsewhere.
+        let weak_cursor = cursor.downgrade();
+        let follow = move |date: Date| {
+            let cursor = weak_cursor.upgrade().ok_or(CallbackDisconnected)?;
+            cursor.set(date);
+            Ok(())
+        };
+        cursor.set_source(match &self.selection {
+            CalendarSelection::Single(date) => date.for_each_try(move |date| follow(*date)),
+            CalendarSelection::Range(range) => range.for_each_try(move |range| follow(range.end)),
+        });
+
+        // Today's date is only checked when the displayed dates change, rather
+        // than by each day as it is drawn.
+        let today = cursor.map_each(|_| Date::today());
+
+        let state = CalendarState {
+            cursor,
+            today,
+            pending_start: Dynamic::new(None),
+            pivot: Dynamic::new(initial),
+            focused: Dynamic::new(false),
+            calendar: self,
+        };
+
+        let month = state
+            .cursor
+            .map_each(|date| format!("{} {}", date.month_name(), date.year()));
+        let previous = Button::new("\u{25C0}").on_click({
+            let state = state.clone();
+            move |()| state.move_cursor(state.cursor.get().add_months(-1))
+        });
+        let next = Button::new("\u{25B6}").on_click({
+            let state = state.clone();
+            move |()| state.move_cursor(state.cursor.get().add_months(1))
+        });
+        let header = previous
+            .and(month.centered().expand())
+            .and(next)
+            .into_columns();
+
+        let first_day = state.calendar.first_day_of_week;
+        let weekdays = GridSection::from(array::from_fn::<_, 7, _>(|column| {
+            first_day
+                .offset(i64::try_from(column).expect("less than 7"))
+                .abbreviation()
+        }));
+        let days = (0..6).map(|row| {
+            GridSection::from(array::from_fn::<_, 7, _>(|column| DayCell {
+                index: row * 7 + column,
+                state: state.clone(),
+                calendar: calendar_id,
+            }))
+        });
+        let grid = Grid::from_rows(
+            std::iter::once(weekdays)
+                .chain(days)
+                .collect::<GridWidgets<7>>(),
+        );
+
+        CalendarWidget {
+            child: WidgetRef::new(header.and(grid).into_rows()),
+            state,
+        }
+        .make_with_tag(id)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum CalendarSelection {
+    Single(Dynamic<Date>),
+    Range(Dynamic<DateRange>),
+}
+
+impl CalendarSelection {
+    fn cursor_date(&self) -> Date {
+        match self {
+            CalendarSelection::Single(date) => date.get(),
+            CalendarSelection::Range(range) => range.get().end,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct OnSelect(Arc<dyn Fn(Date) + Send + Sync>);
+
+impl Debug for OnSelect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OnSelect")
+            .field(&Arc::as_ptr(&self.0))
+            .finish()
+    }
+}
+
+#[derive(Clone)]
+struct DisabledDates(Arc<dyn Fn(Date) -> bool + Send + Sync>);
+
+impl Debug for DisabledDates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DisabledDates")
+            .field(&Arc::as_ptr(&self.0))
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum DaySelection {
+    None,
+    Endpoint,
+    Within,
+}
+
+#[derive(Debug, Clone)]
+struct CalendarState {
+    calendar: Calendar,
+    /// The highlighted date, which also determines the displayed month.
+    cursor: Dynamic<Date>,
+    /// The current date, updated each time the cursor moves.
+    today: Dynamic<Date>,
+    /// The start of a range that is waiting for its second date.
+    pending_start: Dynamic<Option<Date>>,
+    /// The date that stays fixed when a range is extended.
+    pivot: Dynamic<Date>,
+    focused: Dynamic<bool>,
+}
+
+impl CalendarState {
+    fn first_visible(&self, cursor: Date) -> Date {
+        let first = cursor.first_of_month();
+        first.add_days(-first.weekday().days_since(self.calendar.first_day_of_week))
+    }
+
+    fn move_cursor(&self, date: Date) {
+        self.cursor.set(self.calendar.clamp(date));
+    }
+
+    fn select(&self, date: Date, extend: bool) {
+        if !self.calendar.allows(date) {
+            return;
+        }
+
+        match &self.calendar.selection {
+            CalendarSelection::Single(selected) => selected.set(date),
+            CalendarSelection::Range(range) => {
+                if extend {
+                    self.pending_start.set(None);
+                    range.set(DateRange::new(self.pivot.get(), date));
+                } else if let Some(start) = self.pending_start.take() {
+                    self.pivot.set(start);
+                    range.set(DateRange::new(start, date));
+                } else {
+                    self.pending_start.set(Some(date));
+                    self.pivot.set(date);
+                    range.set(DateRange::from(date));
+                }
+            }
+        }
+        self.cursor.set(date);
+    }
+
+    /// Selects `date` as an explicit choice of the user, notifying
+    /// [`Calendar::on_select`].
+    fn choose(&self, date: Date, extend: bool) {
+        if !self.calendar.allows(date) {
+            return;
+        }
+
+        self.select(date, extend);
+        if let Some(on_select) = &self.calendar.on_select {
+            (on_select.0)(date);
+        }
+    }
+
+    fn selection_of(&self, date: Date, context: &WidgetContext<'_>) -> DaySelection {
+        match &self.calendar.selection {
+            CalendarSelection::Single(selected) => {
+                if selected.get_tracking_redraw(context) == date {
+                    DaySelection::Endpoint
+                } else {
+                    DaySelection::None
+                }
+            }
+            CalendarSelection::Range(range) => {
+                let range = range.get_tracking_redraw(context);
+                if date == range.start || date == range.end {
+                    DaySelection::Endpoint
+                } else if range.contains(date) {
+                    DaySelection::Within
+                } else {
+                    DaySelection::None
+                }
+            }
+        }
+    }
+}
+
+/// The focusable container of a [`Calendar`] that handles keyboard input.
+#[derive(Debug)]
+struct CalendarWidget {
+    child: WidgetRef,
+    state: CalendarState,
+}
+
+impl WrapperWidget for CalendarWidget {
+    fn child_mut(&mut self) -> &mut WidgetRef {
+        &mut self.child
+    }
+
+    fn accept_focus(&mut self, context: &mut EventContext<'_>) -> bool {
+        context.enabled() && context.get(&AutoFocusableControls).is_all()
+    }
+
+    fn focus(&mut self, _context: &mut EventContext<'_>) {
+        self.state.focused.set(true);
+    }
+
+    fn blur(&mut self, _context: &mut EventContext<'_>) {
+        self.state.focused.set(false);
+    }
+
+    fn keyboard_input(
+        &mut self,
+        _device_id: DeviceId,
+        input: KeyEvent,
+        _is_synthetic: bool,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        let shift = context.modifiers().state().shift_key();
+        let cursor = self.state.cursor.get();
+        let column = cursor
+            .weekday()
+            .days_since(self.state.calendar.first_day_of_week);
+        let (target, can_extend) = match input.logical_key {
+            Key::Named(NamedKey::ArrowLeft) => (cursor.add_days(-1), true),
+            Key::Named(NamedKey::ArrowRight) => (cursor.add_days(1), true),
+            Key::Named(NamedKey::ArrowUp) => (cursor.add_days(-7), true),
+            Key::Named(NamedKey::ArrowDown) => (cursor.add_days(7), true),
+            Key::Named(NamedKey::Home) => (cursor.add_days(-column), true),
+            Key::Named(NamedKey::End) => (cursor.add_days(6 - column), true),
+            Key::Named(NamedKey::PageUp) => {
+                (cursor.add_months(if shift { -12 } else { -1 }), false)
+            }
+            Key::Named(NamedKey::PageDown) => {
+                (cursor.add_months(if shift { 12 } else { 1 }), false)
+            }
+            Key::Named(NamedKey::Enter | NamedKey::Space) => {
+                if input.state.is_pressed() {
+                    self.state.choose(cursor, shift);
+                }
+                return HANDLED;
+            }
+            _ => return IGNORED,
+        };
+
+        if input.state.is_pressed() {
+            if shift
+                && can_extend
+                && matches!(self.state.calendar.selection, CalendarSelection::Range(_))
+            {
+                self.state.select(target, true);
+            } else {
+                self.state.move_cursor(target);
+            }
+        }
+
+        HANDLED
+    }
+}
+
+/// A single day within a [`Calendar`].
+#[derive(Debug)]
+struct DayCell {
+    index: usize,
+    state: CalendarState,
+    calendar: WidgetId,
+}
+
+impl DayCell {
+    fn date(&self, cursor: Date) -> Date {
+        self.state
+            .first_visible(cursor)
+            .add_days(i64::try_from(self.index).expect("less than 42"))
+    }
+}
+
+impl Widget for DayCell {
+    fn redraw(&mut self, context: &mut GraphicsContext<'_, '_, '_, '_>) {
+        let cursor = self.state.cursor.get_tracking_redraw(context);
+        let focused = self.state.focused.get_tracking_redraw(context);
+        let today = self.state.today.get_tracking_redraw(context);
+        let date = self.date(cursor);
+        let selection = self.state.selection_of(date, context);
+        let enabled = self.state.calendar.allows(date);
+
+        let region = Rect::from(context.gfx.region().size);
+        match selection {
+            DaySelection::Endpoint => {
+                let color = context.get(&SelectedDayColor);
+                context.gfx.draw_shape(&Shape::filled_rect(region, color));
+            }
+            DaySelection::Within => {
+                let color = context.get(&DayRangeColor);
+                context.gfx.draw_shape(&Shape::filled_rect(region, color));
+            }
+            DaySelection::None => {}
+        }
+
+        if date == today {
+            let stroke = StrokeOptions::px_wide(Lp::points(1).into_px(context.gfx.scale()).round());
+            let color = context.get(&TodayColor);
+            context.gfx.draw_shape(&Shape::stroked_rect(
+                region.inset(stroke.line_width / 2),
+                stroke.colored(color),
+            ));
+        }
+
+        let text_color = if selection == DaySelection::Endpoint {
+            context.get(&SelectedDayTextColor)
+        } else if !enabled {
+            context.get(&DisabledDayColor)
+        } else if date.month() != cursor.month() {
+            context.get(&OtherMonthDayColor)
+        } else {
+            context.get(&TextColor)
+        };
+        context.apply_current_font_settings();
+        let label = date.day().to_string();
+        let text = &context
+            .gfx
+            .measure_text(Text::<Px>::new(&label, text_color));
+        let center = Point::from(region.size) / 2;
+        context
+            .gfx
+            .draw_measured_text(text.translate_by(center.round()), TextOrigin::Center);
+
+        if focused && date == cursor {
+            context.draw_focus_ring();
+        }
+    }
+
+    fn layout(
+        &mut self,
+        _available_space: Size<ConstraintLimit>,
+        context: &mut LayoutContext<'_, '_, '_, '_>,
+    ) -> Size<UPx> {
+        context.apply_current_font_settings();
+        let measured = context
+            .gfx
+            .measure_text(Text::<Px>::new("00", Color::WHITE));
+        let padding = context
+            .get(&IntrinsicPadding)
+            .into_px(context.gfx.scale())
+            .round();
+        let side = measured.size.width.max(measured.size.height) + padding * 2;
+        Size::squared(side).into_unsigned()
+    }
+
+    fn hit_test(&mut self, _location: Point<Px>, _context: &mut EventContext<'_>) -> bool {
+        true
+    }
+
+    fn hover(
+        &mut self,
+        _location: Point<Px>,
+        context: &mut EventContext<'_>,
+    ) -> Option<CursorIcon> {
+        let date = self.date(self.state.cursor.get());
+        (context.enabled() && self.state.calendar.allows(date)).then_some(CursorIcon::Pointer)
+    }
+
+    fn mouse_down(
+        &mut self,
+        _location: Point<Px>,
+        _device_id: DeviceId,
+        button: MouseButton,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        if button != MouseButton::Left || !context.enabled() {
+            return IGNORED;
+        }
+
+        let date = self.date(self.state.cursor.get());
+        let extend = context.modifiers().state().shift_key();
+        self.state.choose(date, extend);
+        if let Some(mut calendar) = context.for_other(&self.calendar) {
+            calendar.focus();
+        }
+        HANDLED
+    }
+}
+
+/// A text input for a [`Date`] that shows a [`Calendar`] when its button is
+/// clicked.
+///
+/// Dates are entered in the ISO 8601 format: `YYYY-MM-DD`.
+#[must_use]
+pub struct DatePicker {
+    /// The selected date.
+    pub value: Dynamic<Date>,
+    calendar: Calendar,
+    overlay: OverlayLayer,
+}
+
+impl DatePicker {
+    /// Returns a new picker that edits `value`, showing its calendar in
+    /// `overlay`.
+    pub fn new(value: impl IntoDynamic<Date>, overlay: &OverlayLayer) -> Self {
+        let value = value.into_dynamic();
+        Self {
+            calendar: Calendar::new(value.clone()),
+            value,
+            overlay: overlay.clone(),
+        }
+    }
+
+    /// Sets the earliest date that can be selected and returns self.
+    pub fn minimum(mut self, minimum: impl IntoValue<Date>) -> Self {
+        self.calendar = self.calendar.minimum(minimum);
+        self
+    }
+
+    /// Sets the latest date that can be selected and returns self.
+    pub fn maximum(mut self, maximum: impl IntoValue<Date>) -> Self {
+        self.calendar = self.calendar.maximum(maximum);
+        self
+    }
+
+    /// Prevents dates from being selected when `disabled` returns true, and
+    /// returns self.
+    pub fn disabled_when<F>(mut self, disabled: F) -> Self
+    where
+        F: Fn(Date) -> bool + Send + Sync + 'static,
+    {
+        self.calendar = self.calendar.disabled_when(disabled);
+        self
+    }
+
+    /// Sets the day displayed in the first column of the calendar and returns
+    /// self.
+    pub fn first_day_of_week(mut self, weekday: Weekday) -> Self {
+        self.calendar = self.calendar.first_day_of_week(weekday);
+        self
+    }
+}
+
+impl Debug for DatePicker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DatePicker")
+            .field("value", &self.value)
+            .field("calendar", &self.calendar)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MakeWidgetWithTag for DatePicker {
+    fn make_with_tag(self, id: WidgetTag) -> WidgetInstance {
+        let validation = Dynamic::default();
+        let text = bind_text(&self.value, &validation, Date::to_string, {
+            let calendar = self.calendar.clone();
+            move |date| {
+                if calendar.allows(*date) {
+                    Ok(())
+                } else {
+                    Err(String::from("this date is not available"))
+                }
+            }
+        });
+
+        // Choosing a date in the calendar dismisses it.
+        let shown = Dynamic::default();
+        let weak_shown = shown.downgrade();
+        let calendar = self.calendar.on_select(move |_| {
+            if let Some(shown) = weak_shown.upgrade() {
+                shown.set(None);
+            }
+        });
+
+        picker(
+            id,
+            text,
+            validation,
+            shown,
+            calendar.make_widget(),
+            self.overlay,
+        )
+    }
+}
+
+/// A text input for a [`Time`] that shows spinners for each component of the
+/// time when its button is clicked.
+///
+/// Times are entered using a 24-hour clock: `HH:MM` or `HH:MM:SS`.
+#[must_use]
+pub struct TimePicker {
+    /// The selected time.
+    pub value: Dynamic<Time>,
+    seconds: bool,
+    overlay: OverlayLayer,
+}
+
+impl TimePicker {
+    /// Returns a new picker that edits `value`, showing its spinners in
+    /// `overlay`.
+    pub fn new(value: impl IntoDynamic<Time>, overlay: &OverlayLayer) -> Self {
+        Self {
+            value: value.into_dynamic(),
+            seconds: false,
+            overlay: overlay.clone(),
+        }
+    }
+
+    /// Displays and allows editing seconds, and returns self.
+    pub fn with_seconds(mut self) -> Self {
+        self.seconds = true;
+        self
+    }
+}
+
+impl Debug for TimePicker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimePicker")
+            .field("value", &self.value)
+            .field("seconds", &self.seconds)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MakeWidgetWithTag for TimePicker {
+    fn make_with_tag(self, id: WidgetTag) -> WidgetInstance {
+        let seconds = self.seconds;
+        let validation = Dynamic::default();
+        let text = bind_text(
+            &self.value,
+            &validation,
+            move |time| time.format(seconds),
+            |_| Ok(()),
+        );
+
+        let component = |get: fn(&Time) -> u8, with: fn(Time, u8) -> Option<Time>, max: u8| {
+            let value = self.value.clone();
+            let component = self
+                .value
+                .linked(move |time| get(time), move |new| with(value.get(), *new));
+            NumberInput::new(component)
+                .minimum(0)
+                .maximum(max)
+                .format(NumberFormat::PLAIN)
+        };
+        let mut spinners = component(Time::hour, Time::with_hour, 23).and(component(
+            Time::minute,
+            Time::with_minute,
+            59,
+        ));
+        if seconds {
+            spinners = spinners.and(component(Time::second, Time::with_second, 59));
+        }
+
+        picker(
+            id,
+            text,
+            validation,
+            Dynamic::default(),
+            spinners.into_columns().make_widget(),
+            self.overlay,
+        )
+    }
+}
+
+/// Returns a string linked to `value`, updating `validation` as the string is
+/// edited.
+fn bind_text<T>(
+    value: &Dynamic<T>,
+    validation: &Dynamic<Validation>,
+    format: impl Fn(&T) -> String + Clone + Send + 'static,
+    validate: impl Fn(&T) -> Result<(), String> + Send + 'static,
+) -> Dynamic<String>
+where
+    T: FromStr + PartialEq + Clone + Send + 'static,
+    T::Err: Display,
+{
+    let text = Dynamic::new(value.map_ref(&format));
+
+    text.set_source(text.for_each({
+        let value = value.clone();
+        let validation = validation.clone();
+        move |text: &String| {
+            validation.set(match text.parse::<T>() {
+                Ok(parsed) => match validate(&parsed) {
+                    Ok(()) => {
+                        value.set(parsed);
+                        Validation::Valid
+                    }
+                    Err(message) => Validation::Invalid(message),
+                },
+                Err(err) => Validation::Invalid(err.to_string()),
+            });
+        }
+    }));
+
+    let weak_text = text.downgrade();
+    text.set_source(value.for_each_try(move |value| {
+        let text = weak_text.upgrade().ok_or(CallbackDisconnected)?;
+        let displayed = text.map_ref(|text| text.parse::<T>().ok());
+        if displayed.as_ref() != Some(value) {
+            text.set(format(value));
+        }
+        Ok(())
+    }));
+
+    text
+}
+
+/// Returns an input for `text` with a button that toggles showing `popup` in
+/// `overlay`.
+///
+/// The handle of the shown popup is stored in `shown`. Setting it to `None`
+/// dismisses the popup.
+fn picker(
+    id: WidgetTag,
+    text: Dynamic<String>,
+    validation: Dynamic<Validation>,
+    shown: Dynamic<Option<OverlayHandle>>,
+    popup: WidgetInstance,
+    overlay: OverlayLayer,
+) -> WidgetInstance {
+    let picker_id = id.id();
+    let toggle = Button::new("\u{25BE}").on_click(move |()| {
+        let mut shown = shown.lock();
+        if shown.take().is_none() {
+            *shown = Some(
+                overlay
+                    .build_overlay(
+                        popup
+                            .clone()
+                            .contain()
+                            .shadow(ContainerShadow::drop(Lp::mm(1), Lp::mm(2))),
+                    )
+                    .below(picker_id)
+                    .show(),
+            );
+        }
+    });
+
+    Input::new(text)
+        .expand()
+        .and(toggle)
+        .into_columns()
+        .validation(validation)
+        .make_with_tag(id)
+}
+
+define_components! {
+    Calendar {
+        /// The background color of selected dates in a [`Calendar`].
+        SelectedDayColor(Color, "selected_day_color", @PrimaryColor)
+        /// The text color of selected dates in a [`Calendar`].
+        SelectedDayTextColor(Color, "selected_day_text_color", .primary.on_color)
+        /// The background color of dates between the start and end of a
+        /// selected range in a [`Calendar`].
+        DayRangeColor(Color, "day_range_color", @HighlightColor)
+        /// The color of the outline drawn around today's date in a
+        /// [`Calendar`].
+        TodayColor(Color, "today_color", @PrimaryColor)
+        /// The text color of dates outside of the displayed month in a
+        /// [`Calendar`].
+        OtherMonthDayColor(Color, "other_month_day_color", @TextColorVariant)
+        /// The text color of dates that cannot be selected in a [`Calendar`].
+        DisabledDayColor(Color, "disabled_day_color", @DisabledOutlineColor)
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_conversions {
+    use super::{Date, Time};
+
+    impl From<Date> for time::Date {
+        fn from(date: Date) -> Self {
+            let month = time::Month::try_from(date.month).expect("valid month");
+            time::Date::from_calendar_date(date.year, month, date.day)
+                .expect("Date::MIN..=Date::MAX is within time's range")
+        }
+    }
+
+    impl From<time::Date> for Date {
+        /// Converts a date, clamping it to [`Date::MIN`] and [`Date::MAX`].
+        fn from(date: time::Date) -> Self {
+            Date::new(date.year(), u8::from(date.month()), date.day()).unwrap_or(
+                if date.year() < 0 {
+                    Date::MIN
+                } else {
+                    Date::MAX
+                },
+            )
+        }
+    }
+
+    impl From<Time> for time::Time {
+        fn from(value: Time) -> Self {
+            time::Time::from_hms(value.hour, value.minute, value.second).expect("valid time")
+        }
+    }
+
+    impl From<time::Time> for Time {
+        /// Converts a time, discarding any fractional seconds.
+        fn from(value: time::Time) -> Self {
+            Time {
+                hour: value.hour(),
+                minute: value.minute(),
+                second: value.second(),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_conversions {
+    use chrono::{Datelike, NaiveDate, NaiveTime, Timelike};
+
+    use super::{Date, Time};
+
+    impl From<Date> for NaiveDate {
+        fn from(date: Date) -> Self {
+            NaiveDate::from_ymd_opt(date.year, u32::from(date.month), u32::from(date.day))
+                .expect("Date::MIN..=Date::MAX is within chrono's range")
+        }
+    }
+
+    impl From<NaiveDate> for Date {
+        /// Converts a date, clamping it to [`Date::MIN`] and [`Date::MAX`].
+        fn from(date: NaiveDate) -> Self {
+            let month = u8::try_from(date.month()).expect("valid month");
+            let day = u8::try_from(date.day()).expect("valid day");
+            Date::new(date.year(), month, day).unwrap_or(if date.year() < 0 {
+                Date::MIN
+            } else {
+                Date::MAX
+            })
+        }
+    }
+
+    impl From<Time> for NaiveTime {
+        fn from(value: Time) -> Self {
+            NaiveTime::from_hms_opt(
+                u32::from(value.hour),
+                u32::from(value.minute),
+                u32::from(value.second),
+            )
+            .expect("valid time")
+        }
+    }
+
+    impl From<NaiveTime> for Time {
+        /// Converts a time, discarding any fractional seconds.
+        fn from(value: NaiveTime) -> Self {
+            // Leap seconds are represented by chrono as a second value of 59
+            // with additional nanoseconds, so this conversion cannot fail.
+            Time {
+                hour: u8::try_from(value.hour()).expect("valid hour"),
+                minute: u8::try_from(value.minute()).expect("valid minute"),
+                second: u8::try_from(value.second()).expect("valid second"),
+            }
+        }
+    }
+}
+
+#[test]
+fn date_math() {
+    let date = Date::new(2024, 1, 31).unwrap();
+    assert_eq!(date.add_days(1), Date::new(2024, 2, 1).unwrap());
+    assert_eq!(date.add_days(-31), Date::new(2023, 12, 31).unwrap());
+    assert_eq!(date.add_months(1), Date::new(2024, 2, 29).unwrap());
+    assert_eq!(date.add_months(-13), Date::new(2022, 12, 31).unwrap());
+    assert_eq!(date.weekday(), Weekday::Wednesday);
+    assert_eq!(Date::new(1970, 1, 1).unwrap().weekday(), Weekday::Thursday);
+    assert_eq!(Date::new(2000, 2, 29).unwrap().add_days(365).day(), 28);
+    assert_eq!(Date::MAX.add_days(1), Date::MAX);
+    assert_eq!(Date::MIN.add_months(-1), Date::MIN);
+    assert_eq!(Date::new(2023, 2, 29), None);
+    assert_eq!(
+        date.days_until(Date::new(2024, 3, 1).unwrap()),
+        30,
+        "2024 is a leap year"
+    );
+}
+
+#[test]
+fn date_and_time_parsing() {
+    assert_eq!("2024-03-05".parse(), Ok(Date::new(2024, 3, 5).unwrap()));
+    assert_eq!(Date::new(-44, 3, 15).unwrap().to_string(), "-0044-03-15");
+    assert_eq!("-0044-03-15".parse(), Ok(Date::new(-44, 3, 15).unwrap()));
+    assert_eq!("2024-02-30".parse::<Date>(), Err(InvalidDate));
+    assert_eq!("13:45".parse(), Ok(Time::new(13, 45, 0).unwrap()));
+    assert_eq!("07:08:09".parse::<Time>().unwrap().to_string(), "07:08:09");
+    assert_eq!("24:00".parse::<Time>(), Err(InvalidTime));
+}
+
+#[test]
+fn first_day_of_week() {
+    assert_eq!(Weekday::first_for_locale("en-US"), Weekday::Sunday);
+    assert_eq!(Weekday::first_for_locale("en_GB.UTF-8"), Weekday::Monday);
+    assert_eq!(Weekday::first_for_locale("de"), Weekday::Monday);
+    assert_eq!(Weekday::first_for_locale("ar-EG"), Weekday::Saturday);
+    assert_eq!(Weekday::Sunday.days_since(Weekday::Monday), 6);
+    assert_eq!(Weekday::Monday.offset(-1), Weekday::Sunday);
+}
+
+#[test]
+fn inverted_bounds_are_ordered() {
+    let start = Date::new(2024, 1, 10).unwrap();
+    let end = Date::new(2024, 1, 20).unwrap();
+    let calendar = Calendar::new(Dynamic::new(start))
+        .minimum(end)
+        .maximum(start);
+    assert!(calendar.allows(Date::new(2024, 1, 15).unwrap()));
+    assert!(!calendar.allows(Date::new(2024, 1, 21).unwrap()));
+    assert_eq!(calendar.clamp(Date::new(2024, 2, 1).unwrap()), end);
+    assert_eq!(calendar.clamp(Date::MIN), start);
+}
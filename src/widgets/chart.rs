@@ -0,0 +1,854 @@
+//! Lightweight charts drawn without external dependencies.
+
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+use figures::units::{Lp, Px, UPx};
+use figures::{FloatConversion, Point, Rect, Round, ScreenScale, Size, Zero};
+use intentional::{Assert, Cast};
+use kludgine::shapes::{PathBuilder, Shape, StrokeOptions};
+use kludgine::text::{Text, TextOrigin};
+use kludgine::{Color, DrawableExt, Origin};
+
+use crate::animation::{Easing, ZeroToOne};
+use crate::context::{GraphicsContext, LayoutContext};
+use crate::styles::components::{
+    self, IntrinsicPadding, OutlineColor, PrimaryColor, SecondaryColor, TertiaryColor,
+    TextColorVariant,
+};
+use crate::styles::{ColorExt, ColorSource, Dimension};
+use crate::value::{IntoValue, Source, Value};
+use crate::widget::Widget;
+use crate::ConstraintLimit;
+
+/// A chart of one or more [`Series`].
+///
+/// Charts are drawn using shapes, and do not require the `plotters` feature.
+/// When a series' data changes, the chart animates from the previous data to
+/// the new data.
+///
+/// Series are colored using the current theme unless a color is specified with
+/// [`Series::color`]. The first three series use [`ChartPrimarySeriesColor`],
+/// [`ChartSecondarySeriesColor`], and [`ChartTertiarySeriesColor`], and
+/// additional series use variations of these colors.
+#[derive(Debug)]
+#[must_use]
+pub struct Chart {
+    kind: ChartKind,
+    series: Vec<Series>,
+    axes: bool,
+    y_range: Option<Value<Range<f64>>>,
+    transition_duration: Duration,
+    transition: Option<Transition>,
+}
+
+impl Chart {
+    /// Returns a chart of `kind` with no series.
+    pub const fn new(kind: ChartKind) -> Self {
+        Self {
+            kind,
+            series: Vec::new(),
+            axes: true,
+            y_range: None,
+            transition_duration: Duration::from_millis(250),
+            transition: None,
+        }
+    }
+
+    /// Returns a line chart of `series`.
+    pub fn line(series: impl IntoIterator<Item = Series>) -> Self {
+        Self::new(ChartKind::Line).with_series(series)
+    }
+
+    /// Returns an area chart of `series`.
+    pub fn area(series: impl IntoIterator<Item = Series>) -> Self {
+        Self::new(ChartKind::Area).with_series(series)
+    }
+
+    /// Returns a bar chart of `series`.
+    ///
+    /// When multiple series are provided, bars with the same x value are
+    /// grouped side by side.
+    pub fn bar(series: impl IntoIterator<Item = Series>) -> Self {
+        Self::new(ChartKind::Bar).with_series(series)
+    }
+
+    /// Returns a scatter chart of `series`.
+    pub fn scatter(series: impl IntoIterator<Item = Series>) -> Self {
+        Self::new(ChartKind::Scatter).with_series(series)
+    }
+
+    /// Returns a compact line chart of `values` without axes.
+    ///
+    /// Sparklines are intended to be shown inline with other content, and
+    /// size themselves to the height of a line of text when allowed.
+    pub fn sparkline(values: impl IntoValue<Vec<f64>>) -> Self {
+        Self::line([Series::values(values)]).axes(false)
+    }
+
+    /// Adds `series` to this chart and returns self.
+    pub fn with_series(mut self, series: impl IntoIterator<Item = Series>) -> Self {
+        self.series.extend(series);
+        self
+    }
+
+    /// Sets whether the axes and their labels are drawn, and returns self.
+    pub fn axes(mut self, axes: bool) -> Self {
+        self.axes = axes;
+        self
+    }
+
+    /// Displays `range` on the y axis instead of automatically fitting the
+    /// data, and returns self.
+    pub fn y_range(mut self, range: impl IntoValue<Range<f64>>) -> Self {
+        self.y_range = Some(range.into_value());
+        self
+    }
+
+    /// Sets the duration of the animation played when the chart's data
+    /// changes, and returns self.
+    ///
+    /// A duration of zero disables animations.
+    pub fn transition_duration(mut self, duration: Duration) -> Self {
+        self.transition_duration = duration;
+        self
+    }
+
+    fn palette(context: &GraphicsContext<'_, '_, '_, '_>) -> [Color; 3] {
+        [
+            context.get(&ChartPrimarySeriesColor),
+            context.get(&ChartSecondarySeriesColor),
+            context.get(&ChartTertiarySeriesColor),
+        ]
+    }
+
+    fn draw_axes(
+        &self,
+        frame: &ChartFrame,
+        target: &ChartFrame,
+        plot: Rect<Px>,
+        context: &mut GraphicsContext<'_, '_, '_, '_>,
+    ) {
+        let scale = context.gfx.scale();
+        let stroke = StrokeOptions::px_wide(Lp::points(1).into_px(scale).round());
+        let grid = context.get(&ChartGridColor);
+        let axis = context.get(&ChartAxisColor);
+        let label_color = context.get(&ChartLabelColor);
+        let padding = context.get(&IntrinsicPadding).into_px(scale).round() / 2;
+        let right = plot.origin.x + plot.size.width;
+        let bottom = plot.origin.y + plot.size.height;
+
+        context.apply_current_font_settings();
+        for tick in &target.y_ticks.values {
+            if !contains(&frame.y, *tick) {
+                continue;
+            }
+            let y = frame.to_pixel(plot, (frame.x.start, *tick)).y;
+            context.gfx.draw_shape(
+                &PathBuilder::new(Point::new(plot.origin.x, y))
+                    .line_to(Point::new(right, y))
+                    .build()
+                    .stroke(stroke.colored(grid)),
+            );
+            let label = target.y_ticks.label(*tick);
+            let text = context
+                .gfx
+                .measure_text(Text::<Px>::new(&label, label_color));
+            context.gfx.draw_measured_text(
+                text.translate_by(Point::new(
+                    plot.origin.x - padding - text.size.width,
+                    y - text.size.height / 2,
+                )),
+                TextOrigin::TopLeft,
+            );
+        }
+
+        for tick in &target.x_ticks.values {
+            if !contains(&frame.x, *tick) {
+                continue;
+            }
+            let x = frame.to_pixel(plot, (*tick, frame.y.start)).x;
+            let label = target.x_ticks.label(*tick);
+            let text = context
+                .gfx
+                .measure_text(Text::<Px>::new(&label, label_color));
+            context.gfx.draw_measured_text(
+                text.translate_by(Point::new(x - text.size.width / 2, bottom + padding)),
+                TextOrigin::TopLeft,
+            );
+        }
+
+        context.gfx.draw_shape(
+            &PathBuilder::new(plot.origin)
+                .line_to(Point::new(plot.origin.x, bottom))
+                .line_to(Point::new(right, bottom))
+                .build()
+                .stroke(stroke.colored(axis)),
+        );
+    }
+
+    fn draw_series(
+        &self,
+        frame: &ChartFrame,
+        plot: Rect<Px>,
+        context: &mut GraphicsContext<'_, '_, '_, '_>,
+    ) {
+        let scale = context.gfx.scale();
+        let palette = Self::palette(context);
+        let line_width = context.get(&ChartLineWidth).into_px(scale).round();
+        let stroke = StrokeOptions::px_wide(line_width);
+        let baseline = frame.baseline();
+        let bar_width = frame.bar_width(plot, frame.series.len());
+
+        for (index, points) in frame.series.iter().enumerate() {
+            let color = match self
+                .series
+                .get(index)
+                .and_then(|series| series.color.as_ref())
+            {
+                Some(color) => color.get_tracking_redraw(context),
+                None => series_color(&palette, index),
+            };
+
+            match self.kind {
+                ChartKind::Line | ChartKind::Area => {
+                    let mut pixels = points.iter().map(|point| frame.to_pixel(plot, *point));
+                    let Some(first) = pixels.next() else {
+                        continue;
+                    };
+                    let mut line = PathBuilder::new(first);
+                    let mut last = first;
+                    for pixel in pixels {
+                        line = line.line_to(pixel);
+                        last = pixel;
+                    }
+                    if self.kind == ChartKind::Area {
+                        let base = frame.to_pixel(plot, (frame.x.start, baseline)).y;
+                        let mut area = PathBuilder::new(Point::new(first.x, base));
+                        for point in points {
+                            area = area.line_to(frame.to_pixel(plot, *point));
+                        }
+                        context.gfx.draw_shape(
+                            &area
+                                .line_to(Point::new(last.x, base))
+                                .close()
+                                .fill(color.with_alpha(96)),
+                        );
+                    }
+                    if points.len() == 1 {
+                        context.gfx.draw_shape(
+                            &Shape::filled_circle(line_width, color, Origin::Center)
+                                .translate_by(first),
+                        );
+                    } else {
+                        context
+                            .gfx
+                            .draw_shape(&line.build().stroke(stroke.colored(color)));
+                    }
+                }
+                ChartKind::Bar => {
+                    let base = frame.to_pixel(plot, (frame.x.start, baseline)).y;
+                    #[allow(clippy::cast_precision_loss)] // there are never that many series
+                    let offset = bar_width * (index as f32 - (frame.series.len() as f32 - 1.) / 2.);
+                    for point in points {
+                        let top = frame.to_pixel(plot, *point);
+                        let left = Px::from(top.x.into_float() + offset - bar_width / 2.);
+                        let bar = Rect::from_extents(
+                            Point::new(left, top.y.min(base)),
+                            Point::new(left + Px::from(bar_width), top.y.max(base)),
+                        );
+                        context.gfx.draw_shape(&Shape::filled_rect(bar, color));
+                    }
+                }
+                ChartKind::Scatter => {
+                    let radius = line_width * 3 / 2;
+                    for point in points {
+                        context.gfx.draw_shape(
+                            &Shape::filled_circle(radius, color, Origin::Center)
+                                .translate_by(frame.to_pixel(plot, *point)),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the area the data is plotted within, reserving space for the
+    /// axis labels of `frame`.
+    fn plot_area(
+        &self,
+        frame: &ChartFrame,
+        context: &mut GraphicsContext<'_, '_, '_, '_>,
+    ) -> Rect<Px> {
+        let size = context.gfx.region().size;
+        let scale = context.gfx.scale();
+        let line_width = context.get(&ChartLineWidth).into_px(scale).round();
+        if !self.axes {
+            // Keep lines and points from being clipped at the edges.
+            let inset = line_width * 2;
+            return Rect::new(
+                Point::squared(inset),
+                Size::new(size.width - inset * 2, size.height - inset * 2),
+            );
+        }
+
+        context.apply_current_font_settings();
+        let padding = context.get(&IntrinsicPadding).into_px(scale).round() / 2;
+        let label_width = frame
+            .y_ticks
+            .values
+            .iter()
+            .map(|tick| {
+                context
+                    .gfx
+                    .measure_text(Text::<Px>::new(&frame.y_ticks.label(*tick), Color::WHITE))
+                    .size
+                    .width
+            })
+            .max()
+            .unwrap_or(Px::ZERO);
+        let line_height = context
+            .gfx
+            .measure_text(Text::<Px>::new("0", Color::WHITE))
+            .size
+            .height;
+        let left = label_width + padding * 2;
+        let top = line_height / 2;
+        let right = line_height;
+        let bottom = line_height + padding * 2;
+        Rect::new(
+            Point::new(left, top),
+            Size::new(
+                (size.width - left - right).max(Px::ZERO),
+                (size.height - top - bottom).max(Px::ZERO),
+            ),
+        )
+    }
+}
+
+impl Widget for Chart {
+    fn redraw(&mut self, context: &mut GraphicsContext<'_, '_, '_, '_>) {
+        let data = self
+            .series
+            .iter()
+            .map(|series| series.data.get_tracking_redraw(context))
+            .collect::<Vec<_>>();
+        let y_range = self
+            .y_range
+            .as_ref()
+            .map(|range| range.get_tracking_redraw(context));
+
+        let size = context.gfx.region().size;
+        let tick_spacing = Lp::mm(25).into_px(context.gfx.scale()).get().max(2);
+        let tick_counts = if self.axes {
+            Size::new(
+                (size.width.get() / tick_spacing).clamp(2, 10),
+                (size.height.get() / (tick_spacing / 2)).clamp(2, 10),
+            )
+        } else {
+            Size::new(0, 0)
+        };
+        let target = ChartFrame::new(self.kind, data, y_range, tick_counts);
+
+        let now = Instant::now();
+        let easing = context.get(&components::Easing);
+        match &mut self.transition {
+            Some(transition) if transition.to != target => {
+                let current = transition.frame(now, self.transition_duration, &easing);
+                *transition = Transition {
+                    from: current,
+                    to: target,
+                    started: now,
+                };
+            }
+            Some(_) => {}
+            None => {
+                self.transition = Some(Transition {
+                    from: target.clone(),
+                    to: target,
+                    started: now,
+                });
+            }
+        }
+        let transition = self.transition.as_ref().assert("always initialized");
+        let frame = transition.frame(now, self.transition_duration, &easing);
+        if now.saturating_duration_since(transition.started) < self.transition_duration {
+            context.set_needs_redraw();
+        }
+        let target = transition.to.clone();
+
+        let plot = self.plot_area(&target, context);
+        if plot.size.width <= Px::ZERO || plot.size.height <= Px::ZERO {
+            return;
+        }
+        if self.axes {
+            self.draw_axes(&frame, &target, plot, context);
+        }
+        self.draw_series(&frame, plot, context);
+    }
+
+    fn layout(
+        &mut self,
+        available_space: Size<ConstraintLimit>,
+        context: &mut LayoutContext<'_, '_, '_, '_>,
+    ) -> Size<UPx> {
+        if self.axes {
+            return available_space.map(ConstraintLimit::max);
+        }
+
+        let scale = context.gfx.scale();
+        let natural = Size::new(Lp::mm(25), Lp::points(16)).map(|length| length.into_upx(scale));
+        Size::new(
+            fit(available_space.width, natural.width),
+            fit(available_space.height, natural.height),
+        )
+    }
+}
+
+fn fit(limit: ConstraintLimit, natural: UPx) -> UPx {
+    match limit {
+        ConstraintLimit::Fill(size) => size,
+        ConstraintLimit::SizeToFit(max) => natural.min(max),
+    }
+}
+
+/// The style of a [`Chart`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ChartKind {
+    /// Each series is drawn as a line connecting its points.
+    Line,
+    /// Each series is drawn as a line with the area beneath it filled.
+    Area,
+    /// Each point is drawn as a bar extending from zero.
+    Bar,
+    /// Each point is drawn as a dot.
+    Scatter,
+}
+
+/// A series of data displayed in a [`Chart`].
+#[derive(Debug)]
+#[must_use]
+pub struct Series {
+    data: Value<Vec<(f64, f64)>>,
+    color: Option<Value<Color>>,
+}
+
+impl Series {
+    /// Returns a series of `(x, y)` points.
+    pub fn new(points: impl IntoValue<Vec<(f64, f64)>>) -> Self {
+        Self {
+            data: points.into_value(),
+            color: None,
+        }
+    }
+
+    /// Returns a series of y values, using each value's index as its x value.
+    pub fn values(values: impl IntoValue<Vec<f64>>) -> Self {
+        Self::new(values.into_value().map_each(|values| {
+            values
+                .iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    #[allow(clippy::cast_precision_loss)] // series are never that long
+                    let x = index as f64;
+                    (x, *value)
+                })
+                .collect::<Vec<_>>()
+        }))
+    }
+
+    /// Draws this series using `color` instead of a theme color, and returns
+    /// self.
+    pub fn color(mut self, color: impl IntoValue<Color>) -> Self {
+        self.color = Some(color.into_value());
+        self
+    }
+}
+
+/// Returns the color of the series at `index`.
+///
+/// The first series use the colors in `palette`. Further series rotate the
+/// hue of the palette's colors while preserving their lightness.
+fn series_color(palette: &[Color; 3], index: usize) -> Color {
+    let base = palette[index % palette.len()];
+    let round = index / palette.len();
+    if round == 0 {
+        return base;
+    }
+
+    let source = base.source();
+    #[allow(clippy::cast_precision_loss)] // there are never that many series
+    let shift = 35. * round as f32;
+    ColorSource::new(source.hue.into_degrees() + shift, source.saturation).color(base.lightness())
+}
+
+#[derive(Debug)]
+struct Transition {
+    from: ChartFrame,
+    to: ChartFrame,
+    started: Instant,
+}
+
+impl Transition {
+    fn frame(&self, now: Instant, duration: Duration, easing: &impl Easing) -> ChartFrame {
+        let elapsed = now.saturating_duration_since(self.started);
+        if duration.is_zero() || elapsed >= duration {
+            return self.to.clone();
+        }
+        let progress = easing.ease(ZeroToOne::new(
+            elapsed.as_secs_f32() / duration.as_secs_f32(),
+        ));
+        self.from.interpolate(&self.to, f64::from(progress))
+    }
+}
+
+/// The data and axes displayed by a [`Chart`] at a moment in time.
+#[derive(Debug, Clone, PartialEq)]
+struct ChartFrame {
+    series: Vec<Vec<(f64, f64)>>,
+    x: Range<f64>,
+    y: Range<f64>,
+    x_ticks: Ticks,
+    y_ticks: Ticks,
+}
+
+impl ChartFrame {
+    fn new(
+        kind: ChartKind,
+        series: Vec<Vec<(f64, f64)>>,
+        y_range: Option<Range<f64>>,
+        tick_counts: Size<i32>,
+    ) -> Self {
+        // Non-finite points cannot be drawn, and because NaN never compares
+        // equal to itself, keeping them would prevent transitions from ever
+        // completing.
+        let series = series
+            .into_iter()
+            .map(|points| {
+                points
+                    .into_iter()
+                    .filter(|(x, y)| x.is_finite() && y.is_finite())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let points = || series.iter().flatten();
+        let mut x = bounds(points().map(|point| point.0));
+        let mut y = bounds(points().map(|point| point.1));
+
+        match kind {
+            ChartKind::Bar => {
+                // Bars extend from zero and need room on either side of the
+                // outermost values.
+                y = y.start.min(0.)..y.end.max(0.);
+                let spacing = min_spacing(points().map(|point| point.0));
+                x = x.start - spacing / 2.0..x.end + spacing / 2.0;
+            }
+            ChartKind::Area => {
+                y = y.start.min(0.)..y.end.max(0.);
+            }
+            ChartKind::Line | ChartKind::Scatter => {}
+        }
+
+        let x_ticks = Ticks::new(&x, tick_counts.width);
+        let y_ticks = match y_range {
+            Some(range) => {
+                y = range;
+                Ticks::new(&y, tick_counts.height)
+            }
+            None => {
+                let ticks = Ticks::new(&y, tick_counts.height);
+                if let (Some(first), Some(last)) = (ticks.values.first(), ticks.values.last()) {
+                    y = first.min(y.start)..last.max(y.end);
+                }
+                ticks
+            }
+        };
+
+        Self {
+            series,
+            x,
+            y,
+            x_ticks,
+            y_ticks,
+        }
+    }
+
+    /// Returns the y value bars and areas extend from.
+    fn baseline(&self) -> f64 {
+        0_f64.clamp(self.y.start.min(self.y.end), self.y.start.max(self.y.end))
+    }
+
+    fn bar_width(&self, plot: Rect<Px>, series_count: usize) -> f32 {
+        let spacing = min_spacing(self.series.iter().flatten().map(|point| point.0));
+        let slot = fraction(self.x.start + spacing, &self.x) * plot.size.width.into_float();
+        #[allow(clippy::cast_precision_loss)] // there are never that many series
+        let count = series_count.max(1) as f32;
+        slot * 0.8 / count
+    }
+
+    fn to_pixel(&self, plot: Rect<Px>, point: (f64, f64)) -> Point<Px> {
+        Point::new(
+            Px::from(
+                plot.origin.x.into_float()
+                    + fraction(point.0, &self.x) * plot.size.width.into_float(),
+            ),
+            Px::from(
+                plot.origin.y.into_float()
+                    + (1. - fraction(point.1, &self.y)) * plot.size.height.into_float(),
+            ),
+        )
+    }
+
+    /// Returns the frame `progress` of the way between `self` and `target`.
+    ///
+    /// Points that do not exist in `self` grow from the baseline of `target`.
+    fn interpolate(&self, target: &Self, progress: f64) -> Self {
+        let baseline = target.baseline();
+        let series = target
+            .series
+            .iter()
+            .enumerate()
+            .map(|(series_index, points)| {
+                let from = self.series.get(series_index);
+                points
+                    .iter()
+                    .enumerate()
+                    .map(|(index, to)| {
+                        let from = from
+                            .and_then(|from| from.get(index))
+                            .copied()
+                            .unwrap_or((to.0, baseline));
+                        (lerp(from.0, to.0, progress), lerp(from.1, to.1, progress))
+                    })
+                    .collect()
+            })
+            .collect();
+        Self {
+            series,
+            x: lerp(self.x.start, target.x.start, progress)
+                ..lerp(self.x.end, target.x.end, progress),
+            y: lerp(self.y.start, target.y.start, progress)
+                ..lerp(self.y.end, target.y.end, progress),
+            x_ticks: target.x_ticks.clone(),
+            y_ticks: target.y_ticks.clone(),
+        }
+    }
+}
+
+/// Evenly spaced values along an axis.
+#[derive(Debug, Clone, PartialEq)]
+struct Ticks {
+    values: Vec<f64>,
+    decimals: usize,
+}
+
+impl Ticks {
+    /// Returns up to approximately `count` ticks covering `range`, spaced by
+    /// 1, 2, or 5 times a power of ten.
+    fn new(range: &Range<f64>, count: i32) -> Self {
+        if count <= 0 || !range.start.is_finite() || !range.end.is_finite() {
+            return Self {
+                values: Vec::new(),
+                decimals: 0,
+            };
+        }
+
+        // Allows for rounding errors when normalizing the step.
+        const TOLERANCE: f64 = 1e-9;
+
+        let (min, max) = (range.start.min(range.end), range.start.max(range.end));
+        let span = if max > min { max - min } else { 1. };
+        let raw_step = span / f64::from((count - 1).max(1));
+        let magnitude = 10_f64.powf(raw_step.log10().floor());
+        let step = magnitude
+            * match raw_step / magnitude {
+                normalized if normalized <= 1. + TOLERANCE => 1.,
+                normalized if normalized <= 2. + TOLERANCE => 2.,
+                normalized if normalized <= 5. + TOLERANCE => 5.,
+                _ => 10.,
+            };
+
+        let first = (min / step).floor();
+        let last = (max / step).ceil();
+        #[allow(clippy::float_cmp)] // checking for lost precision
+        let unrepresentable = first + 1. == first;
+        if unrepresentable {
+            // The values are too large relative to the step for individual
+            // ticks to be represented.
+            return Self {
+                values: Vec::new(),
+                decimals: 0,
+            };
+        }
+        let values = (0_i32..)
+            .map(|index| (first + f64::from(index)) * step)
+            .take_while(|value| *value <= last * step + step / 2.)
+            // The step is chosen so that at most `count + 2` ticks cover the
+            // range, which also guards against any remaining rounding error.
+            .take(count.cast::<usize>() + 2)
+            .collect();
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let decimals = (-step.log10().floor()).max(0.) as usize;
+        Self { values, decimals }
+    }
+
+    fn label(&self, value: f64) -> String {
+        // Avoid displaying "-0".
+        let value = if value.abs() < f64::EPSILON {
+            0.
+        } else {
+            value
+        };
+        format!("{value:.*}", self.decimals)
+    }
+}
+
+fn bounds(values: impl Iterator<Item = f64>) -> Range<f64> {
+    let (min, max) = values
+        .filter(|value| value.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+            (min.min(value), max.max(value))
+        });
+    if min > max {
+        0.0..1.0
+    } else if min == max {
+        min - 0.5..max + 0.5
+    } else {
+        min..max
+    }
+}
+
+/// Returns the smallest distance between two distinct values.
+fn min_spacing(values: impl Iterator<Item = f64>) -> f64 {
+    let mut values = values.filter(|value| value.is_finite()).collect::<Vec<_>>();
+    values.sort_by(f64::total_cmp);
+    values
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .filter(|spacing| *spacing > 0.)
+        .min_by(f64::total_cmp)
+        .unwrap_or(1.)
+}
+
+fn contains(range: &Range<f64>, value: f64) -> bool {
+    let tolerance = (range.end - range.start).abs() * 1e-9;
+    value >= range.start.min(range.end) - tolerance
+        && value <= range.start.max(range.end) + tolerance
+}
+
+#[allow(clippy::cast_possible_truncation)] // pixel positions don't need double precision
+fn fraction(value: f64, range: &Range<f64>) -> f32 {
+    let span = range.end - range.start;
+    if span == 0. {
+        0.5
+    } else {
+        ((value - range.start) / span) as f32
+    }
+}
+
+fn lerp(from: f64, to: f64, progress: f64) -> f64 {
+    from + (to - from) * progress
+}
+
+define_components! {
+    Chart {
+        /// The color of the first series in a [`Chart`].
+        ChartPrimarySeriesColor(Color, "primary_series_color", @PrimaryColor)
+        /// The color of the second series in a [`Chart`].
+        ChartSecondarySeriesColor(Color, "secondary_series_color", @SecondaryColor)
+        /// The color of the third series in a [`Chart`].
+        ChartTertiarySeriesColor(Color, "tertiary_series_color", @TertiaryColor)
+        /// The color of the axes of a [`Chart`].
+        ChartAxisColor(Color, "axis_color", @OutlineColor)
+        /// The color of the grid lines of a [`Chart`].
+        ChartGridColor(Color, "grid_color", .surface.outline_variant)
+        /// The color of the axis labels of a [`Chart`].
+        ChartLabelColor(Color, "label_color", @TextColorVariant)
+        /// The width of the lines drawn by a [`Chart`].
+        ChartLineWidth(Dimension, "line_width", Dimension::Lp(Lp::points(2)))
+    }
+}
+
+#[test]
+fn nice_ticks() {
+    let ticks = Ticks::new(&(0.0..9.3), 5);
+    assert_eq!(ticks.values, [0., 5., 10.]);
+    assert_eq!(ticks.label(5.), "5");
+
+    let ticks = Ticks::new(&(-1.0..1.0), 5);
+    assert_eq!(ticks.values, [-1., -0.5, 0., 0.5, 1.]);
+    assert_eq!(ticks.label(-0.5), "-0.5");
+
+    assert!(Ticks::new(&(0.0..1.0), 0).values.is_empty());
+}
+
+#[test]
+fn large_values_produce_bounded_ticks() {
+    assert!(Ticks::new(&(1e25..1e25), 5).values.is_empty());
+    assert!(Ticks::new(&(1.7e18..1.7e18), 5).values.is_empty());
+
+    let ticks = Ticks::new(&(1.7e18..1.7e18 + 4096.), 5);
+    assert!(ticks.values.len() <= 7);
+    assert!(ticks.values.windows(2).all(|pair| pair[0] < pair[1]));
+
+    let frame = ChartFrame::new(
+        ChartKind::Line,
+        vec![vec![
+            (1.7e18, 1e25),
+            (1.7e18 + 1., 1e25),
+            (1.7e18 + 2., 1e25),
+        ]],
+        None,
+        Size::new(3, 5),
+    );
+    assert!(frame.x_ticks.values.is_empty());
+    assert!(frame.y_ticks.values.is_empty());
+}
+
+#[test]
+fn frames_fit_data_and_interpolate() {
+    let frame = ChartFrame::new(
+        ChartKind::Bar,
+        vec![vec![(0., 2.), (1., 9.)]],
+        None,
+        Size::new(3, 5),
+    );
+    assert_eq!(frame.x, -0.5..1.5);
+    assert_eq!(frame.y, 0.0..10.0);
+
+    let grown = ChartFrame::new(
+        ChartKind::Bar,
+        vec![vec![(0., 2.), (1., 9.), (2., 4.)]],
+        None,
+        Size::new(3, 5),
+    );
+    let halfway = frame.interpolate(&grown, 0.5);
+    assert_eq!(halfway.series, [vec![(0., 2.), (1., 9.), (2., 2.)]]);
+    assert_eq!(halfway.x, -0.5..2.0);
+}
+
+#[test]
+fn frames_ignore_non_finite_points() {
+    let frame = ChartFrame::new(
+        ChartKind::Line,
+        vec![vec![
+            (0., 2.),
+            (1., f64::NAN),
+            (f64::INFINITY, 3.),
+            (2., 9.),
+        ]],
+        None,
+        Size::new(3, 5),
+    );
+    assert_eq!(frame.series, [vec![(0., 2.), (2., 9.)]]);
+    assert_eq!(
+        frame,
+        ChartFrame::new(
+            ChartKind::Line,
+            vec![vec![(0., 2.), (2., 9.)]],
+            None,
+            Size::new(3, 5),
+        )
+    );
+}
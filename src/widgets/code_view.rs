@@ -0,0 +1,1637 @@
+//! A widget for viewing and editing source code with syntax highlighting.
+
+use std::collections::HashSet;
+use std::fmt::{self, Debug, Formatter};
+use std::ops::Range;
+use std::sync::Arc;
+
+use figures::units::{Lp, Px, UPx};
+use figures::{FloatConversion, IntoUnsigned, Point, Rect, Round, ScreenScale, Size};
+use kludgine::app::winit::event::MouseButton;
+use kludgine::app::winit::keyboard::{Key, NamedKey};
+use kludgine::app::winit::window::CursorIcon;
+use kludgine::cosmic_text::{self, Attrs, Buffer, Family, Metrics, Shaping};
+use kludgine::shapes::Shape;
+use kludgine::text::{MeasuredText, TextOrigin};
+use kludgine::{Color, DrawableExt};
+
+use super::input::{Affinity, BlinkState, Cursor, SelectionState};
+use super::selection::{self, ClickCounter};
+use crate::context::{EventContext, GraphicsContext, LayoutContext, WidgetContext};
+use crate::styles::components::{
+    AutoFocusableControls, HighlightColor, IntrinsicPadding, LineHeight, TextColor, TextSize,
+};
+use crate::utils::ModifiersExt;
+use crate::value::{Destination, Dynamic, Generation, IntoDynamic, Source};
+use crate::widget::{EventHandling, Widget, HANDLED, IGNORED};
+use crate::window::{DeviceId, KeyEvent, WindowLocal};
+use crate::ConstraintLimit;
+
+const INDENTATION: &str = "    ";
+
+/// A widget that displays source code using a monospace font and syntax
+/// highlighting.
+///
+/// The source is highlighted by a [`Highlighter`]. This crate provides
+/// highlighting for several languages through [`Language`].
+///
+/// By default, the source is displayed with line numbers and regions of code
+/// can be folded by clicking on the markers next to the line numbers. When
+/// [`editable`](Self::editable), the source can be edited using the same
+/// editing behaviors as [`Input`](super::Input).
+#[must_use]
+pub struct CodeView {
+    /// The source code displayed by this widget.
+    pub source: Dynamic<String>,
+    highlighter: Arc<dyn Highlighter>,
+    editable: bool,
+    line_numbers: bool,
+    folding: bool,
+    folded: HashSet<usize>,
+    fold_generation: usize,
+    selection: SelectionState,
+    clicks: ClickCounter,
+    mouse_buttons_down: usize,
+    line_navigation_x_target: Option<Px>,
+    blink_state: BlinkState,
+    prepared: WindowLocal<PreparedCode>,
+}
+
+impl CodeView {
+    /// Returns a new widget that displays `source` as plain text.
+    pub fn new(source: impl IntoDynamic<String>) -> Self {
+        Self {
+            source: source.into_dynamic(),
+            highlighter: Arc::new(Language::PlainText),
+            editable: false,
+            line_numbers: true,
+            folding: true,
+            folded: HashSet::new(),
+            fold_generation: 0,
+            selection: SelectionState::default(),
+            clicks: ClickCounter::default(),
+            mouse_buttons_down: 0,
+            line_navigation_x_target: None,
+            blink_state: BlinkState::default(),
+            prepared: WindowLocal::default(),
+        }
+    }
+
+    /// Highlights the source using `highlighter`.
+    pub fn highlighter(mut self, highlighter: impl Highlighter) -> Self {
+        self.highlighter = Arc::new(highlighter);
+        self
+    }
+
+    /// Allows the user to edit the source.
+    pub fn editable(mut self) -> Self {
+        self.editable = true;
+        self
+    }
+
+    /// Sets whether line numbers are shown next to the source.
+    pub fn line_numbers(mut self, show: bool) -> Self {
+        self.line_numbers = show;
+        self
+    }
+
+    /// Sets whether the regions returned from
+    /// [`Highlighter::fold_regions`] can be folded.
+    pub fn folding(mut self, enabled: bool) -> Self {
+        self.folding = enabled;
+        self
+    }
+
+    fn prepare(&mut self, context: &mut GraphicsContext<'_, '_, '_, '_>) {
+        let scale = context.gfx.scale();
+        let key = PreparedKey {
+            generation: self.source.generation(),
+            fold_generation: self.fold_generation,
+            colors: CodeColors::new(context),
+            metrics: Metrics::new(
+                context.get(&TextSize).into_px(scale).into_float(),
+                context.get(&LineHeight).into_px(scale).into_float(),
+            ),
+            padding: context.get(&IntrinsicPadding).into_px(scale).round(),
+            line_numbers: self.line_numbers,
+            folding: self.folding,
+        };
+        if self
+            .prepared
+            .get(context)
+            .map_or(false, |prepared| prepared.key == key)
+        {
+            return;
+        }
+
+        let prepared = self.source.map_ref(|source| {
+            let regions = if self.folding {
+                self.highlighter.fold_regions(source)
+            } else {
+                Vec::new()
+            };
+            let mut tokens = self.highlighter.highlight(source);
+            tokens.retain(|token| {
+                token.range.start < token.range.end
+                    && source.is_char_boundary(token.range.start)
+                    && source.is_char_boundary(token.range.end)
+            });
+            tokens.sort_by_key(|token| token.range.start);
+
+            let lines = visible_lines(source, &regions, &self.folded);
+            let spans = highlighted_spans(source, &lines, &tokens, &key.colors);
+            let display = spans.iter().map(|(text, _)| *text).collect::<String>();
+            let buffer = shape(context, key.metrics, spans);
+            let measured = context.gfx.measure_text_buffer(&buffer, key.colors.text);
+            let text_size = buffer_size(&buffer, key.metrics);
+
+            let gutter = (self.line_numbers || self.folding).then(|| {
+                let total_lines = source.split('\n').count();
+                let digits = total_lines.to_string().len();
+                let contents = lines
+                    .iter()
+                    .map(|line| {
+                        let marker = regions
+                            .iter()
+                            .find(|region| region.start_line == line.number)
+                            .map_or(" ", |_| {
+                                if self.folded.contains(&line.number) {
+                                    "\u{25B8}"
+                                } else {
+                                    "\u{25BE}"
+                                }
+                            });
+                        let number = if self.line_numbers {
+                            format!("{:>digits$} ", line.number + 1)
+                        } else {
+                            String::new()
+                        };
+                        (number, marker)
+                    })
+                    .collect::<Vec<_>>();
+                let mut spans = Vec::with_capacity(contents.len() * 3);
+                for (index, (number, marker)) in contents.iter().enumerate() {
+                    if index > 0 {
+                        spans.push(("\n", monospace(key.colors.line_number)));
+                    }
+                    spans.push((number.as_str(), monospace(key.colors.line_number)));
+                    if self.folding {
+                        spans.push((*marker, monospace(key.colors.fold_marker)));
+                    }
+                }
+                let buffer = shape(context, key.metrics, spans);
+                let size = buffer_size(&buffer, key.metrics);
+                (buffer, size.width + key.padding)
+            });
+            let (gutter, gutter_width) = match gutter {
+                Some((buffer, width)) => (Some(buffer), width),
+                None => (None, Px::ZERO),
+            };
+
+            PreparedCode {
+                key,
+                buffer,
+                measured,
+                display,
+                lines,
+                regions,
+                gutter,
+                gutter_width,
+                text_size,
+            }
+        });
+
+        self.prepared.set(context, prepared);
+    }
+
+    fn cursor_from_point(
+        &self,
+        location: Point<Px>,
+        context: &WidgetContext<'_>,
+    ) -> Option<Cursor> {
+        let prepared = self.prepared.get(context)?;
+        let cursor = selection::cursor_from_point(
+            &prepared.measured,
+            prepared.display.len(),
+            location - prepared.text_origin(),
+        );
+        Some(prepared.to_source(cursor))
+    }
+
+    fn toggle_fold(&mut self, line: usize) {
+        if !self.folded.remove(&line) {
+            self.folded.insert(line);
+        }
+        self.fold_generation += 1;
+    }
+
+    /// Unfolds any folded regions that hide the cursor.
+    fn reveal_cursor(&mut self, context: &WidgetContext<'_>) {
+        let Some(prepared) = self.prepared.get(context) else {
+            return;
+        };
+        let line = self
+            .source
+            .map_ref(|source| line_of(source, self.selection.cursor.offset));
+        let before = self.folded.len();
+        self.folded.retain(|start| {
+            prepared
+                .regions
+                .iter()
+                .find(|region| region.start_line == *start)
+                .map_or(true, |region| !region.hides(line))
+        });
+        if self.folded.len() != before {
+            self.fold_generation += 1;
+        }
+    }
+
+    fn edit(
+        &mut self,
+        context: &mut EventContext<'_>,
+        edit: impl FnOnce(&mut SelectionState, &mut String),
+    ) {
+        if !context.enabled() {
+            return;
+        }
+
+        self.source
+            .map_mut(|mut source| edit(&mut self.selection, &mut *source));
+        if !self.folded.is_empty() {
+            // Any edit can change where the regions are, so everything is
+            // unfolded rather than guessing.
+            self.folded.clear();
+            self.fold_generation += 1;
+        }
+        self.line_navigation_x_target = None;
+        self.blink_state.force_on();
+        context.set_needs_redraw();
+    }
+
+    fn copy_selection_to_clipboard(&mut self, context: &EventContext<'_>) {
+        let selecting = self.mouse_buttons_down > 0;
+        self.source.map_ref(|source| {
+            if let Some(text) = self.selection.selected_text(source, selecting) {
+                selection::copy_to_clipboard(text, context);
+            }
+        });
+    }
+
+    fn paste_from_clipboard(&mut self, context: &mut EventContext<'_>) {
+        let text = match context
+            .cushy()
+            .clipboard_guard()
+            .map(|mut clipboard| clipboard.get_text())
+        {
+            Some(Ok(text)) => text,
+            None | Some(Err(arboard::Error::ConversionFailure)) => return,
+            Some(Err(err)) => {
+                tracing::error!("error retrieving clipboard contents: {err}");
+                return;
+            }
+        };
+        self.edit(context, |selection, source| {
+            selection.replace_selection(source, &text);
+        });
+    }
+
+    fn navigate(&mut self, key: NamedKey, context: &mut EventContext<'_>) {
+        let modifiers = context.modifiers();
+        let affinity = if matches!(
+            key,
+            NamedKey::ArrowLeft | NamedKey::ArrowUp | NamedKey::Home
+        ) {
+            Affinity::Before
+        } else {
+            Affinity::After
+        };
+        match (self.selection.start, modifiers.state().shift_key()) {
+            (None, true) => {
+                self.selection.start = Some(self.selection.cursor);
+            }
+            (Some(start), false) => {
+                self.selection.cursor = if affinity == Affinity::Before {
+                    start.min(self.selection.cursor)
+                } else {
+                    start.max(self.selection.cursor)
+                };
+                self.selection.start = None;
+            }
+            _ => {}
+        }
+
+        if !matches!(key, NamedKey::ArrowUp | NamedKey::ArrowDown) {
+            self.line_navigation_x_target = None;
+        }
+        self.selection.cursor.affinity = Affinity::Before;
+        match key {
+            NamedKey::ArrowUp | NamedKey::ArrowDown => self.move_by_line(affinity, context),
+            NamedKey::Home | NamedKey::End => self.move_to_line_extent(affinity),
+            _ if modifiers.word_select() => self
+                .source
+                .map_ref(|source| self.selection.move_by_word(source, affinity)),
+            _ => self
+                .source
+                .map_ref(|source| self.selection.move_by_grapheme(source, affinity)),
+        }
+
+        self.reveal_cursor(context);
+        self.blink_state.force_on();
+        context.set_needs_redraw();
+    }
+
+    fn move_by_line(&mut self, affinity: Affinity, context: &WidgetContext<'_>) {
+        let Some(prepared) = self.prepared.get(context) else {
+            return;
+        };
+
+        let (mut position, _) = selection::point_from_cursor(
+            &prepared.measured,
+            &prepared.display,
+            prepared.to_display(self.selection.cursor),
+            prepared.display.len(),
+        );
+        if let Some(target_x) = self.line_navigation_x_target {
+            position.x = target_x;
+        } else {
+            self.line_navigation_x_target = Some(position.x);
+        }
+        match affinity {
+            Affinity::Before => position.y -= prepared.measured.line_height,
+            Affinity::After => position.y += prepared.measured.line_height,
+        }
+
+        let cursor =
+            selection::cursor_from_point(&prepared.measured, prepared.display.len(), position);
+        self.selection.cursor = prepared.to_source(cursor);
+    }
+
+    fn move_to_line_extent(&mut self, affinity: Affinity) {
+        self.source.map_ref(|source| {
+            let offset = self.selection.cursor.offset.min(source.len());
+            self.selection.cursor.offset = match affinity {
+                Affinity::Before => source[..offset].rfind('\n').map_or(0, |index| index + 1),
+                Affinity::After => source[offset..]
+                    .find('\n')
+                    .map_or(source.len(), |index| offset + index),
+            };
+        });
+    }
+
+    fn handle_key(&mut self, input: &KeyEvent, context: &mut EventContext<'_>) -> EventHandling {
+        let pressed = input.state.is_pressed();
+        let primary = context.modifiers().primary();
+        match (&input.logical_key, input.text.as_deref()) {
+            (_, Some("a")) if primary => {
+                if pressed {
+                    self.source
+                        .map_ref(|source| self.selection.select_all(source));
+                    context.set_needs_redraw();
+                }
+                HANDLED
+            }
+            (_, Some("c")) if primary => {
+                if pressed {
+                    self.copy_selection_to_clipboard(context);
+                }
+                HANDLED
+            }
+            _ if !self.editable => IGNORED,
+            (_, Some("x")) if primary => {
+                if pressed {
+                    self.copy_selection_to_clipboard(context);
+                    self.edit(context, |selection, source| {
+                        selection.replace_selection(source, "");
+                    });
+                }
+                HANDLED
+            }
+            (_, Some("v")) if primary => {
+                if pressed {
+                    self.paste_from_clipboard(context);
+                }
+                HANDLED
+            }
+            (Key::Named(NamedKey::Backspace), _) => {
+                if pressed {
+                    self.edit(context, SelectionState::delete_backward);
+                }
+                HANDLED
+            }
+            (Key::Named(NamedKey::Delete), _) => {
+                if pressed {
+                    self.edit(context, SelectionState::delete_forward);
+                }
+                HANDLED
+            }
+            (Key::Named(NamedKey::Enter), _) => {
+                if pressed {
+                    self.edit(context, |selection, source| {
+                        let offset = selection.cursor.offset.min(source.len());
+                        let line_start = source[..offset].rfind('\n').map_or(0, |index| index + 1);
+                        let indentation = source[line_start..]
+                            .chars()
+                            .take_while(|ch| matches!(ch, ' ' | '\t'))
+                            .collect::<String>();
+                        selection.replace_selection(source, &format!("\n{indentation}"));
+                    });
+                }
+                HANDLED
+            }
+            (Key::Named(NamedKey::Tab), _) if !context.modifiers().state().shift_key() => {
+                if pressed {
+                    self.edit(context, |selection, source| {
+                        selection.replace_selection(source, INDENTATION);
+                    });
+                }
+                HANDLED
+            }
+            (
+                Key::Named(
+                    key @ (NamedKey::ArrowLeft
+                    | NamedKey::ArrowRight
+                    | NamedKey::ArrowUp
+                    | NamedKey::ArrowDown
+                    | NamedKey::Home
+                    | NamedKey::End),
+                ),
+                _,
+            ) => {
+                if pressed {
+                    self.navigate(*key, context);
+                }
+                HANDLED
+            }
+            (_, Some(text)) if !primary && !text.chars().any(char::is_control) => {
+                if pressed {
+                    self.edit(context, |selection, source| {
+                        selection.replace_selection(source, text);
+                    });
+                }
+                HANDLED
+            }
+            _ => IGNORED,
+        }
+    }
+}
+
+impl Debug for CodeView {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CodeView")
+            .field("source", &self.source)
+            .field("highlighter", &self.highlighter)
+            .field("editable", &self.editable)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Widget for CodeView {
+    fn redraw(&mut self, context: &mut GraphicsContext<'_, '_, '_, '_>) {
+        context.invalidate_when_changed(&self.source);
+        self.prepare(context);
+
+        let focused = context.focused(true);
+        if self.editable {
+            self.blink_state.update(context.elapsed());
+            if focused {
+                context.draw_focus_ring();
+            }
+        }
+
+        let length = self.source.map_ref(String::len);
+        let (start, end) = self.selection.ordered(length, self.mouse_buttons_down > 0);
+        let region = context.gfx.region();
+        let highlight = context.get(&HighlightColor);
+        let current_line_color = context.get(&CurrentLineColor);
+        let prepared = self.prepared.get(context).expect("always initialized");
+        let text_origin = prepared.text_origin();
+        let line_height = prepared.measured.line_height;
+
+        if focused || self.editable {
+            let line = prepared.visible_line_of(self.selection.cursor.offset);
+            context.gfx.draw_shape(&Shape::filled_rect(
+                Rect::new(
+                    Point::new(
+                        Px::ZERO,
+                        text_origin.y
+                            + line_height
+                                .saturating_mul(Px::new(i32::try_from(line).unwrap_or(i32::MAX))),
+                    ),
+                    Size::new(region.size.width, line_height),
+                ),
+                current_line_color,
+            ));
+        }
+
+        if let (true, Some(end)) = (focused, end) {
+            selection::draw_selection(
+                context,
+                &prepared.measured,
+                &prepared.display,
+                (prepared.to_display(start), prepared.to_display(end)),
+                region.size.width - text_origin.x,
+                highlight,
+                text_origin,
+            );
+        }
+
+        context.gfx.draw_text_buffer(
+            prepared.buffer.translate_by(text_origin),
+            prepared.key.colors.text,
+            TextOrigin::TopLeft,
+        );
+        if let Some(gutter) = &prepared.gutter {
+            context.gfx.draw_text_buffer(
+                gutter.translate_by(Point::squared(prepared.key.padding)),
+                prepared.key.colors.line_number,
+                TextOrigin::TopLeft,
+            );
+        }
+
+        if self.editable && focused && end.is_none() && context.enabled() {
+            if self.blink_state.visible {
+                let (location, _) = selection::point_from_cursor(
+                    &prepared.measured,
+                    &prepared.display,
+                    prepared.to_display(self.selection.cursor),
+                    prepared.display.len(),
+                );
+                let cursor_width = Lp::points(2).into_px(context.gfx.scale());
+                context.gfx.draw_shape(
+                    Shape::filled_rect(
+                        Rect::new(
+                            Point::new(location.x - cursor_width / 2, location.y),
+                            Size::new(cursor_width, line_height),
+                        ),
+                        highlight,
+                    )
+                    .translate_by(text_origin),
+                );
+            }
+            context.redraw_in(self.blink_state.remaining_until_blink);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        _available_space: Size<ConstraintLimit>,
+        context: &mut LayoutContext<'_, '_, '_, '_>,
+    ) -> Size<UPx> {
+        self.prepare(context);
+
+        self.prepared
+            .get(context)
+            .map(|prepared| {
+                let padding = prepared.key.padding * 2;
+                Size::new(
+                    prepared.text_size.width + prepared.gutter_width + padding,
+                    prepared.text_size.height + padding,
+                )
+                .into_unsigned()
+            })
+            .unwrap_or_default()
+    }
+
+    fn hit_test(&mut self, _location: Point<Px>, _context: &mut EventContext<'_>) -> bool {
+        true
+    }
+
+    fn hover(&mut self, location: Point<Px>, context: &mut EventContext<'_>) -> Option<CursorIcon> {
+        let prepared = self.prepared.get(context)?;
+        if location.x < prepared.text_origin().x {
+            prepared
+                .foldable_line_at(location.y)
+                .map(|_| CursorIcon::Pointer)
+        } else {
+            Some(CursorIcon::Text)
+        }
+    }
+
+    fn accept_focus(&mut self, context: &mut EventContext<'_>) -> bool {
+        self.editable || self.mouse_buttons_down > 0 || context.get(&AutoFocusableControls).is_all()
+    }
+
+    fn focus(&mut self, context: &mut EventContext<'_>) {
+        self.blink_state.force_on();
+        context.set_needs_redraw();
+    }
+
+    fn blur(&mut self, context: &mut EventContext<'_>) {
+        if !self.editable {
+            self.selection.start = None;
+        }
+        context.set_needs_redraw();
+    }
+
+    fn mouse_down(
+        &mut self,
+        location: Point<Px>,
+        _device_id: DeviceId,
+        _button: MouseButton,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        let Some(prepared) = self.prepared.get(context) else {
+            return IGNORED;
+        };
+
+        if location.x < prepared.text_origin().x {
+            if let Some(line) = prepared.foldable_line_at(location.y) {
+                self.toggle_fold(line);
+                context.set_needs_redraw();
+            }
+            return HANDLED;
+        }
+
+        let Some(cursor) = self.cursor_from_point(location, context) else {
+            return IGNORED;
+        };
+        self.mouse_buttons_down += 1;
+        let clicks = self.clicks.click(location);
+        self.source
+            .map_ref(|source| self.selection.click(source, cursor, clicks));
+        self.line_navigation_x_target = None;
+        self.blink_state.force_on();
+        context.focus();
+        context.set_needs_redraw();
+        HANDLED
+    }
+
+    fn mouse_drag(
+        &mut self,
+        location: Point<Px>,
+        _device_id: DeviceId,
+        _button: MouseButton,
+        context: &mut EventContext<'_>,
+    ) {
+        if self.mouse_buttons_down == 0 {
+            return;
+        }
+        let Some(cursor) = self.cursor_from_point(location, context) else {
+            return;
+        };
+        if self.selection.cursor != cursor {
+            self.selection.cursor = cursor;
+            context.set_needs_redraw();
+        }
+        self.blink_state.force_on();
+    }
+
+    fn mouse_up(
+        &mut self,
+        _location: Option<Point<Px>>,
+        _device_id: DeviceId,
+        _button: MouseButton,
+        _context: &mut EventContext<'_>,
+    ) {
+        self.mouse_buttons_down = self.mouse_buttons_down.saturating_sub(1);
+    }
+
+    fn keyboard_input(
+        &mut self,
+        _device_id: DeviceId,
+        input: KeyEvent,
+        _is_synthetic: bool,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        self.handle_key(&input, context)
+    }
+
+    fn unmounted(&mut self, context: &mut EventContext<'_>) {
+        self.prepared.clear_for(context);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+struct PreparedKey {
+    generation: Generation,
+    fold_generation: usize,
+    colors: CodeColors,
+    metrics: Metrics,
+    padding: Px,
+    line_numbers: bool,
+    folding: bool,
+}
+
+struct PreparedCode {
+    key: PreparedKey,
+    buffer: Buffer,
+    measured: MeasuredText<Px>,
+    display: String,
+    lines: Vec<VisibleLine>,
+    regions: Vec<FoldRegion>,
+    gutter: Option<Buffer>,
+    gutter_width: Px,
+    text_size: Size<Px>,
+}
+
+impl PreparedCode {
+    fn text_origin(&self) -> Point<Px> {
+        Point::new(self.key.padding + self.gutter_width, self.key.padding)
+    }
+
+    fn visible_line_of(&self, offset: usize) -> usize {
+        self.lines
+            .partition_point(|line| line.source.start <= offset)
+            .saturating_sub(1)
+    }
+
+    fn to_display(&self, cursor: Cursor) -> Cursor {
+        let line = &self.lines[self.visible_line_of(cursor.offset)];
+        Cursor {
+            offset: line.display_start + cursor.offset.clamp(line.source.start, line.source.end)
+                - line.source.start,
+            affinity: cursor.affinity,
+        }
+    }
+
+    fn to_source(&self, cursor: Cursor) -> Cursor {
+        let index = self
+            .lines
+            .partition_point(|line| line.display_start <= cursor.offset)
+            .saturating_sub(1);
+        let line = &self.lines[index];
+        Cursor {
+            offset: line.source.start
+                + (cursor.offset.saturating_sub(line.display_start)).min(line.source.len()),
+            affinity: cursor.affinity,
+        }
+    }
+
+    /// Returns the source line number of the visible line at `y` if it is the
+    /// start of a fold region.
+    fn foldable_line_at(&self, y: Px) -> Option<usize> {
+        let line_height = self.measured.line_height.get().max(1);
+        let index = usize::try_from((y - self.key.padding).get() / line_height).ok()?;
+        let number = self.lines.get(index)?.number;
+        self.regions
+            .iter()
+            .any(|region| region.start_line == number)
+            .then_some(number)
+    }
+}
+
+/// A line of source that is not hidden by a folded region.
+#[derive(Debug, Clone, PartialEq)]
+struct VisibleLine {
+    number: usize,
+    source: Range<usize>,
+    display_start: usize,
+}
+
+fn visible_lines(
+    source: &str,
+    regions: &[FoldRegion],
+    folded: &HashSet<usize>,
+) -> Vec<VisibleLine> {
+    let mut lines = Vec::new();
+    let mut display_start = 0;
+    let mut line_start = 0;
+    let mut hidden_until = 0;
+    for (number, line) in source.split('\n').enumerate() {
+        let range = line_start..line_start + line.len();
+        line_start = range.end + 1;
+        if number < hidden_until {
+            continue;
+        }
+
+        if folded.contains(&number) {
+            if let Some(region) = regions.iter().find(|region| region.start_line == number) {
+                hidden_until = region.end_line;
+            }
+        }
+        display_start += if lines.is_empty() { 0 } else { 1 };
+        let len = range.len();
+        lines.push(VisibleLine {
+            number,
+            source: range,
+            display_start,
+        });
+        display_start += len;
+    }
+    lines
+}
+
+fn line_of(source: &str, offset: usize) -> usize {
+    source
+        .get(..offset.min(source.len()))
+        .map_or(0, |before| before.matches('\n').count())
+}
+
+fn highlighted_spans<'a>(
+    source: &'a str,
+    lines: &[VisibleLine],
+    tokens: &[Token],
+    colors: &CodeColors,
+) -> Vec<(&'a str, Attrs<'static>)> {
+    let mut spans = Vec::new();
+    let mut tokens = tokens.iter().peekable();
+    for (index, line) in lines.iter().enumerate() {
+        if index > 0 {
+            spans.push(("\n", monospace(colors.text)));
+        }
+        let mut offset = line.source.start;
+        while offset < line.source.end {
+            while tokens
+                .peek()
+                .map_or(false, |token| token.range.end <= offset)
+            {
+                tokens.next();
+            }
+            let (end, color) = match tokens.peek() {
+                Some(token) if token.range.start <= offset => (
+                    token.range.end.min(line.source.end),
+                    colors.token(token.kind),
+                ),
+                Some(token) => (token.range.start.min(line.source.end), colors.text),
+                None => (line.source.end, colors.text),
+            };
+            spans.push((&source[offset..end], monospace(color)));
+            offset = end;
+        }
+    }
+    spans
+}
+
+fn monospace(color: Color) -> Attrs<'static> {
+    Attrs::new()
+        .family(Family::Monospace)
+        .color(cosmic_text::Color::rgba(
+            color.red(),
+            color.green(),
+            color.blue(),
+            color.alpha(),
+        ))
+}
+
+fn shape<'s, 'r>(
+    context: &mut GraphicsContext<'_, '_, '_, '_>,
+    metrics: Metrics,
+    spans: impl IntoIterator<Item = (&'s str, Attrs<'r>)>,
+) -> Buffer {
+    let mut buffer = Buffer::new(context.gfx.font_system(), metrics);
+    buffer.set_size(context.gfx.font_system(), f32::MAX, f32::MAX);
+    buffer.set_rich_text(context.gfx.font_system(), spans, Shaping::Advanced);
+    buffer.shape_until_scroll(context.gfx.font_system());
+    buffer
+}
+
+fn buffer_size(buffer: &Buffer, metrics: Metrics) -> Size<Px> {
+    let mut size = Size::new(0_f32, 0_f32);
+    for run in buffer.layout_runs() {
+        size.width = size.width.max(run.line_w);
+        size.height = size.height.max(run.line_top + metrics.line_height);
+    }
+    Size::new(Px::from(size.width.ceil()), Px::from(size.height.ceil()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CodeColors {
+    text: Color,
+    keyword: Color,
+    type_name: Color,
+    function: Color,
+    string: Color,
+    number: Color,
+    constant: Color,
+    property: Color,
+    comment: Color,
+    punctuation: Color,
+    line_number: Color,
+    fold_marker: Color,
+}
+
+impl CodeColors {
+    fn new(context: &WidgetContext<'_>) -> Self {
+        Self {
+            text: context.get(&TextColor),
+            keyword: context.get(&KeywordColor),
+            type_name: context.get(&TypeColor),
+            function: context.get(&FunctionColor),
+            string: context.get(&StringColor),
+            number: context.get(&NumberColor),
+            constant: context.get(&ConstantColor),
+            property: context.get(&PropertyColor),
+            comment: context.get(&CommentColor),
+            punctuation: context.get(&PunctuationColor),
+            line_number: context.get(&LineNumberColor),
+            fold_marker: context.get(&FoldMarkerColor),
+        }
+    }
+
+    const fn token(&self, kind: TokenKind) -> Color {
+        match kind {
+            TokenKind::Keyword => self.keyword,
+            TokenKind::Type => self.type_name,
+            TokenKind::Function => self.function,
+            TokenKind::String => self.string,
+            TokenKind::Number => self.number,
+            TokenKind::Constant => self.constant,
+            TokenKind::Property => self.property,
+            TokenKind::Comment => self.comment,
+            TokenKind::Punctuation => self.punctuation,
+        }
+    }
+}
+
+/// A type that splits source code into highlighted [`Token`]s.
+pub trait Highlighter: Debug + Send + Sync + 'static {
+    /// Returns the highlighted tokens within `source`.
+    ///
+    /// Tokens should be in ascending order, must not overlap, and must start
+    /// and end on character boundaries. Text that is not covered by a token is
+    /// drawn using the default text color.
+    fn highlight(&self, source: &str) -> Vec<Token>;
+
+    /// Returns the regions of `source` that can be folded.
+    ///
+    /// The default implementation returns a region for each pair of matching
+    /// brackets that are more than one line apart, ignoring any brackets
+    /// inside of string and comment tokens.
+    fn fold_regions(&self, source: &str) -> Vec<FoldRegion> {
+        bracket_fold_regions(source, &self.highlight(source))
+    }
+}
+
+/// A highlighted range of source code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    /// The byte range of the token within the source.
+    pub range: Range<usize>,
+    /// The kind of token.
+    pub kind: TokenKind,
+}
+
+impl Token {
+    /// Returns a new token of `kind` covering `range`.
+    #[must_use]
+    pub const fn new(range: Range<usize>, kind: TokenKind) -> Self {
+        Self { range, kind }
+    }
+}
+
+/// The kind of a [`Token`], which determines the color it is drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// A reserved word of the language. Drawn with [`KeywordColor`].
+    Keyword,
+    /// The name of a type. Drawn with [`TypeColor`].
+    Type,
+    /// The name of a function or macro. Drawn with [`FunctionColor`].
+    Function,
+    /// A string or character literal. Drawn with [`StringColor`].
+    String,
+    /// A numeric literal. Drawn with [`NumberColor`].
+    Number,
+    /// A constant or builtin value, such as `true`. Drawn with
+    /// [`ConstantColor`].
+    Constant,
+    /// A property or key name. Drawn with [`PropertyColor`].
+    Property,
+    /// A comment. Drawn with [`CommentColor`].
+    Comment,
+    /// Punctuation and operators. Drawn with [`PunctuationColor`].
+    Punctuation,
+}
+
+/// A region of source code that can be folded by a [`CodeView`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FoldRegion {
+    /// The zero-based line the region starts on. This line remains visible
+    /// when the region is folded.
+    pub start_line: usize,
+    /// The zero-based line the region ends on. This line remains visible when
+    /// the region is folded, and all lines between `start_line` and
+    /// `end_line` are hidden.
+    pub end_line: usize,
+}
+
+impl FoldRegion {
+    const fn hides(&self, line: usize) -> bool {
+        self.start_line < line && line < self.end_line
+    }
+}
+
+fn bracket_fold_regions(source: &str, tokens: &[Token]) -> Vec<FoldRegion> {
+    let mut regions = Vec::<FoldRegion>::new();
+    let mut open = Vec::new();
+    let mut line = 0;
+    let mut tokens = tokens.iter().peekable();
+    for (offset, ch) in source.char_indices() {
+        while tokens
+            .peek()
+            .map_or(false, |token| token.range.end <= offset)
+        {
+            tokens.next();
+        }
+        if tokens.peek().map_or(false, |token| {
+            token.range.start <= offset
+                && matches!(token.kind, TokenKind::String | TokenKind::Comment)
+        }) {
+            line += usize::from(ch == '\n');
+            continue;
+        }
+
+        match ch {
+            '\n' => line += 1,
+            '{' | '[' | '(' => open.push((ch, line)),
+            '}' | ']' | ')' => {
+                let expected = match ch {
+                    '}' => '{',
+                    ']' => '[',
+                    _ => '(',
+                };
+                if let Some(start) = open.iter().rposition(|(bracket, _)| *bracket == expected) {
+                    let (_, start_line) = open[start];
+                    open.truncate(start);
+                    if line > start_line + 1 {
+                        match regions
+                            .iter_mut()
+                            .find(|region| region.start_line == start_line)
+                        {
+                            Some(existing) => existing.end_line = existing.end_line.max(line),
+                            None => regions.push(FoldRegion {
+                                start_line,
+                                end_line: line,
+                            }),
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    regions.sort_by_key(|region| region.start_line);
+    regions
+}
+
+/// A language with built-in syntax highlighting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Language {
+    /// Text without highlighting.
+    #[default]
+    PlainText,
+    /// The [Rust](https://www.rust-lang.org/) programming language.
+    Rust,
+    /// [JSON](https://www.json.org/)
+    Json,
+    /// [TOML](https://toml.io/)
+    Toml,
+}
+
+impl Language {
+    /// Returns the language that files with the extension `extension` are
+    /// typically written in.
+    #[must_use]
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "rs" => Some(Self::Rust),
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            "txt" => Some(Self::PlainText),
+            _ => None,
+        }
+    }
+}
+
+impl Highlighter for Language {
+    fn highlight(&self, source: &str) -> Vec<Token> {
+        match self {
+            Language::PlainText => Vec::new(),
+            Language::Rust => highlight_rust(source),
+            Language::Json => highlight_json(source),
+            Language::Toml => highlight_toml(source),
+        }
+    }
+
+    fn fold_regions(&self, source: &str) -> Vec<FoldRegion> {
+        match self {
+            Language::PlainText => Vec::new(),
+            Language::Rust | Language::Json => {
+                bracket_fold_regions(source, &self.highlight(source))
+            }
+            Language::Toml => toml_fold_regions(source),
+        }
+    }
+}
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "type", "unsafe", "use",
+    "where", "while", "yield",
+];
+
+struct Scanner<'a> {
+    source: &'a str,
+    offset: usize,
+    tokens: Vec<Token>,
+}
+
+impl<'a> Scanner<'a> {
+    const fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            offset: 0,
+            tokens: Vec::new(),
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.source[self.offset..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.rest().chars().nth(n)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.offset += ch.len_utf8();
+        Some(ch)
+    }
+
+    fn eat_while(&mut self, mut predicate: impl FnMut(char) -> bool) {
+        while self.peek().map_or(false, &mut predicate) {
+            self.bump();
+        }
+    }
+
+    fn eat_until(&mut self, terminator: &str) {
+        self.offset = self
+            .rest()
+            .find(terminator)
+            .map_or(self.source.len(), |index| {
+                self.offset + index + terminator.len()
+            });
+    }
+
+    fn eat_line(&mut self) {
+        self.offset = self
+            .rest()
+            .find('\n')
+            .map_or(self.source.len(), |index| self.offset + index);
+    }
+
+    fn eat_string(&mut self, quote: char, escapes: bool) {
+        self.bump();
+        while let Some(ch) = self.bump() {
+            if ch == quote {
+                break;
+            } else if escapes && ch == '\\' {
+                self.bump();
+            }
+        }
+    }
+
+    fn eat_number(&mut self) {
+        let start = self.offset;
+        while let Some(ch) = self.peek() {
+            if ch.is_ascii_alphanumeric() || ch == '_' {
+                self.bump();
+            } else if ch == '.' && self.peek_nth(1).map_or(false, |ch| ch.is_ascii_digit()) {
+                self.bump();
+            } else if matches!(ch, '+' | '-')
+                && self.source[..self.offset].ends_with(&['e', 'E'][..])
+                && !self.source[start..].starts_with("0x")
+            {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Returns the next character that isn't a space or tab.
+    fn next_significant(&self) -> Option<char> {
+        self.rest().chars().find(|ch| !matches!(ch, ' ' | '\t'))
+    }
+
+    fn push(&mut self, start: usize, kind: TokenKind) {
+        if start == self.offset {
+            return;
+        }
+        match self.tokens.last_mut() {
+            Some(last) if last.kind == kind && last.range.end == start => {
+                last.range.end = self.offset;
+            }
+            _ => self.tokens.push(Token::new(start..self.offset, kind)),
+        }
+    }
+}
+
+fn is_identifier_start(ch: char) -> bool {
+    ch.is_alphabetic() || ch == '_'
+}
+
+fn is_identifier(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+fn highlight_rust(source: &str) -> Vec<Token> {
+    let mut scanner = Scanner::new(source);
+    while let Some(ch) = scanner.peek() {
+        let start = scanner.offset;
+        let rest = scanner.rest();
+        if ch.is_whitespace() {
+            scanner.bump();
+        } else if rest.starts_with("//") {
+            scanner.eat_line();
+            scanner.push(start, TokenKind::Comment);
+        } else if rest.starts_with("/*") {
+            eat_block_comment(&mut scanner);
+            scanner.push(start, TokenKind::Comment);
+        } else if eat_raw_string(&mut scanner) {
+            scanner.push(start, TokenKind::String);
+        } else if ch == '"' || (ch == 'b' && matches!(scanner.peek_nth(1), Some('"' | '\''))) {
+            if ch == 'b' {
+                scanner.bump();
+            }
+            let quote = scanner.peek().expect("checked above");
+            scanner.eat_string(quote, true);
+            scanner.push(start, TokenKind::String);
+        } else if ch == '\'' {
+            if scanner.peek_nth(1) == Some('\\') || scanner.peek_nth(2) == Some('\'') {
+                scanner.eat_string('\'', true);
+                scanner.push(start, TokenKind::String);
+            } else {
+                // A lifetime or label.
+                scanner.bump();
+                scanner.eat_while(is_identifier);
+                scanner.push(start, TokenKind::Type);
+            }
+        } else if ch.is_ascii_digit() {
+            scanner.eat_number();
+            scanner.push(start, TokenKind::Number);
+        } else if is_identifier_start(ch) {
+            scanner.eat_while(is_identifier);
+            let identifier = &source[start..scanner.offset];
+            let kind = if RUST_KEYWORDS.contains(&identifier) {
+                Some(TokenKind::Keyword)
+            } else if matches!(identifier, "true" | "false") {
+                Some(TokenKind::Constant)
+            } else if scanner.peek() == Some('!') && scanner.peek_nth(1) != Some('=') {
+                scanner.bump();
+                Some(TokenKind::Function)
+            } else if scanner.next_significant() == Some('(') {
+                Some(TokenKind::Function)
+            } else if identifier.starts_with(char::is_uppercase) {
+                if identifier.len() > 1 && !identifier.contains(char::is_lowercase) {
+                    Some(TokenKind::Constant)
+                } else {
+                    Some(TokenKind::Type)
+                }
+            } else {
+                None
+            };
+            if let Some(kind) = kind {
+                scanner.push(start, kind);
+            }
+        } else if ch.is_ascii_punctuation() {
+            scanner.bump();
+            scanner.push(start, TokenKind::Punctuation);
+        } else {
+            scanner.bump();
+        }
+    }
+    scanner.tokens
+}
+
+fn eat_block_comment(scanner: &mut Scanner<'_>) {
+    let mut depth = 0_usize;
+    while !scanner.rest().is_empty() {
+        if scanner.rest().starts_with("/*") {
+            depth += 1;
+            scanner.offset += 2;
+        } else if scanner.rest().starts_with("*/") {
+            scanner.offset += 2;
+            depth -= 1;
+            if depth == 0 {
+                break;
+            }
+        } else {
+            scanner.bump();
+        }
+    }
+}
+
+fn eat_raw_string(scanner: &mut Scanner<'_>) -> bool {
+    let rest = scanner.rest();
+    let Some(after_prefix) = rest.strip_prefix("br").or_else(|| rest.strip_prefix('r')) else {
+        return false;
+    };
+    let hashes = after_prefix.len() - after_prefix.trim_start_matches('#').len();
+    if !after_prefix[hashes..].starts_with('"') {
+        return false;
+    }
+
+    scanner.offset += rest.len() - after_prefix.len() + hashes + 1;
+    let mut terminator = String::from("\"");
+    terminator.extend(std::iter::repeat('#').take(hashes));
+    scanner.eat_until(&terminator);
+    true
+}
+
+fn highlight_json(source: &str) -> Vec<Token> {
+    let mut scanner = Scanner::new(source);
+    while let Some(ch) = scanner.peek() {
+        let start = scanner.offset;
+        if ch.is_whitespace() {
+            scanner.bump();
+        } else if ch == '"' {
+            scanner.eat_string('"', true);
+            let kind = if scanner.rest().trim_start().starts_with(':') {
+                TokenKind::Property
+            } else {
+                TokenKind::String
+            };
+            scanner.push(start, kind);
+        } else if ch == '-' || ch.is_ascii_digit() {
+            scanner.bump();
+            scanner.eat_number();
+            scanner.push(start, TokenKind::Number);
+        } else if is_identifier_start(ch) {
+            scanner.eat_while(is_identifier);
+            if matches!(&source[start..scanner.offset], "true" | "false" | "null") {
+                scanner.push(start, TokenKind::Constant);
+            }
+        } else if ch.is_ascii_punctuation() {
+            scanner.bump();
+            scanner.push(start, TokenKind::Punctuation);
+        } else {
+            scanner.bump();
+        }
+    }
+    scanner.tokens
+}
+
+fn is_toml_bare_key(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-')
+}
+
+fn highlight_toml(source: &str) -> Vec<Token> {
+    let mut scanner = Scanner::new(source);
+    let mut line_start = true;
+    let mut expecting_key = true;
+    while let Some(ch) = scanner.peek() {
+        let start = scanner.offset;
+        if ch == '\n' {
+            scanner.bump();
+            line_start = true;
+            expecting_key = true;
+            continue;
+        } else if ch.is_whitespace() {
+            scanner.bump();
+            continue;
+        }
+
+        if ch == '#' {
+            scanner.eat_line();
+            scanner.push(start, TokenKind::Comment);
+        } else if ch == '[' && line_start {
+            scanner.eat_line();
+            let header_end = source[start..scanner.offset]
+                .rfind(']')
+                .map_or(scanner.offset, |index| start + index + 1);
+            scanner.offset = header_end;
+            scanner.push(start, TokenKind::Type);
+        } else if ch == '"' || ch == '\'' {
+            let triple = if ch == '"' { "\"\"\"" } else { "'''" };
+            if scanner.rest().starts_with(triple) {
+                scanner.offset += triple.len();
+                scanner.eat_until(triple);
+            } else {
+                scanner.eat_string(ch, ch == '"');
+            }
+            let kind = if expecting_key && matches!(scanner.next_significant(), Some('=' | '.')) {
+                TokenKind::Property
+            } else {
+                TokenKind::String
+            };
+            scanner.push(start, kind);
+        } else if is_toml_bare_key(ch)
+            || (matches!(ch, '+' | '-')
+                && scanner.peek_nth(1).map_or(false, |ch| ch.is_ascii_digit()))
+        {
+            scanner.bump();
+            scanner.eat_while(|ch| is_toml_bare_key(ch) || matches!(ch, ':' | '+'));
+            if expecting_key && matches!(scanner.next_significant(), Some('=' | '.')) {
+                scanner.push(start, TokenKind::Property);
+            } else {
+                // Allow floating point numbers and times to include periods.
+                scanner.eat_while(|ch| is_toml_bare_key(ch) || matches!(ch, ':' | '+' | '.'));
+                let value = &source[start..scanner.offset];
+                let kind = if matches!(
+                    value,
+                    "true" | "false" | "inf" | "+inf" | "-inf" | "nan" | "+nan" | "-nan"
+                ) {
+                    Some(TokenKind::Constant)
+                } else if value
+                    .starts_with(|ch: char| ch.is_ascii_digit() || matches!(ch, '+' | '-'))
+                {
+                    Some(TokenKind::Number)
+                } else {
+                    None
+                };
+                if let Some(kind) = kind {
+                    scanner.push(start, kind);
+                }
+            }
+        } else {
+            match ch {
+                '=' => expecting_key = false,
+                '{' | ',' => expecting_key = true,
+                _ => {}
+            }
+            scanner.bump();
+            if ch.is_ascii_punctuation() {
+                scanner.push(start, TokenKind::Punctuation);
+            }
+        }
+        line_start = false;
+    }
+    scanner.tokens
+}
+
+fn toml_fold_regions(source: &str) -> Vec<FoldRegion> {
+    let mut regions = Vec::new();
+    let mut table: Option<usize> = None;
+    let mut last_content = 0;
+    for (line, text) in source.lines().enumerate() {
+        let text = text.trim();
+        if text.starts_with('[') {
+            if let Some(start_line) = table {
+                if last_content > start_line {
+                    regions.push(FoldRegion {
+                        start_line,
+                        end_line: line,
+                    });
+                }
+            }
+            table = Some(line);
+            last_content = line;
+        } else if !text.is_empty() {
+            last_content = line;
+        }
+    }
+    if let Some(start_line) = table {
+        if last_content > start_line {
+            regions.push(FoldRegion {
+                start_line,
+                end_line: last_content + 1,
+            });
+        }
+    }
+    regions
+}
+
+define_components! {
+    CodeView {
+        /// The color of keywords within a [`CodeView`].
+        KeywordColor(Color, "keyword_color", .primary.color)
+        /// The color of type names within a [`CodeView`].
+        TypeColor(Color, "type_color", .tertiary.color)
+        /// The color of function and macro names within a [`CodeView`].
+        FunctionColor(Color, "function_color", .secondary.color)
+        /// The color of string and character literals within a [`CodeView`].
+        StringColor(Color, "string_color", .tertiary.color_bright)
+        /// The color of numeric literals within a [`CodeView`].
+        NumberColor(Color, "number_color", .secondary.color_dim)
+        /// The color of constants and builtin values within a [`CodeView`].
+        ConstantColor(Color, "constant_color", .primary.color_bright)
+        /// The color of property and key names within a [`CodeView`].
+        PropertyColor(Color, "property_color", .secondary.color_bright)
+        /// The color of comments within a [`CodeView`].
+        CommentColor(Color, "comment_color", .surface.on_color_variant)
+        /// The color of punctuation and operators within a [`CodeView`].
+        PunctuationColor(Color, "punctuation_color", @TextColor)
+        /// The color of the line numbers shown beside the source of a
+        /// [`CodeView`].
+        LineNumberColor(Color, "line_number_color", .surface.outline)
+        /// The color of the markers shown beside regions of a [`CodeView`]
+        /// that can be folded.
+        FoldMarkerColor(Color, "fold_marker_color", .surface.on_color_variant)
+        /// The background color of the line containing the cursor in a
+        /// [`CodeView`].
+        CurrentLineColor(Color, "current_line_color", .surface.low_container)
+    }
+}
+
+#[test]
+fn builtin_highlighting() {
+    fn kinds(source: &str, tokens: &[Token]) -> Vec<(String, TokenKind)> {
+        tokens
+            .iter()
+            .map(|token| (source[token.range.clone()].to_string(), token.kind))
+            .collect()
+    }
+
+    let source = "fn main() {\n    // hi\n    println!(\"{}\", 1);\n}";
+    assert_eq!(
+        kinds(source, &Language::Rust.highlight(source)),
+        vec![
+            ("fn".to_string(), TokenKind::Keyword),
+            ("main".to_string(), TokenKind::Function),
+            ("()".to_string(), TokenKind::Punctuation),
+            ("{".to_string(), TokenKind::Punctuation),
+            ("// hi".to_string(), TokenKind::Comment),
+            ("println!".to_string(), TokenKind::Function),
+            ("(".to_string(), TokenKind::Punctuation),
+            ("\"{}\"".to_string(), TokenKind::String),
+            (",".to_string(), TokenKind::Punctuation),
+            ("1".to_string(), TokenKind::Number),
+            (");".to_string(), TokenKind::Punctuation),
+            ("}".to_string(), TokenKind::Punctuation),
+        ]
+    );
+    assert_eq!(
+        Language::Rust.fold_regions(source),
+        vec![FoldRegion {
+            start_line: 0,
+            end_line: 3
+        }]
+    );
+
+    let source = "{\"a\": [true, -1.5e3]}";
+    assert_eq!(
+        kinds(source, &Language::Json.highlight(source)),
+        vec![
+            ("{".to_string(), TokenKind::Punctuation),
+            ("\"a\"".to_string(), TokenKind::Property),
+            (":".to_string(), TokenKind::Punctuation),
+            ("[".to_string(), TokenKind::Punctuation),
+            ("true".to_string(), TokenKind::Constant),
+            (",".to_string(), TokenKind::Punctuation),
+            ("-1.5e3".to_string(), TokenKind::Number),
+            ("]}".to_string(), TokenKind::Punctuation),
+        ]
+    );
+
+    let source =
+        "[package]\nname = \"cushy\"\n# comment\nversion.workspace = true\n\n[dependencies]\na = 1";
+    assert_eq!(
+        kinds(source, &Language::Toml.highlight(source)),
+        vec![
+            ("[package]".to_string(), TokenKind::Type),
+            ("name".to_string(), TokenKind::Property),
+            ("=".to_string(), TokenKind::Punctuation),
+            ("\"cushy\"".to_string(), TokenKind::String),
+            ("# comment".to_string(), TokenKind::Comment),
+            ("version".to_string(), TokenKind::Property),
+            (".".to_string(), TokenKind::Punctuation),
+            ("workspace".to_string(), TokenKind::Property),
+            ("=".to_string(), TokenKind::Punctuation),
+            ("true".to_string(), TokenKind::Constant),
+            ("[dependencies]".to_string(), TokenKind::Type),
+            ("a".to_string(), TokenKind::Property),
+            ("=".to_string(), TokenKind::Punctuation),
+            ("1".to_string(), TokenKind::Number),
+        ]
+    );
+    assert_eq!(
+        Language::Toml.fold_regions(source),
+        vec![
+            FoldRegion {
+                start_line: 0,
+                end_line: 5
+            },
+            FoldRegion {
+                start_line: 5,
+                end_line: 7
+            }
+        ]
+    );
+}
+
+#[test]
+fn folded_lines() {
+    let source = "a {\n  b\n  c\n}\nd";
+    let regions = Language::Rust.fold_regions(source);
+    let mut folded = HashSet::new();
+    folded.insert(0);
+    let lines = visible_lines(source, &regions, &folded);
+    assert_eq!(
+        lines,
+        vec![
+            VisibleLine {
+                number: 0,
+                source: 0..3,
+                display_start: 0,
+            },
+            VisibleLine {
+                number: 3,
+                source: 12..13,
+                display_start: 4,
+            },
+            VisibleLine {
+                number: 4,
+                source: 14..15,
+                display_start: 6,
+            },
+        ]
+    );
+}
@@ -1,17 +1,19 @@
 //! A visual container widget.
 
 use std::ops::Div;
+use std::sync::Arc;
 
 use figures::units::{Lp, Px, UPx};
 use figures::{Abs, Angle, IntoSigned, IntoUnsigned, Point, Rect, Round, ScreenScale, Size, Zero};
 use kludgine::shapes::{CornerRadii, PathBuilder, Shape};
-use kludgine::Color;
+use kludgine::{AnyTexture, Color};
 
 use crate::context::{EventContext, GraphicsContext, LayoutContext, WidgetContext};
 use crate::styles::components::{CornerRadius, IntrinsicPadding, Opacity, SurfaceColor};
 use crate::styles::{Component, ContainerLevel, Dimension, Edges, RequireInvalidation, Styles};
 use crate::value::{Dynamic, IntoValue, Source, Value};
 use crate::widget::{MakeWidget, RootBehavior, Widget, WidgetInstance, WidgetRef};
+use crate::widgets::image::draw_nine_slice;
 use crate::ConstraintLimit;
 
 /// A visual container widget, optionally applying padding and a background
@@ -19,7 +21,7 @@ use crate::ConstraintLimit;
 ///
 /// # Background Color Selection
 ///
-/// This widget has three different modes for coloring its background:
+/// This widget has four different modes for coloring its background:
 ///
 /// - [`ContainerBackground::Auto`]: The background color is automatically
 ///   selected by using the [next](ContainerLevel::next) level from the next
@@ -32,6 +34,8 @@ use crate::ConstraintLimit;
 /// - [`ContainerBackground::Level`]: The
 ///   [`SurfaceTheme`](crate::styles::SurfaceTheme) container color associated
 ///   with the given level will be used.
+/// - [`ContainerBackground::NineSlice`]: The specified texture will be drawn
+///   using nine-slice scaling.
 #[derive(Debug)]
 pub struct Container {
     /// The configured background selection.
@@ -48,7 +52,7 @@ pub struct Container {
 }
 
 /// A strategy of applying a background to a [`Container`].
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub enum ContainerBackground {
     /// Automatically select a [`ContainerLevel`] by picking the
     /// [next](ContainerLevel::next) level after the previous parent
@@ -84,6 +88,68 @@ pub enum ContainerBackground {
     /// [hh]: ContainerLevel::Highest
     /// [hhc]: crate::styles::SurfaceTheme::highest_container
     Level(ContainerLevel),
+    /// Draws the specified texture using nine-slice scaling.
+    ///
+    /// The contained widget is padded by at least the nine-slice's edges.
+    /// Nested containers with an [automatic](Self::Auto) background treat this
+    /// container as if it were filled with the nine-slice's tint color.
+    NineSlice(NineSlice),
+}
+
+/// A texture drawn as a [`Container`]'s background using nine-slice scaling.
+///
+/// See [`ImageScaling::NineSlice`](crate::widgets::image::ImageScaling::NineSlice)
+/// for more information about how the texture is scaled.
+///
+/// Two instances are equal when they were cloned from the same instance and
+/// their edges and tint are equal.
+#[derive(Debug, Clone)]
+pub struct NineSlice {
+    texture: Arc<AnyTexture>,
+    /// The borders of the texture that are preserved while scaling.
+    pub edges: Edges<Dimension>,
+    /// The color to multiply the texture's colors by.
+    pub tint: Color,
+}
+
+impl NineSlice {
+    /// Returns a new nine-slice background that draws `texture` preserving
+    /// borders of `edges`.
+    #[must_use]
+    pub fn new(texture: impl Into<AnyTexture>, edges: impl Into<Edges<Dimension>>) -> Self {
+        Self {
+            texture: Arc::new(texture.into()),
+            edges: edges.into(),
+            tint: Color::WHITE,
+        }
+    }
+
+    /// Multiplies the texture's colors by `tint` and returns self.
+    #[must_use]
+    pub fn tinted(mut self, tint: Color) -> Self {
+        self.tint = tint;
+        self
+    }
+
+    /// Returns the texture this background draws.
+    #[must_use]
+    pub fn texture(&self) -> &AnyTexture {
+        &self.texture
+    }
+}
+
+impl PartialEq for NineSlice {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.texture, &other.texture)
+            && self.edges == other.edges
+            && self.tint == other.tint
+    }
+}
+
+impl From<NineSlice> for ContainerBackground {
+    fn from(value: NineSlice) -> Self {
+        Self::NineSlice(value)
+    }
 }
 
 impl From<ContainerLevel> for ContainerBackground {
@@ -151,6 +217,16 @@ impl Container {
         self
     }
 
+    /// Sets this container to render `background` using nine-slice scaling,
+    /// and then returns the updated container.
+    #[must_use]
+    pub fn background_nine_slice(mut self, background: impl IntoValue<NineSlice>) -> Self {
+        self.background = background
+            .into_value()
+            .map_each(|background| ContainerBackground::from(background.clone()));
+        self
+    }
+
     /// Renders `shadow` behind the container's background.
     #[must_use]
     pub fn shadow(mut self, shadow: impl IntoValue<ContainerShadow>) -> Self {
@@ -170,6 +246,7 @@ impl Container {
         let background = match self.background.get() {
             ContainerBackground::Color(color) => EffectiveBackground::Color(color),
             ContainerBackground::Level(level) => EffectiveBackground::Level(level),
+            ContainerBackground::NineSlice(slice) => EffectiveBackground::Color(slice.tint),
             ContainerBackground::Auto => {
                 EffectiveBackground::Level(match context.get(&CurrentContainerBackground) {
                     EffectiveBackground::Color(_) => ContainerLevel::default(),
@@ -220,7 +297,15 @@ impl Widget for Container {
 
         let background = self.effective_background_color(context);
         let background = background.with_alpha_f32(background.alpha_f32() * *opacity);
-        if background.alpha() > 0 {
+        if let ContainerBackground::NineSlice(slice) = self.background.get() {
+            draw_nine_slice(
+                &slice.texture,
+                Rect::from(context.gfx.region().size),
+                slice.edges,
+                background,
+                context,
+            );
+        } else if background.alpha() > 0 {
             let shadow = self
                 .shadow
                 .get_tracking_invalidate(context)
@@ -280,6 +365,13 @@ impl Widget for Container {
             .bottom
             .max(corner_radii.bottom_right / std::f32::consts::PI)
             .max(corner_radii.bottom_left / std::f32::consts::PI);
+        if let ContainerBackground::NineSlice(slice) = self.background.get() {
+            let edges = slice.edges.into_upx(context.gfx.scale());
+            padding.left = padding.left.max(edges.left);
+            padding.top = padding.top.max(edges.top);
+            padding.right = padding.right.max(edges.right);
+            padding.bottom = padding.bottom.max(edges.bottom);
+        }
         let padding_amount = padding.size();
 
         let shadow = self
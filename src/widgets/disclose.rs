@@ -1,9 +1,11 @@
 //! A widget that hides/shows associated content.
 
+use std::collections::BTreeSet;
 use std::time::Duration;
 
 use figures::units::{Lp, Px, UPx};
 use figures::{Angle, IntoSigned, Point, Rect, Round, ScreenScale, Size, Zero};
+use kludgine::app::winit::keyboard::{Key, NamedKey};
 use kludgine::app::winit::window::CursorIcon;
 use kludgine::shapes::{PathBuilder, StrokeOptions};
 use kludgine::{Color, DrawableExt};
@@ -15,10 +17,10 @@ use crate::styles::components::{HighlightColor, IntrinsicPadding, LineHeight, Ou
 use crate::styles::Dimension;
 use crate::value::{Destination, Dynamic, IntoDynamic, IntoValue, Source, Value};
 use crate::widget::{
-    EventHandling, MakeWidget, MakeWidgetWithTag, Widget, WidgetInstance, WidgetRef, WidgetTag,
-    HANDLED, IGNORED,
+    EventHandling, MakeWidget, MakeWidgetWithTag, Widget, WidgetId, WidgetInstance, WidgetList,
+    WidgetRef, WidgetTag, WrapperWidget, HANDLED, IGNORED,
 };
-use crate::window::DeviceId;
+use crate::window::{DeviceId, KeyEvent};
 use crate::ConstraintLimit;
 
 /// A widget that hides and shows another widget.
@@ -66,6 +68,207 @@ impl MakeWidgetWithTag for Disclose {
     }
 }
 
+/// A group of [`Disclose`] sections that coordinate which sections are
+/// expanded.
+///
+/// By default, a group behaves as an accordion: expanding one section collapses
+/// the others. Use [`expanded_set()`](Self::expanded_set) to allow multiple
+/// sections to be expanded at once.
+///
+/// The group owns the expansion state of its sections. Each section's initial
+/// [`collapsed`](Disclose::collapsed) value is used to seed the group's
+/// expanded sections, and a [`Dynamic`] provided as a section's collapsed value
+/// is kept updated as the group expands and collapses the section. When only
+/// one section can be expanded, the first section that starts expanded wins.
+///
+/// When a section's header is focused, the up and down arrow keys move focus
+/// to the previous and next headers, and the Home and End keys move focus to
+/// the first and last headers.
+pub struct DiscloseGroup {
+    sections: Vec<Disclose>,
+    expanded: GroupExpansion,
+}
+
+enum GroupExpansion {
+    Single(Dynamic<Option<usize>>),
+    Multiple(Dynamic<BTreeSet<usize>>),
+}
+
+impl GroupExpansion {
+    fn seed(&self, index: usize, collapsed: bool) {
+        if collapsed {
+            return;
+        }
+        match self {
+            GroupExpansion::Single(expanded) => {
+                let _result = expanded.try_compare_swap(&None, Some(index));
+            }
+            GroupExpansion::Multiple(expanded) => {
+                expanded.map_mut(|mut expanded| {
+                    expanded.insert(index);
+                });
+            }
+        }
+    }
+
+    fn collapsed(&self, index: usize) -> Dynamic<bool> {
+        match self {
+            GroupExpansion::Single(expanded) => {
+                let current = expanded.clone();
+                expanded.linked(
+                    move |expanded| *expanded != Some(index),
+                    move |collapsed| {
+                        if *collapsed {
+                            // Only clear the expanded section if this section
+                            // is still the one expanded. This fails when the
+                            // change originated from `expanded`, in which case
+                            // there is nothing to update.
+                            let _result = current.try_compare_swap(&Some(index), None);
+                            None
+                        } else {
+                            Some(Some(index))
+                        }
+                    },
+                )
+            }
+            GroupExpansion::Multiple(expanded) => {
+                let current = expanded.clone();
+                expanded.linked(
+                    move |expanded| !expanded.contains(&index),
+                    move |collapsed| {
+                        let _result = current.try_map_mut(|mut expanded| {
+                            if expanded.contains(&index) == *collapsed {
+                                if *collapsed {
+                                    expanded.remove(&index);
+                                } else {
+                                    expanded.insert(index);
+                                }
+                            }
+                        });
+                        None::<BTreeSet<usize>>
+                    },
+                )
+            }
+        }
+    }
+}
+
+impl DiscloseGroup {
+    /// Returns a new group containing `sections`. Sections start expanded
+    /// only if their collapsed value was initially `false`.
+    #[must_use]
+    pub fn new(sections: impl IntoIterator<Item = Disclose>) -> Self {
+        Self {
+            sections: sections.into_iter().collect(),
+            expanded: GroupExpansion::Single(Dynamic::default()),
+        }
+    }
+
+    /// Adds `section` to the end of this group and returns self.
+    #[must_use]
+    pub fn and(mut self, section: Disclose) -> Self {
+        self.sections.push(section);
+        self
+    }
+
+    /// Stores the index of the expanded section in `expanded` and returns
+    /// self.
+    ///
+    /// Only one section can be expanded at a time. Expanding a section
+    /// collapses the previously expanded section.
+    #[must_use]
+    pub fn expanded(mut self, expanded: impl IntoDynamic<Option<usize>>) -> Self {
+        self.expanded = GroupExpansion::Single(expanded.into_dynamic());
+        self
+    }
+
+    /// Stores the indices of the expanded sections in `expanded` and returns
+    /// self.
+    ///
+    /// Any number of sections can be expanded at a time.
+    #[must_use]
+    pub fn expanded_set(mut self, expanded: impl IntoDynamic<BTreeSet<usize>>) -> Self {
+        self.expanded = GroupExpansion::Multiple(expanded.into_dynamic());
+        self
+    }
+}
+
+impl MakeWidgetWithTag for DiscloseGroup {
+    fn make_with_tag(self, tag: WidgetTag) -> WidgetInstance {
+        let mut headers = Vec::with_capacity(self.sections.len());
+        let mut sections = WidgetList::with_capacity(self.sections.len());
+        for (index, mut section) in self.sections.into_iter().enumerate() {
+            self.expanded
+                .seed(index, section.collapsed.map(|collapsed| *collapsed));
+            let collapsed = self.expanded.collapsed(index);
+            if let Value::Dynamic(requested) = &section.collapsed {
+                requested.set(collapsed.get());
+                let requested = requested.clone();
+                collapsed.set_source(
+                    collapsed.for_each_cloned(move |collapsed| requested.set(collapsed)),
+                );
+            }
+            section.collapsed = Value::Dynamic(collapsed);
+            let (section_tag, section_id) = WidgetTag::new();
+            headers.push(section_id);
+            sections.push(section.make_with_tag(section_tag));
+        }
+
+        DiscloseGroupWidget {
+            sections: WidgetRef::new(sections.into_rows()),
+            headers,
+        }
+        .make_with_tag(tag)
+    }
+}
+
+#[derive(Debug)]
+struct DiscloseGroupWidget {
+    sections: WidgetRef,
+    headers: Vec<WidgetId>,
+}
+
+impl WrapperWidget for DiscloseGroupWidget {
+    fn child_mut(&mut self) -> &mut WidgetRef {
+        &mut self.sections
+    }
+
+    fn keyboard_input(
+        &mut self,
+        _device_id: DeviceId,
+        input: KeyEvent,
+        _is_synthetic: bool,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        // Only navigate when one of this group's headers is focused. Nested
+        // groups and the contents of each section receive key events first.
+        let Some(focused) = self.headers.iter().position(|header| {
+            context
+                .for_other(header)
+                .map_or(false, |header| header.focused(false))
+        }) else {
+            return IGNORED;
+        };
+
+        let last = self.headers.len() - 1;
+        let target = match input.logical_key {
+            Key::Named(NamedKey::ArrowUp) => focused.saturating_sub(1),
+            Key::Named(NamedKey::ArrowDown) => (focused + 1).min(last),
+            Key::Named(NamedKey::Home) => 0,
+            Key::Named(NamedKey::End) => last,
+            _ => return IGNORED,
+        };
+
+        if input.state.is_pressed() && target != focused {
+            if let Some(mut header) = context.for_other(&self.headers[target]) {
+                header.focus();
+            }
+        }
+
+        HANDLED
+    }
+}
+
 #[derive(Debug)]
 struct DiscloseIndicator {
     label: Option<WidgetRef>,
@@ -358,3 +561,64 @@ define_components! {
         IndicatorSize(Dimension, "size", @LineHeight)
     }
 }
+
+#[test]
+fn accordion_collapses_siblings() {
+    let expanded = Dynamic::new(None);
+    let group = GroupExpansion::Single(expanded.clone());
+    let first = group.collapsed(0);
+    let second = group.collapsed(1);
+
+    first.set(false);
+    assert_eq!(expanded.get(), Some(0));
+    second.set(false);
+    assert_eq!(expanded.get(), Some(1));
+    assert!(first.get());
+    second.set(true);
+    assert_eq!(expanded.get(), None);
+
+    let expanded = Dynamic::new(BTreeSet::new());
+    let group = GroupExpansion::Multiple(expanded.clone());
+    let first = group.collapsed(0);
+    let second = group.collapsed(1);
+    first.set(false);
+    second.set(false);
+    assert_eq!(expanded.get(), BTreeSet::from([0, 1]));
+    first.set(true);
+    assert_eq!(expanded.get(), BTreeSet::from([1]));
+}
+
+#[test]
+fn sections_seed_group_expansion() {
+    use crate::widgets::Space;
+
+    let first = Dynamic::new(true);
+    let second = Dynamic::new(false);
+    let third = Dynamic::new(false);
+    let expanded = Dynamic::new(None);
+    let _group = DiscloseGroup::new([
+        Disclose::new(Space::clear()).collapsed(first.clone()),
+        Disclose::new(Space::clear()).collapsed(second.clone()),
+        Disclose::new(Space::clear()).collapsed(third.clone()),
+    ])
+    .expanded(expanded.clone())
+    .make_widget();
+
+    assert_eq!(expanded.get(), Some(1));
+    // Only one section can be expanded, so the third section is collapsed.
+    assert!(third.get());
+
+    expanded.set(Some(0));
+    assert!(!first.get());
+    assert!(second.get());
+
+    let expanded = Dynamic::new(BTreeSet::new());
+    let _group = DiscloseGroup::new([
+        Disclose::new(Space::clear()).collapsed(false),
+        Disclose::new(Space::clear()),
+        Disclose::new(Space::clear()).collapsed(false),
+    ])
+    .expanded_set(expanded.clone())
+    .make_widget();
+    assert_eq!(expanded.get(), BTreeSet::from([0, 2]));
+}
@@ -0,0 +1,1217 @@
+//! An in-app browser for files and directories.
+//!
+//! [`FileBrowser`] lists the contents of a directory and lets the user
+//! navigate the file system and select files. [`FileDialog`] presents a
+//! [`FileBrowser`] in a [`Modal`] layer as an open or save dialog, which is
+//! useful on platforms without native file dialogs.
+use std::cmp::Ordering;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf, MAIN_SEPARATOR};
+use std::thread::JoinHandle;
+use std::time::SystemTime;
+
+use figures::units::{Lp, Px, UPx};
+use figures::{IntoSigned, IntoUnsigned, Point, Rect, Round, ScreenScale, Size, Zero};
+use intentional::Cast;
+use kludgine::app::winit::event::MouseButton;
+use kludgine::app::winit::keyboard::{Key, NamedKey};
+use kludgine::shapes::{Shape, StrokeOptions};
+use kludgine::text::{Text, TextOrigin};
+use kludgine::{Color, DrawableExt};
+
+use crate::animation::ZeroToOne;
+use crate::context::{EventContext, GraphicsContext, LayoutContext};
+use crate::styles::components::{HighlightColor, IntrinsicPadding, OutlineColor, TextColor};
+use crate::utils::ModifiersExt;
+use crate::value::{Destination, Dynamic, IntoDynamic, MapEach, Source, WeakDynamic};
+use crate::widget::{
+    Callback, EventHandling, MakeWidget, MakeWidgetWithTag, OnceCallback, Widget, WidgetInstance,
+    WidgetList, WidgetTag, HANDLED, IGNORED,
+};
+use crate::widgets::button::ButtonKind;
+use crate::widgets::calendar::{Date, Time};
+use crate::widgets::checkbox::Checkable;
+use crate::widgets::input::InputValue;
+use crate::widgets::layers::Modal;
+use crate::widgets::progress::{Progress, ProgressBar};
+use crate::widgets::selection::ClickCounter;
+use crate::widgets::{Button, Custom, Data, Select, Switcher};
+use crate::window::{DeviceId, KeyEvent};
+use crate::ConstraintLimit;
+
+/// A widget that lists the contents of a directory.
+///
+/// Each entry is shown with its name, size, and modification time.
+/// Directories are listed first and can be opened by double clicking them or
+/// pressing Enter. The path bar above the list navigates to any ancestor
+/// directory, and Backspace navigates to the parent directory.
+///
+/// Directories are read on a background thread. The progress of reading a
+/// directory is displayed beneath the list and can be observed using
+/// [`progress()`](Self::progress).
+#[derive(Debug)]
+pub struct FileBrowser {
+    directory: Dynamic<PathBuf>,
+    selection: Dynamic<Vec<PathBuf>>,
+    filters: Vec<FileFilter>,
+    filter: Dynamic<usize>,
+    show_hidden: Dynamic<bool>,
+    progress: Dynamic<Progress>,
+    multiple: bool,
+    on_open: Option<Callback<Vec<PathBuf>>>,
+}
+
+impl FileBrowser {
+    /// Returns a browser that lists the contents of `directory`.
+    ///
+    /// `directory` is updated as the user navigates.
+    #[must_use]
+    pub fn new(directory: impl IntoDynamic<PathBuf>) -> Self {
+        Self {
+            directory: directory.into_dynamic(),
+            selection: Dynamic::default(),
+            filters: Vec::new(),
+            filter: Dynamic::new(0),
+            show_hidden: Dynamic::new(false),
+            progress: Dynamic::new(Progress::Indeterminant),
+            multiple: false,
+            on_open: None,
+        }
+    }
+
+    /// Stores the paths of the selected entries in `selection` and returns
+    /// self.
+    ///
+    /// The selection is cleared each time the directory changes.
+    #[must_use]
+    pub fn selection(mut self, selection: impl IntoDynamic<Vec<PathBuf>>) -> Self {
+        self.selection = selection.into_dynamic();
+        self
+    }
+
+    /// Allows selecting multiple entries and returns self.
+    ///
+    /// Holding the primary modifier toggles entries, and holding shift selects
+    /// a range of entries.
+    #[must_use]
+    pub fn multiple(mut self) -> Self {
+        self.multiple = true;
+        self
+    }
+
+    /// Adds `filter` to the list of filters the user can choose between and
+    /// returns self.
+    ///
+    /// The first filter added is active by default. Filters only apply to
+    /// files: directories are always listed.
+    #[must_use]
+    pub fn filter(mut self, filter: FileFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Uses `index` to store the index of the active filter and returns self.
+    #[must_use]
+    pub fn active_filter(mut self, index: impl IntoDynamic<usize>) -> Self {
+        self.filter = index.into_dynamic();
+        self
+    }
+
+    /// Lists hidden entries when `show_hidden` is true and returns self.
+    ///
+    /// Hidden entries are not listed by default.
+    #[must_use]
+    pub fn show_hidden(mut self, show_hidden: impl IntoDynamic<bool>) -> Self {
+        self.show_hidden = show_hidden.into_dynamic();
+        self
+    }
+
+    /// Reports the progress of reading directories to `progress` and returns
+    /// self.
+    #[must_use]
+    pub fn progress(mut self, progress: impl IntoDynamic<Progress>) -> Self {
+        self.progress = progress.into_dynamic();
+        self
+    }
+
+    /// Invokes `on_open` with the selected paths when a file is opened by
+    /// double clicking it or pressing Enter, and returns self.
+    #[must_use]
+    pub fn on_open<F>(mut self, on_open: F) -> Self
+    where
+        F: FnMut(Vec<PathBuf>) + Send + 'static,
+    {
+        self.on_open = Some(Callback::new(on_open));
+        self
+    }
+}
+
+impl MakeWidgetWithTag for FileBrowser {
+    fn make_with_tag(self, id: WidgetTag) -> WidgetInstance {
+        let listing = Dynamic::new(Listing::Loading);
+        spawn_load(
+            self.directory.get(),
+            self.directory.downgrade(),
+            listing.clone(),
+            self.progress.clone(),
+        );
+        let directory_changed = self.directory.for_each({
+            let weak_directory = self.directory.downgrade();
+            let listing = listing.clone();
+            let progress = self.progress.clone();
+            let selection = self.selection.clone();
+            move |path| {
+                selection.set(Vec::new());
+                spawn_load(
+                    path.clone(),
+                    weak_directory.clone(),
+                    listing.clone(),
+                    progress.clone(),
+                );
+            }
+        });
+
+        let filters = self.filters.clone();
+        let entries = (&listing, &self.filter, &self.show_hidden).map_each(
+            move |(listing, filter, show_hidden)| match listing {
+                Listing::Loaded(entries) => entries
+                    .iter()
+                    .filter(|entry| {
+                        (*show_hidden || !entry.is_hidden())
+                            && (entry.is_directory
+                                || filters
+                                    .get(*filter)
+                                    .map_or(true, |filter| filter.matches(&entry.name)))
+                    })
+                    .cloned()
+                    .collect(),
+                Listing::Loading | Listing::Failed(_) => Vec::new(),
+            },
+        );
+        let status = (&listing, &entries).map_each(|(listing, entries)| match listing {
+            Listing::Loading => String::from("Loading..."),
+            Listing::Failed(error) => error.clone(),
+            Listing::Loaded(_) if entries.len() == 1 => String::from("1 item"),
+            Listing::Loaded(_) => format!("{} items", entries.len()),
+        });
+        let loaded = listing.map_each(|listing| !matches!(listing, Listing::Loading));
+
+        let path_bar = Button::new("\u{2191}")
+            .on_click({
+                let directory = self.directory.clone();
+                move |()| navigate_to_parent(&directory)
+            })
+            .and(breadcrumbs(&self.directory))
+            .into_columns();
+
+        let list = FileList {
+            entries,
+            directory: self.directory.clone(),
+            selection: self.selection,
+            multiple: self.multiple,
+            on_open: self.on_open,
+            cursor: None,
+            anchor: None,
+            clicks: ClickCounter::default(),
+            row_height: Px::ZERO,
+            padding: Px::ZERO,
+            size_width: Px::ZERO,
+            modified_width: Px::ZERO,
+        }
+        .vertical_scroll()
+        .contain()
+        .expand();
+
+        let mut footer = WidgetList::new();
+        if self.filters.len() > 1 {
+            for (index, filter) in self.filters.iter().enumerate() {
+                footer.push(Select::new(index, self.filter.clone(), filter.name.clone()));
+            }
+        }
+        let footer = footer
+            .and(self.show_hidden.into_checkbox("Show hidden"))
+            .and(status.expand_horizontally())
+            .and(
+                ProgressBar::new(self.progress)
+                    .width(Lp::inches(1))
+                    .collapse_horizontally(loaded),
+            )
+            .into_columns();
+
+        let directory = self.directory;
+        let browser = Custom::new(path_bar.and(list).and(footer).into_rows()).on_keyboard_input(
+            move |_device_id, input: KeyEvent, _is_synthetic, _context| {
+                if input.logical_key == Key::Named(NamedKey::Backspace) {
+                    if input.state.is_pressed() {
+                        navigate_to_parent(&directory);
+                    }
+                    HANDLED
+                } else {
+                    IGNORED
+                }
+            },
+        );
+
+        Data::new_wrapping(directory_changed, browser).make_with_tag(id)
+    }
+}
+
+fn navigate_to_parent(directory: &Dynamic<PathBuf>) {
+    let parent = directory.map_ref(|directory| directory.parent().map(Path::to_path_buf));
+    if let Some(parent) = parent {
+        directory.set(parent);
+    }
+}
+
+fn breadcrumbs(directory: &Dynamic<PathBuf>) -> impl MakeWidget {
+    Switcher::mapping(directory.clone(), |path, directory| {
+        let mut ancestors = path
+            .ancestors()
+            .filter(|ancestor| !ancestor.as_os_str().is_empty())
+            .collect::<Vec<_>>();
+        ancestors.reverse();
+        ancestors
+            .into_iter()
+            .map(|ancestor| {
+                let label = ancestor.file_name().map_or_else(
+                    || ancestor.display().to_string(),
+                    |name| name.to_string_lossy().into_owned(),
+                );
+                let ancestor = ancestor.to_path_buf();
+                let directory = directory.clone();
+                Button::new(label)
+                    .kind(ButtonKind::Transparent)
+                    .on_click(move |()| directory.set(ancestor.clone()))
+            })
+            .collect::<WidgetList>()
+            .into_columns()
+            .make_widget()
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Listing {
+    Loading,
+    Loaded(Vec<FileEntry>),
+    Failed(String),
+}
+
+fn spawn_load(
+    path: PathBuf,
+    directory: WeakDynamic<PathBuf>,
+    listing: Dynamic<Listing>,
+    progress: Dynamic<Progress>,
+) -> JoinHandle<()> {
+    listing.set(Listing::Loading);
+    std::thread::spawn(move || {
+        // Ignore the progress and result if the browser has navigated elsewhere
+        // while this directory was being read.
+        let is_current = || {
+            directory.upgrade().map_or(false, |directory| {
+                directory.map_ref(|current| current == &path)
+            })
+        };
+        let report = |value| {
+            if is_current() {
+                progress.set(value);
+            }
+        };
+
+        let result = match read_directory(&path, report) {
+            Ok(entries) => Listing::Loaded(entries),
+            Err(err) => Listing::Failed(format!("{}: {err}", path.display())),
+        };
+
+        if is_current() {
+            progress.set(Progress::Percent(ZeroToOne::ONE));
+            listing.set(result);
+        }
+    })
+}
+
+/// Reads the entries of `path`, sorted with directories first, invoking
+/// `report` as progress is made.
+fn read_directory(path: &Path, report: impl Fn(Progress)) -> io::Result<Vec<FileEntry>> {
+    report(Progress::Indeterminant);
+    let dir_entries = fs::read_dir(path)?
+        .filter_map(Result::ok)
+        .collect::<Vec<_>>();
+    let total = dir_entries.len().cast::<f32>();
+
+    let mut entries = Vec::with_capacity(dir_entries.len());
+    for (index, entry) in dir_entries.into_iter().enumerate() {
+        let path = entry.path();
+        // Follow symbolic links when possible so that links to directories
+        // can be navigated into.
+        let metadata = fs::metadata(&path).or_else(|_| entry.metadata()).ok();
+        let is_directory = metadata.as_ref().map_or(false, fs::Metadata::is_dir);
+        entries.push(FileEntry {
+            name: entry.file_name().to_string_lossy().into_owned(),
+            size: metadata
+                .as_ref()
+                .filter(|metadata| !metadata.is_dir())
+                .map(fs::Metadata::len),
+            modified: metadata.and_then(|metadata| metadata.modified().ok()),
+            is_directory,
+            path,
+        });
+
+        if index % 64 == 0 {
+            report(Progress::Percent(ZeroToOne::new(
+                index.cast::<f32>() / total,
+            )));
+        }
+    }
+    entries.sort_by(compare_entries);
+    Ok(entries)
+}
+
+fn compare_entries(a: &FileEntry, b: &FileEntry) -> Ordering {
+    b.is_directory
+        .cmp(&a.is_directory)
+        .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        .then_with(|| a.name.cmp(&b.name))
+}
+
+/// An entry in a directory listed by a [`FileBrowser`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEntry {
+    /// The full path of this entry.
+    pub path: PathBuf,
+    /// The file name of this entry.
+    pub name: String,
+    /// True if this entry is a directory.
+    pub is_directory: bool,
+    /// The size of this entry in bytes, if it is a file.
+    pub size: Option<u64>,
+    /// The time this entry was last modified, if available.
+    pub modified: Option<SystemTime>,
+}
+
+impl FileEntry {
+    /// Returns true if this entry's name begins with a `.`.
+    #[must_use]
+    pub fn is_hidden(&self) -> bool {
+        self.name.starts_with('.')
+    }
+}
+
+/// A named set of patterns used to limit which files a [`FileBrowser`]
+/// lists.
+///
+/// Patterns are matched against file names without regard to case. A `*`
+/// matches any number of characters, and a `?` matches exactly one character.
+///
+/// ```rust
+/// use cushy::widgets::file_browser::FileFilter;
+///
+/// let images = FileFilter::extensions("Images", ["png", "jpg"]);
+/// assert!(images.matches("photo.JPG"));
+/// assert!(!images.matches("notes.txt"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileFilter {
+    name: String,
+    patterns: Vec<String>,
+}
+
+impl FileFilter {
+    /// Returns a filter named `name` that lists files matching any of
+    /// `patterns`.
+    pub fn new<Pattern>(
+        name: impl Into<String>,
+        patterns: impl IntoIterator<Item = Pattern>,
+    ) -> Self
+    where
+        Pattern: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            patterns: patterns.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Returns a filter named `name` that lists files ending with any of
+    /// `extensions`.
+    ///
+    /// Extensions should not include the leading `.`.
+    pub fn extensions<Extension>(
+        name: impl Into<String>,
+        extensions: impl IntoIterator<Item = Extension>,
+    ) -> Self
+    where
+        Extension: AsRef<str>,
+    {
+        Self::new(
+            name,
+            extensions
+                .into_iter()
+                .map(|extension| format!("*.{}", extension.as_ref())),
+        )
+    }
+
+    /// Returns a filter that lists all files.
+    #[must_use]
+    pub fn all() -> Self {
+        Self::new("All files", ["*"])
+    }
+
+    /// Returns the name of this filter.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns true if `file_name` matches any of this filter's patterns.
+    #[must_use]
+    pub fn matches(&self, file_name: &str) -> bool {
+        self.patterns
+            .iter()
+            .any(|pattern| glob_matches(pattern, file_name))
+    }
+}
+
+/// Returns true if `name` matches `pattern`, ignoring case.
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern = pattern
+        .chars()
+        .flat_map(char::to_lowercase)
+        .collect::<Vec<_>>();
+    let name = name
+        .chars()
+        .flat_map(char::to_lowercase)
+        .collect::<Vec<_>>();
+
+    // The position to resume from after a mismatch: the pattern index after
+    // the most recent `*`, and the name index it is currently matched up to.
+    let mut resume = None;
+    let (mut p, mut n) = (0, 0);
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                p += 1;
+                resume = Some((p, n));
+            }
+            Some('?') => {
+                p += 1;
+                n += 1;
+            }
+            Some(ch) if *ch == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => {
+                let Some((resume_p, resume_n)) = resume else {
+                    return false;
+                };
+                p = resume_p;
+                n = resume_n + 1;
+                resume = Some((resume_p, n));
+            }
+        }
+    }
+    pattern[p..].iter().all(|ch| *ch == '*')
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes.cast::<f64>() / 1024.;
+    let mut unit = 0;
+    while value >= 1024. && unit < UNITS.len() - 1 {
+        value /= 1024.;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+fn format_modified(modified: SystemTime) -> String {
+    let time = Time::local(modified);
+    format!(
+        "{} {:02}:{:02}",
+        Date::local(modified),
+        time.hour(),
+        time.minute()
+    )
+}
+
+#[derive(Debug)]
+struct FileList {
+    entries: Dynamic<Vec<FileEntry>>,
+    directory: Dynamic<PathBuf>,
+    selection: Dynamic<Vec<PathBuf>>,
+    multiple: bool,
+    on_open: Option<Callback<Vec<PathBuf>>>,
+    cursor: Option<usize>,
+    anchor: Option<usize>,
+    clicks: ClickCounter,
+    row_height: Px,
+    padding: Px,
+    size_width: Px,
+    modified_width: Px,
+}
+
+impl FileList {
+    /// Returns the index of the entry at `y`, if any.
+    fn entry_at(&self, y: Px) -> Option<usize> {
+        if self.row_height <= 0 || y < self.row_height {
+            return None;
+        }
+        let index = usize::try_from(y.get() / self.row_height.get() - 1).ok()?;
+        (index < self.entries.map_ref(Vec::len)).then_some(index)
+    }
+
+    fn select(&mut self, index: usize, extend: bool, toggle: bool) {
+        let Some(path) = self
+            .entries
+            .map_ref(|entries| entries.get(index).map(|entry| entry.path.clone()))
+        else {
+            return;
+        };
+        self.cursor = Some(index);
+
+        match (self.multiple, self.anchor) {
+            (true, Some(anchor)) if extend => {
+                let range = anchor.min(index)..=anchor.max(index);
+                let paths = self.entries.map_ref(|entries| {
+                    entries[range]
+                        .iter()
+                        .map(|entry| entry.path.clone())
+                        .collect()
+                });
+                self.selection.set(paths);
+            }
+            (true, _) if toggle => {
+                self.selection.map_mut(|mut selection| {
+                    if let Some(existing) = selection.iter().position(|selected| selected == &path)
+                    {
+                        selection.remove(existing);
+                    } else {
+                        selection.push(path);
+                    }
+                });
+                self.anchor = Some(index);
+            }
+            _ => {
+                self.selection.set(vec![path]);
+                self.anchor = Some(index);
+            }
+        }
+    }
+
+    fn activate(&mut self, index: usize) {
+        let Some(entry) = self.entries.map_ref(|entries| entries.get(index).cloned()) else {
+            return;
+        };
+
+        if entry.is_directory {
+            self.cursor = None;
+            self.anchor = None;
+            self.directory.set(entry.path);
+        } else if let Some(on_open) = &mut self.on_open {
+            let mut paths = self.selection.get();
+            if !paths.contains(&entry.path) {
+                paths = vec![entry.path];
+            }
+            on_open.invoke(paths);
+        }
+    }
+
+    fn draw_text(
+        text: &str,
+        origin: Point<Px>,
+        clip: Rect<Px>,
+        color: Color,
+        context: &mut GraphicsContext<'_, '_, '_, '_>,
+    ) {
+        let mut gfx = context.gfx.clipped_to(clip);
+        let text = gfx.measure_text(Text::<Px>::new(text, color));
+        gfx.draw_measured_text(text.translate_by(origin - clip.origin), TextOrigin::TopLeft);
+    }
+}
+
+impl Widget for FileList {
+    #[allow(clippy::too_many_lines)]
+    fn redraw(&mut self, context: &mut GraphicsContext<'_, '_, '_, '_>) {
+        context.redraw_when_changed(&self.entries);
+        let selection = self.selection.get_tracking_redraw(context);
+        let focused = context.focused(true);
+        let text_color = context.get(&TextColor);
+        let highlight_color = context.get(&HighlightColor);
+        let outline_color = context.get(&OutlineColor);
+        let line_width = Lp::points(1).into_px(context.gfx.scale()).round();
+        context.apply_current_font_settings();
+
+        let region = context.gfx.region();
+        let width = region.size.width;
+        let row_height = self.row_height;
+        let half_padding = self.padding / 2;
+        let name_width = (width - self.size_width - self.modified_width).max(Px::ZERO);
+        let size_column = Rect::new(
+            Point::new(name_width, Px::ZERO),
+            Size::new(self.size_width, row_height),
+        );
+        let modified_column = Rect::new(
+            Point::new(name_width + self.size_width, Px::ZERO),
+            Size::new(self.modified_width, row_height),
+        );
+        let name_column = Rect::new(Point::ZERO, Size::new(name_width, row_height));
+
+        for (label, column) in [
+            ("Name", name_column),
+            ("Size", size_column),
+            ("Modified", modified_column),
+        ] {
+            Self::draw_text(
+                label,
+                column.origin + Point::new(self.padding, half_padding),
+                column,
+                text_color,
+                context,
+            );
+        }
+        context.gfx.draw_shape(&Shape::filled_rect(
+            Rect::new(
+                Point::new(Px::ZERO, row_height - line_width),
+                Size::new(width, line_width),
+            ),
+            outline_color,
+        ));
+
+        // Only draw the rows that are visible, as directories can contain a
+        // large number of entries.
+        if row_height <= 0 {
+            return;
+        }
+        let clip = context.gfx.clip_rect().into_signed();
+        let visible_top = clip.origin.y - region.origin.y;
+        let first = usize::try_from(visible_top.get() / row_height.get() - 1).unwrap_or(0);
+        let visible_rows =
+            usize::try_from(clip.size.height.get() / row_height.get() + 2).unwrap_or_default();
+
+        let cursor = self.cursor;
+        let padding = self.padding;
+        let size_width = self.size_width;
+        self.entries.map_ref(|entries| {
+            for (index, entry) in entries.iter().enumerate().skip(first).take(visible_rows) {
+                let top = row_height * (index + 1).cast::<i32>();
+                let row = Rect::new(Point::new(Px::ZERO, top), Size::new(width, row_height));
+                if selection.contains(&entry.path) {
+                    context
+                        .gfx
+                        .draw_shape(&Shape::filled_rect(row, highlight_color));
+                }
+                if focused && cursor == Some(index) {
+                    context.gfx.draw_shape(&Shape::stroked_rect(
+                        row.inset(line_width / 2),
+                        StrokeOptions::px_wide(line_width).colored(outline_color),
+                    ));
+                }
+
+                let text_top = top + half_padding;
+                let name = if entry.is_directory {
+                    format!("{}{MAIN_SEPARATOR}", entry.name)
+                } else {
+                    entry.name.clone()
+                };
+                Self::draw_text(
+                    &name,
+                    Point::new(padding, text_top),
+                    Rect::new(Point::new(Px::ZERO, top), name_column.size),
+                    text_color,
+                    context,
+                );
+
+                if let Some(size) = entry.size {
+                    let size = format_size(size);
+                    let measured = context.gfx.measure_text(Text::<Px>::new(&size, text_color));
+                    context.gfx.draw_measured_text(
+                        measured.translate_by(Point::new(
+                            name_width + size_width - padding - measured.size.width,
+                            text_top,
+                        )),
+                        TextOrigin::TopLeft,
+                    );
+                }
+
+                if let Some(modified) = entry.modified {
+                    Self::draw_text(
+                        &format_modified(modified),
+                        Point::new(modified_column.origin.x + padding, text_top),
+                        Rect::new(
+                            Point::new(modified_column.origin.x, top),
+                            modified_column.size,
+                        ),
+                        text_color,
+                        context,
+                    );
+                }
+            }
+        });
+    }
+
+    fn layout(
+        &mut self,
+        available_space: Size<ConstraintLimit>,
+        context: &mut LayoutContext<'_, '_, '_, '_>,
+    ) -> Size<UPx> {
+        context.apply_current_font_settings();
+        self.padding = context
+            .get(&IntrinsicPadding)
+            .into_px(context.gfx.scale())
+            .round();
+        let measure = |text: &str, context: &mut LayoutContext<'_, '_, '_, '_>| {
+            context
+                .gfx
+                .measure_text(Text::<Px>::new(text, Color::WHITE))
+                .size
+        };
+        let line = measure("Modified", context);
+        self.row_height = line.height + self.padding;
+        self.size_width = measure("0000.0 KiB", context).width + self.padding * 2;
+        self.modified_width = measure("0000-00-00 00:00", context).width + self.padding * 2;
+
+        context.invalidate_when_changed(&self.entries);
+        let rows = self.entries.map_ref(Vec::len) + 1;
+        let height = self.row_height * rows.cast::<i32>();
+        let minimum_width = self.size_width + self.modified_width + line.width * 2;
+
+        Size::new(
+            available_space
+                .width
+                .fit_measured(minimum_width, context.gfx.scale()),
+            height.into_unsigned(),
+        )
+    }
+
+    fn hit_test(&mut self, _location: Point<Px>, _context: &mut EventContext<'_>) -> bool {
+        true
+    }
+
+    fn accept_focus(&mut self, context: &mut EventContext<'_>) -> bool {
+        context.enabled()
+    }
+
+    fn focus(&mut self, context: &mut EventContext<'_>) {
+        context.set_needs_redraw();
+    }
+
+    fn blur(&mut self, context: &mut EventContext<'_>) {
+        context.set_needs_redraw();
+    }
+
+    fn mouse_down(
+        &mut self,
+        location: Point<Px>,
+        _device_id: DeviceId,
+        button: MouseButton,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        if button != MouseButton::Left {
+            return IGNORED;
+        }
+
+        context.focus();
+        let Some(index) = self.entry_at(location.y) else {
+            return HANDLED;
+        };
+
+        if self.clicks.click(location) == 2 {
+            self.activate(index);
+        } else {
+            let modifiers = context.modifiers().state();
+            self.select(index, modifiers.shift_key(), modifiers.primary());
+        }
+        context.set_needs_redraw();
+        HANDLED
+    }
+
+    fn keyboard_input(
+        &mut self,
+        _device_id: DeviceId,
+        input: KeyEvent,
+        _is_synthetic: bool,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        let count = self.entries.map_ref(Vec::len);
+        let cursor = self.cursor.filter(|cursor| *cursor < count);
+        let next = match (&input.logical_key, cursor) {
+            (Key::Named(NamedKey::Enter), Some(cursor)) => {
+                if input.state.is_pressed() {
+                    self.activate(cursor);
+                }
+                return HANDLED;
+            }
+            _ if count == 0 => return IGNORED,
+            (Key::Named(NamedKey::ArrowUp), cursor) => cursor.map_or(0, |c| c.saturating_sub(1)),
+            (Key::Named(NamedKey::ArrowDown), cursor) => {
+                cursor.map_or(0, |c| (c + 1).min(count - 1))
+            }
+            (Key::Named(NamedKey::Home), _) => 0,
+            (Key::Named(NamedKey::End), _) => count - 1,
+            _ => return IGNORED,
+        };
+
+        if input.state.is_pressed() {
+            let modifiers = context.modifiers().state();
+            if modifiers.primary() && self.multiple {
+                self.cursor = Some(next);
+            } else {
+                self.select(next, modifiers.shift_key(), false);
+            }
+            context.set_needs_redraw();
+        }
+        HANDLED
+    }
+}
+
+/// A modal dialog for choosing files to open or a location to save a file.
+///
+/// ```rust,no_run
+/// use cushy::widgets::file_browser::{FileDialog, FileFilter};
+/// use cushy::widgets::Modal;
+///
+/// let modal = Modal::new();
+/// FileDialog::open()
+///     .filter(FileFilter::extensions("Text", ["txt", "md"]))
+///     .on_confirm(|paths| println!("Opening {paths:?}"))
+///     .show_in(&modal);
+/// ```
+#[derive(Debug)]
+pub struct FileDialog {
+    save: bool,
+    title: String,
+    directory: PathBuf,
+    file_name: String,
+    filters: Vec<FileFilter>,
+    multiple: bool,
+    on_confirm: Option<OnceCallback<Vec<PathBuf>>>,
+    on_cancel: Option<OnceCallback>,
+}
+
+impl FileDialog {
+    /// Returns a dialog for choosing existing files to open.
+    #[must_use]
+    pub fn open() -> Self {
+        Self::new(false, "Open")
+    }
+
+    /// Returns a dialog for choosing the location to save a file to.
+    #[must_use]
+    pub fn save() -> Self {
+        Self::new(true, "Save")
+    }
+
+    fn new(save: bool, title: &str) -> Self {
+        Self {
+            save,
+            title: title.to_string(),
+            directory: std::env::current_dir().unwrap_or_default(),
+            file_name: String::new(),
+            filters: Vec::new(),
+            multiple: false,
+            on_confirm: None,
+            on_cancel: None,
+        }
+    }
+
+    /// Sets the dialog's title and returns self.
+    #[must_use]
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Sets the directory the dialog initially lists and returns self.
+    ///
+    /// The current working directory is listed by default.
+    #[must_use]
+    pub fn directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = directory.into();
+        self
+    }
+
+    /// Sets the initial file name of a save dialog and returns self.
+    #[must_use]
+    pub fn file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = file_name.into();
+        self
+    }
+
+    /// Adds `filter` to the filters the user can choose between and returns
+    /// self.
+    #[must_use]
+    pub fn filter(mut self, filter: FileFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Allows an open dialog to choose multiple files and returns self.
+    #[must_use]
+    pub fn multiple(mut self) -> Self {
+        self.multiple = true;
+        self
+    }
+
+    /// Invokes `on_confirm` with the chosen paths when the dialog is
+    /// confirmed, and returns self.
+    ///
+    /// Save dialogs always provide a single path, which may not exist yet.
+    #[must_use]
+    pub fn on_confirm<F>(mut self, on_confirm: F) -> Self
+    where
+        F: FnOnce(Vec<PathBuf>) + Send + 'static,
+    {
+        self.on_confirm = Some(OnceCallback::new(on_confirm));
+        self
+    }
+
+    /// Invokes `on_cancel` when the dialog is dismissed without choosing a
+    /// path, and returns self.
+    #[must_use]
+    pub fn on_cancel<F>(mut self, on_cancel: F) -> Self
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.on_cancel = Some(OnceCallback::new(move |()| on_cancel()));
+        self
+    }
+
+    /// Presents this dialog in `modal`.
+    pub fn show_in(self, modal: &Modal) {
+        let directory = Dynamic::new(self.directory);
+        let selection = Dynamic::<Vec<PathBuf>>::default();
+        let file_name = Dynamic::new(self.file_name);
+        let on_confirm = Dynamic::new(self.on_confirm);
+        let on_cancel = Dynamic::new(self.on_cancel);
+
+        let confirm = {
+            let modal = modal.clone();
+            move |paths: Vec<PathBuf>| {
+                modal.dismiss();
+                if let Some(on_confirm) = on_confirm.take() {
+                    on_confirm.invoke(paths);
+                }
+            }
+        };
+
+        let mut browser = FileBrowser::new(directory.clone()).selection(selection.clone());
+        for filter in self.filters {
+            browser = browser.filter(filter);
+        }
+
+        let (browser, name_row, can_confirm) = if self.save {
+            // Selecting a file fills in its name so that it can be replaced.
+            let name_callback = selection.for_each({
+                let file_name = file_name.clone();
+                move |selection: &Vec<PathBuf>| {
+                    if let [selected] = selection.as_slice() {
+                        if !selected.is_dir() {
+                            if let Some(name) = selected.file_name() {
+                                file_name.set(name.to_string_lossy().into_owned());
+                            }
+                        }
+                    }
+                }
+            });
+            let browser = browser.on_open({
+                let confirm = confirm.clone();
+                move |mut paths| {
+                    paths.truncate(1);
+                    confirm(paths);
+                }
+            });
+            let can_confirm = file_name.map_each(|name| !name.trim().is_empty());
+            (
+                Data::new_wrapping(name_callback, browser.make_widget()).make_widget(),
+                Some(
+                    "Name"
+                        .and(file_name.clone().into_input().expand_horizontally())
+                        .into_columns()
+                        .make_widget(),
+                ),
+                can_confirm,
+            )
+        } else {
+            let browser = if self.multiple {
+                browser.multiple()
+            } else {
+                browser
+            };
+            let browser = browser.on_open(confirm.clone());
+            let can_confirm = selection.map_each(|selection| !selection.is_empty());
+            (browser.make_widget(), None, can_confirm)
+        };
+
+        let confirm_button = Button::new(if self.save { "Save" } else { "Open" })
+            .on_click({
+                let save = self.save;
+                move |()| {
+                    if save {
+                        let name = file_name.map_ref(|name| name.trim().to_string());
+                        if !name.is_empty() {
+                            let path = directory.map_ref(|directory| directory.join(name));
+                            confirm(vec![path]);
+                        }
+                    } else {
+                        let selected = selection.get();
+                        if let [only] = selected.as_slice() {
+                            if only.is_dir() {
+                                directory.set(only.clone());
+                                return;
+                            }
+                        }
+                        let files = selected
+                            .into_iter()
+                            .filter(|path| !path.is_dir())
+                            .collect::<Vec<_>>();
+                        if !files.is_empty() {
+                            confirm(files);
+                        }
+                    }
+                }
+            })
+            .with_enabled(can_confirm);
+        let cancel_button = Button::new("Cancel").kind(ButtonKind::Outline).on_click({
+            let modal = modal.clone();
+            move |()| {
+                modal.dismiss();
+                if let Some(on_cancel) = on_cancel.take() {
+                    on_cancel.invoke(());
+                }
+            }
+        });
+
+        let mut contents = WidgetList::new().and(self.title).and(browser.expand());
+        if let Some(name_row) = name_row {
+            contents.push(name_row);
+        }
+        let contents = contents
+            .and(
+                cancel_button
+                    .and(confirm_button)
+                    .into_columns()
+                    .align_right(),
+            )
+            .into_rows()
+            .width(Lp::inches(6))
+            .height(Lp::inches(4));
+
+        modal.present(contents);
+    }
+}
+
+#[test]
+fn glob_matching() {
+    assert!(glob_matches("*", "anything.txt"));
+    assert!(glob_matches("*", ""));
+    assert!(glob_matches("*.rs", "lib.rs"));
+    assert!(glob_matches("*.RS", "lib.rs"));
+    assert!(!glob_matches("*.rs", "lib.rsx"));
+    assert!(glob_matches("file?.txt", "file1.txt"));
+    assert!(!glob_matches("file?.txt", "file.txt"));
+    assert!(glob_matches("a*b*c", "aXXbYYbZc"));
+    assert!(!glob_matches("a*b*c", "aXXbYY"));
+    assert!(glob_matches("*.tar.gz", "archive.tar.gz"));
+}
+
+#[test]
+fn size_formatting() {
+    assert_eq!(format_size(0), "0 B");
+    assert_eq!(format_size(1023), "1023 B");
+    assert_eq!(format_size(1536), "1.5 KiB");
+    assert_eq!(format_size(5 * 1024 * 1024), "5.0 MiB");
+}
+
+#[test]
+fn entries_sort_directories_first() {
+    let entry = |name: &str, is_directory| FileEntry {
+        path: PathBuf::from(name),
+        name: name.to_string(),
+        is_directory,
+        size: None,
+        modified: None,
+    };
+    let mut entries = vec![
+        entry("b.txt", false),
+        entry("Zeta", true),
+        entry("A.txt", false),
+        entry("alpha", true),
+    ];
+    entries.sort_by(compare_entries);
+    let names = entries
+        .iter()
+        .map(|entry| entry.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["alpha", "Zeta", "A.txt", "b.txt"]);
+}
+
+#[test]
+fn loading_a_directory() {
+    let path = std::env::temp_dir().join(format!("cushy-file-browser-{}", std::process::id()));
+    fs::create_dir_all(path.join("nested")).unwrap();
+    fs::write(path.join("b.txt"), "hello").unwrap();
+    fs::write(path.join("a.txt"), "").unwrap();
+
+    let directory = Dynamic::new(path.clone());
+    let listing = Dynamic::new(Listing::Loading);
+    let progress = Dynamic::new(Progress::Indeterminant);
+    spawn_load(
+        path.clone(),
+        directory.downgrade(),
+        listing.clone(),
+        progress.clone(),
+    );
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while listing.get() == Listing::Loading && std::time::Instant::now() < deadline {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    let result = listing.get();
+    fs::remove_dir_all(&path).unwrap();
+
+    let Listing::Loaded(entries) = result else {
+        unreachable!("directory should load: {result:?}")
+    };
+    let summary = entries
+        .iter()
+        .map(|entry| (entry.name.as_str(), entry.is_directory, entry.size))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        [
+            ("nested", true, None),
+            ("a.txt", false, Some(0)),
+            ("b.txt", false, Some(5))
+        ]
+    );
+    assert_eq!(progress.get(), Progress::Percent(ZeroToOne::ONE));
+}
+
+#[test]
+fn stale_loads_are_ignored() {
+    let path = std::env::temp_dir().join(format!("cushy-stale-load-{}", std::process::id()));
+    fs::create_dir_all(&path).unwrap();
+    fs::write(path.join("a.txt"), "").unwrap();
+
+    // The browser has already navigated elsewhere.
+    let directory = Dynamic::new(path.join("elsewhere"));
+    let listing = Dynamic::new(Listing::Loading);
+    let progress = Dynamic::new(Progress::Percent(ZeroToOne::new(0.25)));
+    spawn_load(
+        path.clone(),
+        directory.downgrade(),
+        listing.clone(),
+        progress.clone(),
+    )
+    .join()
+    .unwrap();
+    fs::remove_dir_all(&path).unwrap();
+
+    assert_eq!(listing.get(), Listing::Loading);
+    assert_eq!(progress.get(), Progress::Percent(ZeroToOne::new(0.25)));
+}
@@ -1,13 +1,33 @@
 //! A widget that displays an image/texture.
 
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::io::{BufRead, BufReader, Cursor, Read, Seek};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+
 use figures::units::{Px, UPx};
-use figures::{FloatConversion, IntoSigned, IntoUnsigned, Point, Rect, Size, Zero};
-use kludgine::{AnyTexture, CollectedTexture, LazyTexture, SharedTexture, Texture, TextureRegion};
+use figures::{
+    FloatConversion, Fraction, IntoSigned, IntoUnsigned, Point, Rect, ScreenScale, Size, Zero,
+};
+use image::{DynamicImage, ImageError};
+use intentional::Assert;
+use kludgine::wgpu::FilterMode;
+use kludgine::{
+    AnyTexture, CollectedTexture, Color, LazyTexture, SharedTexture, Texture, TextureBlit,
+    TextureRegion,
+};
 
 use crate::animation::ZeroToOne;
-use crate::context::LayoutContext;
-use crate::value::{IntoValue, Source, Value};
-use crate::widget::Widget;
+use crate::context::{GraphicsContext, LayoutContext};
+use crate::styles::components::{ErrorColor, TextColor};
+use crate::styles::{Dimension, Edges};
+use crate::utils::{IgnorePoison, Lazy};
+use crate::value::{Destination, Dynamic, DynamicReader, IntoValue, Source, Value};
+use crate::widget::{MakeWidget, MakeWidgetWithTag, Widget, WidgetInstance, WidgetTag};
+use crate::widgets::progress::{Progress, ProgressBar};
+use crate::widgets::Switcher;
 use crate::ConstraintLimit;
 
 /// A widget that displays an image/texture.
@@ -17,6 +37,11 @@ pub struct Image {
     pub contents: Value<AnyTexture>,
     /// The scaling strategy to apply.
     pub scaling: Value<ImageScaling>,
+    /// The color to multiply the image's colors by. [`Color::WHITE`] draws the
+    /// image unchanged.
+    pub tint: Value<Color>,
+    /// The opacity to draw the image with.
+    pub opacity: Value<ZeroToOne>,
 }
 
 impl Image {
@@ -26,9 +51,36 @@ impl Image {
         Self {
             contents: contents.into_value(),
             scaling: Value::default(),
+            tint: Value::Constant(Color::WHITE),
+            opacity: Value::Constant(ZeroToOne::ONE),
         }
     }
 
+    /// Returns an image that loads the image file at `path` on a background
+    /// thread.
+    ///
+    /// The loaded texture is stored in the [shared](TextureCache::shared)
+    /// texture cache, allowing multiple images of the same path to share a
+    /// single texture. To use a different cache, use [`TextureCache::load`].
+    pub fn from_path(path: impl AsRef<Path>) -> AsyncImage {
+        TextureCache::shared().load(path)
+    }
+
+    /// Returns an image that decodes `bytes` on a background thread.
+    ///
+    /// The image format is detected from the contents of `bytes`.
+    pub fn from_bytes(bytes: impl AsRef<[u8]> + Send + 'static) -> AsyncImage {
+        AsyncImage::spawn(move || decode(Cursor::new(bytes)))
+    }
+
+    /// Returns an image that reads and decodes the contents of `reader` on a
+    /// background thread.
+    ///
+    /// The image format is detected from the contents read from `reader`.
+    pub fn from_reader(reader: impl Read + Seek + Send + 'static) -> AsyncImage {
+        AsyncImage::spawn(move || decode(BufReader::new(reader)))
+    }
+
     /// Applies the `scaling` strategies and returns self.
     #[must_use]
     pub fn scaling(mut self, scaling: impl IntoValue<ImageScaling>) -> Self {
@@ -110,44 +162,64 @@ impl Image {
         })
     }
 
+    /// Applies the nine-slice scaling strategy and returns self.
+    ///
+    /// The nine-slice scaling strategy stretches the image to fill the surface
+    /// while preserving the borders described by `edges`. See
+    /// [`ImageScaling::NineSlice`] for more information.
+    #[must_use]
+    pub fn nine_slice(self, edges: impl IntoValue<Edges<Dimension>>) -> Self {
+        self.scaling(match edges.into_value() {
+            Value::Constant(edges) => Value::Constant(ImageScaling::NineSlice(edges)),
+            Value::Dynamic(edges) => Value::Dynamic(edges.map_each_cloned(ImageScaling::NineSlice)),
+        })
+    }
+
+    /// Multiplies the image's colors by `tint` and returns self.
+    ///
+    /// Tinting with [`Color::WHITE`] draws the image unchanged.
+    #[must_use]
+    pub fn tint(mut self, tint: impl IntoValue<Color>) -> Self {
+        self.tint = tint.into_value();
+        self
+    }
+
+    /// Draws the image with `opacity` and returns self.
+    #[must_use]
+    pub fn opacity(mut self, opacity: impl IntoValue<ZeroToOne>) -> Self {
+        self.opacity = opacity.into_value();
+        self
+    }
+
     fn calculate_image_rect(
         &self,
         texture: &AnyTexture,
         within_size: Size<UPx>,
         context: &mut crate::context::GraphicsContext<'_, '_, '_, '_>,
     ) -> Rect<Px> {
-        let within_size = within_size.into_signed();
-        let size = texture.size().into_signed();
-        match self.scaling.get_tracking_invalidate(context) {
-            ImageScaling::Aspect { mode, orientation } => {
-                let scale_width = within_size.width.into_float() / size.width.into_float();
-                let scale_height = within_size.height.into_float() / size.height.into_float();
-
-                let effective_scale = match mode {
-                    Aspect::Fill => scale_width.max(scale_height),
-                    Aspect::Fit => scale_width.min(scale_height),
-                };
-                let scaled = size * effective_scale;
-
-                let x = (within_size.width - scaled.width) * *orientation.width;
-                let y = (within_size.height - scaled.height) * *orientation.height;
-
-                Rect::new(Point::new(x, y), scaled)
-            }
-            ImageScaling::Stretch => within_size.into(),
-            ImageScaling::Scale(factor) => {
-                let size = size.map(|px| px * factor);
-                size.into()
-            }
-        }
+        self.scaling
+            .get_tracking_invalidate(context)
+            .image_rect(texture.size(), within_size)
     }
 }
 
 impl Widget for Image {
     fn redraw(&mut self, context: &mut crate::context::GraphicsContext<'_, '_, '_, '_>) {
+        let tint = self.tint.get_tracking_redraw(context);
+        let opacity = self.opacity.get_tracking_redraw(context);
+        let color = tint.with_alpha_f32(tint.alpha_f32() * *opacity);
         self.contents.map(|texture| {
             let rect = self.calculate_image_rect(texture, context.gfx.size(), context);
-            context.gfx.draw_texture(texture, rect);
+            match self.scaling.get() {
+                ImageScaling::NineSlice(edges) => {
+                    draw_nine_slice(texture, rect, edges, color, context);
+                }
+                _ if color == Color::WHITE => context.gfx.draw_texture(texture, rect),
+                _ => context.gfx.draw_textured_shape(
+                    &TextureBlit::new(Rect::from(texture.size()), rect, color),
+                    texture,
+                ),
+            }
         });
     }
 
@@ -182,6 +254,52 @@ pub enum ImageScaling {
     /// In this mode, the widget will request that its size be the size of the
     /// contained image.
     Scale(f32),
+
+    /// The image is divided into nine slices by the contained edges. The
+    /// corners are drawn without scaling, the top and bottom edges are
+    /// stretched horizontally, the left and right edges are stretched
+    /// vertically, and the center is stretched to fill the remaining area.
+    ///
+    /// The edges are measured in the image's pixels when converted at a scale
+    /// of 1.0, and are drawn at their size when converted using the display's
+    /// scale. This means [`Px`] edges are drawn using the same number of
+    /// pixels at every scale, while [`Lp`](figures::units::Lp) edges grow with
+    /// the display's scale.
+    ///
+    /// If the surface is too small to fit the borders, the borders are shrunk
+    /// proportionally.
+    NineSlice(Edges<Dimension>),
+}
+
+impl ImageScaling {
+    /// Returns the rectangle an image of `image_size` is drawn in when scaled
+    /// using this strategy within `within_size`.
+    pub(crate) fn image_rect(self, image_size: Size<UPx>, within_size: Size<UPx>) -> Rect<Px> {
+        let within_size = within_size.into_signed();
+        let size = image_size.into_signed();
+        match self {
+            ImageScaling::Aspect { mode, orientation } => {
+                let scale_width = within_size.width.into_float() / size.width.into_float();
+                let scale_height = within_size.height.into_float() / size.height.into_float();
+
+                let effective_scale = match mode {
+                    Aspect::Fill => scale_width.max(scale_height),
+                    Aspect::Fit => scale_width.min(scale_height),
+                };
+                let scaled = size * effective_scale;
+
+                let x = (within_size.width - scaled.width) * *orientation.width;
+                let y = (within_size.height - scaled.height) * *orientation.height;
+
+                Rect::new(Point::new(x, y), scaled)
+            }
+            ImageScaling::Stretch | ImageScaling::NineSlice(_) => within_size.into(),
+            ImageScaling::Scale(factor) => {
+                let size = size.map(|px| px * factor);
+                size.into()
+            }
+        }
+    }
 }
 
 impl Default for ImageScaling {
@@ -191,6 +309,365 @@ impl Default for ImageScaling {
     }
 }
 
+/// An [`Image`] that is loaded on a background thread.
+///
+/// Images are loaded by a small pool of threads shared by all images, which
+/// prevents displaying many images at once from spawning a thread per image.
+///
+/// While the image is loading, a placeholder is shown. By default, the
+/// placeholder is an indeterminant spinner. If the image fails to load, the
+/// error is shown in place of the image.
+///
+/// Instances are created using [`Image::from_path`], [`Image::from_bytes`],
+/// [`Image::from_reader`], or [`TextureCache::load`].
+#[must_use]
+pub struct AsyncImage {
+    state: Dynamic<ImageLoad>,
+    scaling: Value<ImageScaling>,
+    tint: Value<Color>,
+    opacity: Value<ZeroToOne>,
+    placeholder: Option<WidgetInstance>,
+    on_failure: Option<Box<dyn FnMut(&str) -> WidgetInstance + Send>>,
+}
+
+impl AsyncImage {
+    fn new(state: Dynamic<ImageLoad>) -> Self {
+        Self {
+            state,
+            scaling: Value::default(),
+            tint: Value::Constant(Color::WHITE),
+            opacity: Value::Constant(ZeroToOne::ONE),
+            placeholder: None,
+            on_failure: None,
+        }
+    }
+
+    fn spawn<F>(load: F) -> Self
+    where
+        F: FnOnce() -> Result<DynamicImage, ImageError> + Send + 'static,
+    {
+        let state = Dynamic::new(ImageLoad::Loading);
+        load_in_background({
+            let state = state.clone();
+            move || finish_loading(&state, load())
+        });
+        Self::new(state)
+    }
+
+    /// Applies the `scaling` strategy to the image once loaded, and returns
+    /// self.
+    pub fn scaling(mut self, scaling: impl IntoValue<ImageScaling>) -> Self {
+        self.scaling = scaling.into_value();
+        self
+    }
+
+    /// Multiplies the loaded image's colors by `tint` and returns self.
+    ///
+    /// See [`Image::tint`] for more information.
+    pub fn tint(mut self, tint: impl IntoValue<Color>) -> Self {
+        self.tint = tint.into_value();
+        self
+    }
+
+    /// Draws the loaded image with `opacity` and returns self.
+    pub fn opacity(mut self, opacity: impl IntoValue<ZeroToOne>) -> Self {
+        self.opacity = opacity.into_value();
+        self
+    }
+
+    /// Shows `placeholder` while the image is loading and returns self.
+    pub fn placeholder(mut self, placeholder: impl MakeWidget) -> Self {
+        self.placeholder = Some(placeholder.make_widget());
+        self
+    }
+
+    /// Shows the widget returned by `on_failure` if the image fails to load,
+    /// and returns self.
+    ///
+    /// `on_failure` is invoked with a description of the error.
+    pub fn on_failure<F, W>(mut self, mut on_failure: F) -> Self
+    where
+        F: FnMut(&str) -> W + Send + 'static,
+        W: MakeWidget,
+    {
+        self.on_failure = Some(Box::new(move |error| on_failure(error).make_widget()));
+        self
+    }
+
+    /// Returns a reader of the current loading state of this image.
+    #[must_use]
+    pub fn state(&self) -> DynamicReader<ImageLoad> {
+        self.state.create_reader()
+    }
+}
+
+impl Debug for AsyncImage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncImage")
+            .field("state", &self.state)
+            .field("scaling", &self.scaling)
+            .field("tint", &self.tint)
+            .field("opacity", &self.opacity)
+            .field("placeholder", &self.placeholder)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MakeWidgetWithTag for AsyncImage {
+    fn make_with_tag(self, id: WidgetTag) -> WidgetInstance {
+        let placeholder = self.placeholder.unwrap_or_else(|| {
+            ProgressBar::new(Progress::Indeterminant)
+                .spinner()
+                .centered()
+                .make_widget()
+        });
+        let mut on_failure = self.on_failure.unwrap_or_else(|| {
+            Box::new(|error: &str| {
+                error
+                    .to_string()
+                    .with_dynamic(&TextColor, ErrorColor)
+                    .centered()
+                    .make_widget()
+            })
+        });
+        let scaling = self.scaling;
+        let tint = self.tint;
+        let opacity = self.opacity;
+
+        Switcher::mapping(self.state, move |state, _| match state {
+            ImageLoad::Loading => placeholder.clone(),
+            ImageLoad::Loaded(texture) => Image {
+                contents: Value::Constant(texture.clone()),
+                scaling: scaling.clone(),
+                tint: tint.clone(),
+                opacity: opacity.clone(),
+            }
+            .make_widget(),
+            ImageLoad::Failed(error) => on_failure(error),
+        })
+        .make_with_tag(id)
+    }
+}
+
+/// The loading state of an [`AsyncImage`].
+#[derive(Debug, Clone)]
+pub enum ImageLoad {
+    /// The image is still being loaded.
+    Loading,
+    /// The image has been loaded.
+    Loaded(AnyTexture),
+    /// The image could not be loaded. The error's description is contained.
+    Failed(String),
+}
+
+fn decode(reader: impl BufRead + Seek) -> Result<DynamicImage, ImageError> {
+    image::io::Reader::new(reader)
+        .with_guessed_format()
+        .map_err(ImageError::IoError)?
+        .decode()
+}
+
+/// The maximum number of threads used to load images.
+const MAX_LOADING_THREADS: usize = 4;
+
+type LoadFunction = Box<dyn FnOnce() + Send>;
+
+/// Invokes `load` on one of the threads shared by all images being loaded.
+fn load_in_background<F>(load: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    static LOADER: Lazy<Mutex<mpsc::Sender<LoadFunction>>> = Lazy::new(|| {
+        let (sender, receiver) = mpsc::channel::<LoadFunction>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let threads = std::thread::available_parallelism()
+            .map_or(1, NonZeroUsize::get)
+            .min(MAX_LOADING_THREADS);
+        for index in 0..threads {
+            let receiver = receiver.clone();
+            std::thread::Builder::new()
+                .name(format!("image-loader-{index}"))
+                .spawn(move || loop {
+                    // The lock is only held while waiting for the next image,
+                    // allowing the other threads to load images meanwhile.
+                    let Ok(load) = receiver.lock().ignore_poison().recv() else {
+                        break;
+                    };
+                    load();
+                })
+                .assert("error spawning image loading thread");
+        }
+        Mutex::new(sender)
+    });
+
+    LOADER
+        .lock()
+        .ignore_poison()
+        .send(Box::new(load))
+        .assert("image loading threads not running");
+}
+
+fn finish_loading(state: &Dynamic<ImageLoad>, result: Result<DynamicImage, ImageError>) {
+    let result = match result {
+        Ok(image) => ImageLoad::Loaded(AnyTexture::from(LazyTexture::from_image(
+            image,
+            FilterMode::Linear,
+        ))),
+        Err(err) => ImageLoad::Failed(err.to_string()),
+    };
+    state.map_mut(|mut state| *state = result);
+}
+
+/// A least-recently-used cache of textures loaded from paths.
+///
+/// Images loaded through a cache share their textures, which avoids decoding
+/// and uploading the same image multiple times when it is displayed in
+/// multiple places, such as thumbnails in a list. When more than
+/// [`capacity`](Self::new) paths have been loaded, the least recently loaded
+/// path is removed from the cache. Images that have already loaded a removed
+/// path continue to display their texture.
+///
+/// Paths that fail to load are not kept in the cache.
+#[derive(Debug, Clone)]
+pub struct TextureCache {
+    data: Arc<Mutex<CacheData>>,
+}
+
+#[derive(Debug)]
+struct CacheData {
+    capacity: usize,
+    entries: HashMap<PathBuf, CacheEntry>,
+    uses: u64,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    state: Dynamic<ImageLoad>,
+    last_used: u64,
+}
+
+impl TextureCache {
+    /// The number of textures the [shared](Self::shared) cache holds.
+    pub const DEFAULT_CAPACITY: usize = 256;
+
+    /// Returns a new cache that holds up to `capacity` textures.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            data: Arc::new(Mutex::new(CacheData {
+                capacity: capacity.max(1),
+                entries: HashMap::new(),
+                uses: 0,
+            })),
+        }
+    }
+
+    /// Returns the cache used by [`Image::from_path`].
+    #[must_use]
+    pub fn shared() -> Self {
+        static SHARED: Lazy<TextureCache> =
+            Lazy::new(|| TextureCache::new(TextureCache::DEFAULT_CAPACITY));
+        SHARED.clone()
+    }
+
+    /// Returns an image that displays the image file at `path`.
+    ///
+    /// If `path` is already in this cache, the cached texture is used.
+    /// Otherwise, the file is loaded on a background thread and stored in this
+    /// cache.
+    pub fn load(&self, path: impl AsRef<Path>) -> AsyncImage {
+        let path = path.as_ref();
+        let (state, inserted) = self.data.lock().ignore_poison().entry(path);
+        if inserted {
+            let cache = self.clone();
+            let path = path.to_path_buf();
+            let state = state.clone();
+            load_in_background(move || {
+                let result = image::open(&path);
+                if result.is_err() {
+                    cache
+                        .data
+                        .lock()
+                        .ignore_poison()
+                        .remove_failed(&path, &state);
+                }
+                finish_loading(&state, result);
+            });
+        }
+        AsyncImage::new(state)
+    }
+
+    /// Removes `path` from this cache.
+    pub fn remove(&self, path: impl AsRef<Path>) {
+        self.data
+            .lock()
+            .ignore_poison()
+            .entries
+            .remove(path.as_ref());
+    }
+
+    /// Removes all textures from this cache.
+    pub fn clear(&self) {
+        self.data.lock().ignore_poison().entries.clear();
+    }
+
+    /// Returns the number of paths in this cache.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.lock().ignore_poison().entries.len()
+    }
+
+    /// Returns true if this cache contains no paths.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl CacheData {
+    /// Returns the state for `path`, and whether a new entry was inserted.
+    fn entry(&mut self, path: &Path) -> (Dynamic<ImageLoad>, bool) {
+        self.uses += 1;
+        if let Some(entry) = self.entries.get_mut(path) {
+            entry.last_used = self.uses;
+            return (entry.state.clone(), false);
+        }
+
+        if self.entries.len() >= self.capacity {
+            let least_recent = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(path, _)| path.clone());
+            if let Some(least_recent) = least_recent {
+                self.entries.remove(&least_recent);
+            }
+        }
+
+        let state = Dynamic::new(ImageLoad::Loading);
+        self.entries.insert(
+            path.to_path_buf(),
+            CacheEntry {
+                state: state.clone(),
+                last_used: self.uses,
+            },
+        );
+        (state, true)
+    }
+
+    /// Removes `path` after `state` failed to load, unless `path` has since
+    /// been evicted and loaded again into a new state.
+    fn remove_failed(&mut self, path: &Path, state: &Dynamic<ImageLoad>) {
+        if self
+            .entries
+            .get(path)
+            .map_or(false, |entry| &entry.state == state)
+        {
+            self.entries.remove(path);
+        }
+    }
+}
+
 impl IntoValue<AnyTexture> for Texture {
     fn into_value(self) -> Value<AnyTexture> {
         Value::Constant(AnyTexture::from(self))
@@ -221,6 +698,86 @@ impl IntoValue<AnyTexture> for TextureRegion {
     }
 }
 
+/// Draws `texture` into `destination` using nine-slice scaling.
+///
+/// See [`ImageScaling::NineSlice`] for more information.
+pub(crate) fn draw_nine_slice(
+    texture: &AnyTexture,
+    destination: Rect<Px>,
+    edges: Edges<Dimension>,
+    color: Color,
+    context: &mut GraphicsContext<'_, '_, '_, '_>,
+) {
+    let size = texture.size().into_signed();
+    let source_edges = edges.into_px(Fraction::ONE);
+    let destination_edges = edges.into_px(context.gfx.scale());
+
+    let source_x = slice_stops(Px::ZERO, size.width, source_edges.left, source_edges.right);
+    let source_y = slice_stops(Px::ZERO, size.height, source_edges.top, source_edges.bottom);
+    let destination_x = slice_stops(
+        destination.origin.x,
+        destination.size.width,
+        destination_edges.left,
+        destination_edges.right,
+    );
+    let destination_y = slice_stops(
+        destination.origin.y,
+        destination.size.height,
+        destination_edges.top,
+        destination_edges.bottom,
+    );
+
+    for row in 0..3 {
+        for column in 0..3 {
+            let source = Rect::new(
+                Point::new(source_x[column], source_y[row]),
+                Size::new(
+                    source_x[column + 1] - source_x[column],
+                    source_y[row + 1] - source_y[row],
+                ),
+            );
+            let destination = Rect::new(
+                Point::new(destination_x[column], destination_y[row]),
+                Size::new(
+                    destination_x[column + 1] - destination_x[column],
+                    destination_y[row + 1] - destination_y[row],
+                ),
+            );
+            if source.size.width > 0
+                && source.size.height > 0
+                && destination.size.width > 0
+                && destination.size.height > 0
+            {
+                context.gfx.draw_textured_shape(
+                    &TextureBlit::new(source.into_unsigned(), destination, color),
+                    texture,
+                );
+            }
+        }
+    }
+}
+
+/// Returns the boundaries of the three slices along one axis of length
+/// `length` starting at `origin`, with borders of `start` and `end`.
+///
+/// If the borders do not fit within `length`, they are shrunk proportionally.
+fn slice_stops(origin: Px, length: Px, start: Px, end: Px) -> [Px; 4] {
+    let length = length.max(Px::ZERO);
+    let mut start = start.max(Px::ZERO);
+    let mut end = end.max(Px::ZERO);
+    let borders = start + end;
+    if borders > length {
+        start = Px::new(start.get() * length.get() / borders.get());
+        end = length - start;
+    }
+    [
+        origin,
+        origin + start,
+        origin + length - end,
+        origin + length,
+    ]
+}
+
 /// An aspect mode for scaling an [`Image`].
 #[derive(Default, Debug, Clone, Copy, Eq, PartialEq)]
 pub enum Aspect {
@@ -233,3 +790,46 @@ pub enum Aspect {
     /// size it can be to cover the entire surface.
     Fill,
 }
+
+#[test]
+fn nine_slice_stops() {
+    assert_eq!(
+        slice_stops(Px::new(5), Px::new(10), Px::new(3), Px::new(2)),
+        [Px::new(5), Px::new(8), Px::new(13), Px::new(15)]
+    );
+    // Borders that don't fit are shrunk proportionally.
+    assert_eq!(
+        slice_stops(Px::ZERO, Px::new(4), Px::new(3), Px::new(3)),
+        [Px::ZERO, Px::new(2), Px::new(2), Px::new(4)]
+    );
+}
+
+#[test]
+fn texture_cache_evicts_least_recently_used() {
+    let cache = TextureCache::new(2);
+    let mut data = cache.data.lock().ignore_poison();
+    assert!(data.entry(Path::new("a")).1);
+    assert!(data.entry(Path::new("b")).1);
+    assert!(!data.entry(Path::new("a")).1);
+    assert!(data.entry(Path::new("c")).1);
+    assert!(data.entries.contains_key(Path::new("a")));
+    assert!(!data.entries.contains_key(Path::new("b")));
+    assert!(data.entries.contains_key(Path::new("c")));
+}
+
+#[test]
+fn texture_cache_only_removes_failed_loads() {
+    let cache = TextureCache::new(2);
+    let mut data = cache.data.lock().ignore_poison();
+    let (failed, _) = data.entry(Path::new("a"));
+
+    // While the first load was running, the entry was evicted and loaded
+    // again.
+    data.entries.remove(Path::new("a"));
+    let (reloaded, _) = data.entry(Path::new("a"));
+    data.remove_failed(Path::new("a"), &failed);
+    assert!(data.entries.contains_key(Path::new("a")));
+
+    data.remove_failed(Path::new("a"), &reloaded);
+    assert!(!data.entries.contains_key(Path::new("a")));
+}
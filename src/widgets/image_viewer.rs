@@ -0,0 +1,419 @@
+//! A widget that displays an image that can be zoomed and panned.
+
+use figures::units::{Lp, Px, UPx};
+use figures::{FloatConversion, IntoSigned, Point, Rect, Round, ScreenScale, Size, Zero};
+use kludgine::app::winit::event::{MouseButton, MouseScrollDelta, TouchPhase};
+use kludgine::app::winit::keyboard::{Key, NamedKey};
+use kludgine::app::winit::window::CursorIcon;
+use kludgine::shapes::{Shape, StrokeOptions};
+use kludgine::AnyTexture;
+
+use crate::context::{EventContext, GraphicsContext, LayoutContext};
+use crate::styles::components::{HighlightColor, SurfaceColor};
+use crate::styles::Dimension;
+use crate::utils::wheel_zoom_factor;
+use crate::value::{Destination, Dynamic, IntoDynamic, IntoValue, Source, Value};
+use crate::widget::{EventHandling, Widget, HANDLED, IGNORED};
+use crate::widgets::selection::ClickCounter;
+use crate::window::{DeviceId, KeyEvent};
+use crate::ConstraintLimit;
+
+/// A widget that displays an image that can be zoomed and panned.
+///
+/// - Scrolling the mouse wheel zooms in and out around the cursor.
+/// - Dragging the image pans it.
+/// - Double clicking toggles between fitting the image to the viewer and
+///   displaying it at its actual size.
+/// - When focused, `+` and `-` zoom in and out, `0` fits the image to the
+///   viewer, `1` displays the image at its actual size, and the arrow keys pan
+///   the image.
+///
+/// The zoom level, offset, and fit state are stored in [`Dynamic`]s that can be
+/// shared between multiple viewers to keep them synchronized.
+///
+/// Pinch to zoom is not supported, as widgets do not receive touchpad
+/// magnification or touch events.
+#[derive(Debug)]
+#[must_use]
+pub struct ImageViewer {
+    contents: Value<AnyTexture>,
+    zoom: Dynamic<f32>,
+    offset: Dynamic<Point<f32>>,
+    fit: Dynamic<bool>,
+    minimum_zoom: f32,
+    maximum_zoom: f32,
+    minimap: bool,
+    view_size: Size<Px>,
+    cursor: Option<Point<Px>>,
+    drag: Option<(Point<Px>, Point<f32>)>,
+    clicks: ClickCounter,
+}
+
+impl ImageViewer {
+    /// Returns a viewer that displays `contents`, initially fit to the viewer.
+    pub fn new(contents: impl IntoValue<AnyTexture>) -> Self {
+        Self {
+            contents: contents.into_value(),
+            zoom: Dynamic::new(1.),
+            offset: Dynamic::new(Point::ZERO),
+            fit: Dynamic::new(true),
+            minimum_zoom: 1. / 32.,
+            maximum_zoom: 32.,
+            minimap: false,
+            view_size: Size::ZERO,
+            cursor: None,
+            drag: None,
+            clicks: ClickCounter::default(),
+        }
+    }
+
+    /// Uses `zoom` to store the zoom level and returns self.
+    ///
+    /// A zoom level of `1.0` displays one image pixel per screen pixel. The zoom
+    /// level is ignored while the viewer is [fit](Self::fit) to the image.
+    pub fn zoom(mut self, zoom: impl IntoDynamic<f32>) -> Self {
+        self.zoom = zoom.into_dynamic();
+        self
+    }
+
+    /// Uses `offset` to store the offset of the image and returns self.
+    ///
+    /// The offset is measured in image pixels from the image's center to the
+    /// center of the viewer, which allows viewers of different sizes to
+    /// display the same region of an image.
+    pub fn offset(mut self, offset: impl IntoDynamic<Point<f32>>) -> Self {
+        self.offset = offset.into_dynamic();
+        self
+    }
+
+    /// Uses `fit` to control whether the image is fit to the viewer and
+    /// returns self.
+    ///
+    /// While `fit` is true, the image is centered and scaled to be as large as
+    /// possible without clipping. Zooming or panning sets `fit` to false.
+    pub fn fit(mut self, fit: impl IntoDynamic<bool>) -> Self {
+        self.fit = fit.into_dynamic();
+        self
+    }
+
+    /// Limits the zoom level to `minimum..=maximum` and returns self.
+    ///
+    /// By default, the zoom level is limited to `1/32..=32`.
+    pub fn zoom_limits(mut self, minimum: f32, maximum: f32) -> Self {
+        self.minimum_zoom = minimum.min(maximum);
+        self.maximum_zoom = maximum.max(minimum);
+        self
+    }
+
+    /// Displays a minimap of the entire image while it is larger than the
+    /// viewer, and returns self.
+    pub fn minimap(mut self) -> Self {
+        self.minimap = true;
+        self
+    }
+
+    fn image_size(&self) -> Size<f32> {
+        self.contents.map(|texture| {
+            let size = texture.size().into_signed();
+            Size::new(size.width.into_float(), size.height.into_float())
+        })
+    }
+
+    fn fit_zoom(&self) -> f32 {
+        let image = self.image_size();
+        if image.width <= 0. || image.height <= 0. {
+            return 1.;
+        }
+        (self.view_size.width.into_float() / image.width)
+            .min(self.view_size.height.into_float() / image.height)
+    }
+
+    /// Returns the current zoom level and offset, taking into account whether
+    /// the image is fit to the viewer.
+    fn effective_view(&self) -> (f32, Point<f32>) {
+        if self.fit.get() {
+            (self.fit_zoom(), Point::ZERO)
+        } else {
+            (self.zoom.get(), self.offset.get())
+        }
+    }
+
+    fn center(&self) -> Point<f32> {
+        Point::new(
+            self.view_size.width.into_float(),
+            self.view_size.height.into_float(),
+        ) / 2.
+    }
+
+    /// Sets the zoom level to `zoom`, keeping the image location beneath
+    /// `anchor` in place.
+    fn zoom_around(&mut self, zoom: f32, anchor: Point<f32>, context: &mut EventContext<'_>) {
+        let (current_zoom, offset) = self.effective_view();
+        let zoom = zoom.clamp(self.minimum_zoom, self.maximum_zoom);
+        let offset = zoom_offset(offset, anchor - self.center(), current_zoom, zoom);
+
+        self.fit.set(false);
+        self.zoom.set(zoom);
+        self.offset.set(offset);
+        context.set_needs_redraw();
+    }
+
+    fn pan_by(&mut self, amount: Point<f32>, context: &mut EventContext<'_>) {
+        let (zoom, offset) = self.effective_view();
+        self.fit.set(false);
+        self.zoom.set(zoom);
+        self.offset.set(offset + amount / zoom);
+        context.set_needs_redraw();
+    }
+
+    fn actual_size(&mut self, context: &mut EventContext<'_>) {
+        self.fit.set(false);
+        self.zoom.set(1.);
+        self.offset.set(Point::ZERO);
+        context.set_needs_redraw();
+    }
+
+    fn draw_minimap(
+        &self,
+        texture: &AnyTexture,
+        image_rect: Rect<f32>,
+        context: &mut GraphicsContext<'_, '_, '_, '_>,
+    ) {
+        let view = Rect::from(Size::new(
+            self.view_size.width.into_float(),
+            self.view_size.height.into_float(),
+        ));
+        let Some(visible) = view.intersection(&image_rect) else {
+            return;
+        };
+        if visible == image_rect {
+            return;
+        }
+
+        let scale = context.gfx.scale();
+        let padding = Lp::points(8).into_px(scale).into_float();
+        let extent = context.get(&MinimapSize).into_px(scale).into_float();
+        let image = image_rect.size;
+        let minimap_scale = (extent / image.width).min(extent / image.height);
+        let size = image * minimap_scale;
+        let origin = Point::new(
+            view.size.width - size.width - padding,
+            view.size.height - size.height - padding,
+        );
+        let minimap = Rect::new(origin, size);
+
+        let line_width = Lp::points(1).into_px(scale).round();
+        let outline = Rect::new(
+            minimap.origin - Point::squared(1.),
+            minimap.size + Size::squared(2.),
+        );
+        context.gfx.draw_shape(&Shape::filled_rect(
+            outline.cast::<Px>(),
+            context.get(&SurfaceColor),
+        ));
+        context.gfx.draw_texture(texture, minimap.cast::<Px>());
+
+        // The region of the image that is currently visible.
+        let ratio = size.width / image.width;
+        let visible = Rect::new(
+            origin + (visible.origin - image_rect.origin) * ratio,
+            visible.size * ratio,
+        );
+        context.gfx.draw_shape(&Shape::stroked_rect(
+            visible.cast::<Px>(),
+            StrokeOptions::px_wide(line_width).colored(context.get(&HighlightColor)),
+        ));
+    }
+}
+
+/// Returns the offset that keeps the image location beneath `anchor` in place
+/// when changing the zoom level from `zoom` to `new_zoom`.
+///
+/// `anchor` is measured from the center of the viewer.
+fn zoom_offset(offset: Point<f32>, anchor: Point<f32>, zoom: f32, new_zoom: f32) -> Point<f32> {
+    offset + anchor / new_zoom - anchor / zoom
+}
+
+impl Widget for ImageViewer {
+    fn redraw(&mut self, context: &mut GraphicsContext<'_, '_, '_, '_>) {
+        self.contents.redraw_when_changed(context);
+        context.redraw_when_changed(&self.zoom);
+        context.redraw_when_changed(&self.offset);
+        context.redraw_when_changed(&self.fit);
+
+        self.view_size = context.gfx.region().size;
+        let (zoom, offset) = self.effective_view();
+        let center = self.center();
+        let image = self.image_size();
+        let size = image * zoom;
+        let image_rect = Rect::new(center + offset * zoom - size / 2., size);
+
+        self.contents.map(|texture| {
+            context.gfx.draw_texture(texture, image_rect.cast::<Px>());
+            if self.minimap {
+                self.draw_minimap(texture, image_rect, context);
+            }
+        });
+
+        context.draw_focus_ring();
+    }
+
+    fn layout(
+        &mut self,
+        available_space: Size<ConstraintLimit>,
+        _context: &mut LayoutContext<'_, '_, '_, '_>,
+    ) -> Size<UPx> {
+        available_space.map(ConstraintLimit::max)
+    }
+
+    fn accept_focus(&mut self, context: &mut EventContext<'_>) -> bool {
+        context.enabled()
+    }
+
+    fn hit_test(&mut self, _location: Point<Px>, _context: &mut EventContext<'_>) -> bool {
+        true
+    }
+
+    fn hover(
+        &mut self,
+        location: Point<Px>,
+        _context: &mut EventContext<'_>,
+    ) -> Option<CursorIcon> {
+        self.cursor = Some(location);
+        Some(if self.drag.is_some() {
+            CursorIcon::Grabbing
+        } else {
+            CursorIcon::Grab
+        })
+    }
+
+    fn unhover(&mut self, _context: &mut EventContext<'_>) {
+        self.cursor = None;
+    }
+
+    fn mouse_down(
+        &mut self,
+        location: Point<Px>,
+        _device_id: DeviceId,
+        button: MouseButton,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        if button != MouseButton::Left {
+            return IGNORED;
+        }
+
+        context.focus();
+        if self.clicks.click(location) == 2 {
+            if self.fit.get() {
+                self.actual_size(context);
+            } else {
+                self.fit.set(true);
+            }
+        } else {
+            let (_, offset) = self.effective_view();
+            self.drag = Some((location, offset));
+        }
+        HANDLED
+    }
+
+    fn mouse_drag(
+        &mut self,
+        location: Point<Px>,
+        _device_id: DeviceId,
+        _button: MouseButton,
+        context: &mut EventContext<'_>,
+    ) {
+        let Some((start, start_offset)) = self.drag else {
+            return;
+        };
+        let (zoom, _) = self.effective_view();
+        let delta = location - start;
+        let delta = Point::new(delta.x.into_float(), delta.y.into_float());
+
+        self.fit.set(false);
+        self.zoom.set(zoom);
+        self.offset.set(start_offset + delta / zoom);
+        context.set_needs_redraw();
+    }
+
+    fn mouse_up(
+        &mut self,
+        _location: Option<Point<Px>>,
+        _device_id: DeviceId,
+        _button: MouseButton,
+        _context: &mut EventContext<'_>,
+    ) {
+        self.drag = None;
+    }
+
+    fn mouse_wheel(
+        &mut self,
+        _device_id: DeviceId,
+        delta: MouseScrollDelta,
+        _phase: TouchPhase,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        let Some(cursor) = self.cursor else {
+            return IGNORED;
+        };
+        let (zoom, _) = self.effective_view();
+        let cursor = Point::new(cursor.x.into_float(), cursor.y.into_float());
+        self.zoom_around(zoom * wheel_zoom_factor(delta), cursor, context);
+        HANDLED
+    }
+
+    fn keyboard_input(
+        &mut self,
+        _device_id: DeviceId,
+        input: KeyEvent,
+        _is_synthetic: bool,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        if !input.state.is_pressed() {
+            return IGNORED;
+        }
+
+        let center = self.center();
+        let pan = self.view_size.width.min(self.view_size.height).into_float() / 10.;
+        let (zoom, _) = self.effective_view();
+        match input.logical_key {
+            Key::Character(ch) if ch == "+" || ch == "=" => {
+                self.zoom_around(zoom * 1.25, center, context);
+            }
+            Key::Character(ch) if ch == "-" => {
+                self.zoom_around(zoom / 1.25, center, context);
+            }
+            Key::Character(ch) if ch == "0" => {
+                self.fit.set(true);
+            }
+            Key::Character(ch) if ch == "1" => {
+                self.actual_size(context);
+            }
+            Key::Named(NamedKey::ArrowLeft) => self.pan_by(Point::new(pan, 0.), context),
+            Key::Named(NamedKey::ArrowRight) => self.pan_by(Point::new(-pan, 0.), context),
+            Key::Named(NamedKey::ArrowUp) => self.pan_by(Point::new(0., pan), context),
+            Key::Named(NamedKey::ArrowDown) => self.pan_by(Point::new(0., -pan), context),
+            _ => return IGNORED,
+        }
+        HANDLED
+    }
+}
+
+define_components! {
+    ImageViewer {
+        /// The largest dimension of the minimap shown by an [`ImageViewer`].
+        MinimapSize(Dimension, "minimap_size", Dimension::Lp(Lp::points(96)))
+    }
+}
+
+#[test]
+fn zooming_keeps_anchor_in_place() {
+    // A point 100 pixels right of the center at 2x zoom is 50 image pixels
+    // from the image's center. After zooming to 4x, it should remain at the
+    // same image location.
+    let anchor = Point::new(100., 0.);
+    let offset = zoom_offset(Point::ZERO, anchor, 2., 4.);
+    assert_eq!(offset, Point::new(-25., 0.));
+    let image_location = anchor / 4. - offset;
+    assert_eq!(image_location, anchor / 2.);
+}
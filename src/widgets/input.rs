@@ -9,19 +9,17 @@ use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
 use figures::units::{Lp, Px, UPx};
-use figures::{
-    Abs, FloatConversion, IntoSigned, IntoUnsigned, Point, Rect, Round, ScreenScale, Size, Zero,
-};
-use intentional::Cast;
+use figures::{IntoSigned, IntoUnsigned, Point, Rect, Round, ScreenScale, Size, Zero};
 use kludgine::app::winit::event::{ElementState, Ime};
 use kludgine::app::winit::keyboard::{Key, NamedKey};
 use kludgine::app::winit::window::{CursorIcon, ImePurpose};
 use kludgine::shapes::{Shape, StrokeOptions};
 use kludgine::text::{MeasuredText, Text, TextOrigin};
 use kludgine::{CanRenderTo, Color, DrawableExt};
-use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
+use unicode_segmentation::UnicodeSegmentation;
 use zeroize::Zeroizing;
 
+use super::selection::{self, ClickCounter};
 use crate::context::{EventContext, GraphicsContext, LayoutContext};
 use crate::styles::components::{HighlightColor, IntrinsicPadding, OutlineColor, TextColor};
 use crate::utils::ModifiersExt;
@@ -47,6 +45,7 @@ pub struct Input<Storage = String> {
     blink_state: BlinkState,
     needs_to_select_all: bool,
     mouse_buttons_down: usize,
+    clicks: ClickCounter,
     line_navigation_x_target: Option<Px>,
     window_focused: bool,
 }
@@ -119,6 +118,7 @@ where
             selection: SelectionState::default(),
             on_key: None,
             mouse_buttons_down: 0,
+            clicks: ClickCounter::default(),
             needs_to_select_all: true,
             line_navigation_x_target: None,
             window_focused: false,
@@ -158,13 +158,8 @@ where
     }
 
     fn select_all(&mut self) {
-        self.value.map_ref(|value| {
-            let text = value.as_str();
-
-            self.selection.start = Some(Cursor::default());
-            self.selection.cursor.offset = text.len();
-            self.selection.cursor.affinity = Affinity::After;
-        });
+        self.value
+            .map_ref(|value| self.selection.select_all(value.as_str()));
     }
 
     fn forward_delete(&mut self, context: &mut EventContext<'_>) {
@@ -172,33 +167,8 @@ where
             return;
         }
 
-        let (cursor, selection) = self.selected_range();
-        if let Some(selection) = selection {
-            self.replace_range(cursor, selection, "");
-        } else {
-            let mut value = self.value.lock();
-            if let Some(length) = value.as_str()[cursor.offset..]
-                .graphemes(true)
-                .next()
-                .map(str::len)
-            {
-                value
-                    .as_string_mut()
-                    .replace_range(cursor.offset..cursor.offset + length, "");
-            }
-        }
-    }
-
-    fn replace_range(&mut self, start: Cursor, end: Cursor, new_text: &str) {
-        self.value.map_mut(|mut value| {
-            let value = value.as_string_mut();
-            let start = start.offset.min(value.len().saturating_sub(1));
-            let end = end.offset.min(value.len());
-            value.replace_range(start..end, new_text);
-
-            self.selection.cursor.offset = start + new_text.len();
-            self.selection.start = None;
-        });
+        self.value
+            .map_mut(|mut value| self.selection.delete_forward(value.as_string_mut()));
     }
 
     fn delete(&mut self, context: &mut EventContext<'_>) {
@@ -206,26 +176,8 @@ where
             return;
         }
 
-        let (cursor, selection) = self.selected_range();
-        if let Some(selection) = selection {
-            self.replace_range(cursor, selection, "");
-        } else if cursor.offset > 0 {
-            let mut value = self.value.lock();
-            let length = value.as_str().len();
-
-            if length == 0 || cursor.offset == 0 {
-                return;
-            }
-
-            if let Ok(Some(offset)) = GraphemeCursor::new(cursor.offset, value.as_str().len(), true)
-                .prev_boundary(value.as_str(), 0)
-            {
-                value
-                    .as_string_mut()
-                    .replace_range(offset..cursor.offset, "");
-                self.selection.cursor.offset -= cursor.offset - offset;
-            }
-        }
+        self.value
+            .map_mut(|mut value| self.selection.delete_backward(value.as_string_mut()));
     }
 
     fn move_cursor(
@@ -251,69 +203,12 @@ where
 
     fn move_cursor_by_grapheme(&mut self, affinity: Affinity) {
         let value = self.value.lock();
-        let length = value.as_str().len();
-
-        match affinity {
-            Affinity::Before => {
-                if let Some((_, grapheme)) =
-                    value
-                        .as_str()
-                        .grapheme_indices(true)
-                        .find(|(index, grapheme)| {
-                            index + grapheme.len() == self.selection.cursor.offset
-                        })
-                {
-                    self.selection.cursor.offset -= grapheme.len();
-                } else {
-                    self.selection.cursor.offset = 0;
-                }
-            }
-            Affinity::After => {
-                if self.selection.cursor.offset < length {
-                    if let Some(grapheme) = value.as_str()[self.selection.cursor.offset..]
-                        .graphemes(true)
-                        .next()
-                    {
-                        self.selection.cursor.offset += grapheme.len();
-                    } else {
-                        self.selection.cursor.offset = length;
-                    }
-                }
-            }
-        }
+        self.selection.move_by_grapheme(value.as_str(), affinity);
     }
 
     fn move_cursor_by_word(&mut self, affinity: Affinity) {
         let value = self.value.lock();
-        let length = value.as_str().len();
-        match affinity {
-            Affinity::Before => {
-                let mut words = value.as_str().unicode_word_indices().peekable();
-                while let Some((index, _)) = words.next() {
-                    let next_starts_after_selection = words
-                        .peek()
-                        .map_or(true, |(index, _)| *index >= self.selection.cursor.offset);
-                    if next_starts_after_selection {
-                        self.selection.cursor.offset = index;
-                        return;
-                    }
-                }
-
-                self.selection.cursor.offset = 0;
-            }
-            Affinity::After => {
-                if self.selection.cursor.offset < length {
-                    if let Some((index, word)) = value.as_str()[self.selection.cursor.offset..]
-                        .unicode_word_indices()
-                        .next()
-                    {
-                        self.selection.cursor.offset += index + word.len();
-                    } else {
-                        self.selection.cursor.offset = length;
-                    }
-                }
-            }
-        }
+        self.selection.move_by_word(value.as_str(), affinity);
     }
 
     fn move_cursor_by_line_extent(&mut self, affinity: Affinity, context: &mut EventContext<'_>) {
@@ -363,40 +258,18 @@ where
         self.selection.cursor = self.cursor_from_point(position, context);
     }
 
-    fn constrain_selection(&mut self) {
-        let length = self.value.map_ref(|s| s.as_str().len());
-        self.selection.cursor.offset = self.selection.cursor.offset.min(length);
-        if let Some(start) = &mut self.selection.start {
-            start.offset = start.offset.min(length);
-        }
-    }
-
     fn selected_range(&mut self) -> (Cursor, Option<Cursor>) {
-        self.constrain_selection();
-        match self.selection.start {
-            Some(start) => match start.offset.cmp(&self.selection.cursor.offset) {
-                Ordering::Less => (start, Some(self.selection.cursor)),
-                Ordering::Equal => {
-                    if self.mouse_buttons_down == 0 {
-                        self.selection.start = None;
-                    }
-                    (self.selection.cursor, None)
-                }
-                Ordering::Greater => (self.selection.cursor, Some(start)),
-            },
-            None => (self.selection.cursor, None),
-        }
+        let length = self.value.map_ref(|s| s.as_str().len());
+        self.selection.ordered(length, self.mouse_buttons_down > 0)
     }
 
     fn map_selected_text<R>(&mut self, map: impl FnOnce(&str) -> R) -> Option<R> {
-        let (cursor, Some(end)) = self.selected_range() else {
-            return None;
-        };
-
-        Some(
-            self.value
-                .map_ref(|value| map(&value.as_str()[cursor.offset..end.offset])),
-        )
+        let selecting = self.mouse_buttons_down > 0;
+        self.value.map_ref(|value| {
+            self.selection
+                .selected_text(value.as_str(), selecting)
+                .map(map)
+        })
     }
 
     fn is_masked(&self) -> bool {
@@ -408,14 +281,7 @@ where
             return;
         }
 
-        self.map_selected_text(|text| {
-            if let Some(mut clipboard) = context.cushy().clipboard_guard() {
-                match clipboard.set_text(text) {
-                    Ok(()) => {}
-                    Err(err) => tracing::error!("error copying to clipboard: {err}"),
-                }
-            }
-        });
+        self.map_selected_text(|text| selection::copy_to_clipboard(text, context));
     }
 
     fn replace_selection(&mut self, new_text: &str, context: &mut EventContext<'_>) {
@@ -423,22 +289,10 @@ where
             return;
         }
 
-        let selected_range = self.selected_range();
-        match selected_range {
-            (start, Some(end)) => {
-                self.replace_range(start, end, new_text);
-            }
-            (cursor, None) => {
-                let mut value = self.value.lock();
-                if cursor.offset < value.as_str().len() {
-                    value.as_string_mut().insert_str(cursor.offset, new_text);
-                    self.selection.cursor.offset += new_text.len();
-                } else {
-                    value.as_string_mut().push_str(new_text);
-                    self.selection.cursor.offset += new_text.len();
-                }
-            }
-        };
+        self.value.map_mut(|mut value| {
+            self.selection
+                .replace_selection(value.as_string_mut(), new_text);
+        });
     }
 
     fn paste_from_clipboard(&mut self, context: &mut EventContext<'_>) -> bool {
@@ -652,154 +506,23 @@ where
         }
     }
 
-    #[allow(clippy::too_many_lines)] // it's text layout, c'mon
+    fn map_rendered_text<R>(&self, cache: &CachedLayout, map: impl FnOnce(&str) -> R) -> R {
+        if cache.key.mask_bytes > 0 {
+            map(&self.mask)
+        } else {
+            self.value.map_ref(|value| map(value.as_str()))
+        }
+    }
+
     fn point_from_cursor(
         &self,
         cache: &CachedLayout,
         cursor: Cursor,
         total_bytes: usize,
     ) -> (Point<Px>, Px) {
-        if cache.measured.glyphs.is_empty()
-            || (cursor.offset == 0 && cursor.affinity == Affinity::Before)
-        {
-            return (Point::default(), Px::ZERO);
-        }
-
-        // Space between glyphs isn't represented in the glyphs. If the cursor rests
-        // within characters that have no glyphs (whitespace), we need to
-        // approximate the position based on the location of the nearest glyphs.
-        let mut closest_before_index = 0;
-        let mut closest_after_index = usize::MAX;
-        let mut bottom_right_index = 0;
-        let mut bottom_right_line = 0;
-        let mut bottom_right_rect = Rect::default();
-        let mut unrendered_offset = 0;
-        for (index, glyph) in cache.measured.glyphs.iter().enumerate() {
-            unrendered_offset = unrendered_offset.max(glyph.info.end);
-            let rect = glyph.rect();
-            if bottom_right_rect.size.width == 0
-                || glyph.info.line > bottom_right_line
-                || (glyph.info.line == bottom_right_line
-                    && rect.origin.x > bottom_right_rect.origin.x)
-            {
-                bottom_right_line = glyph.info.line;
-                bottom_right_index = index;
-                bottom_right_rect = rect;
-            }
-
-            match (
-                glyph.info.start.cmp(&cursor.offset),
-                cursor.offset.cmp(&glyph.info.end),
-            ) {
-                (Ordering::Less | Ordering::Equal, Ordering::Less) => {
-                    // cosmic text may have grouped multiple graphemes into a single glyph.
-                    let mut grapheme_offset = Px::ZERO;
-                    if glyph.info.start < cursor.offset {
-                        let clustered_bytes = glyph.info.end - glyph.info.start;
-                        if clustered_bytes > 1 {
-                            let clustered_graphemes = if cache.key.mask_bytes > 0 {
-                                self.mask[glyph.info.start..glyph.info.end]
-                                    .graphemes(true)
-                                    .count()
-                            } else {
-                                self.value.map_ref(|value| {
-                                    value.as_str()[glyph.info.start..glyph.info.end]
-                                        .graphemes(true)
-                                        .count()
-                                })
-                            };
-                            if clustered_graphemes > 1 {
-                                let cursor_offset = cursor.offset - glyph.info.start;
-
-                                grapheme_offset = rect.size.width * cursor_offset.cast::<f32>()
-                                    / clustered_graphemes.cast::<f32>();
-                            }
-                        }
-                    }
-
-                    return (
-                        Point::new(
-                            rect.origin.x + grapheme_offset,
-                            cache.measured.line_height.saturating_mul(Px::new(
-                                i32::try_from(glyph.info.line).unwrap_or(i32::MAX),
-                            )),
-                        ),
-                        rect.size.width,
-                    );
-                }
-                (Ordering::Less, _) => {
-                    closest_before_index = closest_before_index.max(index);
-                }
-                (_, Ordering::Less) => {
-                    closest_after_index = closest_after_index.min(index);
-                }
-                _ => {}
-            }
-        }
-
-        if closest_after_index == usize::MAX {
-            let bottom_right = &cache.measured.glyphs[bottom_right_index];
-            let bottom_y = cache.measured.line_height.saturating_mul(Px::new(
-                i32::try_from(bottom_right.info.line).unwrap_or(i32::MAX),
-            ));
-            // No glyph could be found that started/contained the cursors offset.
-            let mut bottom_right_cursor = Point::new(
-                bottom_right_rect.origin.x + bottom_right_rect.size.width,
-                bottom_y,
-            );
-            let bytes_after_glyph = total_bytes - unrendered_offset;
-            if !(bottom_right.info.end == cursor.offset || bytes_after_glyph == 0) {
-                // We're rendering past the end of the text. We shuld probably try to
-                // estimate the amount of whitespace should be visible based on the
-                // number of whitespace characters at the end of the text.
-                let space_past_glyph = bottom_right.info.line_width - bottom_right_cursor.x;
-                let space_per_byte =
-                    space_past_glyph.into_float() / bytes_after_glyph.cast::<f32>();
-                let cursor_position = space_per_byte
-                    * (cursor.offset.saturating_sub(unrendered_offset)).cast::<f32>();
-
-                bottom_right_cursor.x += Px::from(cursor_position);
-            }
-
-            // The cursor should be placed after the bottom_right glyph
-            (bottom_right_cursor, Px::ZERO)
-        } else {
-            let before = &cache.measured.glyphs[closest_before_index];
-            let after = &cache.measured.glyphs[closest_after_index];
-            let before_rect = before.rect();
-            let after_rect = after.rect();
-            let before_y = cache
-                .measured
-                .line_height
-                .saturating_mul(Px::new(i32::try_from(before.info.line).unwrap_or(i32::MAX)));
-
-            if before.info.line == after.info.line {
-                let before_right = before_rect.origin.x + before_rect.size.width;
-                let space_between = after_rect.origin.x - before_right;
-                let bytes_between = after.info.start - before.info.end;
-                let space_per_byte = space_between.into_float() / bytes_between.cast::<f32>();
-                let cursor_position =
-                    space_per_byte * (cursor.offset - before.info.end).cast::<f32>();
-
-                (
-                    Point::new(before_right + Px::from(cursor_position), before_y),
-                    Px::from(space_per_byte),
-                )
-            } else {
-                match cursor.affinity {
-                    Affinity::Before => {
-                        // TODO We need to look out for whitespace at the end of the line.
-                        let mut origin = before_rect.origin;
-                        origin.x += before_rect.size.width;
-                        (origin, before_y)
-                    }
-                    Affinity::After => (
-                        Point::new(Px::ZERO, before_y + cache.measured.line_height),
-                        Px::ZERO,
-                    ),
-                }
-            }
-        }
+        self.map_rendered_text(cache, |text| {
+            selection::point_from_cursor(&cache.measured, text, cursor, total_bytes)
+        })
     }
 
     fn cursor_from_point(&mut self, location: Point<Px>, context: &mut EventContext<'_>) -> Cursor {
@@ -831,102 +554,7 @@ where
             .get(&IntrinsicPadding)
             .into_px(context.kludgine.scale())
             .round();
-        let mut location = location - padding;
-        if location.y < 0 {
-            location.y = Px::ZERO;
-        }
-        if location.x < 0 {
-            location.x = Px::ZERO;
-        }
-
-        let mut closest: Option<(Cursor, i32, usize, Point<Px>)> = None;
-        let mut current_line = usize::MAX;
-        let mut current_line_y = Px::ZERO;
-        for (index, glyph) in cache.measured.glyphs.iter().enumerate() {
-            if current_line != glyph.info.line {
-                current_line = glyph.info.line;
-
-                current_line_y = cache
-                    .measured
-                    .line_height
-                    .saturating_mul(Px::new(i32::try_from(current_line).unwrap_or(i32::MAX)));
-            }
-            let mut rect = glyph.rect();
-            if !glyph.visible() {
-                rect.size.height = cache.measured.line_height;
-            }
-            let relative = location - Point::new(rect.origin.x, current_line_y);
-            if relative.x >= 0
-                && relative.y >= 0
-                && relative.x <= rect.size.width
-                && relative.y <= cache.measured.line_height
-            {
-                return if relative.x > rect.size.width / 2 {
-                    if glyph.info.end < cache.bytes {
-                        Cursor {
-                            offset: glyph.info.end,
-                            affinity: Affinity::Before,
-                        }
-                    } else {
-                        Cursor {
-                            offset: glyph.info.start,
-                            affinity: Affinity::After,
-                        }
-                    }
-                } else {
-                    Cursor {
-                        offset: glyph.info.start,
-                        affinity: Affinity::Before,
-                    }
-                };
-            }
-
-            // Make relative be relative to the center of the glyph for a nearest search.
-            let relative = relative + rect.size / 2;
-
-            let line_height = cache.measured.line_height.get();
-            let xy = relative
-                .x
-                .get()
-                .saturating_mul(
-                    ((relative.y.get() + line_height - 1) / line_height * line_height)
-                        .saturating_pow(2),
-                )
-                .saturating_abs();
-            let cursor = Cursor {
-                offset: if relative.x < 0 || relative.y < 0 {
-                    glyph.info.start
-                } else {
-                    glyph.info.end
-                },
-                affinity: Affinity::Before,
-            };
-            match closest {
-                Some((_, closest_xy, ..)) if xy < closest_xy => {
-                    closest = Some((cursor, xy, index, relative));
-                }
-                None => closest = Some((cursor, xy, index, relative)),
-                _ => {}
-            }
-        }
-
-        if let Some((closest, _, index, relative)) = closest {
-            // Having whitespace not in the measured text is really annoying.
-            // This trick only works for the first line of text. Maybe we should
-            // try and create a structure that organizes the glyphs into lines
-            // so that it's easier to inspect and detect when there's
-            // whitespace. For now, this is just a hack that helps get *some*
-            // selection at the end of the input for trailing whitespace.
-            if relative.x.abs() < cache.measured.line_height && index < cache.measured.glyphs.len()
-            {
-                return closest;
-            }
-        }
-
-        Cursor {
-            offset: cache.bytes,
-            affinity: Affinity::After,
-        }
+        selection::cursor_from_point(&cache.measured, cache.bytes, location - padding)
     }
 }
 
@@ -981,8 +609,14 @@ where
         self.mouse_buttons_down += 1;
         context.focus();
         self.needs_to_select_all = false;
-        self.selection.cursor = self.cursor_from_point(location, context);
-        self.selection.start = Some(self.selection.cursor);
+        let clicks = self.clicks.click(location);
+        let cursor = self.cursor_from_point(location, context);
+        if clicks > 1 && self.is_masked() {
+            self.select_all();
+        } else {
+            self.value
+                .map_ref(|value| self.selection.click(value.as_str(), cursor, clicks));
+        }
         context.set_needs_redraw();
         HANDLED
     }
@@ -1065,74 +699,24 @@ where
                 ImePurpose::Normal
             });
 
-            if let Some(selection) = info.selection {
-                let (start, end) = if selection < info.cursor {
-                    (selection, info.cursor)
+            if let Some(selection_start) = info.selection {
+                let (start, end) = if selection_start < info.cursor {
+                    (selection_start, info.cursor)
                 } else {
-                    (info.cursor, selection)
+                    (info.cursor, selection_start)
                 };
 
-                let (start_position, _) =
-                    self.point_from_cursor(info.cache, start, info.cache.bytes);
-                let (end_position, end_width) =
-                    self.point_from_cursor(info.cache, end, info.cache.bytes);
-
-                if start_position.y == end_position.y {
-                    // Single line selection
-                    let width = end_position.x - start_position.x;
-                    context.gfx.draw_shape(
-                        Shape::filled_rect(
-                            Rect::new(
-                                start_position,
-                                Size::new(width, info.cache.measured.line_height),
-                            ),
-                            highlight,
-                        )
-                        .translate_by(padding),
+                self.map_rendered_text(info.cache, |text| {
+                    selection::draw_selection(
+                        context,
+                        &info.cache.measured,
+                        text,
+                        (start, end),
+                        size.width.into_signed(),
+                        highlight,
+                        padding,
                     );
-                } else {
-                    // Draw from start to end of line,
-                    let width = size.width.into_signed() - start_position.x;
-                    context.gfx.draw_shape(
-                        Shape::filled_rect(
-                            Rect::new(
-                                start_position,
-                                Size::new(width, info.cache.measured.line_height),
-                            ),
-                            highlight,
-                        )
-                        .translate_by(padding),
-                    );
-                    // Fill region between
-                    let bottom_of_first_line = start_position.y + info.cache.measured.line_height;
-                    let distance_between = end_position.y - bottom_of_first_line;
-                    if distance_between > 0 {
-                        context.gfx.draw_shape(
-                            Shape::filled_rect(
-                                Rect::new(
-                                    Point::new(Px::ZERO, bottom_of_first_line),
-                                    Size::new(size.width.into_signed(), distance_between),
-                                ),
-                                highlight,
-                            )
-                            .translate_by(padding),
-                        );
-                    }
-                    // Draw from 0 to end + width
-                    context.gfx.draw_shape(
-                        Shape::filled_rect(
-                            Rect::new(
-                                Point::new(Px::ZERO, end_position.y),
-                                Size::new(
-                                    end_position.x + end_width,
-                                    info.cache.measured.line_height,
-                                ),
-                            ),
-                            highlight,
-                        )
-                        .translate_by(padding),
-                    );
-                }
+                });
             } else if window_focused && context.enabled() {
                 let (location, _) =
                     self.point_from_cursor(info.cache, info.cursor, info.cache.bytes);
@@ -1247,9 +831,9 @@ where
 struct NotVisible(Point<Px>, usize);
 
 #[derive(Clone, Copy)]
-struct BlinkState {
-    visible: bool,
-    remaining_until_blink: Duration,
+pub(crate) struct BlinkState {
+    pub visible: bool,
+    pub remaining_until_blink: Duration,
 }
 
 impl Default for BlinkState {
@@ -0,0 +1,316 @@
+//! Forms that edit the fields of a value.
+//!
+//! The [`Editable`] trait describes how to build a widget that edits a value.
+//! It is implemented for common primitive types and can be derived for structs
+//! and fieldless enums. [`Inspector`] displays the editor for a value in a
+//! scrollable region.
+//!
+//! ```rust
+//! use cushy::value::Dynamic;
+//! use cushy::widgets::inspector::{Editable, Inspector};
+//!
+//! #[derive(Editable, Clone, PartialEq)]
+//! enum Quality {
+//!     Low,
+//!     High,
+//! }
+//!
+//! #[derive(Editable, Clone, PartialEq)]
+//! struct Display {
+//!     #[editable(label = "Fullscreen mode")]
+//!     fullscreen: bool,
+//!     #[editable(slider, min = 0.5, max = 2.0)]
+//!     scale: f32,
+//!     #[editable(min = 30, max = 240, step = 10)]
+//!     max_fps: u32,
+//!     quality: Quality,
+//! }
+//!
+//! #[derive(Editable, Clone, PartialEq)]
+//! struct Settings {
+//!     #[editable(validate = not_empty)]
+//!     name: String,
+//!     display: Display,
+//!     #[editable(skip)]
+//!     revision: u64,
+//! }
+//!
+//! fn not_empty(name: &String) -> Result<(), &'static str> {
+//!     if name.is_empty() {
+//!         Err("name is required")
+//!     } else {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let settings = Dynamic::new(Settings {
+//!     name: String::from("Player"),
+//!     display: Display {
+//!         fullscreen: false,
+//!         scale: 1.0,
+//!         max_fps: 60,
+//!         quality: Quality::High,
+//!     },
+//!     revision: 0,
+//! });
+//! let inspector = Inspector::new(settings);
+//! ```
+use crate::value::{CallbackDisconnected, Destination, Dynamic, IntoDynamic, Source};
+use crate::widget::{MakeWidget, MakeWidgetWithTag, WidgetInstance, WidgetList, WidgetTag};
+use crate::widgets::checkbox::Checkable;
+use crate::widgets::grid::{GridDimension, GridWidgets};
+use crate::widgets::input::InputValue;
+use crate::widgets::{Disclose, Grid, NumberInput};
+
+/// A type that can be edited by a widget.
+///
+/// This trait can be derived for structs and fieldless enums:
+///
+/// - Structs edit each field using a labeled [`Form`]. Fields that are
+///   themselves structs deriving `Editable` become collapsible sections.
+/// - Enums edit the value by selecting one of the variants.
+///
+/// Each field or variant can be customized using `#[editable(...)]`:
+///
+/// | Option | Effect |
+/// |--------|--------|
+/// | `skip` | The field or variant is omitted. |
+/// | `label = "Label"` | Uses `Label` instead of a label generated from the name. |
+/// | `min = expr`, `max = expr`, `step = expr` | Edits a number using a [`NumberInput`] with these limits. |
+/// | `slider` | Edits the field using a [`Slider`](crate::widgets::Slider). `min` and `max` are required. |
+/// | `text` | Edits the field as a string using [`Dynamic::linked_string`]. |
+/// | `with = path` | Calls `path(Dynamic<FieldType>)` to create the editor. |
+/// | `validate = path` | Displays the result of `path(&FieldType) -> Result<(), impl Display>` beneath the editor. |
+///
+/// Each field is [linked](linked_field) to the edited value, so changes made
+/// in the form are written back to the original [`Dynamic`], and changes to
+/// the original are reflected in the form.
+pub trait Editable: Clone + PartialEq + Send + 'static {
+    /// When true, [`Form::edit`] places this type's editor in a collapsible
+    /// section rather than next to its label.
+    const SECTION: bool = false;
+
+    /// Returns a widget that edits `value`.
+    fn editor(value: Dynamic<Self>) -> WidgetInstance;
+}
+
+/// Derives [`Editable`](trait@Editable) for structs and fieldless enums.
+pub use cushy_macros::Editable;
+
+impl Editable for String {
+    fn editor(value: Dynamic<Self>) -> WidgetInstance {
+        value.into_input().make_widget()
+    }
+}
+
+impl Editable for bool {
+    fn editor(value: Dynamic<Self>) -> WidgetInstance {
+        value.into_checkbox("").make_widget()
+    }
+}
+
+macro_rules! impl_editable_number {
+    ($($type:ty),+) => {
+        $(
+            impl Editable for $type {
+                fn editor(value: Dynamic<Self>) -> WidgetInstance {
+                    NumberInput::new(value).make_widget()
+                }
+            }
+        )+
+    };
+}
+
+impl_editable_number!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+/// Returns a dynamic containing the field of `parent` returned by `field`.
+///
+/// The returned dynamic is updated when `parent` changes, and updating the
+/// returned dynamic writes the new value back into `parent` using
+/// `field_mut`. This is how [`Editable`] links each field's editor to the
+/// value being edited.
+pub fn linked_field<T, F, Get, GetMut>(
+    parent: &Dynamic<T>,
+    field: Get,
+    field_mut: GetMut,
+) -> Dynamic<F>
+where
+    T: Send + 'static,
+    F: Clone + PartialEq + Send + 'static,
+    Get: Fn(&T) -> &F + Clone + Send + 'static,
+    GetMut: Fn(&mut T) -> &mut F + Send + 'static,
+{
+    let child = Dynamic::new(parent.map_ref(|parent| field(parent).clone()));
+
+    let weak_child = child.downgrade();
+    child.set_source(parent.for_each_try({
+        let field = field.clone();
+        move |parent| {
+            let child = weak_child.upgrade().ok_or(CallbackDisconnected)?;
+            child.set(field(parent).clone());
+            Ok(())
+        }
+    }));
+
+    let weak_parent = parent.downgrade();
+    parent.set_source(child.for_each_try(move |child| {
+        let parent = weak_parent.upgrade().ok_or(CallbackDisconnected)?;
+        // The parent is locked when this change originated from the parent.
+        let _result = parent.try_map_mut(|mut parent| {
+            if field(&*parent) != child {
+                *field_mut(&mut *parent) = child.clone();
+            }
+        });
+        Ok(())
+    }));
+
+    child
+}
+
+/// A labeled list of editors.
+///
+/// Fields are displayed with their labels aligned in a column. Sections are
+/// displayed as [`Disclose`] widgets spanning the width of the form.
+#[derive(Debug, Default)]
+#[must_use]
+pub struct Form {
+    parts: Vec<FormPart>,
+}
+
+#[derive(Debug)]
+enum FormPart {
+    Field(WidgetInstance, WidgetInstance),
+    Section(WidgetInstance, WidgetInstance),
+}
+
+impl Form {
+    /// Returns an empty form.
+    pub const fn new() -> Self {
+        Self { parts: Vec::new() }
+    }
+
+    /// Adds `editor` labeled by `label` and returns self.
+    pub fn field(mut self, label: impl MakeWidget, editor: impl MakeWidget) -> Self {
+        self.parts
+            .push(FormPart::Field(label.make_widget(), editor.make_widget()));
+        self
+    }
+
+    /// Adds a collapsible section labeled by `label` that shows `contents`,
+    /// and returns self.
+    pub fn section(mut self, label: impl MakeWidget, contents: impl MakeWidget) -> Self {
+        self.parts.push(FormPart::Section(
+            label.make_widget(),
+            contents.make_widget(),
+        ));
+        self
+    }
+
+    /// Adds the [`Editable::editor`] for `value` labeled by `label` and returns
+    /// self.
+    ///
+    /// If [`Editable::SECTION`] is true, the editor is added as a
+    /// [section](Self::section).
+    pub fn edit<T>(self, label: impl MakeWidget, value: Dynamic<T>) -> Self
+    where
+        T: Editable,
+    {
+        if T::SECTION {
+            self.section(label, T::editor(value))
+        } else {
+            self.field(label, T::editor(value))
+        }
+    }
+}
+
+impl MakeWidgetWithTag for Form {
+    fn make_with_tag(self, id: WidgetTag) -> WidgetInstance {
+        fn finish_grid(fields: &mut GridWidgets<2>, widgets: &mut WidgetList) {
+            if !fields.is_empty() {
+                widgets.push(Grid::from_rows(std::mem::take(fields)).dimensions([
+                    GridDimension::FitContent,
+                    GridDimension::Fractional { weight: 1 },
+                ]));
+            }
+        }
+
+        let mut widgets = WidgetList::new();
+        let mut fields = GridWidgets::new();
+        for part in self.parts {
+            match part {
+                FormPart::Field(label, editor) => {
+                    fields.push([label.align_left().make_widget(), editor].into());
+                }
+                FormPart::Section(label, contents) => {
+                    finish_grid(&mut fields, &mut widgets);
+                    widgets.push(Disclose::new(contents).labelled_by(label));
+                }
+            }
+        }
+        finish_grid(&mut fields, &mut widgets);
+
+        widgets.into_rows().make_with_tag(id)
+    }
+}
+
+/// A widget that displays the [`Editable::editor`] for a value.
+#[derive(Debug)]
+#[must_use]
+pub struct Inspector<T> {
+    value: Dynamic<T>,
+}
+
+impl<T> Inspector<T>
+where
+    T: Editable,
+{
+    /// Returns an inspector that edits `value`.
+    pub fn new(value: impl IntoDynamic<T>) -> Self {
+        Self {
+            value: value.into_dynamic(),
+        }
+    }
+}
+
+impl<T> MakeWidgetWithTag for Inspector<T>
+where
+    T: Editable,
+{
+    fn make_with_tag(self, id: WidgetTag) -> WidgetInstance {
+        T::editor(self.value).vertical_scroll().make_with_tag(id)
+    }
+}
+
+#[test]
+fn linked_fields() {
+    #[derive(Clone, PartialEq, Debug)]
+    struct Pair {
+        a: u32,
+        b: String,
+    }
+
+    let pair = Dynamic::new(Pair {
+        a: 1,
+        b: String::from("one"),
+    });
+    let a = linked_field(&pair, |pair| &pair.a, |pair| &mut pair.a);
+    let b = linked_field(&pair, |pair| &pair.b, |pair| &mut pair.b);
+
+    a.set(2);
+    assert_eq!(pair.map_ref(|pair| pair.a), 2);
+    b.set(String::from("two"));
+    assert_eq!(
+        pair.get(),
+        Pair {
+            a: 2,
+            b: String::from("two")
+        }
+    );
+
+    pair.set(Pair {
+        a: 3,
+        b: String::from("three"),
+    });
+    assert_eq!(a.get(), 3);
+    assert_eq!(b.get(), "three");
+}
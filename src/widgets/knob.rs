@@ -0,0 +1,502 @@
+//! A rotary control for adjusting a value.
+use figures::units::{Lp, Px, UPx};
+use figures::{Angle, FloatConversion, Point, Round, ScreenScale, Size, Zero};
+use intentional::Cast;
+use kludgine::app::winit::event::{MouseButton, MouseScrollDelta, TouchPhase};
+use kludgine::app::winit::keyboard::{Key, NamedKey};
+use kludgine::app::winit::window::CursorIcon;
+use kludgine::shapes::{Path, Shape, StrokeOptions};
+use kludgine::{Color, DrawableExt, Origin};
+
+use crate::animation::{LinearInterpolate, PercentBetween, ZeroToOne};
+use crate::context::{EventContext, GraphicsContext, LayoutContext};
+use crate::styles::components::{AutoFocusableControls, OutlineColor};
+use crate::styles::Dimension;
+use crate::value::{Destination, Dynamic, IntoDynamic, IntoValue, Source, Value};
+use crate::widget::{EventHandling, Widget, HANDLED, IGNORED};
+use crate::widgets::selection::ClickCounter;
+use crate::widgets::slider::{
+    DisabledInactiveTrackColor, DisabledKnobColor, DisabledTrackColor, InactiveTrackColor,
+    KnobColor, KnobSize, SliderValue, TickColor, TrackColor, TrackSize,
+};
+use crate::window::{DeviceId, KeyEvent};
+use crate::ConstraintLimit;
+
+/// The angle the track starts at, measured clockwise from the right.
+const TRACK_START: f32 = 135.;
+/// The number of degrees the track sweeps through.
+const TRACK_SWEEP: f32 = 270.;
+
+/// A circular control that adjusts a value by rotating.
+///
+/// Dragging the knob vertically, scrolling the mouse wheel, or pressing the
+/// arrow keys adjusts the value. Double clicking the knob resets it to its
+/// default value, which is the value the knob was created with unless
+/// [`default_value`](Self::default_value) is used.
+///
+/// Ranged values, such as `RangeInclusive<T>`, adjust the end of the range
+/// while the arc is drawn from the start of the range.
+#[derive(Debug)]
+pub struct Knob<T>
+where
+    T: SliderValue,
+{
+    /// The current value.
+    pub value: Dynamic<T>,
+    /// The minimum value represented by this knob.
+    pub minimum: Value<T::Value>,
+    /// The maximum value represented by this knob.
+    pub maximum: Value<T::Value>,
+    /// The percentage to step when adjusting the knob using the keyboard or
+    /// mouse wheel.
+    ///
+    /// This defaults to `0.05`/5%. When detents are enabled, the knob instead
+    /// steps between detents.
+    pub step: Value<ZeroToOne>,
+    default: T,
+    center: Option<Value<T::Value>>,
+    detents: Option<usize>,
+    drag_start: Option<(Px, ZeroToOne)>,
+    clicks: ClickCounter,
+}
+
+impl<T> Knob<T>
+where
+    T: SliderValue,
+{
+    /// Returns a new knob using `value` as the knob's value, keeping the value
+    /// between `min` and `max`.
+    #[must_use]
+    pub fn new(
+        value: impl IntoDynamic<T>,
+        min: impl IntoValue<T::Value>,
+        max: impl IntoValue<T::Value>,
+    ) -> Self {
+        let value = value.into_dynamic();
+        Self {
+            default: value.get(),
+            value,
+            minimum: min.into_value(),
+            maximum: max.into_value(),
+            step: Value::Constant(ZeroToOne::new(0.05)),
+            center: None,
+            detents: None,
+            drag_start: None,
+            clicks: ClickCounter::default(),
+        }
+    }
+
+    /// Sets the value this knob is reset to when double clicked and returns
+    /// self.
+    #[must_use]
+    pub fn default_value(mut self, default: T) -> Self {
+        self.default = default;
+        self
+    }
+
+    /// The percentage to step when adjusting the knob using the keyboard or
+    /// mouse wheel.
+    ///
+    /// This defaults to `0.05`/5%.
+    #[must_use]
+    pub fn step_by(mut self, percent: impl IntoValue<ZeroToOne>) -> Self {
+        self.step = percent.into_value();
+        self
+    }
+
+    /// Snaps the knob to `count` evenly spaced stops, including at both ends
+    /// of the track, and returns self.
+    ///
+    /// The stops are drawn inside of the track.
+    #[must_use]
+    pub fn detents(mut self, count: usize) -> Self {
+        self.detents = Some(count);
+        self
+    }
+
+    /// Draws the active portion of the track from `center` to the current
+    /// value rather than from the minimum, and returns self.
+    ///
+    /// This is useful for values like panning or balance, where the knob
+    /// adjusts a value in either direction from a neutral position.
+    #[must_use]
+    pub fn bipolar(mut self, center: impl IntoValue<T::Value>) -> Self {
+        self.center = Some(center.into_value());
+        self
+    }
+
+    fn position(&self, min: &T::Value, max: &T::Value) -> ZeroToOne {
+        let (start, end) = T::into_parts(self.value.get());
+        end.unwrap_or(start).percent_between(min, max)
+    }
+
+    fn set_position(&mut self, position: ZeroToOne) {
+        let min = self.minimum.get();
+        let max = self.maximum.get();
+        let position = snap(position, self.detents);
+        let value = min.lerp(&max, *position);
+        let new_value = match T::into_parts(self.value.get()) {
+            (_, None) => T::from_parts(value, None),
+            (start, Some(_)) => {
+                let end = if value < start { start.clone() } else { value };
+                T::from_parts(start, Some(end))
+            }
+        };
+        self.value.set(new_value);
+    }
+
+    fn step(&mut self, forwards: bool, factor: f32) {
+        let amount = match self.detents {
+            Some(count) if count > 1 => 1. / (count - 1).cast::<f32>(),
+            _ => *self.step.get(),
+        } * factor;
+        let current = *self.position(&self.minimum.get(), &self.maximum.get());
+        let next = if forwards {
+            current + amount
+        } else {
+            current - amount
+        };
+        self.set_position(ZeroToOne::new(next));
+    }
+
+    fn draw_arc(
+        center: Point<Px>,
+        radius: Px,
+        start: ZeroToOne,
+        end: ZeroToOne,
+        track_size: Px,
+        color: Color,
+        context: &mut GraphicsContext<'_, '_, '_, '_>,
+    ) {
+        if end > start {
+            context.gfx.draw_shape(
+                &Path::arc(
+                    center,
+                    Size::squared(radius),
+                    track_angle(start),
+                    Angle::degrees_f((*end - *start) * TRACK_SWEEP),
+                )
+                .stroke(StrokeOptions::px_wide(track_size).colored(color)),
+            );
+        }
+    }
+}
+
+/// Returns the angle of `position` along the track.
+fn track_angle(position: ZeroToOne) -> Angle {
+    Angle::degrees_f(TRACK_START + *position * TRACK_SWEEP)
+}
+
+/// Returns `position` snapped to the nearest of `detents` evenly spaced stops.
+fn snap(position: ZeroToOne, detents: Option<usize>) -> ZeroToOne {
+    match detents {
+        Some(count) if count > 1 => {
+            let intervals = (count - 1).cast::<f32>();
+            ZeroToOne::new((*position * intervals).round() / intervals)
+        }
+        _ => position,
+    }
+}
+
+/// Returns the portion of the track that is drawn as active.
+fn active_arc(origin: ZeroToOne, position: ZeroToOne) -> (ZeroToOne, ZeroToOne) {
+    if position < origin {
+        (position, origin)
+    } else {
+        (origin, position)
+    }
+}
+
+impl<T> Widget for Knob<T>
+where
+    T: SliderValue,
+{
+    fn redraw(&mut self, context: &mut GraphicsContext<'_, '_, '_, '_>) {
+        let (track_color, inactive_track_color, knob_color) = if context.enabled() {
+            (
+                context.get(&TrackColor),
+                context.get(&InactiveTrackColor),
+                context.get(&KnobColor),
+            )
+        } else {
+            (
+                context.get(&DisabledTrackColor),
+                context.get(&DisabledInactiveTrackColor),
+                context.get(&DisabledKnobColor),
+            )
+        };
+        let scale = context.gfx.scale();
+        let track_size = context.get(&TrackSize).into_px(scale);
+        let half_knob = (context.get(&KnobSize).into_px(scale) / 2).round();
+        let focus_ring_width = (Lp::points(2).into_px(scale) / 2).ceil() * 2;
+
+        let value = self.value.get_tracking_redraw(context);
+        let min = self.minimum.get_tracking_redraw(context);
+        let max = self.maximum.get_tracking_redraw(context);
+        let (start, end) = T::into_parts(value);
+        let position = end.as_ref().unwrap_or(&start).percent_between(&min, &max);
+        let origin = match (&self.center, &end) {
+            (_, Some(_)) => start.percent_between(&min, &max),
+            (Some(center), None) => {
+                center.map_tracking_redraw(context, |center| center.percent_between(&min, &max))
+            }
+            (None, None) => ZeroToOne::ZERO,
+        };
+
+        let size = context.gfx.region().size;
+        let center = (Point::from(size) / 2).round();
+        let radius = size.width.min(size.height) / 2 - half_knob - focus_ring_width;
+        if radius <= 0 {
+            return;
+        }
+        Self::draw_arc(
+            center,
+            radius,
+            ZeroToOne::ZERO,
+            ZeroToOne::ONE,
+            track_size,
+            inactive_track_color,
+            context,
+        );
+        let (active_start, active_end) = active_arc(origin, position);
+        Self::draw_arc(
+            center,
+            radius,
+            active_start,
+            active_end,
+            track_size,
+            track_color,
+            context,
+        );
+
+        if let Some(count) = self.detents.filter(|count| *count > 1) {
+            let tick_color = context.get(&TickColor);
+            let detent_radius = (track_size / 4).max(Px::new(1));
+            let intervals = (count - 1).cast::<f32>();
+            for index in 0..count {
+                let angle = track_angle(ZeroToOne::new(index.cast::<f32>() / intervals));
+                let location = Point::new(radius - track_size * 3 / 2, Px::ZERO).rotate_by(angle);
+                context.gfx.draw_shape(
+                    Shape::filled_circle(detent_radius, tick_color, Origin::Center)
+                        .translate_by(center + location),
+                );
+            }
+        }
+
+        let knob_center = center + Point::new(radius, Px::ZERO).rotate_by(track_angle(position));
+        context.gfx.draw_shape(
+            Shape::filled_circle(half_knob, knob_color, Origin::Center).translate_by(knob_center),
+        );
+        if context.focused(true) {
+            let focus_color = context.get(&OutlineColor);
+            context.gfx.draw_shape(
+                Shape::stroked_circle(
+                    half_knob,
+                    Origin::Center,
+                    StrokeOptions::px_wide(focus_ring_width).colored(focus_color),
+                )
+                .translate_by(knob_center),
+            );
+        }
+    }
+
+    fn layout(
+        &mut self,
+        available_space: Size<ConstraintLimit>,
+        context: &mut LayoutContext<'_, '_, '_, '_>,
+    ) -> Size<UPx> {
+        let dial_size = context.get(&DialSize).into_upx(context.gfx.scale());
+        let size = available_space
+            .map(|constraint| constraint.fit_measured(dial_size, context.gfx.scale()));
+        Size::squared(size.width.min(size.height))
+    }
+
+    fn hit_test(&mut self, _location: Point<Px>, _context: &mut EventContext<'_>) -> bool {
+        true
+    }
+
+    fn hover(
+        &mut self,
+        _location: Point<Px>,
+        context: &mut EventContext<'_>,
+    ) -> Option<CursorIcon> {
+        Some(if context.enabled() {
+            CursorIcon::NsResize
+        } else {
+            CursorIcon::NotAllowed
+        })
+    }
+
+    fn accept_focus(&mut self, context: &mut EventContext<'_>) -> bool {
+        context.enabled() && context.get(&AutoFocusableControls).is_all()
+    }
+
+    fn focus(&mut self, context: &mut EventContext<'_>) {
+        context.set_needs_redraw();
+    }
+
+    fn blur(&mut self, context: &mut EventContext<'_>) {
+        context.set_needs_redraw();
+    }
+
+    fn mouse_down(
+        &mut self,
+        location: Point<Px>,
+        _device_id: DeviceId,
+        button: MouseButton,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        if button != MouseButton::Left {
+            return IGNORED;
+        }
+        if !context.enabled() {
+            return HANDLED;
+        }
+
+        context.focus();
+        if self.clicks.click(location) == 2 {
+            self.value.set(self.default.clone());
+            self.drag_start = None;
+        } else {
+            let position = self.position(&self.minimum.get(), &self.maximum.get());
+            self.drag_start = Some((location.y, position));
+        }
+        HANDLED
+    }
+
+    fn mouse_drag(
+        &mut self,
+        location: Point<Px>,
+        _device_id: DeviceId,
+        _button: MouseButton,
+        context: &mut EventContext<'_>,
+    ) {
+        let Some((start_y, start_position)) = self.drag_start else {
+            return;
+        };
+        // Dragging upwards increases the value, and a full sweep of the track
+        // takes the same distance regardless of the knob's size.
+        let sweep_distance = context.get(&KnobDragDistance).into_px(context.gfx.scale());
+        let delta = (start_y - location.y).into_float() / sweep_distance.into_float();
+        self.set_position(ZeroToOne::new(*start_position + delta));
+    }
+
+    fn mouse_up(
+        &mut self,
+        _location: Option<Point<Px>>,
+        _device_id: DeviceId,
+        button: MouseButton,
+        _context: &mut EventContext<'_>,
+    ) {
+        if button == MouseButton::Left {
+            self.drag_start = None;
+        }
+    }
+
+    fn keyboard_input(
+        &mut self,
+        _device_id: DeviceId,
+        input: KeyEvent,
+        _is_synthetic: bool,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        let forwards = match input.logical_key {
+            Key::Named(NamedKey::ArrowLeft | NamedKey::ArrowDown) => false,
+            Key::Named(NamedKey::ArrowRight | NamedKey::ArrowUp) => true,
+            _ => return IGNORED,
+        };
+        if input.state.is_pressed() && context.enabled() {
+            self.step(forwards, 1.);
+        }
+
+        HANDLED
+    }
+
+    fn mouse_wheel(
+        &mut self,
+        _device_id: DeviceId,
+        delta: MouseScrollDelta,
+        _phase: TouchPhase,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        if context.enabled() {
+            let factor: f32 = match delta {
+                MouseScrollDelta::LineDelta(_, y) => y,
+                MouseScrollDelta::PixelDelta(pt) => pt.y.cast(),
+            };
+
+            let (forwards, factor) = if factor.is_sign_negative() {
+                (false, -factor)
+            } else {
+                (true, factor)
+            };
+
+            self.step(forwards, factor);
+        }
+
+        HANDLED
+    }
+
+    fn summarize(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_struct("Knob")
+            .field("value", &self.value)
+            .field("min", &self.minimum)
+            .field("max", &self.maximum)
+            .finish()
+    }
+}
+
+define_components! {
+    Knob {
+        /// The width and height of a [`Knob`].
+        DialSize(Dimension, "dial_size", Dimension::Lp(Lp::points(48)))
+        /// The vertical distance a [`Knob`] must be dragged to sweep across its
+        /// entire track.
+        KnobDragDistance(Dimension, "drag_distance", Dimension::Lp(Lp::points(200)))
+    }
+}
+
+/// A value that can be adjusted using a [`Knob`].
+pub trait Turnable<T>: IntoDynamic<T> + Sized
+where
+    T: SliderValue,
+{
+    /// Returns a new knob using the value of `self`. The knob will be limited
+    /// to values between `min` and `max`.
+    fn knob_between(self, min: impl IntoValue<T::Value>, max: impl IntoValue<T::Value>) -> Knob<T> {
+        Knob::new(self.into_dynamic(), min, max)
+    }
+}
+
+impl<U, T> Turnable<U> for T
+where
+    T: IntoDynamic<U>,
+    U: SliderValue,
+{
+}
+
+#[test]
+fn detent_snapping() {
+    assert_eq!(snap(ZeroToOne::new(0.3), None), ZeroToOne::new(0.3));
+    assert_eq!(snap(ZeroToOne::new(0.3), Some(5)), ZeroToOne::new(0.25));
+    assert_eq!(snap(ZeroToOne::new(0.4), Some(5)), ZeroToOne::new(0.5));
+    assert_eq!(snap(ZeroToOne::new(0.9), Some(2)), ZeroToOne::ONE);
+    assert_eq!(snap(ZeroToOne::new(0.9), Some(1)), ZeroToOne::new(0.9));
+}
+
+#[test]
+fn bipolar_arc() {
+    let center = ZeroToOne::new(0.5);
+    assert_eq!(
+        active_arc(center, ZeroToOne::new(0.2)),
+        (ZeroToOne::new(0.2), center)
+    );
+    assert_eq!(
+        active_arc(center, ZeroToOne::new(0.8)),
+        (center, ZeroToOne::new(0.8))
+    );
+    assert_eq!(
+        active_arc(ZeroToOne::ZERO, ZeroToOne::new(0.8)),
+        (ZeroToOne::ZERO, ZeroToOne::new(0.8))
+    );
+}
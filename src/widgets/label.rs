@@ -4,15 +4,19 @@ use std::fmt::{Display, Write};
 
 use figures::units::{Px, UPx};
 use figures::{Point, Round, Size};
+use kludgine::app::winit::event::MouseButton;
+use kludgine::app::winit::window::CursorIcon;
 use kludgine::text::{MeasuredText, Text, TextOrigin};
 use kludgine::{CanRenderTo, Color, DrawableExt};
 
-use super::input::CowString;
-use crate::context::{GraphicsContext, LayoutContext, Trackable, WidgetContext};
-use crate::styles::components::TextColor;
+use super::input::{CowString, Cursor, SelectionState};
+use super::selection::{self, ClickCounter};
+use crate::context::{EventContext, GraphicsContext, LayoutContext, Trackable, WidgetContext};
+use crate::styles::components::{AutoFocusableControls, HighlightColor, TextColor};
+use crate::utils::ModifiersExt;
 use crate::value::{Dynamic, Generation, IntoReadOnly, ReadOnly, Value};
-use crate::widget::{Widget, WidgetInstance};
-use crate::window::WindowLocal;
+use crate::widget::{EventHandling, Widget, WidgetInstance, HANDLED, IGNORED};
+use crate::window::{DeviceId, KeyEvent, WindowLocal};
 use crate::ConstraintLimit;
 
 /// A read-only text widget.
@@ -22,6 +26,14 @@ pub struct Label<T> {
     pub display: ReadOnly<T>,
     displayed: String,
     prepared_text: WindowLocal<(MeasuredText<Px>, Option<Generation>, Px, Color)>,
+    selection: Option<LabelSelection>,
+}
+
+#[derive(Debug, Default)]
+struct LabelSelection {
+    state: SelectionState,
+    clicks: ClickCounter,
+    mouse_buttons_down: usize,
 }
 
 impl<T> Label<T>
@@ -34,9 +46,42 @@ where
             display: text.into_read_only(),
             displayed: String::new(),
             prepared_text: WindowLocal::default(),
+            selection: None,
         }
     }
 
+    /// Allows the user to select and copy this label's text.
+    ///
+    /// The text can be selected by dragging with the mouse, double-clicking
+    /// selects a word, and triple-clicking selects a line. While focused, the
+    /// selection can be copied to the clipboard using the primary modifier and
+    /// C.
+    #[must_use]
+    pub fn selectable(mut self) -> Self {
+        self.selection = Some(LabelSelection::default());
+        self
+    }
+
+    fn text_origin(&self, context: &WidgetContext<'_>) -> Option<Point<Px>> {
+        let (prepared, ..) = self.prepared_text.get(context)?;
+        let size = context.last_layout()?.size;
+        Some((Point::from(size) / 2).round() - Point::from(prepared.size) / 2)
+    }
+
+    fn cursor_from_point(
+        &self,
+        location: Point<Px>,
+        context: &WidgetContext<'_>,
+    ) -> Option<Cursor> {
+        let origin = self.text_origin(context)?;
+        let (prepared, ..) = self.prepared_text.get(context)?;
+        Some(selection::cursor_from_point(
+            prepared,
+            self.displayed.len(),
+            location - origin,
+        ))
+    }
+
     fn prepared_text(
         &mut self,
         context: &mut GraphicsContext<'_, '_, '_, '_>,
@@ -86,10 +131,45 @@ where
 
         let prepared_text = self.prepared_text(context, text_color, size.width);
 
-        context.gfx.draw_measured_text(
-            prepared_text.translate_by(center.round()),
-            TextOrigin::Center,
-        );
+        if self.selection.is_none() {
+            context.gfx.draw_measured_text(
+                prepared_text.translate_by(center.round()),
+                TextOrigin::Center,
+            );
+            return;
+        }
+
+        let origin = center.round() - Point::from(prepared_text.size) / 2;
+        let selection = self.selection.as_mut().expect("checked above");
+        if context.focused(true) {
+            let selecting = selection.mouse_buttons_down > 0;
+            if let (start, Some(end)) = selection.state.ordered(self.displayed.len(), selecting) {
+                let highlight = context.get(&HighlightColor);
+                let prepared_text = self
+                    .prepared_text
+                    .get(context)
+                    .map(|(prepared, ..)| prepared)
+                    .expect("always initialized");
+                selection::draw_selection(
+                    context,
+                    prepared_text,
+                    &self.displayed,
+                    (start, end),
+                    prepared_text.size.width,
+                    highlight,
+                    origin,
+                );
+            }
+        }
+
+        let prepared_text = self
+            .prepared_text
+            .get(context)
+            .map(|(prepared, ..)| prepared)
+            .expect("always initialized");
+        context
+            .gfx
+            .draw_measured_text(prepared_text.translate_by(origin), TextOrigin::TopLeft);
     }
 
     fn layout(
@@ -108,9 +188,129 @@ where
         fmt.debug_tuple("Label").field(&self.display).finish()
     }
 
-    fn unmounted(&mut self, context: &mut crate::context::EventContext<'_>) {
+    fn unmounted(&mut self, context: &mut EventContext<'_>) {
         self.prepared_text.clear_for(context);
     }
+
+    fn hit_test(&mut self, _location: Point<Px>, _context: &mut EventContext<'_>) -> bool {
+        self.selection.is_some()
+    }
+
+    fn hover(
+        &mut self,
+        _location: Point<Px>,
+        _context: &mut EventContext<'_>,
+    ) -> Option<CursorIcon> {
+        self.selection.is_some().then_some(CursorIcon::Text)
+    }
+
+    fn accept_focus(&mut self, context: &mut EventContext<'_>) -> bool {
+        self.selection.as_ref().map_or(false, |selection| {
+            selection.mouse_buttons_down > 0 || context.get(&AutoFocusableControls).is_all()
+        })
+    }
+
+    fn blur(&mut self, context: &mut EventContext<'_>) {
+        if let Some(selection) = &mut self.selection {
+            selection.state.start = None;
+            context.set_needs_redraw();
+        }
+    }
+
+    fn mouse_down(
+        &mut self,
+        location: Point<Px>,
+        _device_id: DeviceId,
+        button: MouseButton,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        if button != MouseButton::Left {
+            return IGNORED;
+        }
+        let Some(cursor) = self
+            .selection
+            .is_some()
+            .then(|| self.cursor_from_point(location, context))
+            .flatten()
+        else {
+            return IGNORED;
+        };
+        let selection = self.selection.as_mut().expect("checked above");
+
+        selection.mouse_buttons_down += 1;
+        let clicks = selection.clicks.click(location);
+        selection.state.click(&self.displayed, cursor, clicks);
+        context.focus();
+        context.set_needs_redraw();
+        HANDLED
+    }
+
+    fn mouse_drag(
+        &mut self,
+        location: Point<Px>,
+        _device_id: DeviceId,
+        _button: MouseButton,
+        context: &mut EventContext<'_>,
+    ) {
+        let Some(cursor) = self.cursor_from_point(location, context) else {
+            return;
+        };
+        if let Some(selection) = &mut self.selection {
+            if selection.state.cursor != cursor {
+                selection.state.cursor = cursor;
+                context.set_needs_redraw();
+            }
+        }
+    }
+
+    fn mouse_up(
+        &mut self,
+        _location: Option<Point<Px>>,
+        _device_id: DeviceId,
+        button: MouseButton,
+        _context: &mut EventContext<'_>,
+    ) {
+        if button != MouseButton::Left {
+            return;
+        }
+        if let Some(selection) = &mut self.selection {
+            selection.mouse_buttons_down = selection.mouse_buttons_down.saturating_sub(1);
+        }
+    }
+
+    fn keyboard_input(
+        &mut self,
+        _device_id: DeviceId,
+        input: KeyEvent,
+        _is_synthetic: bool,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        let Some(selection) = &mut self.selection else {
+            return IGNORED;
+        };
+        if !context.modifiers().primary() {
+            return IGNORED;
+        }
+
+        match input.text.as_deref() {
+            Some("c") => {
+                if input.state.is_pressed() {
+                    if let Some(text) = selection.state.selected_text(&self.displayed, false) {
+                        selection::copy_to_clipboard(text, context);
+                    }
+                }
+                HANDLED
+            }
+            Some("a") => {
+                if input.state.is_pressed() {
+                    selection.state.select_all(&self.displayed);
+                    context.set_needs_redraw();
+                }
+                HANDLED
+            }
+            _ => IGNORED,
+        }
+    }
 }
 
 macro_rules! impl_make_widget {
@@ -16,10 +16,11 @@ use crate::context::{AsEventContext, EventContext, GraphicsContext, LayoutContex
 use crate::utils::IgnorePoison;
 use crate::value::{Destination, Dynamic, DynamicGuard, IntoValue, Source, Value};
 use crate::widget::{
-    Callback, MakeWidget, MountedChildren, MountedWidget, Widget, WidgetId, WidgetList, WidgetRef,
-    WrapperWidget,
+    Callback, MakeWidget, MakeWidgetWithTag, MountedChildren, MountedWidget, Widget, WidgetId,
+    WidgetList, WidgetRef, WidgetTag, WrapperWidget, HANDLED,
 };
 use crate::widgets::container::ContainerShadow;
+use crate::widgets::{Custom, Space, Switcher};
 use crate::ConstraintLimit;
 
 /// A Z-direction stack of widgets.
@@ -784,3 +785,61 @@ impl WrapperWidget for Tooltipped {
         self.data.shown_tooltip.set(None);
     }
 }
+
+/// A layer that presents a single widget above the layers beneath it.
+///
+/// While a widget is presented, the layers beneath are dimmed using the
+/// theme's scrim color and do not receive mouse input. This widget is for use
+/// inside of a [`Layers`] widget, and is typically the last layer.
+#[derive(Debug, Clone, Default)]
+pub struct Modal {
+    modal: Dynamic<Option<WidgetInstance>>,
+}
+
+impl Modal {
+    /// Returns a new modal layer that is not presenting anything.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Presents `contents` centered above the layers beneath this one,
+    /// replacing any currently presented widget.
+    pub fn present(&self, contents: impl MakeWidget) {
+        self.modal.set(Some(contents.make_widget()));
+    }
+
+    /// Dismisses the currently presented widget, if any.
+    pub fn dismiss(&self) {
+        self.modal.set(None);
+    }
+
+    /// Returns true if this layer is currently presenting a widget.
+    #[must_use]
+    pub fn visible(&self) -> bool {
+        self.modal.map_ref(Option::is_some)
+    }
+}
+
+impl MakeWidgetWithTag for Modal {
+    fn make_with_tag(self, id: WidgetTag) -> WidgetInstance {
+        Switcher::mapping(self.modal, |modal, _| match modal {
+            Some(contents) => Custom::new(
+                contents
+                    .clone()
+                    .contain()
+                    .shadow(ContainerShadow::drop(Lp::mm(1), Lp::mm(2)))
+                    .centered(),
+            )
+            .on_redraw(|context| {
+                let scrim = context.theme_pair().scrim;
+                context.fill(scrim.with_alpha(128));
+            })
+            .on_hit_test(|_, _| true)
+            .on_mouse_down(|_, _, _, _| HANDLED)
+            .make_widget(),
+            None => Space::clear().make_widget(),
+        })
+        .make_with_tag(id)
+    }
+}
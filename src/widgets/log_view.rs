@@ -0,0 +1,704 @@
+//! A widget that displays a stream of log lines.
+use std::collections::VecDeque;
+use std::io::BufRead;
+use std::sync::mpsc;
+
+use figures::units::{Px, UPx};
+use figures::{FloatConversion, IntoSigned, Point, Rect, Round, ScreenScale, Size, Zero};
+use intentional::Cast;
+use kludgine::app::winit::event::{MouseButton, MouseScrollDelta, TouchPhase};
+use kludgine::app::winit::keyboard::{Key, NamedKey};
+use kludgine::cosmic_text::FamilyOwned;
+use kludgine::shapes::Shape;
+use kludgine::text::{Text, TextOrigin};
+use kludgine::{Color, DrawableExt};
+use tracing::level_filters::LevelFilter;
+use tracing::Level;
+
+use crate::context::{EventContext, GraphicsContext, LayoutContext};
+use crate::styles::components::{IntrinsicPadding, LineHeight, TextColor};
+use crate::value::{Destination, Dynamic, Generation, IntoDynamic, Source};
+use crate::widget::{EventHandling, Widget, HANDLED, IGNORED};
+use crate::widgets::scroll::ScrollBarThickness;
+use crate::window::{DeviceId, KeyEvent};
+use crate::ConstraintLimit;
+
+/// A single line displayed by a [`LogView`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogLine {
+    /// The severity of this line, if known.
+    pub level: Option<Level>,
+    /// The text of this line.
+    pub text: String,
+}
+
+impl LogLine {
+    /// Returns a line containing `text` with no severity.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            level: None,
+            text: text.into(),
+        }
+    }
+
+    /// Returns a line containing `text` with the severity `level`.
+    pub fn with_level(level: Level, text: impl Into<String>) -> Self {
+        Self {
+            level: Some(level),
+            text: text.into(),
+        }
+    }
+
+    /// Returns a line containing `text`, detecting its severity from the
+    /// first few words of the line.
+    ///
+    /// This recognizes the level names commonly written by logging libraries,
+    /// such as `ERROR`, `WARN`, and `INFO`, which makes it useful for
+    /// displaying the output of other processes.
+    ///
+    /// ```rust
+    /// use cushy::widgets::log_view::LogLine;
+    /// use tracing::Level;
+    ///
+    /// let line = LogLine::detect_level("2024-01-01T00:00:00Z WARN disk almost full");
+    /// assert_eq!(line.level, Some(Level::WARN));
+    /// ```
+    pub fn detect_level(text: impl Into<String>) -> Self {
+        let text = text.into();
+        let level = text
+            .split(|ch: char| !ch.is_ascii_alphabetic())
+            .filter(|word| !word.is_empty())
+            .take(4)
+            .find_map(|word| match word {
+                "ERROR" | "error" | "Error" => Some(Level::ERROR),
+                "WARN" | "WARNING" | "warn" | "warning" | "Warning" => Some(Level::WARN),
+                "INFO" | "info" => Some(Level::INFO),
+                "DEBUG" | "debug" => Some(Level::DEBUG),
+                "TRACE" | "trace" => Some(Level::TRACE),
+                _ => None,
+            });
+        Self { level, text }
+    }
+}
+
+impl From<String> for LogLine {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<&'_ str> for LogLine {
+    fn from(text: &'_ str) -> Self {
+        Self::new(text)
+    }
+}
+
+/// A bounded, shareable collection of [`LogLine`]s.
+///
+/// Cloning a buffer returns a handle to the same lines. Once the buffer
+/// contains its capacity of lines, the oldest lines are removed as new lines
+/// are appended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogBuffer {
+    lines: Dynamic<LogLines>,
+}
+
+impl LogBuffer {
+    /// The number of lines a buffer created with [`LogBuffer::new`] retains.
+    pub const DEFAULT_CAPACITY: usize = 10_000;
+
+    /// Returns an empty buffer that retains up to
+    /// [`DEFAULT_CAPACITY`](Self::DEFAULT_CAPACITY) lines.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+
+    /// Returns an empty buffer that retains up to `capacity` lines.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            lines: Dynamic::new(LogLines {
+                lines: VecDeque::new(),
+                first: 0,
+                capacity: capacity.max(1),
+            }),
+        }
+    }
+
+    /// Appends `line` to this buffer.
+    pub fn push(&self, line: impl Into<LogLine>) {
+        let line = line.into();
+        self.lines.map_mut(|mut lines| lines.push(line));
+    }
+
+    /// Appends all of `lines` to this buffer.
+    pub fn extend<Line>(&self, lines: impl IntoIterator<Item = Line>)
+    where
+        Line: Into<LogLine>,
+    {
+        self.lines.map_mut(|mut buffer| {
+            for line in lines {
+                buffer.push(line.into());
+            }
+        });
+    }
+
+    /// Removes all lines from this buffer.
+    pub fn clear(&self) {
+        self.lines.map_mut(|mut lines| {
+            let cleared = lines.lines.len().cast::<u64>();
+            lines.first += cleared;
+            lines.lines.clear();
+        });
+    }
+
+    /// Returns the number of lines in this buffer.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lines.map_ref(|lines| lines.lines.len())
+    }
+
+    /// Returns true if this buffer contains no lines.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends each line received from `receiver` to this buffer.
+    ///
+    /// Lines are received on a background thread, which exits once all
+    /// senders for `receiver` have been dropped.
+    pub fn receive<Line>(&self, receiver: mpsc::Receiver<Line>)
+    where
+        Line: Into<LogLine> + Send + 'static,
+    {
+        let buffer = self.clone();
+        std::thread::spawn(move || {
+            while let Ok(line) = receiver.recv() {
+                // Append any other lines that have already arrived at once to
+                // avoid notifying observers for every line.
+                buffer.extend(std::iter::once(line).chain(receiver.try_iter()));
+            }
+        });
+    }
+
+    /// Appends each line read from `reader` to this buffer, detecting each
+    /// line's severity using [`LogLine::detect_level`].
+    ///
+    /// Lines are read on a background thread, which exits once `reader`
+    /// reaches its end or returns an error. This can be used to display the
+    /// output of a child process.
+    pub fn read_from<Reader>(&self, reader: Reader)
+    where
+        Reader: BufRead + Send + 'static,
+    {
+        let buffer = self.clone();
+        std::thread::spawn(move || {
+            for line in reader.lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                buffer.push(LogLine::detect_level(line));
+            }
+        });
+    }
+
+    /// Returns a [`tracing_subscriber::Layer`] that appends each event
+    /// recorded by [`tracing`] to this buffer.
+    ///
+    /// To display an application's own logs, install this layer before
+    /// running the application:
+    ///
+    /// ```rust,no_run
+    /// use cushy::widgets::log_view::LogBuffer;
+    /// use tracing_subscriber::layer::SubscriberExt;
+    /// use tracing_subscriber::util::SubscriberInitExt;
+    ///
+    /// let logs = LogBuffer::new();
+    /// tracing_subscriber::registry()
+    ///     .with(tracing_subscriber::fmt::layer())
+    ///     .with(logs.layer())
+    ///     .init();
+    /// ```
+    #[cfg(feature = "tracing-output")]
+    #[must_use]
+    pub fn layer(&self) -> LogLayer {
+        LogLayer {
+            buffer: self.clone(),
+        }
+    }
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct LogLines {
+    lines: VecDeque<LogLine>,
+    /// The index of `lines[0]` across every line ever pushed into this
+    /// buffer.
+    first: u64,
+    capacity: usize,
+}
+
+impl LogLines {
+    fn push(&mut self, line: LogLine) {
+        if self.lines.len() == self.capacity {
+            self.lines.pop_front();
+            self.first += 1;
+        }
+        self.lines.push_back(line);
+    }
+
+    fn end(&self) -> u64 {
+        self.first + self.lines.len().cast::<u64>()
+    }
+}
+
+/// A [`tracing_subscriber::Layer`] that appends events to a [`LogBuffer`].
+///
+/// This type is returned from [`LogBuffer::layer`].
+#[cfg(feature = "tracing-output")]
+#[derive(Debug, Clone)]
+pub struct LogLayer {
+    buffer: LogBuffer,
+}
+
+#[cfg(feature = "tracing-output")]
+impl<S> tracing_subscriber::Layer<S> for LogLayer
+where
+    S: tracing::Subscriber,
+{
+    fn on_event(
+        &self,
+        event: &tracing::Event<'_>,
+        _context: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        let metadata = event.metadata();
+        let mut visitor = EventVisitor::default();
+        event.record(&mut visitor);
+        let line = LogLine::with_level(
+            *metadata.level(),
+            format!(
+                "{} {}: {}{}",
+                metadata.level(),
+                metadata.target(),
+                visitor.message,
+                visitor.fields
+            ),
+        );
+        // Events can be recorded while the buffer is being displayed on the
+        // same thread. Rather than deadlocking, these events are dropped.
+        let _result = self.buffer.lines.try_map_mut(|mut lines| lines.push(line));
+    }
+}
+
+#[cfg(feature = "tracing-output")]
+#[derive(Default)]
+struct EventVisitor {
+    message: String,
+    fields: String,
+}
+
+#[cfg(feature = "tracing-output")]
+impl tracing::field::Visit for EventVisitor {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        use std::fmt::Write;
+
+        if field.name() == "message" {
+            let _result = write!(self.message, "{value:?}");
+        } else {
+            let _result = write!(self.fields, " {}={value:?}", field.name());
+        }
+    }
+}
+
+/// A widget that displays the lines of a [`LogBuffer`].
+///
+/// Only the visible lines are drawn, which allows displaying large logs that
+/// are updated frequently. Each line is colored based on its severity.
+///
+/// While the view is scrolled to the bottom, it follows new lines as they are
+/// appended. Scrolling up stops following, and scrolling back to the bottom
+/// or pressing End resumes it.
+#[derive(Debug)]
+pub struct LogView {
+    buffer: LogBuffer,
+    filter: Dynamic<String>,
+    regex: bool,
+    level: Dynamic<LevelFilter>,
+    follow: Dynamic<bool>,
+    matcher: Matcher,
+    filter_generations: Option<(Generation, Generation)>,
+    matched: VecDeque<u64>,
+    scanned: u64,
+    scroll: Px,
+    line_height: Px,
+    visible_height: Px,
+}
+
+impl LogView {
+    /// Returns a view that displays the lines in `buffer`.
+    #[must_use]
+    pub fn new(buffer: &LogBuffer) -> Self {
+        Self {
+            buffer: buffer.clone(),
+            filter: Dynamic::default(),
+            regex: false,
+            level: Dynamic::new(LevelFilter::TRACE),
+            follow: Dynamic::new(true),
+            matcher: Matcher::All,
+            filter_generations: None,
+            matched: VecDeque::new(),
+            scanned: 0,
+            scroll: Px::ZERO,
+            line_height: Px::ZERO,
+            visible_height: Px::ZERO,
+        }
+    }
+
+    /// Only displays lines containing `filter` and returns self.
+    ///
+    /// The text is matched without regard to case. An empty filter displays
+    /// all lines.
+    #[must_use]
+    pub fn filter(mut self, filter: impl IntoDynamic<String>) -> Self {
+        self.filter = filter.into_dynamic();
+        self.regex = false;
+        self
+    }
+
+    /// Only displays lines matching the regular expression `pattern` and
+    /// returns self.
+    ///
+    /// All lines are displayed while `pattern` is empty or is not a valid
+    /// regular expression.
+    #[cfg(feature = "regex")]
+    #[must_use]
+    pub fn regex_filter(mut self, pattern: impl IntoDynamic<String>) -> Self {
+        self.filter = pattern.into_dynamic();
+        self.regex = true;
+        self
+    }
+
+    /// Only displays lines at least as severe as `level` and returns self.
+    ///
+    /// Lines without a severity are always displayed.
+    #[must_use]
+    pub fn level(mut self, level: impl IntoDynamic<LevelFilter>) -> Self {
+        self.level = level.into_dynamic();
+        self
+    }
+
+    /// Uses `follow` to control whether this view follows new lines and
+    /// returns self.
+    ///
+    /// `follow` is updated as the user scrolls.
+    #[must_use]
+    pub fn following(mut self, follow: impl IntoDynamic<bool>) -> Self {
+        self.follow = follow.into_dynamic();
+        self
+    }
+
+    /// Updates the lines that match the current filters, returning the number
+    /// of matching lines that have been removed from the buffer.
+    fn refresh(&mut self) -> usize {
+        let generations = (self.filter.generation(), self.level.generation());
+        if self.filter_generations != Some(generations) {
+            self.filter_generations = Some(generations);
+            self.matcher = self
+                .filter
+                .map_ref(|filter| Matcher::new(filter, self.regex));
+            self.matched.clear();
+            self.scanned = 0;
+        }
+
+        let level = self.level.get();
+        self.buffer.lines.map_ref(|lines| {
+            let mut removed = 0;
+            while self
+                .matched
+                .front()
+                .map_or(false, |index| *index < lines.first)
+            {
+                self.matched.pop_front();
+                removed += 1;
+            }
+
+            let start = self.scanned.max(lines.first);
+            let skip = (start - lines.first).cast::<usize>();
+            for (index, line) in (start..).zip(lines.lines.iter().skip(skip)) {
+                if line.level.map_or(true, |line_level| line_level <= level)
+                    && self.matcher.matches(&line.text)
+                {
+                    self.matched.push_back(index);
+                }
+            }
+            self.scanned = lines.end();
+            removed
+        })
+    }
+
+    fn max_scroll(&self) -> Px {
+        (self.line_height * self.matched.len().cast::<i32>() - self.visible_height).max(Px::ZERO)
+    }
+
+    fn scroll_to(&mut self, scroll: Px, context: &mut EventContext<'_>) -> bool {
+        let max_scroll = self.max_scroll();
+        let scroll = scroll.max(Px::ZERO).min(max_scroll);
+        let changed = scroll != self.scroll;
+        self.scroll = scroll;
+        self.follow.set(scroll >= max_scroll);
+        context.set_needs_redraw();
+        changed
+    }
+
+    fn line_color(level: Option<Level>, context: &GraphicsContext<'_, '_, '_, '_>) -> Color {
+        match level {
+            Some(Level::ERROR) => context.get(&LogErrorColor),
+            Some(Level::WARN) => context.get(&LogWarningColor),
+            Some(Level::DEBUG) => context.get(&LogDebugColor),
+            Some(Level::TRACE) => context.get(&LogTraceColor),
+            Some(Level::INFO) | None => context.get(&TextColor),
+        }
+    }
+}
+
+impl Widget for LogView {
+    fn redraw(&mut self, context: &mut GraphicsContext<'_, '_, '_, '_>) {
+        context.redraw_when_changed(&self.buffer.lines);
+        context.redraw_when_changed(&self.filter);
+        context.redraw_when_changed(&self.level);
+        let follow = self.follow.get_tracking_redraw(context);
+
+        let removed = self.refresh();
+        self.visible_height = context.gfx.region().size.height;
+        let max_scroll = self.max_scroll();
+        self.scroll = if follow {
+            max_scroll
+        } else {
+            // Keep the same lines visible as older lines are removed.
+            (self.scroll - self.line_height * removed.cast::<i32>())
+                .max(Px::ZERO)
+                .min(max_scroll)
+        };
+
+        if self.line_height <= 0 {
+            return;
+        }
+
+        context.apply_current_font_settings();
+        context.gfx.set_font_family(FamilyOwned::Monospace);
+        let padding = context
+            .get(&IntrinsicPadding)
+            .into_px(context.gfx.scale())
+            .round();
+        let first = usize::try_from(self.scroll.get() / self.line_height.get()).unwrap_or_default();
+        let mut y = self.line_height * first.cast::<i32>() - self.scroll;
+        let end_y = self.visible_height;
+
+        let matched = &self.matched;
+        let line_height = self.line_height;
+        self.buffer.lines.map_ref(|lines| {
+            for index in matched.iter().skip(first) {
+                if y >= end_y {
+                    break;
+                }
+                let Some(line) = index
+                    .checked_sub(lines.first)
+                    .and_then(|offset| lines.lines.get(offset.cast::<usize>()))
+                else {
+                    continue;
+                };
+
+                let color = Self::line_color(line.level, context);
+                let text = context.gfx.measure_text(Text::<Px>::new(&line.text, color));
+                context.gfx.draw_measured_text(
+                    text.translate_by(Point::new(padding, y)),
+                    TextOrigin::TopLeft,
+                );
+                y += line_height;
+            }
+        });
+
+        let content_height = self.line_height * self.matched.len().cast::<i32>();
+        if content_height > self.visible_height {
+            let size = context.gfx.region().size;
+            let bar_width = context
+                .get(&ScrollBarThickness)
+                .into_px(context.gfx.scale())
+                .round();
+            let bar_height = (size.height
+                * (size.height.into_float() / content_height.into_float()))
+            .max(bar_width);
+            let bar_top =
+                (size.height - bar_height) * (self.scroll.into_float() / max_scroll.into_float());
+            context.gfx.draw_shape(&Shape::filled_rect(
+                Rect::new(
+                    Point::new(size.width - bar_width, bar_top),
+                    Size::new(bar_width, bar_height),
+                ),
+                context.get(&TextColor).with_alpha(96),
+            ));
+        }
+    }
+
+    fn layout(
+        &mut self,
+        available_space: Size<ConstraintLimit>,
+        context: &mut LayoutContext<'_, '_, '_, '_>,
+    ) -> Size<UPx> {
+        self.line_height = context
+            .get(&LineHeight)
+            .into_px(context.gfx.scale())
+            .round();
+        let size = available_space.map(ConstraintLimit::max);
+        self.visible_height = size.height.into_signed();
+        size
+    }
+
+    fn hit_test(&mut self, _location: Point<Px>, _context: &mut EventContext<'_>) -> bool {
+        true
+    }
+
+    fn accept_focus(&mut self, context: &mut EventContext<'_>) -> bool {
+        context.enabled()
+    }
+
+    fn mouse_down(
+        &mut self,
+        _location: Point<Px>,
+        _device_id: DeviceId,
+        _button: MouseButton,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        context.focus();
+        HANDLED
+    }
+
+    fn mouse_wheel(
+        &mut self,
+        _device_id: DeviceId,
+        delta: MouseScrollDelta,
+        _phase: TouchPhase,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        let amount = match delta {
+            MouseScrollDelta::LineDelta(_, y) => self.line_height * y,
+            MouseScrollDelta::PixelDelta(px) => Px::from(px.y.cast::<f32>()),
+        };
+        if self.scroll_to(self.scroll - amount, context) {
+            HANDLED
+        } else {
+            IGNORED
+        }
+    }
+
+    fn keyboard_input(
+        &mut self,
+        _device_id: DeviceId,
+        input: KeyEvent,
+        _is_synthetic: bool,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        let page = (self.visible_height - self.line_height).max(self.line_height);
+        let scroll = match input.logical_key {
+            Key::Named(NamedKey::ArrowUp) => self.scroll - self.line_height,
+            Key::Named(NamedKey::ArrowDown) => self.scroll + self.line_height,
+            Key::Named(NamedKey::PageUp) => self.scroll - page,
+            Key::Named(NamedKey::PageDown) => self.scroll + page,
+            Key::Named(NamedKey::Home) => Px::ZERO,
+            Key::Named(NamedKey::End) => self.max_scroll(),
+            _ => return IGNORED,
+        };
+        if input.state.is_pressed() {
+            self.scroll_to(scroll, context);
+        }
+        HANDLED
+    }
+}
+
+#[derive(Debug)]
+enum Matcher {
+    All,
+    Text(String),
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+}
+
+impl Matcher {
+    #[cfg_attr(not(feature = "regex"), allow(unused_variables))]
+    fn new(filter: &str, regex: bool) -> Self {
+        if filter.is_empty() {
+            return Self::All;
+        }
+
+        #[cfg(feature = "regex")]
+        if regex {
+            return regex::Regex::new(filter).map_or(Self::All, Self::Regex);
+        }
+        Self::Text(filter.to_lowercase())
+    }
+
+    fn matches(&self, text: &str) -> bool {
+        match self {
+            Matcher::All => true,
+            Matcher::Text(filter) => text.to_lowercase().contains(filter),
+            #[cfg(feature = "regex")]
+            Matcher::Regex(regex) => regex.is_match(text),
+        }
+    }
+}
+
+define_components! {
+    LogView {
+        /// The color of error lines in a [`LogView`].
+        LogErrorColor(Color, "error_color", .error.color)
+        /// The color of warning lines in a [`LogView`].
+        LogWarningColor(Color, "warning_color", .tertiary.color)
+        /// The color of debug lines in a [`LogView`].
+        LogDebugColor(Color, "debug_color", .surface.on_color_variant)
+        /// The color of trace lines in a [`LogView`].
+        LogTraceColor(Color, "trace_color", .surface.outline)
+    }
+}
+
+#[test]
+fn level_detection() {
+    assert_eq!(
+        LogLine::detect_level("ERROR failed to connect").level,
+        Some(Level::ERROR)
+    );
+    assert_eq!(
+        LogLine::detect_level("[2024-01-01 12:00:00] warning: unused variable").level,
+        Some(Level::WARN)
+    );
+    assert_eq!(LogLine::detect_level("no errors here").level, None);
+    assert_eq!(
+        LogLine::detect_level("compiling crate that mentions an error later on").level,
+        None
+    );
+}
+
+#[test]
+fn buffer_capacity() {
+    let buffer = LogBuffer::with_capacity(2);
+    buffer.extend(["a", "b", "c"]);
+    buffer.lines.map_ref(|lines| {
+        assert_eq!(lines.first, 1);
+        assert_eq!(lines.end(), 3);
+        assert_eq!(lines.lines[0].text, "b");
+    });
+}
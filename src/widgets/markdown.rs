@@ -0,0 +1,702 @@
+//! A widget that renders CommonMark-formatted text.
+
+use std::array;
+use std::mem;
+use std::sync::{Arc, Mutex};
+
+use figures::units::Lp;
+use kludgine::AnyTexture;
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag};
+
+use super::grid::{GridSection, GridWidgets};
+use super::list::{List, ListStyle};
+use super::rich_text::Span;
+use super::{Delimiter, Grid, Image, RichText, Space, Stack, Switcher};
+use crate::utils::IgnorePoison;
+use crate::value::{IntoReadOnly, ReadOnly, Source};
+use crate::widget::{
+    Callback, MakeWidget, MakeWidgetWithTag, WidgetInstance, WidgetList, WidgetTag,
+};
+
+/// A widget that displays [CommonMark](https://commonmark.org)-formatted
+/// text.
+///
+/// Headings use the same styles as [`MakeWidget::h1()`] through
+/// [`MakeWidget::h6()`], lists are displayed using [`List`], tables are
+/// displayed using [`Grid`], and paragraphs are displayed using [`RichText`].
+/// Tables with more than 32 columns are displayed with each row laid out
+/// independently, which means their columns may not line up.
+///
+/// When the source is a [`Dynamic<String>`](crate::value::Dynamic), the
+/// displayed contents will be updated each time the source is changed.
+#[derive(Debug)]
+pub struct Markdown {
+    source: ReadOnly<String>,
+    on_link: Option<Callback<String>>,
+    images: Option<Callback<String, Option<AnyTexture>>>,
+}
+
+impl Markdown {
+    /// Returns a new widget that displays `source` as Markdown.
+    pub fn new(source: impl IntoReadOnly<String>) -> Self {
+        Self {
+            source: source.into_read_only(),
+            on_link: None,
+            images: None,
+        }
+    }
+
+    /// Invokes `on_link` with the link's destination when a link is clicked.
+    #[must_use]
+    pub fn on_link<F>(mut self, on_link: F) -> Self
+    where
+        F: FnMut(String) + Send + 'static,
+    {
+        self.on_link = Some(Callback::new(on_link));
+        self
+    }
+
+    /// Uses `load_image` to load the texture of each image that is displayed.
+    ///
+    /// `load_image` is provided the image's destination. When `None` is
+    /// returned or no loader has been provided, the image's alternate text is
+    /// displayed instead.
+    #[must_use]
+    pub fn images<F>(mut self, load_image: F) -> Self
+    where
+        F: FnMut(String) -> Option<AnyTexture> + Send + 'static,
+    {
+        self.images = Some(Callback::new(load_image));
+        self
+    }
+}
+
+impl MakeWidgetWithTag for Markdown {
+    fn make_with_tag(self, tag: WidgetTag) -> WidgetInstance {
+        let on_link = self.on_link.map(|on_link| Arc::new(Mutex::new(on_link)));
+        let mut images = self.images;
+        match self.source {
+            ReadOnly::Constant(source) => {
+                render(&source, on_link.as_ref(), &mut images).make_with_tag(tag)
+            }
+            ReadOnly::Reader(source) => Switcher::new(
+                source.map_each(move |source| render(source, on_link.as_ref(), &mut images)),
+            )
+            .make_with_tag(tag),
+        }
+    }
+}
+
+type SharedLinkCallback = Arc<Mutex<Callback<String>>>;
+
+fn render(
+    source: &str,
+    on_link: Option<&SharedLinkCallback>,
+    images: &mut Option<Callback<String, Option<AnyTexture>>>,
+) -> WidgetInstance {
+    rows(parse(source, on_link, images)).make_widget()
+}
+
+/// Parses `source` into the blocks that are displayed.
+fn parse(
+    source: &str,
+    on_link: Option<&SharedLinkCallback>,
+    images: &mut Option<Callback<String, Option<AnyTexture>>>,
+) -> Vec<MarkdownBlock> {
+    let mut builder = MarkdownBuilder {
+        on_link,
+        images,
+        blocks: vec![Block::new(BlockKind::Root)],
+        spans: Vec::new(),
+        strong: 0,
+        emphasis: 0,
+        strikethrough: 0,
+        link: None,
+        image: None,
+        code: None,
+    };
+    for event in Parser::new_ext(
+        source,
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS,
+    ) {
+        builder.handle(event);
+    }
+    builder.finish()
+}
+
+struct MarkdownBuilder<'a> {
+    on_link: Option<&'a SharedLinkCallback>,
+    images: &'a mut Option<Callback<String, Option<AnyTexture>>>,
+    blocks: Vec<Block>,
+    spans: Vec<Span>,
+    strong: usize,
+    emphasis: usize,
+    strikethrough: usize,
+    link: Option<String>,
+    image: Option<(String, String)>,
+    code: Option<String>,
+}
+
+impl MarkdownBuilder<'_> {
+    fn handle(&mut self, event: Event<'_>) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => {
+                if let Some(code) = &mut self.code {
+                    code.push_str(&text);
+                } else if let Some((_, alt)) = &mut self.image {
+                    alt.push_str(&text);
+                } else {
+                    self.push_text(&text);
+                }
+            }
+            Event::Code(code) => {
+                let span = self.styled(Span::new(code.into_string()).monospace());
+                self.spans.push(span);
+            }
+            Event::Html(html) => self.push_text(&html),
+            Event::FootnoteReference(label) => self.push_text(&format!("[{label}]")),
+            Event::SoftBreak => self.push_text(" "),
+            Event::HardBreak => self.push_text("\n"),
+            Event::Rule => {
+                self.flush_paragraph();
+                self.push_block(MarkdownBlock::Rule);
+            }
+            Event::TaskListMarker(checked) => {
+                self.push_text(if checked { "\u{2611} " } else { "\u{2610} " });
+            }
+        }
+    }
+
+    fn start(&mut self, tag: Tag<'_>) {
+        match tag {
+            Tag::Paragraph | Tag::Heading(..) => self.flush_paragraph(),
+            Tag::BlockQuote | Tag::FootnoteDefinition(_) => {
+                self.flush_paragraph();
+                self.blocks.push(Block::new(BlockKind::Quote));
+            }
+            Tag::CodeBlock(_) => {
+                self.flush_paragraph();
+                self.code = Some(String::new());
+            }
+            Tag::List(first) => {
+                self.flush_paragraph();
+                self.blocks.push(Block::new(BlockKind::List {
+                    ordered: first.is_some(),
+                    items: Vec::new(),
+                }));
+            }
+            Tag::Item => self.blocks.push(Block::new(BlockKind::Item)),
+            Tag::Table(alignments) => {
+                self.flush_paragraph();
+                self.blocks.push(Block::new(BlockKind::Table {
+                    columns: alignments.len(),
+                    head: Vec::new(),
+                    rows: Vec::new(),
+                }));
+            }
+            Tag::TableHead | Tag::TableRow => {
+                self.blocks
+                    .push(Block::new(BlockKind::TableRow { cells: Vec::new() }));
+            }
+            Tag::TableCell => self.spans.clear(),
+            Tag::Emphasis => self.emphasis += 1,
+            Tag::Strong => self.strong += 1,
+            Tag::Strikethrough => self.strikethrough += 1,
+            Tag::Link(_, destination, _) => self.link = Some(destination.into_string()),
+            Tag::Image(_, destination, _) => {
+                self.image = Some((destination.into_string(), String::new()));
+            }
+        }
+    }
+
+    fn end(&mut self, tag: Tag<'_>) {
+        match tag {
+            Tag::Paragraph => self.flush_paragraph(),
+            Tag::Heading(level, ..) => {
+                if !self.spans.is_empty() {
+                    let spans = mem::take(&mut self.spans);
+                    self.push_block(MarkdownBlock::Heading(level, spans));
+                }
+            }
+            Tag::BlockQuote | Tag::FootnoteDefinition(_) => {
+                self.flush_paragraph();
+                let quote = self.pop_block();
+                self.push_block(MarkdownBlock::Quote(quote.children));
+            }
+            Tag::CodeBlock(_) => {
+                let mut code = self.code.take().unwrap_or_default();
+                if code.ends_with('\n') {
+                    code.pop();
+                }
+                self.push_block(MarkdownBlock::Code(code));
+            }
+            Tag::List(_) => {
+                if let BlockKind::List { ordered, items } = self.pop_block().kind {
+                    self.push_block(MarkdownBlock::List { ordered, items });
+                }
+            }
+            Tag::Item => {
+                self.flush_paragraph();
+                let item = self.pop_block();
+                if let Some(Block {
+                    kind: BlockKind::List { items, .. },
+                    ..
+                }) = self.blocks.last_mut()
+                {
+                    items.push(item.children);
+                }
+            }
+            Tag::Table(_) => {
+                if let BlockKind::Table {
+                    columns,
+                    head,
+                    rows,
+                } = self.pop_block().kind
+                {
+                    self.push_block(MarkdownBlock::Table {
+                        columns,
+                        head,
+                        rows,
+                    });
+                }
+            }
+            Tag::TableHead | Tag::TableRow => {
+                let BlockKind::TableRow { cells } = self.pop_block().kind else {
+                    return;
+                };
+                if let Some(Block {
+                    kind: BlockKind::Table { head, rows, .. },
+                    ..
+                }) = self.blocks.last_mut()
+                {
+                    if matches!(tag, Tag::TableHead) {
+                        *head = cells;
+                    } else {
+                        rows.push(cells);
+                    }
+                }
+            }
+            Tag::TableCell => {
+                let cell = mem::take(&mut self.spans);
+                if let Some(Block {
+                    kind: BlockKind::TableRow { cells },
+                    ..
+                }) = self.blocks.last_mut()
+                {
+                    cells.push(cell);
+                }
+            }
+            Tag::Emphasis => self.emphasis = self.emphasis.saturating_sub(1),
+            Tag::Strong => self.strong = self.strong.saturating_sub(1),
+            Tag::Strikethrough => self.strikethrough = self.strikethrough.saturating_sub(1),
+            Tag::Link(..) => self.link = None,
+            Tag::Image(..) => {
+                let Some((destination, alt)) = self.image.take() else {
+                    return;
+                };
+                let texture = self
+                    .images
+                    .as_mut()
+                    .and_then(|images| images.invoke(destination));
+                if let Some(texture) = texture {
+                    self.flush_paragraph();
+                    self.push_block(MarkdownBlock::Image(texture));
+                } else {
+                    let span = self.styled(Span::new(alt).italic());
+                    self.spans.push(span);
+                }
+            }
+        }
+    }
+
+    fn styled(&self, mut span: Span) -> Span {
+        if self.strong > 0 {
+            span = span.bold();
+        }
+        if self.emphasis > 0 {
+            span = span.italic();
+        }
+        if self.strikethrough > 0 {
+            span = span.struck_through();
+        }
+        if let Some(destination) = &self.link {
+            let destination = destination.clone();
+            let on_link = self.on_link.cloned();
+            span = span.on_click(move |()| {
+                if let Some(on_link) = &on_link {
+                    on_link.lock().ignore_poison().invoke(destination.clone());
+                }
+            });
+        }
+        span
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let span = self.styled(Span::new(text));
+        self.spans.push(span);
+    }
+
+    fn flush_paragraph(&mut self) {
+        if !self.spans.is_empty() {
+            let spans = mem::take(&mut self.spans);
+            self.push_block(MarkdownBlock::Paragraph(spans));
+        }
+    }
+
+    fn push_block(&mut self, block: MarkdownBlock) {
+        self.blocks
+            .last_mut()
+            .expect("root block always present")
+            .children
+            .push(block);
+    }
+
+    fn pop_block(&mut self) -> Block {
+        if self.blocks.len() > 1 {
+            self.blocks.pop().expect("length checked")
+        } else {
+            Block::new(BlockKind::Root)
+        }
+    }
+
+    fn finish(mut self) -> Vec<MarkdownBlock> {
+        self.flush_paragraph();
+        self.blocks
+            .drain(..)
+            .flat_map(|block| block.children)
+            .collect()
+    }
+}
+
+/// A block of parsed Markdown, which is converted into a widget once parsing
+/// is complete.
+#[derive(Debug)]
+enum MarkdownBlock {
+    Paragraph(Vec<Span>),
+    Heading(HeadingLevel, Vec<Span>),
+    Code(String),
+    Quote(Vec<MarkdownBlock>),
+    List {
+        ordered: bool,
+        items: Vec<Vec<MarkdownBlock>>,
+    },
+    Table {
+        columns: usize,
+        head: Vec<Vec<Span>>,
+        rows: Vec<Vec<Vec<Span>>>,
+    },
+    Rule,
+    Image(AnyTexture),
+}
+
+impl MarkdownBlock {
+    fn into_widget(self) -> WidgetInstance {
+        match self {
+            MarkdownBlock::Paragraph(spans) => RichText::new(spans).make_widget(),
+            MarkdownBlock::Heading(level, spans) => {
+                let text = RichText::new(spans);
+                match level {
+                    HeadingLevel::H1 => text.h1(),
+                    HeadingLevel::H2 => text.h2(),
+                    HeadingLevel::H3 => text.h3(),
+                    HeadingLevel::H4 => text.h4(),
+                    HeadingLevel::H5 => text.h5(),
+                    HeadingLevel::H6 => text.h6(),
+                }
+                .make_widget()
+            }
+            MarkdownBlock::Code(code) => RichText::new(vec![Span::new(code).monospace()])
+                .contain()
+                .make_widget(),
+            MarkdownBlock::Quote(blocks) => Space::primary()
+                .width(Lp::points(3))
+                .and(rows(blocks).expand())
+                .into_columns()
+                .make_widget(),
+            MarkdownBlock::List { ordered, items } => {
+                List::new(items.into_iter().map(rows).collect::<WidgetList>())
+                    .style(if ordered {
+                        ListStyle::Decimal
+                    } else {
+                        ListStyle::Disc
+                    })
+                    .make_widget()
+            }
+            MarkdownBlock::Table {
+                columns,
+                head,
+                rows,
+            } => {
+                let mut table_rows = Vec::with_capacity(rows.len() + 1);
+                if !head.is_empty() {
+                    table_rows.push(
+                        head.into_iter()
+                            .map(|cell| table_cell(cell).h6().make_widget())
+                            .collect(),
+                    );
+                }
+                table_rows.extend(
+                    rows.into_iter()
+                        .map(|row| row.into_iter().map(table_cell).collect()),
+                );
+                table(columns, table_rows)
+            }
+            MarkdownBlock::Rule => Delimiter::horizontal().make_widget(),
+            MarkdownBlock::Image(texture) => Image::new(texture).aspect_fit().make_widget(),
+        }
+    }
+}
+
+/// Returns `blocks` displayed as rows.
+fn rows(blocks: Vec<MarkdownBlock>) -> Stack {
+    blocks
+        .into_iter()
+        .map(MarkdownBlock::into_widget)
+        .collect::<WidgetList>()
+        .into_rows()
+}
+
+fn table_cell(spans: Vec<Span>) -> WidgetInstance {
+    if spans.is_empty() {
+        Space::clear().make_widget()
+    } else {
+        RichText::new(spans).make_widget()
+    }
+}
+
+struct Block {
+    kind: BlockKind,
+    children: Vec<MarkdownBlock>,
+}
+
+impl Block {
+    const fn new(kind: BlockKind) -> Self {
+        Self {
+            kind,
+            children: Vec::new(),
+        }
+    }
+}
+
+enum BlockKind {
+    Root,
+    Quote,
+    List {
+        ordered: bool,
+        items: Vec<Vec<MarkdownBlock>>,
+    },
+    Item,
+    Table {
+        columns: usize,
+        head: Vec<Vec<Span>>,
+        rows: Vec<Vec<Vec<Span>>>,
+    },
+    TableRow {
+        cells: Vec<Vec<Span>>,
+    },
+}
+
+macro_rules! table_grid {
+    ($columns:expr, $rows:expr, $($count:literal)+) => {
+        match $columns {
+            $($count => grid::<$count>($rows),)+
+            _ => unaligned_table($rows),
+        }
+    };
+}
+
+fn table(columns: usize, rows: Vec<Vec<WidgetInstance>>) -> WidgetInstance {
+    table_grid!(
+        columns, rows,
+        1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16
+        17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32
+    )
+}
+
+/// Displays a table that has too many columns to be displayed using a
+/// [`Grid`] by laying out each row independently.
+fn unaligned_table(rows: Vec<Vec<WidgetInstance>>) -> WidgetInstance {
+    rows.into_iter()
+        .map(|row| row.into_iter().collect::<WidgetList>().into_columns())
+        .collect::<WidgetList>()
+        .into_rows()
+        .make_widget()
+}
+
+fn grid<const N: usize>(rows: Vec<Vec<WidgetInstance>>) -> WidgetInstance {
+    Grid::from_rows(
+        rows.into_iter()
+            .map(|row| {
+                let mut cells = row.into_iter();
+                GridSection::from(array::from_fn::<_, N, _>(|_| {
+                    cells.next().unwrap_or_else(|| Space::clear().make_widget())
+                }))
+            })
+            .collect::<GridWidgets<N>>(),
+    )
+    .make_widget()
+}
+
+#[cfg(test)]
+fn parse_test(source: &str) -> Vec<MarkdownBlock> {
+    parse(source, None, &mut None)
+}
+
+#[cfg(test)]
+fn span_text(spans: &[Span]) -> String {
+    spans.iter().map(|span| span.text.as_str()).collect()
+}
+
+#[test]
+fn headings() {
+    let blocks = parse_test("# Title\n\n### Sub *heading*\n\nBody");
+    let [MarkdownBlock::Heading(HeadingLevel::H1, title), MarkdownBlock::Heading(HeadingLevel::H3, sub), MarkdownBlock::Paragraph(body)] =
+        blocks.as_slice()
+    else {
+        unreachable!("unexpected blocks: {blocks:?}")
+    };
+    assert_eq!(span_text(title), "Title");
+    assert_eq!(span_text(sub), "Sub heading");
+    assert_eq!(sub[1].style, Some(kludgine::cosmic_text::Style::Italic));
+    assert_eq!(span_text(body), "Body");
+}
+
+#[test]
+fn nested_lists() {
+    let blocks = parse_test("1. one\n2. two\n   - nested\n");
+    let [MarkdownBlock::List {
+        ordered: true,
+        items,
+    }] = blocks.as_slice()
+    else {
+        unreachable!("unexpected blocks: {blocks:?}")
+    };
+    let [first, second] = items.as_slice() else {
+        unreachable!("unexpected items: {items:?}")
+    };
+    let [MarkdownBlock::Paragraph(one)] = first.as_slice() else {
+        unreachable!("unexpected item: {first:?}")
+    };
+    assert_eq!(span_text(one), "one");
+    let [MarkdownBlock::Paragraph(two), MarkdownBlock::List {
+        ordered: false,
+        items: nested,
+    }] = second.as_slice()
+    else {
+        unreachable!("unexpected item: {second:?}")
+    };
+    assert_eq!(span_text(two), "two");
+    let [nested] = nested.as_slice() else {
+        unreachable!("unexpected items: {nested:?}")
+    };
+    let [MarkdownBlock::Paragraph(nested)] = nested.as_slice() else {
+        unreachable!("unexpected item: {nested:?}")
+    };
+    assert_eq!(span_text(nested), "nested");
+}
+
+#[test]
+fn code_blocks() {
+    let blocks = parse_test("Before\n\n```rust\nfn main() {}\n```\n");
+    let [MarkdownBlock::Paragraph(_), MarkdownBlock::Code(code)] = blocks.as_slice() else {
+        unreachable!("unexpected blocks: {blocks:?}")
+    };
+    assert_eq!(code, "fn main() {}");
+}
+
+#[test]
+fn strikethrough() {
+    let blocks = parse_test("a ~~b~~ c");
+    let [MarkdownBlock::Paragraph(spans)] = blocks.as_slice() else {
+        unreachable!("unexpected blocks: {blocks:?}")
+    };
+    let struck = spans
+        .iter()
+        .map(|span| (span.text.as_str(), span.strikethrough))
+        .collect::<Vec<_>>();
+    assert_eq!(struck, [("a ", false), ("b", true), (" c", false)]);
+}
+
+#[test]
+fn tables() {
+    let blocks = parse_test("| a | b |\n|---|---|\n| 1 | |\n| 2 | 3 |\n");
+    let [MarkdownBlock::Table {
+        columns: 2,
+        head,
+        rows,
+    }] = blocks.as_slice()
+    else {
+        unreachable!("unexpected blocks: {blocks:?}")
+    };
+    let text = |row: &[Vec<Span>]| row.iter().map(|cell| span_text(cell)).collect::<Vec<_>>();
+    assert_eq!(text(head), ["a", "b"]);
+    assert_eq!(
+        rows.iter().map(|row| text(row)).collect::<Vec<_>>(),
+        [["1", ""], ["2", "3"]]
+    );
+
+    let widget = blocks.into_iter().next().expect("one block").into_widget();
+    assert!(widget.lock().downcast_ref::<Grid<2>>().is_some());
+}
+
+#[test]
+fn wide_tables_are_unaligned() {
+    let table_source = |columns: usize| {
+        let row = |cell: &str| format!("|{}\n", format!(" {cell} |").repeat(columns));
+        format!("{}{}{}", row("a"), row("---"), row("1"))
+    };
+
+    let blocks = parse_test(&table_source(32));
+    let [MarkdownBlock::Table { columns: 32, .. }] = blocks.as_slice() else {
+        unreachable!("unexpected blocks: {blocks:?}")
+    };
+    let widget = blocks.into_iter().next().expect("one block").into_widget();
+    assert!(widget.lock().downcast_ref::<Grid<32>>().is_some());
+
+    let blocks = parse_test(&table_source(33));
+    let [MarkdownBlock::Table {
+        columns: 33,
+        head,
+        rows,
+    }] = blocks.as_slice()
+    else {
+        unreachable!("unexpected blocks: {blocks:?}")
+    };
+    assert_eq!(head.len(), 33);
+    assert_eq!(rows[0].len(), 33);
+    let widget = blocks.into_iter().next().expect("one block").into_widget();
+    assert!(widget.lock().downcast_ref::<Stack>().is_some());
+}
+
+#[test]
+fn links_invoke_on_link() {
+    let clicked = Arc::new(Mutex::new(Vec::new()));
+    let on_link = Arc::new(Mutex::new(Callback::new({
+        let clicked = clicked.clone();
+        move |destination: String| clicked.lock().ignore_poison().push(destination)
+    })));
+    let blocks = parse(
+        "[cushy](https://example.com) and **[bold](bold.md)**",
+        Some(&on_link),
+        &mut None,
+    );
+    let [MarkdownBlock::Paragraph(spans)] = blocks.as_slice() else {
+        unreachable!("unexpected blocks: {blocks:?}")
+    };
+    let links = spans
+        .iter()
+        .filter_map(|span| span.link.as_ref().map(|link| (span, link)))
+        .collect::<Vec<_>>();
+    assert_eq!(links.len(), 2);
+    assert_eq!(links[1].0.weight, Some(kludgine::cosmic_text::Weight::BOLD));
+    assert!(spans[1].link.is_none());
+    for (_, link) in links {
+        link.invoke();
+    }
+    assert_eq!(
+        *clicked.lock().ignore_poison(),
+        ["https://example.com", "bold.md"]
+    );
+}
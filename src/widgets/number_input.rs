@@ -0,0 +1,641 @@
+//! A text input for editing numbers.
+
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+
+use figures::units::{Lp, Px};
+use figures::{Point, Ranged, ScreenScale};
+use intentional::Cast;
+use kludgine::app::winit::event::{MouseButton, MouseScrollDelta, TouchPhase};
+use kludgine::app::winit::keyboard::{Key, NamedKey};
+use kludgine::app::winit::window::CursorIcon;
+
+use super::{Button, Input};
+use crate::context::EventContext;
+use crate::utils::locale_language_and_region;
+use crate::value::{
+    CallbackDisconnected, Destination, Dynamic, IntoDynamic, IntoValue, Source, Validation, Value,
+};
+use crate::widget::{
+    EventHandling, MakeWidget, MakeWidgetWithTag, WidgetInstance, WidgetRef, WidgetTag,
+    WrapperWidget, HANDLED, IGNORED,
+};
+use crate::window::{DeviceId, KeyEvent};
+
+/// The number of steps taken when using the page up and page down keys.
+const PAGE_STEPS: i32 = 10;
+/// The number of pixels a scroll wheel must report to count as a step.
+const PIXELS_PER_WHEEL_STEP: f32 = 20.;
+
+/// A text input that edits a number.
+///
+/// The value can be typed directly, or it can be stepped using the arrow keys,
+/// the page up and page down keys, the buttons beside the input, or the mouse
+/// wheel. If a [`label`](Self::label) is provided, dragging horizontally on
+/// the label also steps the value.
+///
+/// Text that cannot be parsed, or that is outside of the minimum and maximum,
+/// is not stored in [`value`](Self::value). Instead, the input displays a
+/// validation error.
+#[must_use]
+pub struct NumberInput<T>
+where
+    T: Number,
+{
+    /// The current value.
+    pub value: Dynamic<T>,
+    /// The minimum value allowed.
+    pub minimum: Value<T>,
+    /// The maximum value allowed.
+    pub maximum: Value<T>,
+    /// The amount the value is changed by each step.
+    pub step: Value<T>,
+    format: NumberFormat,
+    label: Option<WidgetInstance>,
+    validation: Dynamic<Validation>,
+}
+
+impl<T> NumberInput<T>
+where
+    T: Number,
+{
+    /// Returns a new input that edits `value`, allowing any value the type
+    /// can represent.
+    pub fn new(value: impl IntoDynamic<T>) -> Self {
+        Self {
+            value: value.into_dynamic(),
+            minimum: Value::Constant(T::MIN),
+            maximum: Value::Constant(T::MAX),
+            step: Value::Constant(T::DEFAULT_STEP),
+            format: NumberFormat::system(),
+            label: None,
+            validation: Dynamic::default(),
+        }
+    }
+
+    /// Sets the minimum value allowed to `min` and returns self.
+    pub fn minimum(mut self, min: impl IntoValue<T>) -> Self {
+        self.minimum = min.into_value();
+        self
+    }
+
+    /// Sets the maximum value allowed to `max` and returns self.
+    pub fn maximum(mut self, max: impl IntoValue<T>) -> Self {
+        self.maximum = max.into_value();
+        self
+    }
+
+    /// Sets the amount the value changes by each step and returns self.
+    ///
+    /// This defaults to [`Number::DEFAULT_STEP`].
+    pub fn step_by(mut self, step: impl IntoValue<T>) -> Self {
+        self.step = step.into_value();
+        self
+    }
+
+    /// Sets the format used to display and parse the value and returns self.
+    ///
+    /// This defaults to [`NumberFormat::system()`].
+    pub fn format(mut self, format: NumberFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Displays `label` before the input and returns self.
+    ///
+    /// Dragging horizontally on the label steps the value.
+    pub fn label(mut self, label: impl MakeWidget) -> Self {
+        self.label = Some(label.make_widget());
+        self
+    }
+
+    /// Returns the validation status of the text entered into this input.
+    #[must_use]
+    pub const fn text_validation(&self) -> &Dynamic<Validation> {
+        &self.validation
+    }
+}
+
+impl<T> Debug for NumberInput<T>
+where
+    T: Number,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NumberInput")
+            .field("value", &self.value)
+            .field("minimum", &self.minimum)
+            .field("maximum", &self.maximum)
+            .field("step", &self.step)
+            .field("format", &self.format)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> MakeWidgetWithTag for NumberInput<T>
+where
+    T: Number,
+{
+    fn make_with_tag(self, id: WidgetTag) -> WidgetInstance {
+        let format = self.format;
+        let stepper = Stepper {
+            value: self.value,
+            minimum: self.minimum,
+            maximum: self.maximum,
+            step: self.step,
+        };
+        let text = Dynamic::new(stepper.value.map_ref(|value| format.format(value)));
+
+        // Store the value as the user types, as long as it is valid.
+        text.set_source(text.for_each({
+            let stepper = stepper.clone();
+            let validation = self.validation.clone();
+            move |text: &String| {
+                validation.set(match stepper.parse(text, &format) {
+                    Ok(value) => {
+                        stepper.value.set(value);
+                        Validation::Valid
+                    }
+                    Err(error) => Validation::Invalid(error),
+                });
+            }
+        }));
+
+        // Update the text when the value is changed by something other than
+        // the text.
+        let weak_text = text.downgrade();
+        text.set_source(stepper.value.for_each_try(move |value| {
+            let text = weak_text.upgrade().ok_or(CallbackDisconnected)?;
+            let displayed = text.map_ref(|text| format.parse::<T>(text).ok());
+            if displayed != Some(*value) {
+                text.set(format.format(value));
+            }
+            Ok(())
+        }));
+
+        let input = Input::new(text).on_key({
+            let stepper = stepper.clone();
+            move |input: KeyEvent| {
+                let steps = match input.logical_key {
+                    Key::Named(NamedKey::ArrowUp) => 1,
+                    Key::Named(NamedKey::ArrowDown) => -1,
+                    Key::Named(NamedKey::PageUp) => PAGE_STEPS,
+                    Key::Named(NamedKey::PageDown) => -PAGE_STEPS,
+                    _ => return IGNORED,
+                };
+                if input.state.is_pressed() {
+                    stepper.step(steps);
+                }
+                HANDLED
+            }
+        });
+        let decrement = Button::new("\u{25BC}").on_click({
+            let stepper = stepper.clone();
+            move |()| stepper.step(-1)
+        });
+        let increment = Button::new("\u{25B2}").on_click({
+            let stepper = stepper.clone();
+            move |()| stepper.step(1)
+        });
+
+        let mut contents = input
+            .expand()
+            .and(decrement)
+            .and(increment)
+            .into_columns()
+            .make_widget();
+        if let Some(label) = self.label {
+            contents = ScrubLabel {
+                label: WidgetRef::new(label),
+                stepper: stepper.clone(),
+                drag: None,
+            }
+            .and(contents.expand())
+            .into_columns()
+            .make_widget();
+        }
+
+        NumberInputWidget {
+            contents: WidgetRef::new(contents.validation(self.validation)),
+            stepper,
+            wheel_remainder: 0.,
+        }
+        .make_with_tag(id)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Stepper<T> {
+    value: Dynamic<T>,
+    minimum: Value<T>,
+    maximum: Value<T>,
+    step: Value<T>,
+}
+
+impl<T> Stepper<T>
+where
+    T: Number,
+{
+    fn step(&self, steps: i32) {
+        let min = self.minimum.get();
+        let max = self.maximum.get();
+        let next = self.value.get().offset_by(self.step.get(), steps);
+        self.value.set(if next < min {
+            min
+        } else if next > max {
+            max
+        } else {
+            next
+        });
+    }
+
+    /// Parses `text` using `format`, returning an error message if it is not
+    /// a number between the minimum and maximum.
+    fn parse(&self, text: &str, format: &NumberFormat) -> Result<T, String> {
+        let value = format
+            .parse::<T>(text)
+            .map_err(|_| String::from("not a valid number"))?;
+        let min = self.minimum.get();
+        let max = self.maximum.get();
+        // NaN is neither less than nor greater than the bounds, so values are
+        // only accepted once they are known to be within them.
+        if min <= value && value <= max {
+            Ok(value)
+        } else {
+            Err(format!(
+                "must be between {} and {}",
+                format.format(min),
+                format.format(max)
+            ))
+        }
+    }
+}
+
+#[derive(Debug)]
+struct NumberInputWidget<T> {
+    contents: WidgetRef,
+    stepper: Stepper<T>,
+    wheel_remainder: f32,
+}
+
+impl<T> WrapperWidget for NumberInputWidget<T>
+where
+    T: Number,
+{
+    fn child_mut(&mut self) -> &mut WidgetRef {
+        &mut self.contents
+    }
+
+    fn mouse_wheel(
+        &mut self,
+        _device_id: DeviceId,
+        delta: MouseScrollDelta,
+        _phase: TouchPhase,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        if !context.enabled() {
+            return IGNORED;
+        }
+
+        self.wheel_remainder += match delta {
+            MouseScrollDelta::LineDelta(_, y) => y,
+            MouseScrollDelta::PixelDelta(pt) => pt.y.cast::<f32>() / PIXELS_PER_WHEEL_STEP,
+        };
+        let steps = self.wheel_remainder.trunc();
+        self.wheel_remainder -= steps;
+        #[allow(clippy::cast_possible_truncation)]
+        let steps = steps as i32;
+        if steps != 0 {
+            self.stepper.step(steps);
+        }
+
+        HANDLED
+    }
+}
+
+/// A label that steps a value when dragged horizontally.
+#[derive(Debug)]
+struct ScrubLabel<T> {
+    label: WidgetRef,
+    stepper: Stepper<T>,
+    drag: Option<(Px, i32)>,
+}
+
+impl<T> WrapperWidget for ScrubLabel<T>
+where
+    T: Number,
+{
+    fn child_mut(&mut self) -> &mut WidgetRef {
+        &mut self.label
+    }
+
+    fn hit_test(&mut self, _location: Point<Px>, _context: &mut EventContext<'_>) -> bool {
+        true
+    }
+
+    fn hover(
+        &mut self,
+        _location: Point<Px>,
+        context: &mut EventContext<'_>,
+    ) -> Option<CursorIcon> {
+        context.enabled().then_some(CursorIcon::EwResize)
+    }
+
+    fn mouse_down(
+        &mut self,
+        location: Point<Px>,
+        _device_id: DeviceId,
+        button: MouseButton,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        if button != MouseButton::Left || !context.enabled() {
+            return IGNORED;
+        }
+
+        self.drag = Some((location.x, 0));
+        HANDLED
+    }
+
+    fn mouse_drag(
+        &mut self,
+        location: Point<Px>,
+        _device_id: DeviceId,
+        _button: MouseButton,
+        context: &mut EventContext<'_>,
+    ) {
+        let Some((start, applied)) = &mut self.drag else {
+            return;
+        };
+        let distance = Lp::points(4).into_px(context.kludgine.scale()).get().max(1);
+        let steps = (location.x - *start).get() / distance;
+        if steps != *applied {
+            self.stepper.step(steps - *applied);
+            *applied = steps;
+        }
+    }
+
+    fn mouse_up(
+        &mut self,
+        _location: Option<Point<Px>>,
+        _device_id: DeviceId,
+        button: MouseButton,
+        _context: &mut EventContext<'_>,
+    ) {
+        if button == MouseButton::Left {
+            self.drag = None;
+        }
+    }
+}
+
+/// A numeric type that can be edited using a [`NumberInput`].
+pub trait Number:
+    Copy + PartialOrd + Ranged + Display + FromStr + Debug + Send + Sync + 'static
+{
+    /// The amount values change by each step, unless another step is
+    /// specified.
+    const DEFAULT_STEP: Self;
+
+    /// Returns `self` offset by `step` `steps` times, saturating at the bounds
+    /// of the type.
+    #[must_use]
+    fn offset_by(self, step: Self, steps: i32) -> Self;
+}
+
+macro_rules! impl_integer_number {
+    ($($type:ty),+) => {$(
+        impl Number for $type {
+            const DEFAULT_STEP: Self = 1;
+
+            fn offset_by(self, step: Self, steps: i32) -> Self {
+                let delta = step
+                    .saturating_mul(Self::try_from(steps.unsigned_abs()).unwrap_or(Self::MAX));
+                if steps < 0 {
+                    self.saturating_sub(delta)
+                } else {
+                    self.saturating_add(delta)
+                }
+            }
+        }
+    )+};
+}
+
+impl_integer_number!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+macro_rules! impl_float_number {
+    ($($type:ty),+) => {$(
+        impl Number for $type {
+            const DEFAULT_STEP: Self = 1.;
+
+            #[allow(clippy::cast_precision_loss)]
+            fn offset_by(self, step: Self, steps: i32) -> Self {
+                let next = (self + step * steps as $type).clamp(<$type>::MIN, <$type>::MAX);
+                // Round to the precision of the step to avoid accumulating
+                // floating point error as the value is stepped.
+                let decimals = step
+                    .to_string()
+                    .split_once('.')
+                    .map_or(0, |(_, fraction)| fraction.len());
+                match i32::try_from(decimals) {
+                    Ok(decimals) if decimals > 0 => {
+                        let scale = <$type>::from(10_u8).powi(decimals);
+                        (next * scale).round() / scale
+                    }
+                    _ => next,
+                }
+            }
+        }
+    )+};
+}
+
+impl_float_number!(f32, f64);
+
+/// A type that can be converted into a [`Dynamic`] containing a [`Number`].
+pub trait NumberInputValue<T>: IntoDynamic<T> + Sized
+where
+    T: Number,
+{
+    /// Returns this value as a number input widget.
+    fn into_number_input(self) -> NumberInput<T> {
+        NumberInput::new(self.into_dynamic())
+    }
+}
+
+impl<U, T> NumberInputValue<U> for T
+where
+    T: IntoDynamic<U>,
+    U: Number,
+{
+}
+
+/// Describes how numbers are displayed and parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// The character separating the whole and fractional parts of a number.
+    pub decimal_separator: char,
+    /// The character inserted between each group of three digits in the whole
+    /// part of a number, if any.
+    pub grouping_separator: Option<char>,
+}
+
+impl NumberFormat {
+    /// A format that matches Rust's [`Display`] and [`FromStr`]
+    /// implementations.
+    pub const PLAIN: Self = Self {
+        decimal_separator: '.',
+        grouping_separator: None,
+    };
+
+    /// Returns the format for the operating system's current locale, or
+    /// [`NumberFormat::PLAIN`] if the locale cannot be determined.
+    #[must_use]
+    pub fn system() -> Self {
+        sys_locale::get_locale().map_or(Self::PLAIN, |locale| Self::for_locale(&locale))
+    }
+
+    /// Returns the format typically used by `locale`, which is a
+    /// [BCP 47](https://www.rfc-editor.org/info/bcp47) language tag such as
+    /// `en-US` or `de-DE`.
+    ///
+    /// This is a small approximation of the data in the Unicode CLDR that
+    /// covers the most common decimal and grouping separators.
+    #[must_use]
+    pub fn for_locale(locale: &str) -> Self {
+        let (language, region) = locale_language_and_region(locale);
+
+        match (language.as_str(), region.as_str()) {
+            ("de" | "it" | "fr" | "rm", "CH" | "LI") => Self {
+                decimal_separator: '.',
+                grouping_separator: Some('\u{2019}'),
+            },
+            ("es", "MX" | "US" | "PR" | "GT" | "HN" | "NI" | "PA" | "SV" | "DO") => Self {
+                decimal_separator: '.',
+                grouping_separator: Some(','),
+            },
+            (
+                "be" | "bg" | "cs" | "et" | "fi" | "fr" | "hu" | "hy" | "ka" | "kk" | "ky" | "lt"
+                | "lv" | "nb" | "nn" | "no" | "pl" | "ru" | "sk" | "sq" | "sv" | "uk" | "uz",
+                _,
+            ) => Self {
+                decimal_separator: ',',
+                grouping_separator: Some('\u{a0}'),
+            },
+            (
+                "af" | "az" | "bs" | "ca" | "da" | "de" | "el" | "es" | "eu" | "gl" | "hr" | "id"
+                | "is" | "it" | "mk" | "nl" | "pt" | "ro" | "sl" | "sr" | "tr" | "vi",
+                _,
+            ) => Self {
+                decimal_separator: ',',
+                grouping_separator: Some('.'),
+            },
+            _ => Self {
+                decimal_separator: '.',
+                grouping_separator: Some(','),
+            },
+        }
+    }
+
+    /// Returns `value` formatted using this format.
+    #[must_use]
+    pub fn format(&self, value: impl Display) -> String {
+        let plain = value.to_string();
+        let (sign, digits) = plain
+            .strip_prefix('-')
+            .map_or(("", plain.as_str()), |digits| ("-", digits));
+        let (whole, fraction) = digits
+            .split_once('.')
+            .map_or((digits, None), |(whole, fraction)| (whole, Some(fraction)));
+
+        let mut formatted = String::with_capacity(plain.len() + whole.len() / 3);
+        formatted.push_str(sign);
+        match self.grouping_separator {
+            Some(separator) if whole.bytes().all(|b| b.is_ascii_digit()) => {
+                for (index, digit) in whole.chars().enumerate() {
+                    if index > 0 && (whole.len() - index) % 3 == 0 {
+                        formatted.push(separator);
+                    }
+                    formatted.push(digit);
+                }
+            }
+            _ => formatted.push_str(whole),
+        }
+        if let Some(fraction) = fraction {
+            formatted.push(self.decimal_separator);
+            formatted.push_str(fraction);
+        }
+        formatted
+    }
+
+    /// Parses `text` using this format.
+    ///
+    /// Grouping separators are optional and are ignored wherever they appear.
+    pub fn parse<T>(&self, text: &str) -> Result<T, T::Err>
+    where
+        T: FromStr,
+    {
+        let groups_with_spaces = self.grouping_separator.map_or(false, char::is_whitespace);
+        let normalized = text
+            .trim()
+            .chars()
+            .filter(|ch| {
+                Some(*ch) != self.grouping_separator && !(groups_with_spaces && ch.is_whitespace())
+            })
+            .map(|ch| {
+                if ch == self.decimal_separator {
+                    '.'
+                } else {
+                    ch
+                }
+            })
+            .collect::<String>();
+        normalized.parse()
+    }
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::PLAIN
+    }
+}
+
+#[test]
+fn formatting() {
+    let english = NumberFormat::for_locale("en-US");
+    assert_eq!(english.format(1_234_567), "1,234,567");
+    assert_eq!(english.format(-1234.5), "-1,234.5");
+    assert_eq!(english.parse::<i32>("1,234"), Ok(1234));
+
+    let german = NumberFormat::for_locale("de_DE.UTF-8");
+    assert_eq!(german.format(1234.5), "1.234,5");
+    assert_eq!(german.parse::<f32>("1.234,5"), Ok(1234.5));
+
+    let french = NumberFormat::for_locale("fr-FR");
+    assert_eq!(french.format(-999), "-999");
+    assert_eq!(french.parse::<f64>("12 345,25"), Ok(12_345.25));
+
+    assert_eq!(NumberFormat::PLAIN.format(12345), "12345");
+    assert!(NumberFormat::PLAIN.parse::<u8>("256").is_err());
+}
+
+#[test]
+fn stepping() {
+    assert_eq!(250_u8.offset_by(5, 2), 255);
+    assert_eq!(3_u8.offset_by(5, -1), 0);
+    assert_eq!((-5_i32).offset_by(2, 3), 1);
+    assert_eq!(0.1_f64.offset_by(0.1, 2), 0.3);
+    assert_eq!(1.5_f32.offset_by(0.25, -1), 1.25);
+}
+
+#[test]
+fn validation() {
+    let stepper = Stepper {
+        value: Dynamic::new(0.),
+        minimum: Value::Constant(-1.),
+        maximum: Value::Constant(1.),
+        step: Value::Constant(0.1),
+    };
+    assert_eq!(stepper.parse("0.5", &NumberFormat::PLAIN), Ok(0.5));
+    assert_eq!(stepper.parse("-1", &NumberFormat::PLAIN), Ok(-1.));
+    assert!(stepper.parse("1.5", &NumberFormat::PLAIN).is_err());
+    assert!(stepper.parse("NaN", &NumberFormat::PLAIN).is_err());
+    assert!(stepper.parse("inf", &NumberFormat::PLAIN).is_err());
+    assert!(stepper.parse("one", &NumberFormat::PLAIN).is_err());
+}
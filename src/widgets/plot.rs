@@ -0,0 +1,693 @@
+//! An interactive chart built on the [plotters](https://github.com/plotters-rs/plotters) crate.
+//!
+//! This module is only available when the `plotters` feature is enabled.
+
+use std::error::Error;
+use std::fmt::{self, Debug};
+use std::ops::Range;
+
+use figures::units::{Lp, Px, UPx};
+use figures::{Point, Rect, Round, ScreenScale, Size, Zero};
+use kludgine::app::winit::event::{MouseButton, MouseScrollDelta, TouchPhase};
+use kludgine::app::winit::window::CursorIcon;
+use kludgine::drawing::PlotterBackend;
+use kludgine::shapes::{Shape, StrokeOptions};
+use kludgine::text::{Text, TextOrigin};
+use kludgine::{DrawableExt, Origin};
+use plotters::chart::{ChartBuilder, ChartContext};
+use plotters::coord::cartesian::Cartesian2d;
+use plotters::coord::types::RangedCoordf64;
+
+use crate::context::{EventContext, GraphicsContext, LayoutContext, WidgetContext};
+use crate::styles::components::{
+    HighlightColor, IntrinsicPadding, OpaqueWidgetColor, OutlineColor, PrimaryColor, TextColor,
+};
+use crate::utils::wheel_zoom_factor;
+use crate::value::{Destination, Dynamic, IntoDynamic, Source};
+use crate::widget::{EventHandling, Widget, HANDLED, IGNORED};
+use crate::window::DeviceId;
+use crate::ConstraintLimit;
+
+/// A plotters chart with floating point axes, as drawn by an
+/// [`InteractivePlot`].
+pub type PlotChart<'chart, 'area, 'gfx, 'pass> = ChartContext<
+    'chart,
+    PlotterBackend<'area, 'gfx, 'pass>,
+    Cartesian2d<RangedCoordf64, RangedCoordf64>,
+>;
+
+/// A chart that can be zoomed, panned, and inspected with the mouse.
+///
+/// The visible ranges of each axis are stored in [`Dynamic`]s, allowing other
+/// widgets to observe or change them:
+///
+/// - Scrolling the mouse wheel zooms around the cursor.
+/// - Dragging with the left mouse button pans.
+/// - Dragging with the right mouse button zooms into the selected box.
+///
+/// Points registered using [`PlotContext::hoverable`] are inspected by
+/// hovering: the point nearest to the cursor is highlighted and its value is
+/// shown in a tooltip.
+///
+/// The plot is only redrawn when the ranges change, the hovered point changes,
+/// or a value tracked during rendering changes.
+#[must_use]
+pub struct InteractivePlot {
+    render: Box<dyn PlotFunction>,
+    x: Dynamic<Range<f64>>,
+    y: Dynamic<Range<f64>>,
+    margin: Lp,
+    x_label_area: Lp,
+    y_label_area: Lp,
+    mapping: Option<PlotMapping>,
+    hoverable: Vec<HoverSeries>,
+    cursor: Option<Point<Px>>,
+    hovered: Option<HoveredPoint>,
+    drag: Option<PlotDrag>,
+}
+
+impl InteractivePlot {
+    /// Returns a new plot that displays `x` and `y`, drawing its contents by
+    /// invoking `render`.
+    ///
+    /// `render` is provided a chart whose coordinates are the current ranges.
+    /// The chart's mesh is not drawn automatically, which allows it to be
+    /// configured as needed.
+    pub fn new<F>(
+        x: impl IntoDynamic<Range<f64>>,
+        y: impl IntoDynamic<Range<f64>>,
+        render: F,
+    ) -> Self
+    where
+        F: for<'chart, 'area, 'gfx, 'pass> FnMut(
+                &mut PlotChart<'chart, 'area, 'gfx, 'pass>,
+                &mut PlotContext<'_, '_>,
+            ) -> Result<(), Box<dyn Error>>
+            + Send
+            + 'static,
+    {
+        Self {
+            render: Box::new(render),
+            x: x.into_dynamic(),
+            y: y.into_dynamic(),
+            margin: Lp::points(8),
+            x_label_area: Lp::points(24),
+            y_label_area: Lp::points(48),
+            mapping: None,
+            hoverable: Vec::new(),
+            cursor: None,
+            hovered: None,
+            drag: None,
+        }
+    }
+
+    /// Sets the margin around the chart and returns self.
+    pub fn margin(mut self, margin: Lp) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Sets the sizes reserved for the labels of the x and y axes, and returns
+    /// self.
+    pub fn label_areas(mut self, x_axis: Lp, y_axis: Lp) -> Self {
+        self.x_label_area = x_axis;
+        self.y_label_area = y_axis;
+        self
+    }
+
+    /// Returns the dynamic containing the visible range of the x axis.
+    #[must_use]
+    pub const fn x_range(&self) -> &Dynamic<Range<f64>> {
+        &self.x
+    }
+
+    /// Returns the dynamic containing the visible range of the y axis.
+    #[must_use]
+    pub const fn y_range(&self) -> &Dynamic<Range<f64>> {
+        &self.y
+    }
+
+    /// Sets the displayed ranges.
+    ///
+    /// The mapping is updated immediately so that input events received before
+    /// the next redraw build upon these ranges.
+    fn set_ranges(&mut self, x: Range<f64>, y: Range<f64>) {
+        if let Some(mapping) = &mut self.mapping {
+            mapping.x = x.clone();
+            mapping.y = y.clone();
+        }
+        self.x.set(x);
+        self.y.set(y);
+    }
+
+    fn update_hovered(&mut self, context: &mut EventContext<'_>) {
+        let hovered = match (&self.mapping, self.cursor) {
+            (Some(mapping), Some(cursor)) if self.drag.is_none() => {
+                let radius = Lp::points(12).into_px(context.kludgine.scale());
+                nearest_point(mapping, &self.hoverable, cursor, radius)
+            }
+            _ => None,
+        };
+        if hovered != self.hovered {
+            self.hovered = hovered;
+            context.set_needs_redraw();
+        }
+    }
+
+    fn draw_selection(
+        &self,
+        start: Point<Px>,
+        end: Point<Px>,
+        context: &mut GraphicsContext<'_, '_, '_, '_>,
+    ) {
+        let selection = Rect::from_extents(start.min(end), start.max(end));
+        let fill = context.get(&HighlightColor);
+        let outline = context.get(&PrimaryColor);
+        let stroke = StrokeOptions::px_wide(Lp::points(1).into_px(context.gfx.scale()).round());
+        context.gfx.draw_shape(&Shape::filled_rect(selection, fill));
+        context
+            .gfx
+            .draw_shape(&Shape::stroked_rect(selection, stroke.colored(outline)));
+    }
+
+    fn draw_tooltip(&self, hovered: &HoveredPoint, context: &mut GraphicsContext<'_, '_, '_, '_>) {
+        let Some(mapping) = &self.mapping else {
+            return;
+        };
+        let point = mapping.to_pixel(hovered.point);
+        let scale = context.gfx.scale();
+        let marker = Lp::points(4).into_px(scale);
+        let accent = context.get(&PrimaryColor);
+        context
+            .gfx
+            .draw_shape(&Shape::filled_circle(marker, accent, Origin::Center).translate_by(point));
+
+        let text_color = context.get(&TextColor);
+        context.apply_current_font_settings();
+        let label = hovered.label();
+        let text = context
+            .gfx
+            .measure_text(Text::<Px>::new(&label, text_color));
+        let padding = context.get(&IntrinsicPadding).into_px(scale).round();
+        let size = Size::new(
+            text.size.width + padding * 2,
+            text.size.height + padding * 2,
+        );
+
+        // Prefer placing the tooltip above and to the right of the point,
+        // flipping it when it would leave the plot.
+        let region = context.gfx.region().size;
+        let mut origin = Point::new(point.x + marker * 2, point.y - marker * 2 - size.height);
+        if origin.x + size.width > region.width {
+            origin.x = point.x - marker * 2 - size.width;
+        }
+        if origin.y < Px::ZERO {
+            origin.y = point.y + marker * 2;
+        }
+        let tooltip = Rect::new(origin, size);
+
+        let background = context.get(&OpaqueWidgetColor);
+        let outline = context.get(&OutlineColor);
+        let stroke = StrokeOptions::px_wide(Lp::points(1).into_px(scale).round());
+        context
+            .gfx
+            .draw_shape(&Shape::filled_rect(tooltip, background));
+        context
+            .gfx
+            .draw_shape(&Shape::stroked_rect(tooltip, stroke.colored(outline)));
+        context.gfx.draw_measured_text(
+            text.translate_by(origin + Point::squared(padding)),
+            TextOrigin::TopLeft,
+        );
+    }
+}
+
+impl Widget for InteractivePlot {
+    fn redraw(&mut self, context: &mut GraphicsContext<'_, '_, '_, '_>) {
+        let x = self.x.get_tracking_redraw(context);
+        let y = self.y.get_tracking_redraw(context);
+        let scale = context.gfx.scale();
+        let translation = context.gfx.translation();
+
+        self.hoverable.clear();
+        let GraphicsContext { widget, gfx } = context;
+        let area = gfx.as_plot_area();
+        let mut chart = match ChartBuilder::on(&area)
+            .margin(plot_pixels(self.margin.into_px(scale)))
+            .x_label_area_size(plot_pixels(self.x_label_area.into_px(scale)))
+            .y_label_area_size(plot_pixels(self.y_label_area.into_px(scale)))
+            .build_cartesian_2d(x.clone(), y.clone())
+        {
+            Ok(chart) => chart,
+            Err(err) => {
+                tracing::error!("error building plot: {err}");
+                return;
+            }
+        };
+        let (x_pixels, y_pixels) = chart.plotting_area().get_pixel_range();
+
+        let mut plot = PlotContext {
+            widget,
+            hoverable: &mut self.hoverable,
+        };
+        if let Err(err) = self.render.render(&mut chart, &mut plot) {
+            tracing::error!("error rendering plot: {err}");
+        }
+        drop(chart);
+        drop(area);
+
+        // The plot is drawn relative to the clipping rectangle, which differs
+        // from the widget's region when partially scrolled out of view.
+        let area = Rect::from_extents(
+            Point::new(Px::new(x_pixels.start), Px::new(y_pixels.start)),
+            Point::new(Px::new(x_pixels.end), Px::new(y_pixels.end)),
+        ) - translation;
+        self.mapping = Some(PlotMapping { area, x, y });
+
+        if let Some(PlotDrag::Zoom { start, current }) = self.drag {
+            self.draw_selection(start, current, context);
+        }
+        if let Some(hovered) = &self.hovered {
+            self.draw_tooltip(hovered, context);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        available_space: Size<ConstraintLimit>,
+        _context: &mut LayoutContext<'_, '_, '_, '_>,
+    ) -> Size<UPx> {
+        available_space.map(ConstraintLimit::max)
+    }
+
+    fn hit_test(&mut self, location: Point<Px>, _context: &mut EventContext<'_>) -> bool {
+        self.mapping
+            .as_ref()
+            .map_or(false, |mapping| mapping.area.contains(location))
+    }
+
+    fn hover(&mut self, location: Point<Px>, context: &mut EventContext<'_>) -> Option<CursorIcon> {
+        self.cursor = Some(location);
+        self.update_hovered(context);
+        Some(CursorIcon::Crosshair)
+    }
+
+    fn unhover(&mut self, context: &mut EventContext<'_>) {
+        self.cursor = None;
+        self.update_hovered(context);
+    }
+
+    fn mouse_down(
+        &mut self,
+        location: Point<Px>,
+        _device_id: DeviceId,
+        button: MouseButton,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        self.drag = match button {
+            MouseButton::Left => Some(PlotDrag::Pan { last: location }),
+            MouseButton::Right => Some(PlotDrag::Zoom {
+                start: location,
+                current: location,
+            }),
+            _ => return IGNORED,
+        };
+        self.update_hovered(context);
+        HANDLED
+    }
+
+    fn mouse_drag(
+        &mut self,
+        location: Point<Px>,
+        _device_id: DeviceId,
+        _button: MouseButton,
+        context: &mut EventContext<'_>,
+    ) {
+        let Some(mapping) = &self.mapping else {
+            return;
+        };
+        match &mut self.drag {
+            Some(PlotDrag::Pan { last }) => {
+                let (x, y) = mapping.pan(location - *last);
+                *last = location;
+                self.set_ranges(x, y);
+            }
+            Some(PlotDrag::Zoom { current, .. }) => {
+                *current = location;
+                context.set_needs_redraw();
+            }
+            None => {}
+        }
+    }
+
+    fn mouse_up(
+        &mut self,
+        location: Option<Point<Px>>,
+        _device_id: DeviceId,
+        _button: MouseButton,
+        context: &mut EventContext<'_>,
+    ) {
+        if let (Some(PlotDrag::Zoom { start, current }), Some(mapping)) =
+            (self.drag.take(), &self.mapping)
+        {
+            let end = location.unwrap_or(current);
+            let minimum = Lp::points(4).into_px(context.kludgine.scale());
+            if (end.x - start.x).abs() >= minimum && (end.y - start.y).abs() >= minimum {
+                let (x, y) = mapping.select(start, end);
+                self.set_ranges(x, y);
+            }
+            context.set_needs_redraw();
+        }
+        self.cursor = location;
+        self.update_hovered(context);
+    }
+
+    fn mouse_wheel(
+        &mut self,
+        _device_id: DeviceId,
+        delta: MouseScrollDelta,
+        _phase: TouchPhase,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        let Some(mapping) = &self.mapping else {
+            return IGNORED;
+        };
+        let around = self
+            .cursor
+            .filter(|cursor| mapping.area.contains(*cursor))
+            .unwrap_or_else(|| mapping.area.origin + mapping.area.size / 2);
+        let (x, y) = mapping.zoom(around, f64::from(wheel_zoom_factor(delta)));
+        self.set_ranges(x, y);
+        self.update_hovered(context);
+        HANDLED
+    }
+}
+
+impl Debug for InteractivePlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InteractivePlot")
+            .field("x", &self.x)
+            .field("y", &self.y)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Additional state available while rendering an [`InteractivePlot`].
+pub struct PlotContext<'a, 'context> {
+    /// The context of the plot widget.
+    ///
+    /// This can be used to track values that the plot depends on, causing it
+    /// to be redrawn when they change.
+    pub widget: &'a mut WidgetContext<'context>,
+    hoverable: &'a mut Vec<HoverSeries>,
+}
+
+impl PlotContext<'_, '_> {
+    /// Allows `points` to be inspected by hovering over them. `series` is shown
+    /// in the tooltip alongside the hovered point's value.
+    pub fn hoverable(
+        &mut self,
+        series: impl Into<String>,
+        points: impl IntoIterator<Item = (f64, f64)>,
+    ) {
+        self.hoverable.push(HoverSeries {
+            label: series.into(),
+            points: points.into_iter().collect(),
+        });
+    }
+}
+
+trait PlotFunction: Send + 'static {
+    fn render(
+        &mut self,
+        chart: &mut PlotChart<'_, '_, '_, '_>,
+        context: &mut PlotContext<'_, '_>,
+    ) -> Result<(), Box<dyn Error>>;
+}
+
+impl<F> PlotFunction for F
+where
+    F: for<'chart, 'area, 'gfx, 'pass> FnMut(
+            &mut PlotChart<'chart, 'area, 'gfx, 'pass>,
+            &mut PlotContext<'_, '_>,
+        ) -> Result<(), Box<dyn Error>>
+        + Send
+        + 'static,
+{
+    fn render(
+        &mut self,
+        chart: &mut PlotChart<'_, '_, '_, '_>,
+        context: &mut PlotContext<'_, '_>,
+    ) -> Result<(), Box<dyn Error>> {
+        self(chart, context)
+    }
+}
+
+#[derive(Debug)]
+struct HoverSeries {
+    label: String,
+    points: Vec<(f64, f64)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct HoveredPoint {
+    series: String,
+    point: (f64, f64),
+}
+
+impl HoveredPoint {
+    fn label(&self) -> String {
+        format!("{}: ({}, {})", self.series, self.point.0, self.point.1)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum PlotDrag {
+    Pan {
+        last: Point<Px>,
+    },
+    Zoom {
+        start: Point<Px>,
+        current: Point<Px>,
+    },
+}
+
+/// Converts between widget pixels and data coordinates.
+#[derive(Debug, Clone, PartialEq)]
+struct PlotMapping {
+    area: Rect<Px>,
+    x: Range<f64>,
+    y: Range<f64>,
+}
+
+impl PlotMapping {
+    fn to_data(&self, pixel: Point<Px>) -> (f64, f64) {
+        let x = f64::from((pixel.x - self.area.origin.x).get())
+            / f64::from(self.area.size.width.get().max(1));
+        let y = f64::from((pixel.y - self.area.origin.y).get())
+            / f64::from(self.area.size.height.get().max(1));
+        (
+            lerp(&self.x, x),
+            // Pixels increase downwards, while the y axis increases upwards.
+            lerp(&self.y, 1. - y),
+        )
+    }
+
+    fn to_pixel_f64(&self, point: (f64, f64)) -> (f64, f64) {
+        let x = (point.0 - self.x.start) / span(&self.x);
+        let y = 1. - (point.1 - self.y.start) / span(&self.y);
+        (
+            f64::from(self.area.origin.x.get()) + x * f64::from(self.area.size.width.get()),
+            f64::from(self.area.origin.y.get()) + y * f64::from(self.area.size.height.get()),
+        )
+    }
+
+    #[allow(clippy::cast_possible_truncation)] // plots are much smaller than i32::MAX
+    fn to_pixel(&self, point: (f64, f64)) -> Point<Px> {
+        let (x, y) = self.to_pixel_f64(point);
+        Point::new(Px::new(x.round() as i32), Px::new(y.round() as i32))
+    }
+
+    /// Returns the ranges after moving the contents of the plot by `delta`.
+    fn pan(&self, delta: Point<Px>) -> (Range<f64>, Range<f64>) {
+        let dx =
+            f64::from(delta.x.get()) / f64::from(self.area.size.width.get().max(1)) * span(&self.x);
+        let dy = f64::from(delta.y.get()) / f64::from(self.area.size.height.get().max(1))
+            * span(&self.y);
+        (
+            self.x.start - dx..self.x.end - dx,
+            self.y.start + dy..self.y.end + dy,
+        )
+    }
+
+    /// Returns the ranges after zooming by `factor` while keeping the data
+    /// under `around` in place. Factors greater than 1 zoom in.
+    fn zoom(&self, around: Point<Px>, factor: f64) -> (Range<f64>, Range<f64>) {
+        let (x, y) = self.to_data(around);
+        (
+            zoom_range(&self.x, x, factor),
+            zoom_range(&self.y, y, factor),
+        )
+    }
+
+    /// Returns the ranges that show the box between `start` and `end`.
+    fn select(&self, start: Point<Px>, end: Point<Px>) -> (Range<f64>, Range<f64>) {
+        let start = self.to_data(start);
+        let end = self.to_data(end);
+        (
+            start.0.min(end.0)..start.0.max(end.0),
+            start.1.min(end.1)..start.1.max(end.1),
+        )
+    }
+}
+
+fn span(range: &Range<f64>) -> f64 {
+    let span = range.end - range.start;
+    if span == 0. {
+        f64::EPSILON
+    } else {
+        span
+    }
+}
+
+fn lerp(range: &Range<f64>, fraction: f64) -> f64 {
+    range.start + (range.end - range.start) * fraction
+}
+
+/// The smallest span that can be zoomed into, relative to the magnitude of the
+/// values being displayed.
+const MINIMUM_RELATIVE_SPAN: f64 = 1e-9;
+
+/// The largest span that can be zoomed out to.
+const MAXIMUM_SPAN: f64 = 1e300;
+
+/// Returns `range` zoomed by `factor` around `around`.
+///
+/// The resulting span is limited so that repeated zooming can never collapse
+/// the range or overflow it. Invalid factors leave the range unchanged.
+fn zoom_range(range: &Range<f64>, around: f64, factor: f64) -> Range<f64> {
+    let span = (range.end - range.start).abs();
+    if factor.is_nan() || factor <= 0. || !span.is_normal() || !around.is_finite() {
+        return range.clone();
+    }
+    let minimum = around.abs().max(1.) * MINIMUM_RELATIVE_SPAN;
+    let factor = factor.clamp(span / MAXIMUM_SPAN, span / minimum);
+    around + (range.start - around) / factor..around + (range.end - around) / factor
+}
+
+/// Returns the point in `series` nearest to `cursor`, if it is within
+/// `radius`.
+fn nearest_point(
+    mapping: &PlotMapping,
+    series: &[HoverSeries],
+    cursor: Point<Px>,
+    radius: Px,
+) -> Option<HoveredPoint> {
+    let cursor = (f64::from(cursor.x.get()), f64::from(cursor.y.get()));
+    let radius = f64::from(radius.get());
+    series
+        .iter()
+        .flat_map(|series| {
+            series
+                .points
+                .iter()
+                .map(move |point| (series.label.as_str(), *point))
+        })
+        .filter_map(|(series, point)| {
+            let pixel = mapping.to_pixel_f64(point);
+            let distance = (pixel.0 - cursor.0).hypot(pixel.1 - cursor.1);
+            (distance <= radius).then_some((distance, series, point))
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, series, point)| HoveredPoint {
+            series: series.to_string(),
+            point,
+        })
+}
+
+fn plot_pixels(length: Px) -> u32 {
+    u32::try_from(length.get()).unwrap_or(0)
+}
+
+#[test]
+fn mapping_zoom_and_pan() {
+    let mapping = PlotMapping {
+        area: Rect::new(
+            Point::new(Px::new(10), Px::new(10)),
+            Size::new(Px::new(100), Px::new(50)),
+        ),
+        x: 0.0..10.0,
+        y: 0.0..5.0,
+    };
+    assert_eq!(
+        mapping.to_data(Point::new(Px::new(60), Px::new(10))),
+        (5., 5.)
+    );
+    assert_eq!(
+        mapping.to_pixel((5., 0.)),
+        Point::new(Px::new(60), Px::new(60))
+    );
+
+    let (x, y) = mapping.zoom(Point::new(Px::new(10), Px::new(60)), 2.);
+    assert_eq!((x, y), (0.0..5.0, 0.0..2.5));
+
+    // Invalid factors are ignored, and the span is kept from collapsing.
+    assert_eq!(zoom_range(&(0.0..10.0), 5., 0.), 0.0..10.0);
+    assert_eq!(zoom_range(&(0.0..10.0), 5., -1.), 0.0..10.0);
+    assert_eq!(zoom_range(&(0.0..10.0), 5., f64::NAN), 0.0..10.0);
+    let zoomed = zoom_range(&(0.0..10.0), 5., f64::INFINITY);
+    assert!(zoomed.end - zoomed.start > 0.);
+    assert!((zoomed.end - zoomed.start - 5. * MINIMUM_RELATIVE_SPAN).abs() < 1e-12);
+
+    let (x, y) = mapping.pan(Point::new(Px::new(10), Px::new(-10)));
+    assert_eq!((x, y), (-1.0..9.0, -1.0..4.0));
+
+    let (x, y) = mapping.select(
+        Point::new(Px::new(60), Px::new(10)),
+        Point::new(Px::new(10), Px::new(35)),
+    );
+    assert_eq!((x, y), (0.0..5.0, 2.5..5.0));
+}
+
+#[test]
+fn nearest_hovered_point() {
+    let mapping = PlotMapping {
+        area: Rect::new(
+            Point::new(Px::new(0), Px::new(0)),
+            Size::new(Px::new(100), Px::new(100)),
+        ),
+        x: 0.0..100.0,
+        y: 0.0..100.0,
+    };
+    let series = [
+        HoverSeries {
+            label: String::from("a"),
+            points: vec![(10., 90.), (50., 50.)],
+        },
+        HoverSeries {
+            label: String::from("b"),
+            points: vec![(14., 86.)],
+        },
+    ];
+    assert_eq!(
+        nearest_point(
+            &mapping,
+            &series,
+            Point::new(Px::new(13), Px::new(13)),
+            Px::new(5)
+        ),
+        Some(HoveredPoint {
+            series: String::from("b"),
+            point: (14., 86.)
+        })
+    );
+    assert_eq!(
+        nearest_point(
+            &mapping,
+            &series,
+            Point::new(Px::new(30), Px::new(30)),
+            Px::new(5)
+        ),
+        None
+    );
+}
@@ -1,20 +1,26 @@
 //! Widgets for displaying progress indicators.
 
+use std::collections::VecDeque;
 use std::ops::RangeInclusive;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use figures::units::Px;
-use figures::{Angle, Point, Ranged, ScreenScale, Size, Zero};
-use kludgine::shapes::{Path, StrokeOptions};
+use figures::{Angle, Point, Ranged, Rect, ScreenScale, Size, Zero};
+use kludgine::shapes::{Path, Shape, StrokeOptions};
 use kludgine::Color;
 
 use crate::animation::easings::{EaseInQuadradic, EaseOutQuadradic};
 use crate::animation::{
     AnimationHandle, AnimationTarget, IntoAnimate, PercentBetween, Spawn, ZeroToOne,
 };
-use crate::value::{Destination, Dynamic, IntoReadOnly, IntoReader, MapEach, ReadOnly, Source};
-use crate::widget::{MakeWidget, MakeWidgetWithTag, Widget, WidgetInstance};
-use crate::widgets::slider::{InactiveTrackColor, Slidable, TrackColor, TrackSize};
+use crate::styles::components::{ErrorColor, SecondaryColor, TertiaryColor};
+use crate::value::{
+    Destination, Dynamic, IntoReadOnly, IntoReader, IntoValue, MapEach, ReadOnly, Source, Value,
+};
+use crate::widget::{MakeWidget, MakeWidgetWithTag, Widget, WidgetInstance, WidgetTag};
+use crate::widgets::slider::{
+    InactiveTrackColor, MinimumSliderSize, Slidable, TrackColor, TrackSize,
+};
 use crate::widgets::Data;
 
 /// A bar-shaped progress indicator.
@@ -22,6 +28,7 @@ use crate::widgets::Data;
 pub struct ProgressBar {
     progress: ReadOnly<Progress>,
     spinner: bool,
+    text: Option<WidgetInstance>,
 }
 
 impl ProgressBar {
@@ -31,6 +38,7 @@ impl ProgressBar {
         Self {
             progress: ReadOnly::Constant(Progress::Indeterminant),
             spinner: false,
+            text: None,
         }
     }
 
@@ -40,15 +48,45 @@ impl ProgressBar {
         Self {
             progress: progress.into_read_only(),
             spinner: false,
+            text: None,
         }
     }
 
-    /// Returns a new progress bar that displays `progress`.
+    /// Displays this progress indicator as a ring rather than a bar and
+    /// returns self.
+    ///
+    /// Determinant progress fills the ring clockwise starting at the top.
+    /// Indeterminant progress is shown as an animated spinner.
     #[must_use]
     pub fn spinner(mut self) -> Self {
         self.spinner = true;
         self
     }
+
+    /// Displays `text` centered on top of this progress indicator and returns
+    /// self.
+    #[must_use]
+    pub fn text(mut self, text: impl MakeWidget) -> Self {
+        self.text = Some(text.make_widget());
+        self
+    }
+
+    /// Displays the current percentage centered on top of this progress
+    /// indicator and returns self.
+    ///
+    /// No text is shown while the progress is indeterminant.
+    #[must_use]
+    pub fn percent_text(self) -> Self {
+        let text = self.progress.map_each(|progress| match progress {
+            Progress::Indeterminant => String::new(),
+            Progress::Percent(value) => format_percent(*value),
+        });
+        self.text(text)
+    }
+}
+
+fn format_percent(value: ZeroToOne) -> String {
+    format!("{:.0}%", *value * 100.)
 }
 
 /// A measurement of progress for an indicator widget like [`ProgressBar`].
@@ -62,7 +100,12 @@ pub enum Progress<T = ZeroToOne> {
 }
 
 impl MakeWidgetWithTag for ProgressBar {
-    fn make_with_tag(self, id: crate::widget::WidgetTag) -> WidgetInstance {
+    fn make_with_tag(self, id: WidgetTag) -> WidgetInstance {
+        let (id, outer_id) = if self.text.is_some() {
+            (WidgetTag::unique(), Some(id))
+        } else {
+            (id, None)
+        };
         let start = Dynamic::new(ZeroToOne::ZERO);
         let end = Dynamic::new(ZeroToOne::ZERO);
         let value = (&start, &end).map_each(|(start, end)| *start..=*end);
@@ -99,7 +142,7 @@ impl MakeWidgetWithTag for ProgressBar {
             degree_offset.as_ref(),
         );
 
-        match self.progress {
+        let indicator = match self.progress {
             ReadOnly::Reader(progress) => {
                 let callback = progress.for_each(move |progress| {
                     update_progress_bar(
@@ -115,6 +158,14 @@ impl MakeWidgetWithTag for ProgressBar {
             ReadOnly::Constant(_) => {
                 Data::new_wrapping(indeterminant_animation, slider).make_widget()
             }
+        };
+
+        match (outer_id, self.text) {
+            (Some(id), Some(text)) => indicator
+                .and(text.centered())
+                .into_layers()
+                .make_with_tag(id),
+            _ => indicator,
         }
     }
 }
@@ -392,3 +443,450 @@ impl Widget for Spinner {
         available_space.map(|constraint| constraint.fit_measured(minimum_size, context.gfx.scale()))
     }
 }
+
+/// A progress indicator made up of several stacked segments.
+///
+/// Each segment is drawn after the previous one using its own color. This
+/// allows showing multiple stages of a task in a single indicator, such as
+/// the amount downloaded, verified, and failed. The combined length of all
+/// segments is limited to the length of the track.
+///
+/// ```rust
+/// use cushy::animation::ZeroToOne;
+/// use cushy::value::Dynamic;
+/// use cushy::widgets::progress::SegmentedProgress;
+///
+/// let verified = Dynamic::new(ZeroToOne::new(0.5));
+/// let downloaded = Dynamic::new(ZeroToOne::new(0.25));
+/// let progress = SegmentedProgress::new()
+///     .segment(verified)
+///     .segment(downloaded);
+/// ```
+#[derive(Debug, Default)]
+pub struct SegmentedProgress {
+    segments: Vec<Segment>,
+    circular: bool,
+    text: Option<WidgetInstance>,
+}
+
+impl SegmentedProgress {
+    /// Returns a new progress indicator with no segments.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a segment that is `amount` long and returns self.
+    ///
+    /// Segments without an explicit color are colored using [`TrackColor`],
+    /// [`SecondaryColor`], [`TertiaryColor`], and [`ErrorColor`], in that
+    /// order.
+    #[must_use]
+    pub fn segment(mut self, amount: impl IntoReadOnly<ZeroToOne>) -> Self {
+        self.segments.push(Segment {
+            amount: amount.into_read_only(),
+            color: None,
+        });
+        self
+    }
+
+    /// Adds a segment that is `amount` long and drawn using `color`, and
+    /// returns self.
+    #[must_use]
+    pub fn colored_segment(
+        mut self,
+        amount: impl IntoReadOnly<ZeroToOne>,
+        color: impl IntoValue<Color>,
+    ) -> Self {
+        self.segments.push(Segment {
+            amount: amount.into_read_only(),
+            color: Some(color.into_value()),
+        });
+        self
+    }
+
+    /// Displays this progress indicator as a ring rather than a bar and
+    /// returns self.
+    #[must_use]
+    pub fn circular(mut self) -> Self {
+        self.circular = true;
+        self
+    }
+
+    /// Displays `text` centered on top of this progress indicator and returns
+    /// self.
+    #[must_use]
+    pub fn text(mut self, text: impl MakeWidget) -> Self {
+        self.text = Some(text.make_widget());
+        self
+    }
+}
+
+impl MakeWidgetWithTag for SegmentedProgress {
+    fn make_with_tag(self, id: WidgetTag) -> WidgetInstance {
+        let indicator = SegmentedIndicator {
+            segments: self.segments,
+            circular: self.circular,
+        };
+        match self.text {
+            Some(text) => indicator
+                .and(text.centered())
+                .into_layers()
+                .make_with_tag(id),
+            None => indicator.make_with_tag(id),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Segment {
+    amount: ReadOnly<ZeroToOne>,
+    color: Option<Value<Color>>,
+}
+
+#[derive(Debug)]
+struct SegmentedIndicator {
+    segments: Vec<Segment>,
+    circular: bool,
+}
+
+impl SegmentedIndicator {
+    fn segment_color(
+        &self,
+        index: usize,
+        context: &crate::context::GraphicsContext<'_, '_, '_, '_>,
+    ) -> Color {
+        match &self.segments[index].color {
+            Some(color) => color.map_tracking_redraw(context, |color| *color),
+            None => match index % 4 {
+                0 => context.get(&TrackColor),
+                1 => context.get(&SecondaryColor),
+                2 => context.get(&TertiaryColor),
+                _ => context.get(&ErrorColor),
+            },
+        }
+    }
+}
+
+/// Returns the ending location of each segment, limiting the total length to
+/// one.
+fn segment_ends(amounts: impl IntoIterator<Item = ZeroToOne>) -> Vec<ZeroToOne> {
+    let mut total = 0.;
+    amounts
+        .into_iter()
+        .map(|amount| {
+            total += *amount;
+            ZeroToOne::new(total)
+        })
+        .collect()
+}
+
+impl Widget for SegmentedIndicator {
+    fn redraw(&mut self, context: &mut crate::context::GraphicsContext<'_, '_, '_, '_>) {
+        let track_size = context.get(&TrackSize).into_px(context.gfx.scale());
+        let ends = segment_ends(self.segments.iter().map(|segment| {
+            context.redraw_when_changed(&segment.amount);
+            segment.amount.get()
+        }));
+        let size = context.gfx.region().size;
+
+        if self.circular {
+            let render_size = size.width.min(size.height);
+            let radius = render_size / 2 - track_size;
+            let degree_offset = Angle::degrees(270);
+            let mut start = ZeroToOne::ZERO;
+            for (index, end) in ends.iter().copied().enumerate() {
+                if end > start {
+                    let color = self.segment_color(index, context);
+                    Spinner::draw_arc(
+                        track_size,
+                        radius,
+                        degree_offset,
+                        start,
+                        ZeroToOne::new(*end - *start),
+                        color,
+                        context,
+                    );
+                    start = end;
+                }
+            }
+            if start < ZeroToOne::ONE {
+                Spinner::draw_arc(
+                    track_size,
+                    radius,
+                    degree_offset,
+                    start,
+                    start.one_minus(),
+                    context.get(&InactiveTrackColor),
+                    context,
+                );
+            }
+        } else {
+            let half_track = track_size / 2;
+            let top = (size.height - track_size) / 2;
+            context.gfx.draw_shape(&Shape::filled_round_rect(
+                Rect::new(Point::new(Px::ZERO, top), Size::new(size.width, track_size)),
+                half_track,
+                context.get(&InactiveTrackColor),
+            ));
+            // Segments are drawn last to first so that each segment's rounded
+            // start is covered by the segments before it.
+            for (index, end) in ends.iter().copied().enumerate().rev() {
+                let length = size.width * *end;
+                if length > 0 {
+                    let color = self.segment_color(index, context);
+                    context.gfx.draw_shape(&Shape::filled_round_rect(
+                        Rect::new(Point::new(Px::ZERO, top), Size::new(length, track_size)),
+                        half_track,
+                        color,
+                    ));
+                }
+            }
+        }
+    }
+
+    fn layout(
+        &mut self,
+        available_space: figures::Size<crate::ConstraintLimit>,
+        context: &mut crate::context::LayoutContext<'_, '_, '_, '_>,
+    ) -> figures::Size<figures::units::UPx> {
+        let track_size = context.get(&TrackSize).into_px(context.gfx.scale());
+        if self.circular {
+            let minimum_size = track_size * 4;
+            available_space
+                .map(|constraint| constraint.fit_measured(minimum_size, context.gfx.scale()))
+        } else {
+            let minimum_length = context
+                .get(&MinimumSliderSize)
+                .into_upx(context.gfx.scale());
+            Size::new(
+                available_space
+                    .width
+                    .fit_measured(minimum_length, context.gfx.scale()),
+                track_size.into_upx(context.gfx.scale()),
+            )
+        }
+    }
+}
+
+/// Estimates the rate of progress and the time remaining for a task.
+///
+/// Each call to [`update()`](Self::update) records a sample. The rate of
+/// progress is measured across the samples recorded within the tracker's
+/// window, which is 10 seconds by default. Progress that moves backwards or
+/// becomes indeterminant restarts the estimation.
+///
+/// ```rust
+/// use cushy::animation::ZeroToOne;
+/// use cushy::widgets::progress::{Progress, ProgressTracker};
+///
+/// let mut tracker = ProgressTracker::new();
+/// let estimate = tracker.update(Progress::Percent(ZeroToOne::new(0.1)));
+/// assert_eq!(estimate.remaining, None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProgressTracker {
+    samples: VecDeque<(Instant, f32)>,
+    window: Duration,
+    progress: Progress,
+}
+
+impl Default for ProgressTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressTracker {
+    /// Returns a new tracker with a 10 second window.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            samples: VecDeque::new(),
+            window: Duration::from_secs(10),
+            progress: Progress::Indeterminant,
+        }
+    }
+
+    /// Sets the duration of samples used to estimate the rate of progress and
+    /// returns self.
+    ///
+    /// Shorter windows react to changes in throughput more quickly, while
+    /// longer windows produce steadier estimates.
+    #[must_use]
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Records `progress` as of now and returns the updated estimate.
+    pub fn update(&mut self, progress: Progress) -> ProgressEstimate {
+        self.update_at(progress, Instant::now())
+    }
+
+    /// Records `progress` as of `now` and returns the updated estimate.
+    pub fn update_at(&mut self, progress: Progress, now: Instant) -> ProgressEstimate {
+        self.progress = progress;
+        match progress {
+            Progress::Indeterminant => self.samples.clear(),
+            Progress::Percent(value) => {
+                let value = *value;
+                if self
+                    .samples
+                    .back()
+                    .map_or(false, |(at, last)| value < *last || now < *at)
+                {
+                    self.samples.clear();
+                }
+                self.samples.push_back((now, value));
+                // Keep one sample at or beyond the window's edge so that the
+                // full window is always measured.
+                while self.samples.len() > 2
+                    && now.saturating_duration_since(self.samples[1].0) >= self.window
+                {
+                    self.samples.pop_front();
+                }
+            }
+        }
+        self.estimate()
+    }
+
+    /// Clears all recorded samples.
+    pub fn reset(&mut self) {
+        self.samples.clear();
+        self.progress = Progress::Indeterminant;
+    }
+
+    /// Returns the current estimate.
+    #[must_use]
+    pub fn estimate(&self) -> ProgressEstimate {
+        let rate = match (self.samples.front(), self.samples.back()) {
+            (Some((started_at, start)), Some((ended_at, end))) => {
+                let elapsed = ended_at.saturating_duration_since(*started_at);
+                (!elapsed.is_zero() && end > start).then(|| (end - start) / elapsed.as_secs_f32())
+            }
+            _ => None,
+        };
+        let remaining = match self.progress {
+            Progress::Percent(value) if value >= ZeroToOne::ONE => Some(Duration::ZERO),
+            // Extremely slow rates produce durations too large to represent.
+            Progress::Percent(value) => rate.and_then(|rate| {
+                Duration::try_from_secs_f32(value.one_minus().into_f32() / rate).ok()
+            }),
+            Progress::Indeterminant => None,
+        };
+        ProgressEstimate {
+            progress: self.progress,
+            rate,
+            remaining,
+        }
+    }
+
+    /// Returns a dynamic estimate that is updated each time `progress`
+    /// changes.
+    pub fn track(mut self, progress: impl IntoReader<Progress>) -> Dynamic<ProgressEstimate> {
+        progress
+            .into_reader()
+            .map_each(move |progress| self.update(*progress))
+    }
+}
+
+/// An estimate produced by a [`ProgressTracker`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ProgressEstimate {
+    /// The most recently recorded progress.
+    pub progress: Progress,
+    /// The rate of progress, measured in the fraction of the task completed
+    /// per second.
+    ///
+    /// This is `None` until progress has been observed over a measurable
+    /// amount of time.
+    pub rate: Option<f32>,
+    /// The estimated time until the task is complete.
+    pub remaining: Option<Duration>,
+}
+
+impl ProgressEstimate {
+    /// Returns the throughput for a task made up of `total` units of work,
+    /// measured in units per second.
+    #[must_use]
+    pub fn throughput(&self, total: f64) -> Option<f64> {
+        self.rate.map(|rate| f64::from(rate) * total)
+    }
+}
+
+#[test]
+fn segments_are_clamped() {
+    let ends = segment_ends([
+        ZeroToOne::new(0.25),
+        ZeroToOne::new(0.5),
+        ZeroToOne::new(0.5),
+    ]);
+    assert_eq!(
+        ends,
+        [ZeroToOne::new(0.25), ZeroToOne::new(0.75), ZeroToOne::ONE]
+    );
+}
+
+#[test]
+fn progress_estimation() {
+    let start = Instant::now();
+    let mut tracker = ProgressTracker::new().window(Duration::from_secs(4));
+    let estimate = tracker.update_at(Progress::Percent(ZeroToOne::ZERO), start);
+    assert_eq!(estimate.rate, None);
+    assert_eq!(estimate.remaining, None);
+
+    let estimate = tracker.update_at(
+        Progress::Percent(ZeroToOne::new(0.1)),
+        start + Duration::from_secs(1),
+    );
+    assert!((estimate.rate.unwrap() - 0.1).abs() < 0.001);
+    let remaining = estimate.remaining.unwrap().as_secs_f32();
+    assert!((remaining - 9.).abs() < 0.01);
+    assert!((estimate.throughput(200.).unwrap() - 20.).abs() < 0.01);
+
+    // Once the window is exceeded, only recent samples are measured.
+    tracker.update_at(
+        Progress::Percent(ZeroToOne::new(0.2)),
+        start + Duration::from_secs(2),
+    );
+    tracker.update_at(
+        Progress::Percent(ZeroToOne::new(0.3)),
+        start + Duration::from_secs(6),
+    );
+    let estimate = tracker.update_at(
+        Progress::Percent(ZeroToOne::new(0.5)),
+        start + Duration::from_secs(7),
+    );
+    assert_eq!(tracker.samples.len(), 3);
+    assert!((estimate.rate.unwrap() - 0.06).abs() < 0.001);
+
+    // Moving backwards restarts the estimate.
+    let estimate = tracker.update_at(
+        Progress::Percent(ZeroToOne::new(0.1)),
+        start + Duration::from_secs(8),
+    );
+    assert_eq!(estimate.rate, None);
+
+    let estimate = tracker.update_at(
+        Progress::Percent(ZeroToOne::ONE),
+        start + Duration::from_secs(9),
+    );
+    assert_eq!(estimate.remaining, Some(Duration::ZERO));
+
+    let estimate = tracker.update_at(Progress::Indeterminant, start + Duration::from_secs(10));
+    assert_eq!(estimate, ProgressEstimate::default());
+}
+
+#[test]
+fn unrepresentable_estimates() {
+    let start = Instant::now();
+    let mut tracker = ProgressTracker::new();
+    tracker.update_at(Progress::Percent(ZeroToOne::ZERO), start);
+    let estimate = tracker.update_at(
+        Progress::Percent(ZeroToOne::new(1e-40)),
+        start + Duration::from_secs(10),
+    );
+    assert!(estimate.rate.is_some());
+    assert_eq!(estimate.remaining, None);
+}
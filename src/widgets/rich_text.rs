@@ -0,0 +1,795 @@
+//! A read-only text widget that supports multiple styles of text.
+
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use figures::units::{Px, UPx};
+use figures::{FloatConversion, IntoUnsigned, Point, Rect, ScreenScale, Size, Zero};
+use kludgine::app::winit::event::MouseButton;
+use kludgine::app::winit::keyboard::{Key, NamedKey};
+use kludgine::app::winit::window::CursorIcon;
+use kludgine::cosmic_text::{self, Attrs, Buffer, FamilyOwned, Metrics, Shaping, Style, Weight};
+use kludgine::shapes::{Shape, StrokeOptions};
+use kludgine::text::TextOrigin;
+use kludgine::{Color, DrawableExt};
+
+use crate::context::{EventContext, GraphicsContext, LayoutContext, Trackable, WidgetContext};
+use crate::styles::components::{
+    FontFamily, FontStyle, FontWeight, HighlightColor, LineHeight, TextColor, TextSize,
+};
+use crate::styles::{HorizontalOrder, VisualOrder};
+use crate::utils::IgnorePoison;
+use crate::value::{Generation, IntoReadOnly, ReadOnly};
+use crate::widget::{Callback, EventHandling, Widget, HANDLED, IGNORED};
+use crate::window::{DeviceId, KeyEvent, WindowLocal};
+use crate::ConstraintLimit;
+
+/// A read-only text widget composed of [`Span`]s that can each have their own
+/// styling.
+///
+/// Spans that have a link callback can be clicked or focused and activated
+/// using the keyboard.
+#[derive(Debug)]
+pub struct RichText {
+    /// The spans of text displayed.
+    pub spans: ReadOnly<Vec<Span>>,
+    prepared: WindowLocal<PreparedRichText>,
+    hovered_link: Option<usize>,
+    pressed_link: Option<usize>,
+    focused_link: Option<usize>,
+}
+
+impl RichText {
+    /// Returns a new widget that displays `spans`.
+    pub fn new(spans: impl IntoReadOnly<Vec<Span>>) -> Self {
+        Self {
+            spans: spans.into_read_only(),
+            prepared: WindowLocal::default(),
+            hovered_link: None,
+            pressed_link: None,
+            focused_link: None,
+        }
+    }
+
+    fn prepare(&mut self, context: &mut GraphicsContext<'_, '_, '_, '_>, width: Px) {
+        let check_generation = self.spans.generation();
+        let color = context.get(&TextColor);
+        let link_color = context.get(&LinkColor);
+        let scale = context.gfx.scale();
+        let metrics = Metrics::new(
+            context.get(&TextSize).into_px(scale).into_float(),
+            context.get(&LineHeight).into_px(scale).into_float(),
+        );
+        if let Some(prepared) = self.prepared.get(context) {
+            if prepared.generation == check_generation
+                && prepared.width == width
+                && prepared.color == color
+                && prepared.link_color == link_color
+                && prepared.metrics == metrics
+            {
+                return;
+            }
+        }
+
+        let families = context.get(&FontFamily);
+        let default_family = context
+            .gfx
+            .find_available_font_family(&families)
+            .unwrap_or(FamilyOwned::SansSerif);
+        let default_weight = context.get(&FontWeight);
+        let default_style = context.get(&FontStyle);
+
+        let prepared = self.spans.map(|spans| {
+            let mut buffer = Buffer::new(context.gfx.font_system(), metrics);
+            buffer.set_size(context.gfx.font_system(), width.into_float(), f32::MAX);
+            buffer.set_rich_text(
+                context.gfx.font_system(),
+                spans.iter().enumerate().map(|(index, span)| {
+                    let color = span.effective_color(color, link_color);
+                    (
+                        span.text.as_str(),
+                        Attrs::new()
+                            .family(span.family.as_ref().unwrap_or(&default_family).as_family())
+                            .weight(span.weight.unwrap_or(default_weight))
+                            .style(span.style.unwrap_or(default_style))
+                            .color(cosmic_text::Color::rgba(
+                                color.red(),
+                                color.green(),
+                                color.blue(),
+                                color.alpha(),
+                            ))
+                            .metadata(index),
+                    )
+                }),
+                Shaping::Advanced,
+            );
+            buffer.shape_until_scroll(context.gfx.font_system());
+
+            let segments = SpanSegment::collect(&buffer, metrics);
+            let mut size = Size::new(0_f32, 0_f32);
+            for run in buffer.layout_runs() {
+                size.width = size.width.max(run.line_w);
+                size.height = size.height.max(run.line_top + metrics.line_height);
+            }
+
+            let decorations = SpanDecoration::collect(&segments, spans, color, link_color);
+            let links = link_spans(spans);
+
+            PreparedRichText {
+                buffer,
+                generation: check_generation,
+                width,
+                color,
+                link_color,
+                metrics,
+                size: Size::new(Px::from(size.width.ceil()), Px::from(size.height.ceil())),
+                decorations,
+                links,
+            }
+        });
+
+        self.prepared.set(context, prepared);
+    }
+
+    fn link_at(&self, location: Point<Px>, context: &WidgetContext<'_>) -> Option<usize> {
+        let prepared = self.prepared.get(context)?;
+        link_at(&prepared.decorations, &prepared.links, location)
+    }
+
+    fn links(&self, context: &WidgetContext<'_>) -> &[usize] {
+        self.prepared
+            .get(context)
+            .map(|prepared| prepared.links.as_slice())
+            .unwrap_or_default()
+    }
+
+    fn invoke_link(&self, span: usize, context: &WidgetContext<'_>) {
+        if !context.enabled() {
+            return;
+        }
+        let link = self
+            .spans
+            .map(|spans| spans.get(span).and_then(|span| span.link.clone()));
+        if let Some(link) = link {
+            link.invoke();
+        }
+    }
+}
+
+impl Widget for RichText {
+    fn redraw(&mut self, context: &mut GraphicsContext<'_, '_, '_, '_>) {
+        self.spans.invalidate_when_changed(context);
+
+        let width = context.gfx.region().size.width;
+        self.prepare(context, width);
+
+        let focused = context.focused(true);
+        let highlight = context.get(&HighlightColor);
+        let prepared = self.prepared.get(context).expect("always initialized");
+
+        for decoration in &prepared.decorations {
+            if let Some(background) = decoration.background {
+                context
+                    .gfx
+                    .draw_shape(&Shape::filled_rect(decoration.segment.rect, background));
+            }
+        }
+
+        context.gfx.draw_text_buffer(
+            prepared.buffer.translate_by(Point::<Px>::ZERO),
+            prepared.color,
+            TextOrigin::TopLeft,
+        );
+
+        let underline_width = Px::from(prepared.metrics.font_size / 14.).max(Px::new(1));
+        for decoration in &prepared.decorations {
+            let segment = &decoration.segment;
+            if let Some(color) = decoration.underline {
+                let is_hovered = self.hovered_link == Some(segment.span);
+                let underline = Rect::new(
+                    Point::new(segment.rect.origin.x, segment.baseline + underline_width),
+                    Size::new(
+                        segment.rect.size.width,
+                        if is_hovered {
+                            underline_width * 2
+                        } else {
+                            underline_width
+                        },
+                    ),
+                );
+                context
+                    .gfx
+                    .draw_shape(&Shape::filled_rect(underline, color));
+            }
+
+            if let Some(color) = decoration.strikethrough {
+                // Strike through the middle of lowercase glyphs, which sit
+                // roughly a quarter of the font size above the baseline.
+                let strikethrough = Rect::new(
+                    Point::new(
+                        segment.rect.origin.x,
+                        segment.baseline - Px::from(prepared.metrics.font_size / 4.),
+                    ),
+                    Size::new(segment.rect.size.width, underline_width),
+                );
+                context
+                    .gfx
+                    .draw_shape(&Shape::filled_rect(strikethrough, color));
+            }
+
+            if focused && self.focused_link == Some(segment.span) {
+                context.gfx.draw_shape(&Shape::stroked_rect(
+                    segment.rect,
+                    StrokeOptions::px_wide(underline_width).colored(highlight),
+                ));
+            }
+        }
+    }
+
+    fn layout(
+        &mut self,
+        available_space: Size<ConstraintLimit>,
+        context: &mut LayoutContext<'_, '_, '_, '_>,
+    ) -> Size<UPx> {
+        let width = available_space.width.max().try_into().unwrap_or(Px::MAX);
+        self.prepare(context, width);
+
+        self.prepared
+            .get(context)
+            .map(|prepared| prepared.size.into_unsigned())
+            .unwrap_or_default()
+    }
+
+    fn hit_test(&mut self, location: Point<Px>, context: &mut EventContext<'_>) -> bool {
+        self.link_at(location, context).is_some()
+    }
+
+    fn hover(&mut self, location: Point<Px>, context: &mut EventContext<'_>) -> Option<CursorIcon> {
+        let hovered = self.link_at(location, context);
+        if hovered != self.hovered_link {
+            self.hovered_link = hovered;
+            context.set_needs_redraw();
+        }
+
+        hovered.map(|_| {
+            if context.enabled() {
+                CursorIcon::Pointer
+            } else {
+                CursorIcon::NotAllowed
+            }
+        })
+    }
+
+    fn unhover(&mut self, context: &mut EventContext<'_>) {
+        if self.hovered_link.take().is_some() {
+            context.set_needs_redraw();
+        }
+    }
+
+    fn accept_focus(&mut self, context: &mut EventContext<'_>) -> bool {
+        context.enabled() && !self.links(context).is_empty()
+    }
+
+    fn focus(&mut self, context: &mut EventContext<'_>) {
+        if self.focused_link.is_none() {
+            let advancing = context.focus_is_advancing();
+            let links = self.links(context);
+            self.focused_link = if advancing {
+                links.first().copied()
+            } else {
+                links.last().copied()
+            };
+        }
+        context.set_needs_redraw();
+    }
+
+    fn advance_focus(
+        &mut self,
+        direction: VisualOrder,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        let links = self.links(context);
+        let Some(current) = self
+            .focused_link
+            .and_then(|focused| links.iter().position(|link| *link == focused))
+        else {
+            return IGNORED;
+        };
+
+        let next = match direction.horizontal {
+            HorizontalOrder::LeftToRight => links.get(current + 1),
+            HorizontalOrder::RightToLeft => current.checked_sub(1).and_then(|i| links.get(i)),
+        };
+        let Some(next) = next.copied() else {
+            return IGNORED;
+        };
+        self.focused_link = Some(next);
+        context.set_needs_redraw();
+        HANDLED
+    }
+
+    fn blur(&mut self, context: &mut EventContext<'_>) {
+        self.focused_link = None;
+        context.set_needs_redraw();
+    }
+
+    fn activate(&mut self, context: &mut EventContext<'_>) {
+        if self.pressed_link.is_none() {
+            if let Some(focused) = self.focused_link {
+                self.invoke_link(focused, context);
+            }
+        }
+    }
+
+    fn mouse_down(
+        &mut self,
+        location: Point<Px>,
+        _device_id: DeviceId,
+        button: MouseButton,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        if button != MouseButton::Left {
+            return IGNORED;
+        }
+        let Some(link) = self.link_at(location, context) else {
+            return IGNORED;
+        };
+        self.pressed_link = Some(link);
+        HANDLED
+    }
+
+    fn mouse_up(
+        &mut self,
+        location: Option<Point<Px>>,
+        _device_id: DeviceId,
+        button: MouseButton,
+        context: &mut EventContext<'_>,
+    ) {
+        if button != MouseButton::Left {
+            return;
+        }
+        let Some(pressed) = self.pressed_link.take() else {
+            return;
+        };
+        if location.and_then(|location| self.link_at(location, context)) == Some(pressed) {
+            self.focused_link = Some(pressed);
+            context.focus();
+            self.invoke_link(pressed, context);
+        }
+    }
+
+    fn keyboard_input(
+        &mut self,
+        _device_id: DeviceId,
+        input: KeyEvent,
+        _is_synthetic: bool,
+        context: &mut EventContext<'_>,
+    ) -> EventHandling {
+        let (Key::Named(NamedKey::Enter), Some(focused)) = (&input.logical_key, self.focused_link)
+        else {
+            return IGNORED;
+        };
+
+        if input.state.is_pressed() {
+            self.invoke_link(focused, context);
+        }
+        HANDLED
+    }
+
+    fn summarize(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_tuple("RichText").field(&self.spans).finish()
+    }
+
+    fn unmounted(&mut self, context: &mut EventContext<'_>) {
+        self.prepared.clear_for(context);
+    }
+}
+
+/// A run of text displayed by a [`RichText`] widget.
+///
+/// Each style that is not specified will use the value from the current
+/// theme.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Span {
+    /// The text of this span.
+    pub text: String,
+    /// The font weight of the text.
+    pub weight: Option<Weight>,
+    /// The font style of the text.
+    pub style: Option<Style>,
+    /// The font family of the text.
+    pub family: Option<FamilyOwned>,
+    /// The color of the text.
+    pub color: Option<Color>,
+    /// When true, the text will be underlined.
+    pub underline: bool,
+    /// When true, a line will be drawn through the text.
+    pub strikethrough: bool,
+    /// The color to fill behind the text.
+    pub background: Option<Color>,
+    /// The callback to invoke when this span is clicked or activated.
+    pub link: Option<SpanLink>,
+}
+
+impl Span {
+    /// Returns a new span containing `text` that uses the theme's default
+    /// styles.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the font weight of this span and returns self.
+    #[must_use]
+    pub fn weight(mut self, weight: Weight) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    /// Sets this span's font weight to bold and returns self.
+    #[must_use]
+    pub fn bold(self) -> Self {
+        self.weight(Weight::BOLD)
+    }
+
+    /// Sets the font style of this span and returns self.
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Sets this span's font style to italic and returns self.
+    #[must_use]
+    pub fn italic(self) -> Self {
+        self.style(Style::Italic)
+    }
+
+    /// Sets the font family of this span and returns self.
+    #[must_use]
+    pub fn family(mut self, family: FamilyOwned) -> Self {
+        self.family = Some(family);
+        self
+    }
+
+    /// Sets this span's font family to the monospace family and returns self.
+    #[must_use]
+    pub fn monospace(self) -> Self {
+        self.family(FamilyOwned::Monospace)
+    }
+
+    /// Sets the text color of this span and returns self.
+    #[must_use]
+    pub fn colored(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Underlines this span and returns self.
+    #[must_use]
+    pub fn underlined(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    /// Draws a line through this span's text and returns self.
+    #[must_use]
+    pub fn struck_through(mut self) -> Self {
+        self.strikethrough = true;
+        self
+    }
+
+    /// Sets the background color of this span and returns self.
+    #[must_use]
+    pub fn background_color(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    /// Makes this span a link that invokes `on_click` when clicked or
+    /// activated using the keyboard, and returns self.
+    ///
+    /// Link spans are underlined and use [`LinkColor`] unless a color has been
+    /// specified.
+    #[must_use]
+    pub fn on_click<F>(mut self, on_click: F) -> Self
+    where
+        F: FnMut(()) + Send + 'static,
+    {
+        self.link = Some(SpanLink::new(on_click));
+        self
+    }
+
+    /// Returns the color this span's text is drawn with.
+    fn effective_color(&self, color: Color, link_color: Color) -> Color {
+        self.color.unwrap_or(if self.link.is_some() {
+            link_color
+        } else {
+            color
+        })
+    }
+}
+
+impl From<&'_ str> for Span {
+    fn from(value: &'_ str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for Span {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+/// A shared callback invoked when a [`Span`] is clicked or activated.
+#[derive(Clone)]
+pub struct SpanLink(Arc<Mutex<Callback>>);
+
+impl SpanLink {
+    /// Returns a new link that invokes `on_click` when activated.
+    pub fn new<F>(on_click: F) -> Self
+    where
+        F: FnMut(()) + Send + 'static,
+    {
+        Self(Arc::new(Mutex::new(Callback::new(on_click))))
+    }
+
+    /// Invokes the callback of this link.
+    pub fn invoke(&self) {
+        self.0.lock().ignore_poison().invoke(());
+    }
+}
+
+impl Debug for SpanLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SpanLink")
+            .field(&Arc::as_ptr(&self.0))
+            .finish()
+    }
+}
+
+impl PartialEq for SpanLink {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+#[derive(Debug)]
+struct PreparedRichText {
+    buffer: Buffer,
+    generation: Option<Generation>,
+    width: Px,
+    color: Color,
+    link_color: Color,
+    metrics: Metrics,
+    size: Size<Px>,
+    decorations: Vec<SpanDecoration>,
+    links: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SpanDecoration {
+    segment: SpanSegment,
+    background: Option<Color>,
+    underline: Option<Color>,
+    strikethrough: Option<Color>,
+}
+
+impl SpanDecoration {
+    fn collect(
+        segments: &[SpanSegment],
+        spans: &[Span],
+        color: Color,
+        link_color: Color,
+    ) -> Vec<Self> {
+        segments
+            .iter()
+            .filter_map(|segment| {
+                let span = spans.get(segment.span)?;
+                let text_color = span.effective_color(color, link_color);
+                Some(Self {
+                    segment: *segment,
+                    background: span.background,
+                    underline: (span.underline || span.link.is_some()).then_some(text_color),
+                    strikethrough: span.strikethrough.then_some(text_color),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Returns the indices of the spans in `spans` that are links.
+fn link_spans(spans: &[Span]) -> Vec<usize> {
+    spans
+        .iter()
+        .enumerate()
+        .filter_map(|(index, span)| span.link.is_some().then_some(index))
+        .collect()
+}
+
+/// Returns the index of the link span displayed at `location`, if any.
+fn link_at(decorations: &[SpanDecoration], links: &[usize], location: Point<Px>) -> Option<usize> {
+    decorations
+        .iter()
+        .find(|decoration| decoration.segment.rect.contains(location))
+        .map(|decoration| decoration.segment.span)
+        .filter(|span| links.contains(span))
+}
+
+/// A contiguous area of a single layout line that contains glyphs from a
+/// single span.
+#[derive(Debug, Clone, Copy)]
+struct SpanSegment {
+    span: usize,
+    rect: Rect<Px>,
+    baseline: Px,
+}
+
+impl SpanSegment {
+    fn collect(buffer: &Buffer, metrics: Metrics) -> Vec<Self> {
+        let mut segments = Vec::new();
+        for run in buffer.layout_runs() {
+            let mut current: Option<(usize, f32, f32)> = None;
+            for glyph in run.glyphs {
+                match &mut current {
+                    Some((span, _, right)) if *span == glyph.metadata => {
+                        *right = right.max(glyph.x + glyph.w);
+                    }
+                    _ => {
+                        if let Some(finished) = current.take() {
+                            segments.push(Self::new(finished, run.line_top, run.line_y, metrics));
+                        }
+                        current = Some((glyph.metadata, glyph.x, glyph.x + glyph.w));
+                    }
+                }
+            }
+            if let Some(finished) = current {
+                segments.push(Self::new(finished, run.line_top, run.line_y, metrics));
+            }
+        }
+        segments
+    }
+
+    fn new(
+        (span, left, right): (usize, f32, f32),
+        line_top: f32,
+        line_y: f32,
+        metrics: Metrics,
+    ) -> Self {
+        Self {
+            span,
+            rect: Rect::new(
+                Point::new(Px::from(left.floor()), Px::from(line_top.floor())),
+                Size::new(
+                    Px::from((right - left).ceil()),
+                    Px::from(metrics.line_height.ceil()),
+                ),
+            ),
+            baseline: Px::from(line_y.round()),
+        }
+    }
+}
+
+define_components! {
+    RichText {
+        /// The color of link spans within a [`RichText`] widget.
+        LinkColor(Color, "link_color", .primary.color)
+    }
+}
+
+#[cfg(test)]
+fn test_segment(span: usize, x: i32, y: i32, width: i32) -> SpanSegment {
+    SpanSegment {
+        span,
+        rect: Rect::new(
+            Point::new(Px::new(x), Px::new(y)),
+            Size::new(Px::new(width), Px::new(20)),
+        ),
+        baseline: Px::new(y + 16),
+    }
+}
+
+#[test]
+fn span_builders() {
+    let span = Span::new("styled")
+        .bold()
+        .italic()
+        .monospace()
+        .colored(Color::RED)
+        .underlined()
+        .struck_through()
+        .background_color(Color::WHITE);
+    assert_eq!(span.text, "styled");
+    assert_eq!(span.weight, Some(Weight::BOLD));
+    assert_eq!(span.style, Some(Style::Italic));
+    assert_eq!(span.family, Some(FamilyOwned::Monospace));
+    assert_eq!(span.color, Some(Color::RED));
+    assert!(span.underline);
+    assert!(span.strikethrough);
+    assert_eq!(span.background, Some(Color::WHITE));
+    assert!(span.link.is_none());
+    assert_eq!(Span::from("plain"), Span::new(String::from("plain")));
+
+    let clicked = Arc::new(Mutex::new(0));
+    let link = Span::new("link").on_click({
+        let clicked = clicked.clone();
+        move |()| *clicked.lock().ignore_poison() += 1
+    });
+    link.link.as_ref().expect("link set").invoke();
+    link.clone().link.expect("link cloned").invoke();
+    assert_eq!(*clicked.lock().ignore_poison(), 2);
+    assert_eq!(link.clone(), link);
+}
+
+#[test]
+fn span_decorations() {
+    let spans = [
+        Span::new("plain"),
+        Span::new("link").on_click(|()| {}),
+        Span::new("colored link")
+            .colored(Color::RED)
+            .on_click(|()| {}),
+        Span::new("struck")
+            .struck_through()
+            .background_color(Color::BLUE),
+    ];
+    let segments = [
+        test_segment(0, 0, 0, 10),
+        test_segment(1, 10, 0, 10),
+        test_segment(2, 20, 0, 10),
+        test_segment(3, 30, 0, 10),
+        // Segments referring to spans that no longer exist are ignored.
+        test_segment(4, 40, 0, 10),
+    ];
+    let decorations = SpanDecoration::collect(&segments, &spans, Color::BLACK, Color::GREEN);
+    let summary = decorations
+        .iter()
+        .map(|decoration| {
+            (
+                decoration.segment.span,
+                decoration.background,
+                decoration.underline,
+                decoration.strikethrough,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        [
+            (0, None, None, None),
+            (1, None, Some(Color::GREEN), None),
+            (2, None, Some(Color::RED), None),
+            (3, Some(Color::BLUE), None, Some(Color::BLACK)),
+        ]
+    );
+    assert_eq!(link_spans(&spans), [1, 2]);
+}
+
+#[test]
+fn link_hit_testing() {
+    let spans = [
+        Span::new("before "),
+        Span::new("a link that wraps").on_click(|()| {}),
+        Span::new(" after"),
+    ];
+    // The link wraps onto a second line, producing two segments.
+    let segments = [
+        test_segment(0, 0, 0, 50),
+        test_segment(1, 50, 0, 50),
+        test_segment(1, 0, 20, 30),
+        test_segment(2, 30, 20, 40),
+    ];
+    let decorations = SpanDecoration::collect(&segments, &spans, Color::BLACK, Color::GREEN);
+    let links = link_spans(&spans);
+    let hit = |x, y| link_at(&decorations, &links, Point::new(Px::new(x), Px::new(y)));
+
+    assert_eq!(hit(10, 10), None);
+    assert_eq!(hit(60, 10), Some(1));
+    assert_eq!(hit(10, 30), Some(1));
+    assert_eq!(hit(40, 30), None);
+    assert_eq!(hit(60, 30), None);
+    assert_eq!(hit(200, 10), None);
+}
@@ -0,0 +1,611 @@
+//! Text selection and editing logic shared between [`Input`](super::Input),
+//! [`Label`](super::Label), and [`CodeView`](super::CodeView).
+
+use std::cmp::Ordering;
+use std::time::{Duration, Instant};
+
+use figures::units::Px;
+use figures::{FloatConversion, Point, Rect, Size, Zero};
+use intentional::Cast;
+use kludgine::shapes::Shape;
+use kludgine::text::MeasuredText;
+use kludgine::{Color, DrawableExt};
+use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
+
+use super::input::{Affinity, Cursor, SelectionState};
+use crate::context::{GraphicsContext, WidgetContext};
+
+const MULTI_CLICK_DURATION: Duration = Duration::from_millis(500);
+const MULTI_CLICK_DISTANCE: i32 = 4;
+
+impl SelectionState {
+    /// Clamps the cursor and selection start to be within `length` bytes.
+    pub(crate) fn constrain(&mut self, length: usize) {
+        self.cursor.offset = self.cursor.offset.min(length);
+        if let Some(start) = &mut self.start {
+            start.offset = start.offset.min(length);
+        }
+    }
+
+    /// Returns the selected range in ascending order. If no text is selected,
+    /// the second cursor is `None`.
+    ///
+    /// When the selection is empty and `selecting` is false, the selection
+    /// start is cleared.
+    pub(crate) fn ordered(&mut self, length: usize, selecting: bool) -> (Cursor, Option<Cursor>) {
+        self.constrain(length);
+        match self.start {
+            Some(start) => match start.offset.cmp(&self.cursor.offset) {
+                Ordering::Less => (start, Some(self.cursor)),
+                Ordering::Equal => {
+                    if !selecting {
+                        self.start = None;
+                    }
+                    (self.cursor, None)
+                }
+                Ordering::Greater => (self.cursor, Some(start)),
+            },
+            None => (self.cursor, None),
+        }
+    }
+
+    /// Returns the currently selected portion of `text`, if any text is
+    /// selected.
+    pub(crate) fn selected_text<'a>(&mut self, text: &'a str, selecting: bool) -> Option<&'a str> {
+        let (start, Some(end)) = self.ordered(text.len(), selecting) else {
+            return None;
+        };
+        text.get(start.offset..end.offset)
+    }
+
+    /// Selects the entirety of `text`.
+    pub(crate) fn select_all(&mut self, text: &str) {
+        self.start = Some(Cursor::default());
+        self.cursor.offset = text.len();
+        self.cursor.affinity = Affinity::After;
+    }
+
+    /// Selects the word in `text` that contains `offset`.
+    pub(crate) fn select_word_at(&mut self, text: &str, offset: usize) {
+        let (start, end) = word_at(text, offset);
+        self.start = Some(Cursor {
+            offset: start,
+            affinity: Affinity::Before,
+        });
+        self.cursor = Cursor {
+            offset: end,
+            affinity: Affinity::Before,
+        };
+    }
+
+    /// Selects the line in `text` that contains `offset`. Lines are separated
+    /// by newline characters.
+    pub(crate) fn select_line_at(&mut self, text: &str, offset: usize) {
+        let (start, end) = line_at(text, offset);
+        self.start = Some(Cursor {
+            offset: start,
+            affinity: Affinity::Before,
+        });
+        self.cursor = Cursor {
+            offset: end,
+            affinity: Affinity::After,
+        };
+    }
+
+    /// Updates the selection in response to a mouse press at `cursor` that
+    /// was the `clicks`-th click in a series of clicks.
+    ///
+    /// A single click places the cursor, a double click selects a word, and a
+    /// triple click selects a line.
+    pub(crate) fn click(&mut self, text: &str, cursor: Cursor, clicks: usize) {
+        match clicks {
+            0 | 1 => {
+                self.cursor = cursor;
+                self.start = Some(cursor);
+            }
+            2 => self.select_word_at(text, cursor.offset),
+            _ => self.select_line_at(text, cursor.offset),
+        }
+    }
+
+    /// Replaces the selected portion of `text` with `new_text`, or inserts
+    /// `new_text` at the cursor if nothing is selected.
+    pub(crate) fn replace_selection(&mut self, text: &mut String, new_text: &str) {
+        let (start, end) = self.ordered(text.len(), false);
+        let end = end.map_or(start.offset, |end| end.offset);
+        text.replace_range(start.offset..end, new_text);
+        self.cursor = Cursor {
+            offset: start.offset + new_text.len(),
+            affinity: Affinity::Before,
+        };
+        self.start = None;
+    }
+
+    /// Deletes the selected portion of `text`, or the grapheme before the
+    /// cursor if nothing is selected.
+    pub(crate) fn delete_backward(&mut self, text: &mut String) {
+        if self.ordered(text.len(), false).1.is_some() {
+            self.replace_selection(text, "");
+        } else if let Ok(Some(offset)) =
+            GraphemeCursor::new(self.cursor.offset, text.len(), true).prev_boundary(text, 0)
+        {
+            text.replace_range(offset..self.cursor.offset, "");
+            self.cursor.offset = offset;
+        }
+    }
+
+    /// Deletes the selected portion of `text`, or the grapheme after the
+    /// cursor if nothing is selected.
+    pub(crate) fn delete_forward(&mut self, text: &mut String) {
+        if self.ordered(text.len(), false).1.is_some() {
+            self.replace_selection(text, "");
+        } else if let Some(length) = text[self.cursor.offset..]
+            .graphemes(true)
+            .next()
+            .map(str::len)
+        {
+            text.replace_range(self.cursor.offset..self.cursor.offset + length, "");
+        }
+    }
+
+    /// Moves the cursor by a single grapheme in the direction of `affinity`.
+    pub(crate) fn move_by_grapheme(&mut self, text: &str, affinity: Affinity) {
+        let length = text.len();
+
+        match affinity {
+            Affinity::Before => {
+                if let Some((_, grapheme)) = text
+                    .grapheme_indices(true)
+                    .find(|(index, grapheme)| index + grapheme.len() == self.cursor.offset)
+                {
+                    self.cursor.offset -= grapheme.len();
+                } else {
+                    self.cursor.offset = 0;
+                }
+            }
+            Affinity::After => {
+                if self.cursor.offset < length {
+                    if let Some(grapheme) = text[self.cursor.offset..].graphemes(true).next() {
+                        self.cursor.offset += grapheme.len();
+                    } else {
+                        self.cursor.offset = length;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Moves the cursor by a single word in the direction of `affinity`.
+    pub(crate) fn move_by_word(&mut self, text: &str, affinity: Affinity) {
+        let length = text.len();
+        match affinity {
+            Affinity::Before => {
+                let mut words = text.unicode_word_indices().peekable();
+                while let Some((index, _)) = words.next() {
+                    let next_starts_after_selection = words
+                        .peek()
+                        .map_or(true, |(index, _)| *index >= self.cursor.offset);
+                    if next_starts_after_selection {
+                        self.cursor.offset = index;
+                        return;
+                    }
+                }
+
+                self.cursor.offset = 0;
+            }
+            Affinity::After => {
+                if self.cursor.offset < length {
+                    if let Some((index, word)) =
+                        text[self.cursor.offset..].unicode_word_indices().next()
+                    {
+                        self.cursor.offset += index + word.len();
+                    } else {
+                        self.cursor.offset = length;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the byte range of the word boundary segment in `text` containing
+/// `offset`.
+fn word_at(text: &str, offset: usize) -> (usize, usize) {
+    let mut last = (text.len(), text.len());
+    for (index, segment) in text.split_word_bound_indices() {
+        let end = index + segment.len();
+        if offset < end {
+            return (index, end);
+        }
+        last = (index, end);
+    }
+    last
+}
+
+/// Returns the byte range of the newline-delimited line in `text` containing
+/// `offset`.
+fn line_at(text: &str, offset: usize) -> (usize, usize) {
+    let offset = offset.min(text.len());
+    let start = text[..offset].rfind('\n').map_or(0, |index| index + 1);
+    let end = text[offset..]
+        .find('\n')
+        .map_or(text.len(), |index| offset + index);
+    (start, end)
+}
+
+/// Tracks a series of mouse clicks to detect double and triple clicks.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct ClickCounter {
+    last: Option<(Instant, Point<Px>)>,
+    count: usize,
+}
+
+impl ClickCounter {
+    /// Records a click at `location`, returning the number of clicks in the
+    /// current series.
+    pub fn click(&mut self, location: Point<Px>) -> usize {
+        let now = Instant::now();
+        match self.last {
+            Some((last, last_location))
+                if now.saturating_duration_since(last) <= MULTI_CLICK_DURATION
+                    && (location.x - last_location.x).get().abs() <= MULTI_CLICK_DISTANCE
+                    && (location.y - last_location.y).get().abs() <= MULTI_CLICK_DISTANCE =>
+            {
+                self.count += 1;
+            }
+            _ => self.count = 1,
+        }
+        self.last = Some((now, location));
+        self.count
+    }
+}
+
+/// Places `text` on the clipboard.
+pub(crate) fn copy_to_clipboard(text: &str, context: &WidgetContext<'_>) {
+    if let Some(mut clipboard) = context.cushy().clipboard_guard() {
+        match clipboard.set_text(text) {
+            Ok(()) => {}
+            Err(err) => tracing::error!("error copying to clipboard: {err}"),
+        }
+    }
+}
+
+/// Returns the location of `cursor` within `measured`, which is the measured
+/// layout of `text`, and the width of the glyph at the cursor.
+#[allow(clippy::too_many_lines)] // it's text layout, c'mon
+pub(crate) fn point_from_cursor(
+    measured: &MeasuredText<Px>,
+    text: &str,
+    cursor: Cursor,
+    total_bytes: usize,
+) -> (Point<Px>, Px) {
+    if measured.glyphs.is_empty() || (cursor.offset == 0 && cursor.affinity == Affinity::Before) {
+        return (Point::default(), Px::ZERO);
+    }
+
+    // Space between glyphs isn't represented in the glyphs. If the cursor rests
+    // within characters that have no glyphs (whitespace), we need to
+    // approximate the position based on the location of the nearest glyphs.
+    let mut closest_before_index = 0;
+    let mut closest_after_index = usize::MAX;
+    let mut bottom_right_index = 0;
+    let mut bottom_right_line = 0;
+    let mut bottom_right_rect = Rect::default();
+    let mut unrendered_offset = 0;
+    for (index, glyph) in measured.glyphs.iter().enumerate() {
+        unrendered_offset = unrendered_offset.max(glyph.info.end);
+        let rect = glyph.rect();
+        if bottom_right_rect.size.width == 0
+            || glyph.info.line > bottom_right_line
+            || (glyph.info.line == bottom_right_line && rect.origin.x > bottom_right_rect.origin.x)
+        {
+            bottom_right_line = glyph.info.line;
+            bottom_right_index = index;
+            bottom_right_rect = rect;
+        }
+
+        match (
+            glyph.info.start.cmp(&cursor.offset),
+            cursor.offset.cmp(&glyph.info.end),
+        ) {
+            (Ordering::Less | Ordering::Equal, Ordering::Less) => {
+                // cosmic text may have grouped multiple graphemes into a single glyph.
+                let mut grapheme_offset = Px::ZERO;
+                if glyph.info.start < cursor.offset {
+                    let clustered_bytes = glyph.info.end - glyph.info.start;
+                    if clustered_bytes > 1 {
+                        let clustered_graphemes = text
+                            .get(glyph.info.start..glyph.info.end)
+                            .map_or(1, |cluster| cluster.graphemes(true).count());
+                        if clustered_graphemes > 1 {
+                            let cursor_offset = cursor.offset - glyph.info.start;
+
+                            grapheme_offset = rect.size.width * cursor_offset.cast::<f32>()
+                                / clustered_graphemes.cast::<f32>();
+                        }
+                    }
+                }
+
+                return (
+                    Point::new(
+                        rect.origin.x + grapheme_offset,
+                        measured.line_height.saturating_mul(Px::new(
+                            i32::try_from(glyph.info.line).unwrap_or(i32::MAX),
+                        )),
+                    ),
+                    rect.size.width,
+                );
+            }
+            (Ordering::Less, _) => {
+                closest_before_index = closest_before_index.max(index);
+            }
+            (_, Ordering::Less) => {
+                closest_after_index = closest_after_index.min(index);
+            }
+            _ => {}
+        }
+    }
+
+    if closest_after_index == usize::MAX {
+        let bottom_right = &measured.glyphs[bottom_right_index];
+        let bottom_y = measured.line_height.saturating_mul(Px::new(
+            i32::try_from(bottom_right.info.line).unwrap_or(i32::MAX),
+        ));
+        // No glyph could be found that started/contained the cursors offset.
+        let mut bottom_right_cursor = Point::new(
+            bottom_right_rect.origin.x + bottom_right_rect.size.width,
+            bottom_y,
+        );
+        let bytes_after_glyph = total_bytes - unrendered_offset;
+        if !(bottom_right.info.end == cursor.offset || bytes_after_glyph == 0) {
+            // We're rendering past the end of the text. We shuld probably try to
+            // estimate the amount of whitespace should be visible based on the
+            // number of whitespace characters at the end of the text.
+            let space_past_glyph = bottom_right.info.line_width - bottom_right_cursor.x;
+            let space_per_byte = space_past_glyph.into_float() / bytes_after_glyph.cast::<f32>();
+            let cursor_position =
+                space_per_byte * (cursor.offset.saturating_sub(unrendered_offset)).cast::<f32>();
+
+            bottom_right_cursor.x += Px::from(cursor_position);
+        }
+
+        // The cursor should be placed after the bottom_right glyph
+        (bottom_right_cursor, Px::ZERO)
+    } else {
+        let before = &measured.glyphs[closest_before_index];
+        let after = &measured.glyphs[closest_after_index];
+        let before_rect = before.rect();
+        let after_rect = after.rect();
+        let before_y = measured
+            .line_height
+            .saturating_mul(Px::new(i32::try_from(before.info.line).unwrap_or(i32::MAX)));
+
+        if before.info.line == after.info.line {
+            let before_right = before_rect.origin.x + before_rect.size.width;
+            let space_between = after_rect.origin.x - before_right;
+            let bytes_between = after.info.start - before.info.end;
+            let space_per_byte = space_between.into_float() / bytes_between.cast::<f32>();
+            let cursor_position = space_per_byte * (cursor.offset - before.info.end).cast::<f32>();
+
+            (
+                Point::new(before_right + Px::from(cursor_position), before_y),
+                Px::from(space_per_byte),
+            )
+        } else {
+            match cursor.affinity {
+                Affinity::Before => {
+                    // TODO We need to look out for whitespace at the end of the line.
+                    let mut origin = before_rect.origin;
+                    origin.x += before_rect.size.width;
+                    (origin, before_y)
+                }
+                Affinity::After => (
+                    Point::new(Px::ZERO, before_y + measured.line_height),
+                    Px::ZERO,
+                ),
+            }
+        }
+    }
+}
+
+/// Returns the cursor closest to `location` within `measured`, which contains
+/// `bytes` bytes of text.
+///
+/// `location` is relative to the top-left of the measured text.
+pub(crate) fn cursor_from_point(
+    measured: &MeasuredText<Px>,
+    bytes: usize,
+    mut location: Point<Px>,
+) -> Cursor {
+    if location.y < 0 {
+        location.y = Px::ZERO;
+    }
+    if location.x < 0 {
+        location.x = Px::ZERO;
+    }
+
+    let mut closest: Option<(Cursor, i32, usize, Point<Px>)> = None;
+    let mut current_line = usize::MAX;
+    let mut current_line_y = Px::ZERO;
+    for (index, glyph) in measured.glyphs.iter().enumerate() {
+        if current_line != glyph.info.line {
+            current_line = glyph.info.line;
+
+            current_line_y = measured
+                .line_height
+                .saturating_mul(Px::new(i32::try_from(current_line).unwrap_or(i32::MAX)));
+        }
+        let mut rect = glyph.rect();
+        if !glyph.visible() {
+            rect.size.height = measured.line_height;
+        }
+        let relative = location - Point::new(rect.origin.x, current_line_y);
+        if relative.x >= 0
+            && relative.y >= 0
+            && relative.x <= rect.size.width
+            && relative.y <= measured.line_height
+        {
+            return if relative.x > rect.size.width / 2 {
+                if glyph.info.end < bytes {
+                    Cursor {
+                        offset: glyph.info.end,
+                        affinity: Affinity::Before,
+                    }
+                } else {
+                    Cursor {
+                        offset: glyph.info.start,
+                        affinity: Affinity::After,
+                    }
+                }
+            } else {
+                Cursor {
+                    offset: glyph.info.start,
+                    affinity: Affinity::Before,
+                }
+            };
+        }
+
+        // Make relative be relative to the center of the glyph for a nearest search.
+        let relative = relative + rect.size / 2;
+
+        let line_height = measured.line_height.get();
+        let xy = relative
+            .x
+            .get()
+            .saturating_mul(
+                ((relative.y.get() + line_height - 1) / line_height * line_height)
+                    .saturating_pow(2),
+            )
+            .saturating_abs();
+        let cursor = Cursor {
+            offset: if relative.x < 0 || relative.y < 0 {
+                glyph.info.start
+            } else {
+                glyph.info.end
+            },
+            affinity: Affinity::Before,
+        };
+        match closest {
+            Some((_, closest_xy, ..)) if xy < closest_xy => {
+                closest = Some((cursor, xy, index, relative));
+            }
+            None => closest = Some((cursor, xy, index, relative)),
+            _ => {}
+        }
+    }
+
+    if let Some((closest, _, index, relative)) = closest {
+        // Having whitespace not in the measured text is really annoying.
+        // This trick only works for the first line of text. Maybe we should
+        // try and create a structure that organizes the glyphs into lines
+        // so that it's easier to inspect and detect when there's
+        // whitespace. For now, this is just a hack that helps get *some*
+        // selection at the end of the input for trailing whitespace.
+        if relative.x.abs() < measured.line_height && index < measured.glyphs.len() {
+            return closest;
+        }
+    }
+
+    Cursor {
+        offset: bytes,
+        affinity: Affinity::After,
+    }
+}
+
+/// Fills the area between `start` and `end` with `color`.
+///
+/// `width` is the width of the area the text was laid out within, and all
+/// drawing is offset by `offset`.
+pub(crate) fn draw_selection(
+    context: &mut GraphicsContext<'_, '_, '_, '_>,
+    measured: &MeasuredText<Px>,
+    text: &str,
+    (start, end): (Cursor, Cursor),
+    width: Px,
+    color: Color,
+    offset: Point<Px>,
+) {
+    let bytes = text.len();
+    let (start_position, _) = point_from_cursor(measured, text, start, bytes);
+    let (end_position, end_width) = point_from_cursor(measured, text, end, bytes);
+
+    if start_position.y == end_position.y {
+        // Single line selection
+        let width = end_position.x - start_position.x;
+        context.gfx.draw_shape(
+            Shape::filled_rect(
+                Rect::new(start_position, Size::new(width, measured.line_height)),
+                color,
+            )
+            .translate_by(offset),
+        );
+    } else {
+        // Draw from start to end of line,
+        let first_width = width - start_position.x;
+        context.gfx.draw_shape(
+            Shape::filled_rect(
+                Rect::new(start_position, Size::new(first_width, measured.line_height)),
+                color,
+            )
+            .translate_by(offset),
+        );
+        // Fill region between
+        let bottom_of_first_line = start_position.y + measured.line_height;
+        let distance_between = end_position.y - bottom_of_first_line;
+        if distance_between > 0 {
+            context.gfx.draw_shape(
+                Shape::filled_rect(
+                    Rect::new(
+                        Point::new(Px::ZERO, bottom_of_first_line),
+                        Size::new(width, distance_between),
+                    ),
+                    color,
+                )
+                .translate_by(offset),
+            );
+        }
+        // Draw from 0 to end + width
+        context.gfx.draw_shape(
+            Shape::filled_rect(
+                Rect::new(
+                    Point::new(Px::ZERO, end_position.y),
+                    Size::new(end_position.x + end_width, measured.line_height),
+                ),
+                color,
+            )
+            .translate_by(offset),
+        );
+    }
+}
+
+#[test]
+fn word_and_line_selection() {
+    let text = "hello, world\nsecond line";
+    assert_eq!(word_at(text, 0), (0, 5));
+    assert_eq!(word_at(text, 3), (0, 5));
+    assert_eq!(word_at(text, 5), (5, 6));
+    assert_eq!(word_at(text, 9), (7, 12));
+    assert_eq!(word_at(text, text.len()), (text.len() - 4, text.len()));
+    assert_eq!(line_at(text, 3), (0, 12));
+    assert_eq!(line_at(text, 15), (13, text.len()));
+
+    let mut selection = SelectionState::default();
+    selection.click(
+        text,
+        Cursor {
+            offset: 15,
+            affinity: Affinity::Before,
+        },
+        2,
+    );
+    assert_eq!(selection.selected_text(text, false), Some("second"));
+    selection.click(
+        text,
+        Cursor {
+            offset: 15,
+            affinity: Affinity::Before,
+        },
+        3,
+    );
+    assert_eq!(selection.selected_text(text, false), Some("second line"));
+}
@@ -2,6 +2,7 @@
 use std::fmt::Debug;
 use std::mem;
 use std::ops::RangeInclusive;
+use std::sync::Arc;
 
 use figures::units::{Lp, Px, UPx};
 use figures::{FloatConversion, IntoSigned, Point, Ranged, Rect, Round, ScreenScale, Size, Zero};
@@ -10,17 +11,19 @@ use kludgine::app::winit::event::{MouseButton, MouseScrollDelta, TouchPhase};
 use kludgine::app::winit::keyboard::{Key, NamedKey};
 use kludgine::app::winit::window::CursorIcon;
 use kludgine::shapes::{Shape, StrokeOptions};
+use kludgine::text::{Text, TextOrigin};
 use kludgine::{Color, DrawableExt, Origin};
 
 use crate::animation::{LinearInterpolate, PercentBetween, ZeroToOne};
 use crate::context::{EventContext, GraphicsContext, LayoutContext};
 use crate::styles::components::{
-    AutoFocusableControls, DisabledWidgetAccentColor, OpaqueWidgetColor, OutlineColor,
+    AutoFocusableControls, DisabledWidgetAccentColor, OpaqueWidgetColor, OutlineColor, TextColor,
     WidgetAccentColor,
 };
 use crate::styles::{Dimension, HorizontalOrder, VerticalOrder, VisualOrder};
 use crate::value::{Destination, Dynamic, IntoDynamic, IntoValue, Source, Value};
-use crate::widget::{EventHandling, Widget, HANDLED, IGNORED};
+use crate::widget::{EventHandling, MakeWidget, Widget, HANDLED, IGNORED};
+use crate::widgets::layers::{OverlayHandle, OverlayLayer};
 use crate::window::{DeviceId, KeyEvent};
 use crate::ConstraintLimit;
 
@@ -45,10 +48,22 @@ where
     ///
     /// This defaults to `0.05`/5%.
     pub step: Value<ZeroToOne>,
+    /// The percentage to step when advancing the slider using the Page Up and
+    /// Page Down keys.
+    ///
+    /// This defaults to `0.25`/25%.
+    pub page_step: Value<ZeroToOne>,
     knob_visible: bool,
     interactive: bool,
     knob_size: UPx,
     horizontal: bool,
+    orientation: Option<bool>,
+    scale: Scale<T::Value>,
+    ticks: Ticks<T::Value>,
+    tick_labels: bool,
+    tick_extent: Px,
+    formatter: Option<ValueFormatter<T::Value>>,
+    tooltip: Option<ValueTooltip>,
     rendered_size: Px,
     focused_knob: Option<Knob>,
     previous_focus: Option<Knob>,
@@ -92,8 +107,16 @@ where
             knob_visible: true,
             interactive: true,
             step: Value::Constant(ZeroToOne::new(0.05)),
+            page_step: Value::Constant(ZeroToOne::new(0.25)),
             knob_size: UPx::ZERO,
             horizontal: true,
+            orientation: None,
+            scale: Scale::Linear,
+            ticks: Ticks::None,
+            tick_labels: false,
+            tick_extent: Px::ZERO,
+            formatter: None,
+            tooltip: None,
             rendered_size: Px::ZERO,
             focused_knob: None,
             mouse_buttons_down: 0,
@@ -129,6 +152,104 @@ where
         self
     }
 
+    /// The percentage to step when advancing the slider using the Page Up and
+    /// Page Down keys.
+    ///
+    /// This defaults to `0.25`/25%.
+    #[must_use]
+    pub fn page_step_by(mut self, percent: impl IntoValue<ZeroToOne>) -> Self {
+        self.page_step = percent.into_value();
+        self
+    }
+
+    /// Always displays this slider horizontally and returns self.
+    ///
+    /// By default, a slider is oriented based on the space it is given.
+    #[must_use]
+    pub fn horizontal(mut self) -> Self {
+        self.orientation = Some(true);
+        self
+    }
+
+    /// Always displays this slider vertically and returns self.
+    ///
+    /// Vertical sliders place the minimum value at the bottom of the track.
+    #[must_use]
+    pub fn vertical(mut self) -> Self {
+        self.orientation = Some(false);
+        self
+    }
+
+    /// Positions values along the track logarithmically and returns self.
+    ///
+    /// This is useful for values that are perceived on a logarithmic scale,
+    /// such as frequencies and zoom levels. The minimum must be greater than
+    /// zero. Otherwise, values are positioned linearly.
+    #[must_use]
+    pub fn logarithmic(mut self) -> Self
+    where
+        T::Value: Into<f64>,
+    {
+        self.scale = Scale::Logarithmic(Into::into);
+        self
+    }
+
+    /// Positions values along the track using `mapping` and returns self.
+    #[must_use]
+    pub fn mapping(mut self, mapping: SliderMapping) -> Self {
+        self.scale = Scale::Custom(mapping);
+        self
+    }
+
+    /// Draws tick marks at each of `values` and returns self.
+    #[must_use]
+    pub fn ticks(mut self, values: impl IntoIterator<Item = T::Value>) -> Self {
+        self.ticks = Ticks::Values(values.into_iter().collect());
+        self
+    }
+
+    /// Draws `count` tick marks evenly spaced along the track, including at
+    /// both ends, and returns self.
+    #[must_use]
+    pub fn evenly_spaced_ticks(mut self, count: usize) -> Self {
+        self.ticks = Ticks::Evenly(count);
+        self
+    }
+
+    /// Draws a label next to each tick mark and returns self.
+    ///
+    /// Labels are formatted using the function provided to
+    /// [`format_values`](Self::format_values), or using the value's `Debug`
+    /// implementation if no function has been provided.
+    #[must_use]
+    pub fn tick_labels(mut self) -> Self {
+        self.tick_labels = true;
+        self
+    }
+
+    /// Formats values displayed in tick labels and the value tooltip using
+    /// `format` and returns self.
+    #[must_use]
+    pub fn format_values<F>(mut self, format: F) -> Self
+    where
+        F: Fn(&T::Value) -> String + Send + Sync + 'static,
+    {
+        self.formatter = Some(ValueFormatter(Arc::new(format)));
+        self
+    }
+
+    /// Shows the value being adjusted in a tooltip on `layer` while the
+    /// slider is being dragged, and returns self.
+    #[must_use]
+    pub fn value_tooltip(mut self, layer: &OverlayLayer) -> Self {
+        self.tooltip = Some(ValueTooltip {
+            layer: layer.clone(),
+            text: Dynamic::default(),
+            shown: None,
+        });
+        self
+    }
+
     /// Updates this slider to not show knobs and returns self.
     ///
     /// This also prevents the slider from being focused.
@@ -277,6 +398,149 @@ where
             );
         }
     }
+
+    fn draw_ticks(
+        &self,
+        spec: &TrackSpec,
+        min: &T::Value,
+        max: &T::Value,
+        context: &mut GraphicsContext<'_, '_, '_, '_>,
+    ) {
+        let ticks = self.tick_values(min, max);
+        if ticks.is_empty() {
+            return;
+        }
+
+        let scale = context.gfx.scale();
+        let half_focus_ring = spec.if_knobbed(|| (Lp::points(2).into_px(scale) / 2).ceil());
+        let focus_ring = half_focus_ring * 2;
+        let track_length = self.rendered_size - spec.if_knobbed(|| spec.knob_size + focus_ring);
+        let main_offset = half_focus_ring + spec.if_knobbed(|| spec.half_knob);
+        let cross_start = if spec.knob_size > 0 {
+            spec.knob_size + focus_ring
+        } else {
+            spec.track_size
+        };
+        let tick_length = context.get(&TickLength).into_px(scale);
+        let tick_width = Lp::points(1).into_px(scale).max(Px::new(1));
+        let tick_color = context.get(&TickColor);
+        let text_color = context.get(&TextColor);
+        let label_start = cross_start + tick_length + tick_length / 2;
+        context.apply_current_font_settings();
+
+        for (position, value) in ticks {
+            let main = main_offset + track_length * *position;
+            context.gfx.draw_shape(&Shape::filled_rect(
+                self.orient_rectangle(Rect::new(
+                    Point::new(main - tick_width / 2, cross_start),
+                    Size::new(tick_width, tick_length),
+                )),
+                tick_color,
+            ));
+
+            if self.tick_labels {
+                let label = self.format(&value);
+                let text = &context
+                    .gfx
+                    .measure_text(Text::<Px>::new(&label, text_color));
+                let center = if self.horizontal {
+                    Point::new(main, label_start + text.size.height / 2)
+                } else {
+                    Point::new(label_start + text.size.width / 2, self.rendered_size - main)
+                };
+                context
+                    .gfx
+                    .draw_measured_text(text.translate_by(center.round()), TextOrigin::Center);
+            }
+        }
+    }
+
+    fn measure_ticks(&self, context: &mut LayoutContext<'_, '_, '_, '_>) -> Px {
+        let min = self.minimum.get_tracking_invalidate(context);
+        let max = self.maximum.get_tracking_invalidate(context);
+        let ticks = self.tick_values(&min, &max);
+        if ticks.is_empty() {
+            return Px::ZERO;
+        }
+
+        let tick_length = context.get(&TickLength).into_px(context.gfx.scale());
+        if !self.tick_labels {
+            return tick_length;
+        }
+
+        context.apply_current_font_settings();
+        let label_extent = ticks
+            .iter()
+            .map(|(_, value)| {
+                let size = context
+                    .gfx
+                    .measure_text(Text::<Px>::new(&self.format(value), Color::WHITE))
+                    .size;
+                if self.horizontal {
+                    size.height
+                } else {
+                    size.width
+                }
+            })
+            .max()
+            .unwrap_or_default();
+        tick_length + tick_length / 2 + label_extent
+    }
+
+    fn tick_values(&self, min: &T::Value, max: &T::Value) -> Vec<(ZeroToOne, T::Value)> {
+        match &self.ticks {
+            Ticks::None => Vec::new(),
+            Ticks::Values(values) => values
+                .iter()
+                .filter(|value| *value >= min && *value <= max)
+                .map(|value| (self.position_of(value, min, max), value.clone()))
+                .collect(),
+            Ticks::Evenly(count) => {
+                let last = count.saturating_sub(1).max(1).cast::<f32>();
+                (0..*count)
+                    .map(|index| {
+                        let position = ZeroToOne::new(index.cast::<f32>() / last);
+                        (position, self.value_at(position, min, max))
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    fn format(&self, value: &T::Value) -> String {
+        match &self.formatter {
+            Some(formatter) => (formatter.0)(value),
+            None => format!("{value:?}"),
+        }
+    }
+
+    /// Returns the location along the track of `value`.
+    fn position_of(&self, value: &T::Value, min: &T::Value, max: &T::Value) -> ZeroToOne {
+        let percent = value.percent_between(min, max);
+        match &self.scale {
+            Scale::Linear => percent,
+            Scale::Logarithmic(to_f64) => ZeroToOne::new(logarithmic_position(
+                *percent,
+                to_f64(min.clone()),
+                to_f64(max.clone()),
+            )),
+            Scale::Custom(mapping) => mapping.to_position(percent),
+        }
+    }
+
+    /// Returns the value at `position` along the track.
+    fn value_at(&self, position: ZeroToOne, min: &T::Value, max: &T::Value) -> T::Value {
+        let percent = match &self.scale {
+            Scale::Linear => position,
+            Scale::Logarithmic(to_f64) => ZeroToOne::new(logarithmic_percent(
+                *position,
+                to_f64(min.clone()),
+                to_f64(max.clone()),
+            )),
+            Scale::Custom(mapping) => mapping.from_position(position),
+        };
+        min.lerp(max, *percent)
+    }
 }
 
 impl<T> Slider<T>
@@ -296,15 +560,15 @@ where
 
         let min = self.minimum.get();
         let max = self.maximum.get();
-        let value = min.lerp(&max, percent);
+        let value = self.value_at(ZeroToOne::new(percent), &min, &max);
         let (mut start, mut opt_end) = T::into_parts(self.value.get());
         if let Some(end) = &opt_end {
             let knob = if let Some(knob) = self.focused_knob {
                 knob
             } else {
                 // Check if the click is overlapping either knob
-                let start_percent = start.percent_between(&min, &max);
-                let end_percent = end.percent_between(&min, &max);
+                let start_percent = self.position_of(&start, &min, &max);
+                let end_percent = self.position_of(end, &min, &max);
                 let knob_width_as_percent =
                     self.knob_size.into_float() / 2. / track_width.into_float();
                 let start_delta = percent - *start_percent;
@@ -340,10 +604,33 @@ where
             start = value;
             self.focused_knob = Some(Knob::Start);
         }
+        if let Some(tooltip) = &self.tooltip {
+            let focused = match (self.focused_knob, &opt_end) {
+                (Some(Knob::End), Some(end)) => end,
+                _ => &start,
+            };
+            tooltip.text.set(self.format(focused));
+        }
         self.value.set(T::from_parts(start, opt_end));
     }
 
-    fn step(&mut self, forwards: bool, factor: f32) {
+    fn show_tooltip(&mut self, context: &EventContext<'_>) {
+        let Some(tooltip) = &mut self.tooltip else {
+            return;
+        };
+        if tooltip.shown.is_none() {
+            let id = context.widget().id();
+            let overlay = tooltip.layer.build_overlay(tooltip.text.clone().contain());
+            let overlay = if self.horizontal {
+                overlay.above(id)
+            } else {
+                overlay.right_of(id)
+            };
+            tooltip.shown = Some(overlay.show());
+        }
+    }
+
+    fn step(&mut self, forwards: bool, amount: f32) {
         let Some(focus) = self
             .focused_knob
             .or_else(|| (!T::RANGED).then_some(Knob::Start))
@@ -357,13 +644,12 @@ where
         };
         let min = self.minimum.get();
         let max = self.maximum.get();
-        let step = self.step.get();
-        let mut current_percent = current.percent_between(&min, &max);
+        let mut current_percent = self.position_of(&current, &min, &max);
         let new_value = loop {
             let next = if forwards {
-                *current_percent + *step * factor
+                *current_percent + amount
             } else {
-                *current_percent - *step * factor
+                *current_percent - amount
             };
             if next < 0. {
                 break min.clone();
@@ -371,7 +657,7 @@ where
                 break max.clone();
             }
             current_percent = ZeroToOne::new(next);
-            let generated_value = min.lerp(&max, *current_percent);
+            let generated_value = self.value_at(current_percent, &min, &max);
             if generated_value != current {
                 break generated_value;
             }
@@ -476,26 +762,27 @@ where
                 .map_mut(|mut v| *v = T::from_parts(start_value.clone(), end_value.clone()));
         }
 
-        let start_percent = start_value.percent_between(&min, &max);
-        let end_percent = end_value.map(|end| *end.percent_between(&min, &max));
+        let start_percent = self.position_of(&start_value, &min, &max);
+        let end_percent = end_value.map(|end| *self.position_of(&end, &min, &max));
 
         let size = context.gfx.region().size;
-        self.horizontal = size.width >= size.height;
-
-        self.draw_track(
-            &TrackSpec {
-                size,
-                start: *start_percent,
-                end: end_percent,
-                half_knob,
-                knob_size,
-                track_size,
-                knob_color,
-                track_color,
-                inactive_track_color,
-            },
-            context,
-        );
+        if self.orientation.is_none() && self.tick_extent == 0 {
+            self.horizontal = size.width >= size.height;
+        }
+
+        let spec = TrackSpec {
+            size,
+            start: *start_percent,
+            end: end_percent,
+            half_knob,
+            knob_size,
+            track_size,
+            knob_color,
+            track_color,
+            inactive_track_color,
+        };
+        self.draw_track(&spec, context);
+        self.draw_ticks(&spec, &min, &max, context);
     }
 
     fn layout(
@@ -522,34 +809,38 @@ where
             context.get(&TrackSize).into_upx(context.gfx.scale())
         };
 
-        match (available_space.width, available_space.height) {
-            (ConstraintLimit::Fill(width), ConstraintLimit::Fill(height)) => {
-                // This comparison is done such that if width == height, we end
-                // up with a horizontal slider.
-                if width < height {
-                    // Vertical slider
-                    Size::new(static_side, height.max(minimum_size))
-                } else {
-                    // Horizontal slider
-                    Size::new(width.max(minimum_size), static_side)
-                }
-            }
-            (ConstraintLimit::Fill(width), ConstraintLimit::SizeToFit(_)) => {
-                Size::new(width.max(minimum_size), static_side)
-            }
-            (ConstraintLimit::SizeToFit(_), ConstraintLimit::Fill(height)) => {
-                Size::new(static_side, height.max(minimum_size))
-            }
-            (ConstraintLimit::SizeToFit(width), ConstraintLimit::SizeToFit(_)) => {
-                // When we have no limit on our, we still want to be draggable.
-                // Since we have no limit in both directions, we have to make a
-                // choice: horizontal or vertical. It seems to @ecton at the
-                // time of writing this that when there is no intent from the
-                // user of the slider, a horizontal slider is expected. So, we
-                // set the minimum measurement based on a horizontal
-                // orientation.
-                Size::new(width.min(minimum_size), static_side)
-            }
+        self.horizontal =
+            self.orientation
+                .unwrap_or(match (available_space.width, available_space.height) {
+                    // This comparison is done such that if width == height, we end
+                    // up with a horizontal slider.
+                    (ConstraintLimit::Fill(width), ConstraintLimit::Fill(height)) => {
+                        width >= height
+                    }
+                    (ConstraintLimit::SizeToFit(_), ConstraintLimit::Fill(_)) => false,
+                    // When we have no limit on our, we still want to be draggable.
+                    // Since we have no limit in both directions, we have to make a
+                    // choice: horizontal or vertical. It seems to @ecton at the
+                    // time of writing this that when there is no intent from the
+                    // user of the slider, a horizontal slider is expected.
+                    (ConstraintLimit::Fill(_) | ConstraintLimit::SizeToFit(_), _) => true,
+                });
+        self.tick_extent = self.measure_ticks(context);
+        let static_side = static_side + self.tick_extent.into_upx(context.gfx.scale());
+
+        let main_axis = if self.horizontal {
+            available_space.width
+        } else {
+            available_space.height
+        };
+        let length = match main_axis {
+            ConstraintLimit::Fill(length) => length.max(minimum_size),
+            ConstraintLimit::SizeToFit(length) => length.min(minimum_size),
+        };
+        if self.horizontal {
+            Size::new(length, static_side)
+        } else {
+            Size::new(static_side, length)
         }
     }
 
@@ -642,6 +933,7 @@ where
                 (None, None) => None,
             };
             self.update_from_click(location, previous_focus);
+            self.show_tooltip(context);
             context.focus();
         }
         self.mouse_buttons_down += 1;
@@ -668,6 +960,11 @@ where
         _context: &mut EventContext<'_>,
     ) {
         self.mouse_buttons_down -= 1;
+        if self.mouse_buttons_down == 0 {
+            if let Some(tooltip) = &mut self.tooltip {
+                tooltip.shown = None;
+            }
+        }
     }
 
     fn keyboard_input(
@@ -681,16 +978,22 @@ where
             return IGNORED;
         };
 
-        let forwards = match input.logical_key {
-            Key::Named(NamedKey::ArrowLeft | NamedKey::ArrowUp) => false,
-            Key::Named(NamedKey::ArrowRight | NamedKey::ArrowDown) => true,
+        // Vertical sliders have their minimum at the bottom, so the up arrow
+        // increases the value.
+        let (forwards, amount) = match input.logical_key {
+            Key::Named(NamedKey::ArrowLeft) => (false, self.step.get()),
+            Key::Named(NamedKey::ArrowRight) => (true, self.step.get()),
+            Key::Named(NamedKey::ArrowUp) => (!self.horizontal, self.step.get()),
+            Key::Named(NamedKey::ArrowDown) => (self.horizontal, self.step.get()),
+            Key::Named(NamedKey::PageUp) => (true, self.page_step.get()),
+            Key::Named(NamedKey::PageDown) => (false, self.page_step.get()),
             _ => return IGNORED,
         };
         if !input.state.is_pressed() {
             return HANDLED;
         }
 
-        self.step(forwards, 1.);
+        self.step(forwards, *amount);
 
         HANDLED
     }
@@ -718,7 +1021,7 @@ where
                 (true, factor)
             };
 
-            self.step(forwards, factor);
+            self.step(forwards, *self.step.get() * factor);
         }
 
         // @ecton: Unlike scroll alreas cascasing, I feel like scrolling while
@@ -760,6 +1063,127 @@ impl TrackSpec {
     }
 }
 
+/// A custom mapping between a [`Slider`]'s values and locations along its
+/// track.
+///
+/// Both functions operate on percentages: `to_position` converts the
+/// percentage a value is between the slider's minimum and maximum into the
+/// percentage along the track to display it at, and `from_position` performs
+/// the inverse conversion.
+///
+/// ```rust
+/// use cushy::animation::ZeroToOne;
+/// use cushy::widgets::slider::SliderMapping;
+///
+/// // Give the lower values more room on the track.
+/// let mapping = SliderMapping::new(
+///     |percent| ZeroToOne::new(percent.sqrt()),
+///     |position| ZeroToOne::new(*position * *position),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct SliderMapping {
+    to_position: Arc<dyn Fn(ZeroToOne) -> ZeroToOne + Send + Sync>,
+    from_position: Arc<dyn Fn(ZeroToOne) -> ZeroToOne + Send + Sync>,
+}
+
+impl SliderMapping {
+    /// Returns a new mapping using the provided conversion functions.
+    pub fn new<ToPosition, FromPosition>(
+        to_position: ToPosition,
+        from_position: FromPosition,
+    ) -> Self
+    where
+        ToPosition: Fn(ZeroToOne) -> ZeroToOne + Send + Sync + 'static,
+        FromPosition: Fn(ZeroToOne) -> ZeroToOne + Send + Sync + 'static,
+    {
+        Self {
+            to_position: Arc::new(to_position),
+            from_position: Arc::new(from_position),
+        }
+    }
+
+    /// Returns the location along the track for a value `percent` between the
+    /// minimum and maximum.
+    #[must_use]
+    pub fn to_position(&self, percent: ZeroToOne) -> ZeroToOne {
+        (self.to_position)(percent)
+    }
+
+    /// Returns the percentage between the minimum and maximum for a location
+    /// along the track.
+    #[must_use]
+    pub fn from_position(&self, position: ZeroToOne) -> ZeroToOne {
+        (self.from_position)(position)
+    }
+}
+
+impl Debug for SliderMapping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SliderMapping").finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Scale<T> {
+    Linear,
+    Logarithmic(fn(T) -> f64),
+    Custom(SliderMapping),
+}
+
+#[derive(Debug, Clone)]
+enum Ticks<T> {
+    None,
+    Values(Vec<T>),
+    Evenly(usize),
+}
+
+#[derive(Clone)]
+struct ValueFormatter<T>(Arc<dyn Fn(&T) -> String + Send + Sync>);
+
+impl<T> Debug for ValueFormatter<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ValueFormatter").finish()
+    }
+}
+
+#[derive(Debug)]
+struct ValueTooltip {
+    layer: OverlayLayer,
+    text: Dynamic<String>,
+    shown: Option<OverlayHandle>,
+}
+
+impl Clone for ValueTooltip {
+    fn clone(&self) -> Self {
+        Self {
+            layer: self.layer.clone(),
+            text: Dynamic::default(),
+            shown: None,
+        }
+    }
+}
+
+/// Converts a linear `percent` between `min` and `max` to its logarithmic
+/// location.
+fn logarithmic_position(percent: f32, min: f64, max: f64) -> f32 {
+    if min <= 0. || max <= min {
+        return percent;
+    }
+    let value = min + (max - min) * f64::from(percent);
+    ((value / min).ln() / (max / min).ln()).cast::<f32>()
+}
+
+/// Converts a logarithmic `position` between `min` and `max` to its linear
+/// percentage.
+fn logarithmic_percent(position: f32, min: f64, max: f64) -> f32 {
+    if min <= 0. || max <= min {
+        return position;
+    }
+    let value = min * (max / min).powf(f64::from(position));
+    ((value - min) / (max - min)).cast::<f32>()
+}
+
 define_components! {
     Slider {
         /// The size of the track that the knob of a [`Slider`] traversesq.
@@ -780,6 +1204,10 @@ define_components! {
         InactiveTrackColor(Color, "inactive_track_color", |context| context.get(&OpaqueWidgetColor))
         /// The color of the track that the knob rests.
         DisabledInactiveTrackColor(Color, "disabled_inactive_track_color", |context| context.get(&OpaqueWidgetColor))
+        /// The length of the tick marks drawn beside a [`Slider`]'s track.
+        TickLength(Dimension, "tick_length", Dimension::Lp(Lp::points(4)))
+        /// The color of the tick marks drawn beside a [`Slider`]'s track.
+        TickColor(Color, "tick_color", @OutlineColor)
     }
 }
 
@@ -879,3 +1307,31 @@ where
     U: SliderValue,
 {
 }
+
+#[test]
+fn logarithmic_mapping() {
+    assert!(logarithmic_position(0., 20., 20_000.).abs() < 0.0001);
+    assert!((logarithmic_position(1., 20., 20_000.) - 1.).abs() < 0.0001);
+    // 200 is a third of the way along a 20..20,000 logarithmic track.
+    let percent = (200. - 20.) / (20_000. - 20.);
+    assert!((logarithmic_position(percent, 20., 20_000.) - 1. / 3.).abs() < 0.0001);
+    assert!((logarithmic_percent(1. / 3., 20., 20_000.) - percent).abs() < 0.0001);
+    // Ranges including zero fall back to a linear mapping.
+    assert!((logarithmic_position(0.5, 0., 100.) - 0.5).abs() < f32::EPSILON);
+}
+
+#[test]
+fn evenly_spaced_logarithmic_ticks() {
+    let slider = Slider::new(Dynamic::new(20_f32), 20., 20_000.)
+        .logarithmic()
+        .evenly_spaced_ticks(4);
+    let ticks = slider.tick_values(&20., &20_000.);
+    let expected = [20., 200., 2_000., 20_000.];
+    assert_eq!(ticks.len(), expected.len());
+    for ((_, value), expected) in ticks.iter().zip(expected) {
+        assert!(
+            (value - expected).abs() / expected < 0.001,
+            "{value} != {expected}"
+        );
+    }
+}
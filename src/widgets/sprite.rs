@@ -0,0 +1,693 @@
+//! Sprite sheets and frame-based sprite animations.
+//!
+//! A [`SpriteSheet`] is a texture divided into frames, optionally grouped into
+//! named [tags](SpriteTag). Sheets can be created from a uniform grid or, when
+//! the `aseprite` feature is enabled, from the JSON exported by
+//! [Aseprite](https://www.aseprite.org/).
+//!
+//! A [`SpriteAnimation`] plays the frames of a sheet. It does not depend on a
+//! widget, which allows it to be stored in a
+//! [tilemap object](kludgine::tilemap::Object) and drawn using
+//! [`SpriteAnimation::draw`]. The [`AnimatedSprite`] widget displays a
+//! [`SpriteAnimation`] like an [`Image`](crate::widgets::Image).
+//!
+//! Sprites are not an [`Image`](crate::widgets::Image) source because an
+//! `Image` always draws its entire texture, while each sprite frame is a
+//! region of its sheet's texture that changes as the animation plays.
+//! [`AnimatedSprite`] supports the same [`ImageScaling`] strategies instead.
+
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use figures::units::{Px, UPx};
+use figures::{IntoUnsigned, Point, Rect, Size};
+use kludgine::drawing::Renderer;
+use kludgine::{AnyTexture, Color, TextureBlit};
+
+use crate::context::{GraphicsContext, LayoutContext};
+use crate::value::{Destination, Dynamic, IntoDynamic, IntoValue, Source, Value};
+use crate::widget::Widget;
+use crate::widgets::animated_image::AnimationFrame;
+use crate::widgets::image::ImageScaling;
+use crate::ConstraintLimit;
+
+#[cfg(feature = "aseprite")]
+mod aseprite;
+
+/// A texture divided into frames.
+///
+/// Cloning a sprite sheet is cheap: the frames and tags are shared between
+/// clones.
+#[derive(Debug, Clone)]
+pub struct SpriteSheet {
+    texture: AnyTexture,
+    frames: Arc<[SpriteFrame]>,
+    tags: Arc<HashMap<String, SpriteTag>>,
+}
+
+impl SpriteSheet {
+    /// Returns a sprite sheet that divides `texture` into a grid of frames
+    /// that are each `frame_size`, displaying each frame for `duration`.
+    ///
+    /// Frames are ordered left-to-right, top-to-bottom. Partial frames at the
+    /// right and bottom edges of the texture are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frame_size` has a zero width or height.
+    #[must_use]
+    pub fn grid(texture: impl Into<AnyTexture>, frame_size: Size<UPx>, duration: Duration) -> Self {
+        assert!(
+            frame_size.width > 0 && frame_size.height > 0,
+            "frame_size must not be empty"
+        );
+        let texture = texture.into();
+        let texture_size = texture.size();
+        let columns = texture_size.width.get() / frame_size.width.get();
+        let rows = texture_size.height.get() / frame_size.height.get();
+        let frames = (0..rows)
+            .flat_map(|row| {
+                (0..columns).map(move |column| {
+                    SpriteFrame::new(
+                        Rect::new(
+                            Point::new(
+                                UPx::new(frame_size.width.get() * column),
+                                UPx::new(frame_size.height.get() * row),
+                            ),
+                            frame_size,
+                        ),
+                        duration,
+                    )
+                })
+            })
+            .collect();
+        Self {
+            texture,
+            frames,
+            tags: Arc::default(),
+        }
+    }
+
+    /// Returns a sprite sheet for `texture` using the frames and tags described
+    /// by `json`, which was exported by Aseprite.
+    ///
+    /// Both the "Hash" and "Array" JSON formats are supported. Each frame tag
+    /// becomes a [`SpriteTag`] with the same name. The tag directions
+    /// `forward`, `reverse`, `pingpong`, and `pingpong_reverse` are supported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `json` cannot be parsed or contains a tag that
+    /// refers to frames that do not exist.
+    ///
+    /// This function is only available when the `aseprite` feature is
+    /// enabled.
+    #[cfg(feature = "aseprite")]
+    pub fn from_aseprite_json(
+        texture: impl Into<AnyTexture>,
+        json: &str,
+    ) -> Result<Self, SpriteSheetError> {
+        let sheet: aseprite::AsepriteSheet = serde_json::from_str(json)?;
+        let frames: Arc<[SpriteFrame]> = sheet
+            .frames
+            .into_iter()
+            .map(|frame| {
+                SpriteFrame::new(
+                    Rect::new(
+                        Point::new(UPx::new(frame.frame.x), UPx::new(frame.frame.y)),
+                        Size::new(UPx::new(frame.frame.w), UPx::new(frame.frame.h)),
+                    ),
+                    Duration::from_millis(frame.duration),
+                )
+            })
+            .collect();
+
+        let mut tags = HashMap::with_capacity(sheet.meta.frame_tags.len());
+        for tag in sheet.meta.frame_tags {
+            if tag.from > tag.to || tag.to >= frames.len() {
+                return Err(SpriteSheetError::InvalidTag(tag.name));
+            }
+            let (mode, reverse) = match tag.direction.as_str() {
+                "reverse" => (AnimationMode::Loop, true),
+                "pingpong" => (AnimationMode::PingPong, false),
+                "pingpong_reverse" => (AnimationMode::PingPong, true),
+                _ => (AnimationMode::Loop, false),
+            };
+            tags.insert(
+                tag.name,
+                SpriteTag {
+                    first: tag.from,
+                    last: tag.to,
+                    mode,
+                    reverse,
+                },
+            );
+        }
+
+        Ok(Self {
+            texture: texture.into(),
+            frames,
+            tags: Arc::new(tags),
+        })
+    }
+
+    /// Adds a tag named `name` and returns self.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tag` refers to frames that are not in this sheet.
+    #[must_use]
+    pub fn with_tag(mut self, name: impl Into<String>, tag: SpriteTag) -> Self {
+        assert!(
+            tag.first <= tag.last && tag.last < self.frames.len(),
+            "tag frames out of range"
+        );
+        Arc::make_mut(&mut self.tags).insert(name.into(), tag);
+        self
+    }
+
+    /// Returns the texture the frames are drawn from.
+    #[must_use]
+    pub const fn texture(&self) -> &AnyTexture {
+        &self.texture
+    }
+
+    /// Returns the frames in this sheet.
+    #[must_use]
+    pub fn frames(&self) -> &[SpriteFrame] {
+        &self.frames
+    }
+
+    /// Returns the tag named `name`, if present.
+    #[must_use]
+    pub fn tag(&self, name: &str) -> Option<SpriteTag> {
+        self.tags.get(name).copied()
+    }
+
+    /// Returns an iterator over the names of the tags in this sheet.
+    pub fn tag_names(&self) -> impl Iterator<Item = &str> {
+        self.tags.keys().map(String::as_str)
+    }
+}
+
+/// A single frame of a [`SpriteSheet`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SpriteFrame {
+    /// The region of the sheet's texture this frame displays.
+    pub region: Rect<UPx>,
+    /// The amount of time to display this frame.
+    pub duration: Duration,
+}
+
+impl SpriteFrame {
+    /// Returns a frame that displays `region` for `duration`.
+    ///
+    /// If `duration` is zero, [`AnimationFrame::DEFAULT_DELAY`] is used.
+    #[must_use]
+    pub fn new(region: Rect<UPx>, duration: Duration) -> Self {
+        Self {
+            region,
+            duration: if duration.is_zero() {
+                AnimationFrame::DEFAULT_DELAY
+            } else {
+                duration
+            },
+        }
+    }
+}
+
+/// A named range of frames in a [`SpriteSheet`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SpriteTag {
+    /// The index of the first frame of this tag.
+    pub first: usize,
+    /// The index of the last frame of this tag, inclusive.
+    pub last: usize,
+    /// How the frames are played.
+    pub mode: AnimationMode,
+    /// When true, playback starts at the last frame and moves towards the
+    /// first frame.
+    pub reverse: bool,
+}
+
+impl SpriteTag {
+    /// Returns a tag that loops over the frames `first` through `last`.
+    #[must_use]
+    pub const fn new(first: usize, last: usize) -> Self {
+        Self {
+            first,
+            last,
+            mode: AnimationMode::Loop,
+            reverse: false,
+        }
+    }
+
+    /// Plays the frames using `mode` and returns self.
+    #[must_use]
+    pub const fn mode(mut self, mode: AnimationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Plays the frames in reverse order and returns self.
+    #[must_use]
+    pub const fn reversed(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
+    const fn start(&self) -> usize {
+        if self.reverse {
+            self.last
+        } else {
+            self.first
+        }
+    }
+
+    const fn end(&self) -> usize {
+        if self.reverse {
+            self.first
+        } else {
+            self.last
+        }
+    }
+}
+
+/// How a [`SpriteAnimation`] plays the frames of a [`SpriteTag`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum AnimationMode {
+    /// After the last frame, playback continues from the first frame.
+    #[default]
+    Loop,
+    /// After the last frame, playback changes direction until the first frame
+    /// is reached, and then changes direction again.
+    PingPong,
+    /// Playback stops on the last frame.
+    Once,
+}
+
+/// An animation that plays the frames of a [`SpriteSheet`].
+///
+/// Animations are advanced explicitly using [`advance`](Self::advance), which
+/// allows them to be driven by a [`Tick`](crate::Tick) or any other game loop.
+#[derive(Debug, Clone)]
+pub struct SpriteAnimation {
+    sheet: SpriteSheet,
+    tag_name: Option<String>,
+    tag: SpriteTag,
+    frame: usize,
+    into_frame: Duration,
+    forward: bool,
+    finished: bool,
+}
+
+impl SpriteAnimation {
+    /// Returns an animation that loops over every frame in `sheet`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sheet` has no frames.
+    #[must_use]
+    pub fn new(sheet: SpriteSheet) -> Self {
+        assert!(!sheet.frames.is_empty(), "at least one frame is required");
+        let tag = SpriteTag::new(0, sheet.frames.len() - 1);
+        Self {
+            sheet,
+            tag_name: None,
+            frame: tag.start(),
+            tag,
+            into_frame: Duration::ZERO,
+            forward: true,
+            finished: false,
+        }
+    }
+
+    /// Plays the tag named `tag` and returns self.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sheet does not contain a tag named `tag`.
+    pub fn with_tag(mut self, tag: &str) -> Result<Self, UnknownTag> {
+        self.set_tag(Some(tag))?;
+        Ok(self)
+    }
+
+    /// Plays the tag named `tag`, or every frame in the sheet if `tag` is
+    /// `None`.
+    ///
+    /// If `tag` is already playing, this function does nothing. This allows
+    /// the tag to be set every time the animation is updated without
+    /// restarting it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sheet does not contain a tag named `tag`.
+    pub fn set_tag(&mut self, tag: Option<&str>) -> Result<(), UnknownTag> {
+        if self.tag_name.as_deref() == tag {
+            return Ok(());
+        }
+
+        self.tag = match tag {
+            Some(name) => self
+                .sheet
+                .tag(name)
+                .ok_or_else(|| UnknownTag(name.to_string()))?,
+            None => SpriteTag::new(0, self.sheet.frames.len() - 1),
+        };
+        self.tag_name = tag.map(String::from);
+        self.restart();
+        Ok(())
+    }
+
+    /// Returns the name of the tag being played, or `None` if every frame in
+    /// the sheet is being played.
+    #[must_use]
+    pub fn current_tag(&self) -> Option<&str> {
+        self.tag_name.as_deref()
+    }
+
+    /// Restarts the current tag from its first frame.
+    pub fn restart(&mut self) {
+        self.frame = self.tag.start();
+        self.into_frame = Duration::ZERO;
+        self.forward = true;
+        self.finished = false;
+    }
+
+    /// Advances the animation by `elapsed`.
+    pub fn advance(&mut self, elapsed: Duration) {
+        if self.tag.first == self.tag.last {
+            return;
+        }
+
+        self.into_frame += elapsed;
+        while !self.finished {
+            let duration = self.sheet.frames[self.frame].duration;
+            if self.into_frame < duration {
+                break;
+            }
+            self.into_frame -= duration;
+            self.step();
+        }
+    }
+
+    fn step(&mut self) {
+        let target = if self.forward {
+            self.tag.end()
+        } else {
+            self.tag.start()
+        };
+        if self.frame != target {
+            self.frame = toward(self.frame, target);
+            return;
+        }
+
+        match self.tag.mode {
+            AnimationMode::Loop => self.frame = self.tag.start(),
+            AnimationMode::PingPong => {
+                self.forward = !self.forward;
+                let target = if self.forward {
+                    self.tag.end()
+                } else {
+                    self.tag.start()
+                };
+                self.frame = toward(self.frame, target);
+            }
+            AnimationMode::Once => {
+                self.finished = true;
+                self.into_frame = Duration::ZERO;
+            }
+        }
+    }
+
+    /// Returns true if the animation has stopped on its last frame.
+    ///
+    /// Only tags using [`AnimationMode::Once`] finish.
+    #[must_use]
+    pub const fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Returns the index of the frame currently being displayed.
+    #[must_use]
+    pub const fn frame_index(&self) -> usize {
+        self.frame
+    }
+
+    /// Returns the frame currently being displayed.
+    #[must_use]
+    pub fn current_frame(&self) -> &SpriteFrame {
+        &self.sheet.frames[self.frame]
+    }
+
+    /// Returns the amount of time until the displayed frame changes, or `None`
+    /// if the displayed frame will not change.
+    #[must_use]
+    pub fn remaining_frame_duration(&self) -> Option<Duration> {
+        if self.finished || self.tag.first == self.tag.last {
+            None
+        } else {
+            Some(
+                self.current_frame()
+                    .duration
+                    .saturating_sub(self.into_frame),
+            )
+        }
+    }
+
+    /// Returns the sheet this animation plays.
+    #[must_use]
+    pub const fn sheet(&self) -> &SpriteSheet {
+        &self.sheet
+    }
+
+    /// Draws the current frame into `destination`, multiplying its colors by
+    /// `tint`.
+    ///
+    /// This is intended to be used from
+    /// [`Object::render`](kludgine::tilemap::Object::render).
+    pub fn draw(&self, destination: Rect<Px>, tint: Color, renderer: &mut Renderer<'_, '_>) {
+        renderer.draw_textured_shape(
+            &TextureBlit::new(self.current_frame().region, destination, tint),
+            &self.sheet.texture,
+        );
+    }
+}
+
+impl From<SpriteSheet> for SpriteAnimation {
+    fn from(sheet: SpriteSheet) -> Self {
+        Self::new(sheet)
+    }
+}
+
+/// Returns the index one step from `frame` towards `target`.
+const fn toward(frame: usize, target: usize) -> usize {
+    if target > frame {
+        frame + 1
+    } else {
+        frame - 1
+    }
+}
+
+/// A widget that plays a [`SpriteAnimation`].
+///
+/// The current frame is sized and positioned using an [`ImageScaling`] in the
+/// same way as an [`Image`](crate::widgets::Image). Like
+/// [`AnimatedImage`](crate::widgets::AnimatedImage), the animation only
+/// advances while the widget is being drawn.
+#[derive(Debug)]
+#[must_use]
+pub struct AnimatedSprite {
+    animation: SpriteAnimation,
+    tag: Value<Option<String>>,
+    playing: Dynamic<bool>,
+    scaling: Value<ImageScaling>,
+    tint: Value<Color>,
+    last_redraw: Option<Instant>,
+}
+
+impl AnimatedSprite {
+    /// Returns a widget that plays `animation`.
+    pub fn new(animation: impl Into<SpriteAnimation>) -> Self {
+        Self {
+            animation: animation.into(),
+            tag: Value::Constant(None),
+            playing: Dynamic::new(true),
+            scaling: Value::default(),
+            tint: Value::Constant(Color::WHITE),
+            last_redraw: None,
+        }
+    }
+
+    /// Plays the tag named by `tag` and returns self.
+    ///
+    /// When `tag` is `None` or names a tag the sheet does not contain, every
+    /// frame of the sheet is played.
+    pub fn tag(mut self, tag: impl IntoValue<Option<String>>) -> Self {
+        self.tag = tag.into_value();
+        self
+    }
+
+    /// Uses `playing` to control whether the animation is playing, and
+    /// returns self.
+    ///
+    /// When a tag using [`AnimationMode::Once`] finishes, `playing` is set to
+    /// false. Setting it to true again restarts the tag.
+    pub fn playing(mut self, playing: impl IntoDynamic<bool>) -> Self {
+        self.playing = playing.into_dynamic();
+        self
+    }
+
+    /// Applies the `scaling` strategy and returns self.
+    pub fn scaling(mut self, scaling: impl IntoValue<ImageScaling>) -> Self {
+        self.scaling = scaling.into_value();
+        self
+    }
+
+    /// Multiplies the sprite's colors by `tint` and returns self.
+    pub fn tint(mut self, tint: impl IntoValue<Color>) -> Self {
+        self.tint = tint.into_value();
+        self
+    }
+}
+
+impl Widget for AnimatedSprite {
+    fn redraw(&mut self, context: &mut GraphicsContext<'_, '_, '_, '_>) {
+        let tag = self.tag.get_tracking_redraw(context);
+        if self.animation.set_tag(tag.as_deref()).is_err() {
+            // An unknown tag plays the entire sheet, which always succeeds.
+            let _result = self.animation.set_tag(None);
+        }
+
+        let playing = self.playing.get_tracking_redraw(context);
+        let occluded = context.occluded().clone();
+        let occluded = occluded.get_tracking_redraw(context);
+        let now = Instant::now();
+
+        if playing && self.animation.is_finished() {
+            self.animation.restart();
+        }
+
+        if playing && !occluded {
+            let elapsed = self
+                .last_redraw
+                .map_or(Duration::ZERO, |last| now.saturating_duration_since(last));
+            self.animation.advance(elapsed);
+            if self.animation.is_finished() {
+                self.playing.set(false);
+            } else if let Some(remaining) = self.animation.remaining_frame_duration() {
+                context.redraw_in(remaining);
+            }
+            self.last_redraw = Some(now);
+        } else {
+            self.last_redraw = None;
+        }
+
+        let frame = *self.animation.current_frame();
+        let rect = self
+            .scaling
+            .get_tracking_invalidate(context)
+            .image_rect(frame.region.size, context.gfx.size());
+        let tint = self.tint.get_tracking_redraw(context);
+        context.gfx.draw_textured_shape(
+            &TextureBlit::new(frame.region, rect, tint),
+            self.animation.sheet.texture(),
+        );
+    }
+
+    fn layout(
+        &mut self,
+        available_space: Size<ConstraintLimit>,
+        context: &mut LayoutContext<'_, '_, '_, '_>,
+    ) -> Size<UPx> {
+        let frame = self.animation.current_frame();
+        self.scaling
+            .get_tracking_invalidate(context)
+            .image_rect(frame.region.size, available_space.map(ConstraintLimit::max))
+            .size
+            .into_unsigned()
+    }
+}
+
+/// An error returned when a [`SpriteAnimation`] is asked to play a tag that is
+/// not in its [`SpriteSheet`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UnknownTag(pub String);
+
+impl Display for UnknownTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown sprite tag: {}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownTag {}
+
+/// An error loading a [`SpriteSheet`].
+#[cfg(feature = "aseprite")]
+#[derive(Debug)]
+pub enum SpriteSheetError {
+    /// The JSON could not be parsed.
+    Json(serde_json::Error),
+    /// The named tag refers to frames that are not in the sheet.
+    InvalidTag(String),
+}
+
+#[cfg(feature = "aseprite")]
+impl From<serde_json::Error> for SpriteSheetError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+#[cfg(feature = "aseprite")]
+impl Display for SpriteSheetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpriteSheetError::Json(err) => Display::fmt(err, f),
+            SpriteSheetError::InvalidTag(name) => {
+                write!(f, "tag {name} refers to frames that do not exist")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "aseprite")]
+impl std::error::Error for SpriteSheetError {}
+
+#[test]
+fn ping_pong_and_once_playback() {
+    fn playback(tag: SpriteTag, steps: usize) -> Vec<usize> {
+        let texture = kludgine::LazyTexture::from_image(
+            image::DynamicImage::new_rgba8(4, 1),
+            kludgine::wgpu::FilterMode::Nearest,
+        );
+        let sheet = SpriteSheet::grid(
+            texture,
+            Size::squared(UPx::new(1)),
+            Duration::from_millis(10),
+        )
+        .with_tag("test", tag);
+        let mut animation = SpriteAnimation::new(sheet).with_tag("test").unwrap();
+        let mut frames = vec![animation.frame_index()];
+        for _ in 0..steps {
+            animation.advance(Duration::from_millis(10));
+            frames.push(animation.frame_index());
+        }
+        frames
+    }
+
+    assert_eq!(
+        playback(SpriteTag::new(1, 3).mode(AnimationMode::PingPong), 6),
+        [1, 2, 3, 2, 1, 2, 3]
+    );
+    assert_eq!(
+        playback(SpriteTag::new(0, 3).reversed(), 5),
+        [3, 2, 1, 0, 3, 2]
+    );
+    assert_eq!(
+        playback(SpriteTag::new(0, 2).mode(AnimationMode::Once), 4),
+        [0, 1, 2, 2, 2]
+    );
+}
@@ -0,0 +1,101 @@
+//! Parsing of the JSON exported by [Aseprite](https://www.aseprite.org/).
+
+use std::fmt;
+
+use serde::de::{IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+
+#[derive(Deserialize)]
+pub(super) struct AsepriteSheet {
+    #[serde(deserialize_with = "deserialize_aseprite_frames")]
+    pub(super) frames: Vec<AsepriteFrame>,
+    pub(super) meta: AsepriteMeta,
+}
+
+#[derive(Deserialize)]
+pub(super) struct AsepriteFrame {
+    pub(super) frame: AsepriteRect,
+    pub(super) duration: u64,
+}
+
+#[derive(Deserialize)]
+pub(super) struct AsepriteRect {
+    pub(super) x: u32,
+    pub(super) y: u32,
+    pub(super) w: u32,
+    pub(super) h: u32,
+}
+
+#[derive(Deserialize)]
+pub(super) struct AsepriteMeta {
+    #[serde(default, rename = "frameTags")]
+    pub(super) frame_tags: Vec<AsepriteTag>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct AsepriteTag {
+    pub(super) name: String,
+    pub(super) from: usize,
+    pub(super) to: usize,
+    #[serde(default)]
+    pub(super) direction: String,
+}
+
+/// Deserializes Aseprite frames exported as either an array or a map keyed by
+/// frame name, preserving the order the frames appear in.
+fn deserialize_aseprite_frames<'de, D>(deserializer: D) -> Result<Vec<AsepriteFrame>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct FramesVisitor;
+
+    impl<'de> Visitor<'de> for FramesVisitor {
+        type Value = Vec<AsepriteFrame>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("an array or map of frames")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut frames = Vec::new();
+            while let Some(frame) = seq.next_element()? {
+                frames.push(frame);
+            }
+            Ok(frames)
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut frames = Vec::new();
+            while let Some((IgnoredAny, frame)) = map.next_entry()? {
+                frames.push(frame);
+            }
+            Ok(frames)
+        }
+    }
+
+    deserializer.deserialize_any(FramesVisitor)
+}
+
+#[test]
+fn aseprite_frames_and_tags() {
+    let json = r#"{
+        "frames": {
+            "a": { "frame": { "x": 0, "y": 0, "w": 8, "h": 8 }, "duration": 50 },
+            "b": { "frame": { "x": 8, "y": 0, "w": 8, "h": 8 }, "duration": 0 },
+            "c": { "frame": { "x": 16, "y": 0, "w": 8, "h": 8 }, "duration": 75 }
+        },
+        "meta": {
+            "frameTags": [{ "name": "Walk", "from": 1, "to": 2, "direction": "pingpong" }]
+        }
+    }"#;
+    let sheet: AsepriteSheet = serde_json::from_str(json).unwrap();
+    let origins: Vec<u32> = sheet.frames.iter().map(|frame| frame.frame.x).collect();
+    assert_eq!(origins, [0, 8, 16]);
+    assert_eq!(sheet.meta.frame_tags[0].direction, "pingpong");
+}
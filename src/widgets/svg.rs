@@ -0,0 +1,305 @@
+//! A widget that renders SVG images.
+//!
+//! This module is only available when the `svg` feature is enabled. SVGs are
+//! rasterized on the CPU using [`resvg`] at the exact pixel size they are
+//! displayed at, which keeps them crisp at every scale factor.
+
+use std::borrow::Cow;
+use std::fmt::{self, Display};
+use std::path::Path;
+use std::sync::Arc;
+use std::{fs, io};
+
+use figures::units::{Lp, Px, UPx};
+use figures::{
+    FloatConversion, Fraction, IntoSigned, IntoUnsigned, Point, Rect, ScreenScale, Size, Zero,
+};
+use image::{DynamicImage, RgbaImage};
+use kludgine::wgpu::FilterMode;
+use kludgine::{Color, LazyTexture};
+use resvg::tiny_skia::{Pixmap, Transform};
+use resvg::usvg;
+
+use crate::context::{GraphicsContext, LayoutContext};
+use crate::styles::components::TextColor;
+use crate::value::{IntoValue, Source, Value};
+use crate::widget::Widget;
+use crate::ConstraintLimit;
+
+/// A widget that displays an SVG image.
+///
+/// The image is drawn as large as possible without clipping while preserving
+/// its aspect ratio. When sizing to fit, the SVG's own size is used, treating
+/// each SVG pixel as one pixel at a scale of 1.0.
+///
+/// Any use of `currentColor` in the SVG is replaced with the current
+/// [`TextColor`], allowing icons to follow the theme. A specific color can be
+/// used instead with [`Svg::color`].
+#[derive(Debug)]
+#[must_use]
+pub struct Svg {
+    source: Arc<[u8]>,
+    uses_current_color: bool,
+    size: Size<f32>,
+    color: Option<Value<Color>>,
+    raster: Option<Raster>,
+}
+
+#[derive(Debug)]
+struct Raster {
+    size: Size<UPx>,
+    color: Color,
+    texture: Option<LazyTexture>,
+}
+
+impl Svg {
+    /// Returns a widget that displays the SVG contained in `source`.
+    ///
+    /// `source` may be either an SVG document or a gzip-compressed SVG
+    /// document.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `source` is not a valid SVG.
+    pub fn from_bytes(source: impl Into<Arc<[u8]>>) -> Result<Self, SvgError> {
+        let source = source.into();
+        let tree = parse(&source)?;
+        let size = tree.size();
+        let uses_current_color =
+            std::str::from_utf8(&source).map_or(false, |source| source.contains(CURRENT_COLOR));
+        Ok(Self {
+            source,
+            uses_current_color,
+            size: Size::new(size.width(), size.height()),
+            color: None,
+            raster: None,
+        })
+    }
+
+    /// Returns a widget that displays the SVG contained in the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` cannot be read or is not a valid SVG.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, SvgError> {
+        Self::from_bytes(fs::read(path)?)
+    }
+
+    /// Replaces `currentColor` with `color` instead of the current
+    /// [`TextColor`], and returns self.
+    pub fn color(mut self, color: impl IntoValue<Color>) -> Self {
+        self.color = Some(color.into_value());
+        self
+    }
+
+    /// Returns the size of this SVG when drawn at `scale`.
+    fn natural_size(&self, scale: Fraction) -> Size<UPx> {
+        self.size
+            .map(|length| Lp::from_px(Px::from(length), Fraction::ONE).into_upx(scale))
+    }
+
+    fn rasterize(&self, size: Size<UPx>, color: Color) -> Option<LazyTexture> {
+        let source = if self.uses_current_color {
+            Cow::Owned(
+                replace_current_color(std::str::from_utf8(&self.source).ok()?, color).into_bytes(),
+            )
+        } else {
+            Cow::Borrowed(&*self.source)
+        };
+        let tree = parse(&source).ok()?;
+
+        let mut pixmap = Pixmap::new(size.width.get(), size.height.get())?;
+        let tree_size = tree.size();
+        resvg::render(
+            &tree,
+            Transform::from_scale(
+                size.width.into_float() / tree_size.width(),
+                size.height.into_float() / tree_size.height(),
+            ),
+            &mut pixmap.as_mut(),
+        );
+
+        let mut pixels = pixmap.take();
+        demultiply_alpha(&mut pixels);
+        let image = RgbaImage::from_raw(size.width.get(), size.height.get(), pixels)?;
+        Some(LazyTexture::from_image(
+            DynamicImage::ImageRgba8(image),
+            FilterMode::Linear,
+        ))
+    }
+}
+
+impl Widget for Svg {
+    fn redraw(&mut self, context: &mut GraphicsContext<'_, '_, '_, '_>) {
+        let color = match &self.color {
+            Some(color) => color.get_tracking_redraw(context),
+            None => context.get(&TextColor),
+        };
+
+        let region = context.gfx.region().size.into_unsigned();
+        let size = aspect_fit(self.size, region);
+        if self
+            .raster
+            .as_ref()
+            .map_or(true, |raster| raster.size != size || raster.color != color)
+        {
+            self.raster = Some(Raster {
+                size,
+                color,
+                texture: if size.width > 0 && size.height > 0 {
+                    self.rasterize(size, color)
+                } else {
+                    None
+                },
+            });
+        }
+
+        if let Some(texture) = self
+            .raster
+            .as_ref()
+            .and_then(|raster| raster.texture.as_ref())
+        {
+            let size = size.into_signed();
+            let region = region.into_signed();
+            let origin = Point::new(
+                (region.width - size.width) / 2,
+                (region.height - size.height) / 2,
+            );
+            context.gfx.draw_texture(texture, Rect::new(origin, size));
+        }
+    }
+
+    fn layout(
+        &mut self,
+        available_space: Size<ConstraintLimit>,
+        context: &mut LayoutContext<'_, '_, '_, '_>,
+    ) -> Size<UPx> {
+        let natural = self.natural_size(context.gfx.scale());
+        let fitted = aspect_fit(
+            natural.map(UPx::into_float),
+            Size::new(
+                fit_limit(available_space.width, natural.width),
+                fit_limit(available_space.height, natural.height),
+            ),
+        );
+        Size::new(
+            match available_space.width {
+                ConstraintLimit::Fill(width) => width,
+                ConstraintLimit::SizeToFit(_) => fitted.width,
+            },
+            match available_space.height {
+                ConstraintLimit::Fill(height) => height,
+                ConstraintLimit::SizeToFit(_) => fitted.height,
+            },
+        )
+    }
+}
+
+/// Returns the largest space `limit` allows the image to be drawn within.
+fn fit_limit(limit: ConstraintLimit, natural: UPx) -> UPx {
+    match limit {
+        ConstraintLimit::Fill(size) => size,
+        ConstraintLimit::SizeToFit(max) => natural.min(max),
+    }
+}
+
+/// Returns the largest size with the aspect ratio of `size` that fits within
+/// `within`.
+fn aspect_fit(size: Size<f32>, within: Size<UPx>) -> Size<UPx> {
+    if size.width <= 0. || size.height <= 0. {
+        return Size::ZERO;
+    }
+    let scale =
+        (within.width.into_float() / size.width).min(within.height.into_float() / size.height);
+    size.map(|length| Px::from(length * scale).into_unsigned())
+}
+
+const CURRENT_COLOR: &str = "currentColor";
+
+fn replace_current_color(source: &str, color: Color) -> String {
+    source.replace(
+        CURRENT_COLOR,
+        &format!(
+            "rgba({}, {}, {}, {})",
+            color.red(),
+            color.green(),
+            color.blue(),
+            color.alpha_f32()
+        ),
+    )
+}
+
+/// Converts premultiplied RGBA pixels into RGBA pixels.
+fn demultiply_alpha(pixels: &mut [u8]) {
+    for pixel in pixels.chunks_exact_mut(4) {
+        let alpha = u16::from(pixel[3]);
+        if alpha > 0 && alpha < 255 {
+            for channel in &mut pixel[..3] {
+                *channel = u8::try_from((u16::from(*channel) * 255 + alpha / 2) / alpha)
+                    .unwrap_or(u8::MAX);
+            }
+        }
+    }
+}
+
+fn parse(source: &[u8]) -> Result<usvg::Tree, SvgError> {
+    Ok(usvg::Tree::from_data(source, &usvg::Options::default())?)
+}
+
+/// An error loading an SVG.
+#[derive(Debug)]
+pub enum SvgError {
+    /// An error occurred reading the SVG.
+    Io(io::Error),
+    /// The SVG could not be parsed.
+    Parse(usvg::Error),
+}
+
+impl From<io::Error> for SvgError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<usvg::Error> for SvgError {
+    fn from(value: usvg::Error) -> Self {
+        Self::Parse(value)
+    }
+}
+
+impl Display for SvgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SvgError::Io(err) => Display::fmt(err, f),
+            SvgError::Parse(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+impl std::error::Error for SvgError {}
+
+#[test]
+fn current_color_replacement() {
+    assert_eq!(
+        replace_current_color(
+            r#"<path fill="currentColor" stroke="currentColor"/>"#,
+            Color::new(255, 0, 128, 255)
+        ),
+        r#"<path fill="rgba(255, 0, 128, 1)" stroke="rgba(255, 0, 128, 1)"/>"#
+    );
+}
+
+#[test]
+fn alpha_demultiplication() {
+    let mut pixels = [128, 64, 0, 128, 10, 20, 30, 255, 0, 0, 0, 0];
+    demultiply_alpha(&mut pixels);
+    assert_eq!(pixels, [255, 128, 0, 128, 10, 20, 30, 255, 0, 0, 0, 0]);
+}
+
+#[test]
+fn aspect_fitting() {
+    assert_eq!(
+        aspect_fit(Size::new(24., 12.), Size::new(UPx::new(48), UPx::new(48))),
+        Size::new(UPx::new(48), UPx::new(24))
+    );
+}
@@ -1,16 +1,20 @@
+//! A layered tile-based 2d game surface.
+
 use std::fmt::Debug;
+use std::time::Duration;
 
 use figures::units::{Px, UPx};
-use figures::{Point, Size};
-use intentional::Cast;
+use figures::{FloatConversion, IntoSigned, Point, Rect, Size};
 use kludgine::app::winit::event::{ElementState, MouseScrollDelta, TouchPhase};
 use kludgine::app::winit::window::CursorIcon;
 use kludgine::tilemap;
 use kludgine::tilemap::TileMapFocus;
 
+use crate::animation::{Easing, EasingFunction, ZeroToOne};
 use crate::context::{EventContext, GraphicsContext, LayoutContext, Trackable};
 use crate::tick::Tick;
-use crate::value::{Dynamic, IntoValue, Value};
+use crate::utils::wheel_zoom_factor;
+use crate::value::{Destination, Dynamic, IntoDynamic, IntoValue, Source, Value};
 use crate::widget::{EventHandling, Widget, HANDLED, IGNORED};
 use crate::window::{DeviceId, KeyEvent};
 use crate::ConstraintLimit;
@@ -21,8 +25,14 @@ use crate::ConstraintLimit;
 pub struct TileMap<Layers> {
     layers: Value<Layers>,
     focus: Value<TileMapFocus>,
-    zoom: f32,
+    zoom: Dynamic<f32>,
+    minimum_zoom: f32,
+    maximum_zoom: f32,
+    camera: Value<Camera>,
+    shake: ScreenShake,
     tick: Option<Tick>,
+    camera_state: CameraState,
+    displayed_center: Option<Point<Px>>,
 }
 
 impl<Layers> TileMap<Layers> {
@@ -30,8 +40,14 @@ impl<Layers> TileMap<Layers> {
         Self {
             layers,
             focus: Value::default(),
-            zoom: 1.,
+            zoom: Dynamic::new(1.),
+            minimum_zoom: 1. / 32.,
+            maximum_zoom: 32.,
+            camera: Value::default(),
+            shake: ScreenShake::new(),
             tick: None,
+            camera_state: CameraState::default(),
+            displayed_center: None,
         }
     }
 
@@ -47,13 +63,46 @@ impl<Layers> TileMap<Layers> {
 
     /// Sets the camera's focus and returns self.
     ///
-    /// The tilemap will ensure that `focus` is centered.
-    // TODO how do we allow the camera to "lag" for juice effects?
+    /// The camera follows `focus` as configured by [`Self::camera`]. By
+    /// default, `focus` is always centered.
     pub fn focus_on(mut self, focus: impl IntoValue<TileMapFocus>) -> Self {
         self.focus = focus.into_value();
         self
     }
 
+    /// Uses `zoom` as the camera's zoom level and returns self.
+    ///
+    /// A zoom of 1.0 displays the map at its natural size. Scrolling the mouse
+    /// wheel over the tilemap updates `zoom`.
+    pub fn zoom(mut self, zoom: impl IntoDynamic<f32>) -> Self {
+        self.zoom = zoom.into_dynamic();
+        self
+    }
+
+    /// Limits the zoom level set by scrolling the mouse wheel to
+    /// `minimum..=maximum` and returns self.
+    ///
+    /// By default, the zoom level is limited to `1/32..=32`.
+    pub fn zoom_limits(mut self, minimum: f32, maximum: f32) -> Self {
+        self.minimum_zoom = minimum.min(maximum);
+        self.maximum_zoom = maximum.max(minimum);
+        self
+    }
+
+    /// Uses `camera` to control how the camera follows its
+    /// [focus](Self::focus_on) and returns self.
+    pub fn camera(mut self, camera: impl IntoValue<Camera>) -> Self {
+        self.camera = camera.into_value();
+        self
+    }
+
+    /// Shakes the camera when impulses are applied to `shake`, and returns
+    /// self.
+    pub fn screen_shake(mut self, shake: ScreenShake) -> Self {
+        self.shake = shake;
+        self
+    }
+
     /// Associates a [`Tick`] with this widget and returns self.
     pub fn tick(mut self, tick: Tick) -> Self {
         self.tick = Some(tick);
@@ -66,28 +115,21 @@ where
     Layers: tilemap::Layers,
 {
     fn redraw(&mut self, context: &mut GraphicsContext<'_, '_, '_, '_>) {
-        let focus = self.focus.get();
-        // TODO this needs to be updated to support being placed in side of a scroll view.
-        let redraw_after = match &mut self.layers {
-            Value::Constant(layers) => tilemap::draw(
-                layers,
-                focus,
-                self.zoom,
-                context.elapsed(),
-                context.gfx.inner_graphics(),
-            ),
+        let frame = FrameSettings {
+            focus: self.focus.get(),
+            zoom: self.zoom.get(),
+            camera: self.camera.get(),
+            shake: self.shake.advance(context.elapsed()),
+        };
+        let (redraw_after, displayed_center, animating) = match &mut self.layers {
+            Value::Constant(layers) => draw_layers(layers, &frame, &mut self.camera_state, context),
             Value::Dynamic(layers) => {
                 let mut layers = layers.lock();
                 layers.prevent_notifications();
-                tilemap::draw(
-                    &mut *layers,
-                    focus,
-                    self.zoom,
-                    context.elapsed(),
-                    context.gfx.inner_graphics(),
-                )
+                draw_layers(&mut *layers, &frame, &mut self.camera_state, context)
             }
         };
+        self.displayed_center = Some(displayed_center);
 
         context.draw_focus_ring();
 
@@ -96,11 +138,16 @@ where
             // refreshes.
             tick.rendered(context);
         } else {
-            if let Some(redraw_after) = redraw_after {
+            if animating {
+                context.set_needs_redraw();
+            } else if let Some(redraw_after) = redraw_after {
                 context.redraw_in(redraw_after);
             }
             self.focus.redraw_when_changed(context);
             self.layers.redraw_when_changed(context);
+            self.camera.redraw_when_changed(context);
+            context.redraw_when_changed(&self.zoom);
+            context.redraw_when_changed(&self.shake.0);
         }
     }
 
@@ -131,12 +178,10 @@ where
         _phase: TouchPhase,
         context: &mut EventContext<'_>,
     ) -> EventHandling {
-        let amount = match delta {
-            MouseScrollDelta::LineDelta(_, lines) => lines,
-            MouseScrollDelta::PixelDelta(px) => px.y.cast::<f32>() / 16.0,
-        };
-
-        self.zoom += self.zoom * 0.1 * amount;
+        let factor = wheel_zoom_factor(delta);
+        let (minimum, maximum) = (self.minimum_zoom, self.maximum_zoom);
+        self.zoom
+            .map_mut(|mut zoom| *zoom = (*zoom * factor).clamp(minimum, maximum));
 
         context.set_needs_redraw();
         HANDLED
@@ -148,11 +193,16 @@ where
                 return None;
             };
 
-            let world =
-                tilemap::translate_coordinates(local, context.kludgine.scale(), self.zoom, size);
-            let offset = self
-                .layers
-                .map(|layers| self.focus.get().world_coordinate(layers));
+            let world = tilemap::translate_coordinates(
+                local,
+                context.kludgine.scale(),
+                self.zoom.get(),
+                size,
+            );
+            let offset = self.displayed_center.unwrap_or_else(|| {
+                self.layers
+                    .map(|layers| self.focus.get().world_coordinate(layers))
+            });
 
             tick.set_cursor_position(Some(world + offset));
         }
@@ -208,3 +258,490 @@ where
         }
     }
 }
+
+/// The settings used to draw a single frame of a [`TileMap`].
+struct FrameSettings {
+    focus: TileMapFocus,
+    zoom: f32,
+    camera: Camera,
+    shake: Option<Point<f32>>,
+}
+
+/// Draws `layers`, returning when the layers need to be redrawn, the world
+/// coordinate displayed at the center of the widget, and whether the camera is
+/// still moving.
+fn draw_layers<Layers>(
+    layers: &mut Layers,
+    frame: &FrameSettings,
+    state: &mut CameraState,
+    context: &mut GraphicsContext<'_, '_, '_, '_>,
+) -> (Option<Duration>, Point<Px>, bool)
+where
+    Layers: tilemap::Layers,
+{
+    let effective_zoom = frame.zoom * context.gfx.scale().into_f32();
+    let region = context.gfx.region().size;
+    let view = Size::new(
+        region.width.into_float() / effective_zoom,
+        region.height.into_float() / effective_zoom,
+    );
+
+    let focus = frame.focus.world_coordinate(layers);
+    let moving = state.update(
+        &frame.camera,
+        Point::new(focus.x.into_float(), focus.y.into_float()),
+        view,
+        context.elapsed(),
+    );
+    let mut center = state.position.unwrap_or_default();
+    if let Some(shake) = frame.shake {
+        center += shake;
+    }
+
+    // Kludgine centers the focus within the renderer, whose origin and size
+    // are those of the clipping rectangle. When this widget is only partially
+    // visible, such as when it is inside of a scroll view, the clipping
+    // rectangle's center is not the center of this widget.
+    let clip = context.gfx.clip_rect().size.into_signed();
+    let clip_origin = -context.gfx.translation();
+    let clip_center_offset = Point::new(
+        clip_origin.x.into_float() + clip.width.into_float() / 2. - region.width.into_float() / 2.,
+        clip_origin.y.into_float() + clip.height.into_float() / 2.
+            - region.height.into_float() / 2.,
+    );
+    let rendered_center = Point::new(
+        center.x + clip_center_offset.x / effective_zoom,
+        center.y + clip_center_offset.y / effective_zoom,
+    );
+
+    let redraw_after = tilemap::draw(
+        layers,
+        TileMapFocus::Point(rendered_center.cast()),
+        frame.zoom,
+        context.elapsed(),
+        context.gfx.inner_graphics(),
+    );
+
+    (redraw_after, center.cast(), moving || frame.shake.is_some())
+}
+
+/// Controls how the camera of a [`TileMap`] follows its focus.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[must_use]
+pub struct Camera {
+    /// How the camera moves towards its focus.
+    pub follow: CameraFollow,
+    /// The size of the region around the center of the camera that the focus
+    /// can move within without moving the camera, in world pixels.
+    pub dead_zone: Size<Px>,
+    /// The region of the world, in world pixels, that the camera is kept
+    /// within.
+    ///
+    /// When the visible area is larger than the bounds, the bounds are
+    /// centered.
+    pub bounds: Option<Rect<Px>>,
+}
+
+impl Camera {
+    /// Returns a camera that is always centered on its focus.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves the camera towards its focus using `follow` and returns self.
+    pub fn follow(mut self, follow: CameraFollow) -> Self {
+        self.follow = follow;
+        self
+    }
+
+    /// Allows the focus to move within `dead_zone` without moving the camera,
+    /// and returns self.
+    pub fn dead_zone(mut self, dead_zone: Size<Px>) -> Self {
+        self.dead_zone = dead_zone;
+        self
+    }
+
+    /// Keeps the visible area within `bounds` and returns self.
+    pub fn bounds(mut self, bounds: Rect<Px>) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Returns the closest point to `center` that keeps a view of `view` size
+    /// within this camera's bounds.
+    fn clamp(&self, center: Point<f32>, view: Size<f32>) -> Point<f32> {
+        let Some(bounds) = self.bounds else {
+            return center;
+        };
+        Point::new(
+            clamp_axis(
+                center.x,
+                view.width,
+                bounds.origin.x.into_float(),
+                bounds.size.width.into_float(),
+            ),
+            clamp_axis(
+                center.y,
+                view.height,
+                bounds.origin.y.into_float(),
+                bounds.size.height.into_float(),
+            ),
+        )
+    }
+}
+
+fn clamp_axis(center: f32, view: f32, origin: f32, length: f32) -> f32 {
+    let min = origin + view / 2.;
+    let max = origin + length - view / 2.;
+    if min > max {
+        origin + length / 2.
+    } else {
+        center.clamp(min, max)
+    }
+}
+
+/// How a [`Camera`] moves towards its focus.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum CameraFollow {
+    /// The camera moves immediately.
+    #[default]
+    Snap,
+    /// The camera moves from its current location to the focus over
+    /// `duration` using `easing`.
+    ///
+    /// If the focus moves while the camera is moving, the camera continues
+    /// towards the focus's new location, arriving when `duration` has
+    /// elapsed.
+    Ease {
+        /// The amount of time the camera takes to reach its focus.
+        duration: Duration,
+        /// The easing function to apply.
+        easing: EasingFunction,
+    },
+    /// The camera is pulled towards its focus by a damped spring.
+    Spring {
+        /// The strength of the spring. Higher values move the camera faster.
+        stiffness: f32,
+        /// The amount of damping. A damping of `2 * stiffness.sqrt()` reaches
+        /// the focus as quickly as possible without overshooting it.
+        damping: f32,
+    },
+}
+
+impl CameraFollow {
+    /// Returns a follow strategy that moves the camera to its focus over
+    /// `duration` using `easing`.
+    #[must_use]
+    pub fn ease(duration: Duration, easing: impl Into<EasingFunction>) -> Self {
+        Self::Ease {
+            duration,
+            easing: easing.into(),
+        }
+    }
+
+    /// Returns a spring with `stiffness` that reaches its focus without
+    /// overshooting it.
+    #[must_use]
+    pub fn critically_damped_spring(stiffness: f32) -> Self {
+        Self::Spring {
+            stiffness,
+            damping: 2. * stiffness.sqrt(),
+        }
+    }
+}
+
+/// The state of a [`TileMap`]'s camera between frames.
+#[derive(Debug, Default, Clone, PartialEq)]
+struct CameraState {
+    position: Option<Point<f32>>,
+    goal: Point<f32>,
+    velocity: Point<f32>,
+    ease: Option<EaseProgress>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct EaseProgress {
+    from: Point<f32>,
+    elapsed: Duration,
+}
+
+impl CameraState {
+    /// The longest step used when simulating a spring.
+    const SPRING_STEP: Duration = Duration::from_millis(8);
+    /// The distance and velocity below which a spring is considered settled.
+    const SETTLED: f32 = 0.01;
+
+    /// Moves the camera towards `focus`, returning true if the camera has not
+    /// reached its destination.
+    fn update(
+        &mut self,
+        camera: &Camera,
+        focus: Point<f32>,
+        view: Size<f32>,
+        elapsed: Duration,
+    ) -> bool {
+        let half_dead_zone = Size::new(
+            camera.dead_zone.width.into_float() / 2.,
+            camera.dead_zone.height.into_float() / 2.,
+        );
+        let Some(position) = self.position else {
+            // The first frame is always centered on the focus.
+            self.goal = camera.clamp(focus, view);
+            self.position = Some(self.goal);
+            return false;
+        };
+
+        // The goal only moves when the focus leaves the dead zone.
+        self.goal = camera.clamp(
+            Point::new(
+                self.goal.x.clamp(
+                    focus.x - half_dead_zone.width,
+                    focus.x + half_dead_zone.width,
+                ),
+                self.goal.y.clamp(
+                    focus.y - half_dead_zone.height,
+                    focus.y + half_dead_zone.height,
+                ),
+            ),
+            view,
+        );
+
+        let (position, moving) = match &camera.follow {
+            CameraFollow::Snap => (self.goal, false),
+            CameraFollow::Ease { duration, easing } => {
+                if position == self.goal {
+                    self.ease = None;
+                    (position, false)
+                } else {
+                    let ease = self.ease.get_or_insert(EaseProgress {
+                        from: position,
+                        elapsed: Duration::ZERO,
+                    });
+                    ease.elapsed += elapsed;
+                    let progress = if duration.is_zero() {
+                        ZeroToOne::ONE
+                    } else {
+                        ZeroToOne::new(ease.elapsed.as_secs_f32() / duration.as_secs_f32())
+                    };
+                    if progress == ZeroToOne::ONE {
+                        self.ease = None;
+                        (self.goal, false)
+                    } else {
+                        let eased = easing.ease(progress);
+                        (
+                            Point::new(
+                                ease.from.x + (self.goal.x - ease.from.x) * eased,
+                                ease.from.y + (self.goal.y - ease.from.y) * eased,
+                            ),
+                            true,
+                        )
+                    }
+                }
+            }
+            CameraFollow::Spring { stiffness, damping } => {
+                let mut position = position;
+                let mut remaining = elapsed;
+                while !remaining.is_zero() {
+                    let step = remaining.min(Self::SPRING_STEP);
+                    remaining -= step;
+                    let step = step.as_secs_f32();
+                    let acceleration = Point::new(
+                        (self.goal.x - position.x) * stiffness - self.velocity.x * damping,
+                        (self.goal.y - position.y) * stiffness - self.velocity.y * damping,
+                    );
+                    self.velocity += acceleration * step;
+                    position += self.velocity * step;
+                }
+
+                let distance = Point::new(self.goal.x - position.x, self.goal.y - position.y);
+                if distance.x.abs() < Self::SETTLED
+                    && distance.y.abs() < Self::SETTLED
+                    && self.velocity.x.abs() < Self::SETTLED
+                    && self.velocity.y.abs() < Self::SETTLED
+                {
+                    self.velocity = Point::default();
+                    (self.goal, false)
+                } else {
+                    (position, true)
+                }
+            }
+        };
+        self.position = Some(position);
+        moving
+    }
+}
+
+/// Shakes the camera of a [`TileMap`].
+///
+/// Clones of a `ScreenShake` share the same state, which allows impulses to be
+/// applied from a [`Tick`] or any other code that reacts to game events.
+///
+/// The shake advances by the elapsed time of each frame drawn by the
+/// [`TileMap`] it is installed in, which keeps it in sync with a [`Tick`] that
+/// is being replayed. Each `ScreenShake` should only be installed in a single
+/// [`TileMap`].
+#[derive(Debug, Clone, Default)]
+pub struct ScreenShake(Dynamic<Option<ShakeImpulse>>);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ShakeImpulse {
+    elapsed: Duration,
+    intensity: f32,
+    duration: Duration,
+}
+
+impl ShakeImpulse {
+    /// Returns the current intensity of the shake, or `None` if the shake has
+    /// finished.
+    fn intensity(&self) -> Option<f32> {
+        if self.elapsed >= self.duration {
+            return None;
+        }
+        let remaining = 1. - self.elapsed.as_secs_f32() / self.duration.as_secs_f32();
+        Some(self.intensity * remaining * remaining)
+    }
+}
+
+impl ScreenShake {
+    /// Returns a new screen shake that is not shaking.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shakes the camera by up to `intensity` world pixels, decaying over
+    /// `duration`.
+    ///
+    /// If the camera is already shaking, the stronger of the remaining and new
+    /// intensities is used, and the shake lasts for the longer of the two
+    /// durations.
+    pub fn impulse(&self, intensity: Px, duration: Duration) {
+        self.0.map_mut(|mut shake| {
+            let intensity = intensity.into_float().abs();
+            *shake = Some(
+                match (*shake).and_then(|shake| Some((shake, shake.intensity()?))) {
+                    Some((current, remaining)) => ShakeImpulse {
+                        elapsed: Duration::ZERO,
+                        intensity: intensity.max(remaining),
+                        duration: duration.max(current.duration.saturating_sub(current.elapsed)),
+                    },
+                    None => ShakeImpulse {
+                        elapsed: Duration::ZERO,
+                        intensity,
+                        duration,
+                    },
+                },
+            );
+        });
+    }
+
+    /// Stops shaking the camera.
+    pub fn stop(&self) {
+        self.0.set(None);
+    }
+
+    /// Advances the shake by `elapsed` and returns the offset to apply to the
+    /// camera, or `None` if the camera is not shaking.
+    fn advance(&self, elapsed: Duration) -> Option<Point<f32>> {
+        let mut shake = self.0.lock();
+        // Advancing the shake does not need to notify observers: the tilemap
+        // keeps redrawing while the camera is shaking.
+        shake.prevent_notifications();
+        let impulse = shake.as_mut()?;
+        impulse.elapsed = impulse.elapsed.saturating_add(elapsed);
+        let Some(intensity) = impulse.intensity() else {
+            *shake = None;
+            return None;
+        };
+        // Overlapping waves of unrelated frequencies appear random without
+        // needing a source of randomness.
+        let t = impulse.elapsed.as_secs_f32();
+        Some(Point::new(
+            intensity * ((t * 67.).sin() * 0.6 + (t * 23.).sin() * 0.4),
+            intensity * ((t * 59.).cos() * 0.6 + (t * 31.).cos() * 0.4),
+        ))
+    }
+}
+
+#[test]
+fn camera_dead_zone_and_bounds() {
+    let camera = Camera::new()
+        .dead_zone(Size::squared(Px::new(20)))
+        .bounds(Rect::new(Point::default(), Size::squared(Px::new(200))));
+    let view = Size::squared(50.);
+    let mut state = CameraState::default();
+
+    // The first frame is clamped to the bounds.
+    state.update(&camera, Point::new(0., 100.), view, Duration::ZERO);
+    assert_eq!(state.position, Some(Point::new(25., 100.)));
+
+    // Moving within the dead zone does not move the camera.
+    state.update(&camera, Point::new(30., 105.), view, Duration::ZERO);
+    assert_eq!(state.position, Some(Point::new(25., 100.)));
+
+    // Leaving the dead zone drags the camera along.
+    state.update(&camera, Point::new(50., 100.), view, Duration::ZERO);
+    assert_eq!(state.position, Some(Point::new(40., 100.)));
+}
+
+#[test]
+fn camera_follow_strategies() {
+    let view = Size::squared(50.);
+
+    let camera = Camera::new().follow(CameraFollow::ease(
+        Duration::from_secs(1),
+        EasingFunction::Fn(|progress| *progress),
+    ));
+    let mut state = CameraState::default();
+    state.update(&camera, Point::default(), view, Duration::ZERO);
+    assert!(state.update(
+        &camera,
+        Point::new(100., 0.),
+        view,
+        Duration::from_millis(250)
+    ));
+    assert_eq!(state.position, Some(Point::new(25., 0.)));
+    assert!(!state.update(&camera, Point::new(100., 0.), view, Duration::from_secs(1)));
+    assert_eq!(state.position, Some(Point::new(100., 0.)));
+
+    let camera = Camera::new().follow(CameraFollow::critically_damped_spring(100.));
+    let mut state = CameraState::default();
+    state.update(&camera, Point::default(), view, Duration::ZERO);
+    assert!(state.update(
+        &camera,
+        Point::new(100., 0.),
+        view,
+        Duration::from_millis(100)
+    ));
+    let position = state.position.unwrap();
+    assert!(position.x > 0. && position.x < 100.);
+    assert!(!state.update(&camera, Point::new(100., 0.), view, Duration::from_secs(10)));
+    assert_eq!(state.position, Some(Point::new(100., 0.)));
+}
+
+#[test]
+fn screen_shake_advances_by_elapsed_time() {
+    let shake = ScreenShake::new();
+    assert_eq!(shake.advance(Duration::from_millis(16)), None);
+
+    shake.impulse(Px::new(10), Duration::from_secs(1));
+    assert!(shake.advance(Duration::from_millis(500)).is_some());
+    assert_eq!(
+        shake.0.get().and_then(|impulse| impulse.intensity()),
+        Some(2.5)
+    );
+
+    // A weaker impulse keeps the remaining intensity and duration.
+    shake.impulse(Px::new(1), Duration::from_millis(100));
+    assert_eq!(
+        shake.0.get(),
+        Some(ShakeImpulse {
+            elapsed: Duration::ZERO,
+            intensity: 2.5,
+            duration: Duration::from_millis(500),
+        })
+    );
+
+    assert_eq!(shake.advance(Duration::from_millis(500)), None);
+    assert_eq!(shake.0.get(), None);
+}